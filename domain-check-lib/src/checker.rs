@@ -3,15 +3,132 @@
 //! This module provides the primary `DomainChecker` struct that orchestrates
 //! domain availability checking using RDAP, WHOIS, and bootstrap protocols.
 
-use crate::error::DomainCheckError;
-use crate::protocols::registry::{extract_tld, get_whois_server};
+use crate::concurrent::concurrency_limiter;
+use crate::endpoints::EndpointStats;
+use crate::error::{DomainCheckError, ErrorCategory};
+use crate::protocols::rdap::{shared_http_client, DEFAULT_CONNECT_TIMEOUT};
+use crate::protocols::registry::{endpoint_key, extract_tld, get_whois_server, get_whois_servers};
+use crate::protocols::whois::has_whois_command;
 use crate::protocols::{RdapClient, WhoisClient};
-use crate::types::{CheckConfig, CheckMethod, DomainResult};
-use crate::utils::validate_domain;
+use crate::sink::ResultSink;
+use crate::types::{
+    AmbiguousStatusPolicy, CheckConfig, CheckEvent, CheckMethod, Confidence, DomainInfo,
+    DomainResult, StreamFilter,
+};
+use crate::utils::{normalize_domain_input, validate_domain, validate_domain_strict};
 use futures_util::stream::{Stream, StreamExt};
+use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
-use tokio::sync::Semaphore;
+use std::time::Instant;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Run `check` over `items` on a background task with up to `concurrency`
+/// in flight, handing results over a channel of `capacity`.
+///
+/// When the consumer falls behind and the channel fills, the driver stops
+/// polling, so no new checks start until results are taken. With `ordered`,
+/// results are handed over in input order rather than completion order.
+fn bounded_result_stream<T, F, Fut>(
+    items: Vec<String>,
+    concurrency: usize,
+    capacity: usize,
+    ordered: bool,
+    check: F,
+) -> Pin<Box<dyn Stream<Item = T> + Send + 'static>>
+where
+    T: Send + 'static,
+    F: FnMut(String) -> Fut + Send + 'static,
+    Fut: Future<Output = T> + Send + 'static,
+{
+    let (tx, rx) = tokio::sync::mpsc::channel(capacity.max(1));
+
+    tokio::spawn(async move {
+        let checks = futures_util::stream::iter(items).map(check);
+        let mut results: Pin<Box<dyn Stream<Item = T> + Send>> = if ordered {
+            Box::pin(checks.buffered(concurrency.max(1)))
+        } else {
+            Box::pin(checks.buffer_unordered(concurrency.max(1)))
+        };
+        while let Some(item) = results.next().await {
+            if tx.send(item).await.is_err() {
+                break; // Consumer dropped the stream
+            }
+        }
+    });
+
+    Box::pin(futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|item| (item, rx))
+    }))
+}
+
+/// Drop successful results that don't pass `filter`, keeping errors.
+fn filter_stream<'a>(
+    stream: Pin<Box<dyn Stream<Item = Result<DomainResult, DomainCheckError>> + Send + 'a>>,
+    filter: StreamFilter,
+) -> Pin<Box<dyn Stream<Item = Result<DomainResult, DomainCheckError>> + Send + 'a>> {
+    if filter == StreamFilter::All {
+        return stream;
+    }
+    Box::pin(stream.filter(move |item| {
+        let keep = match item {
+            Ok(result) => filter.matches(result),
+            Err(_) => true,
+        };
+        std::future::ready(keep)
+    }))
+}
+
+/// RDAP client for a configuration, before per-feature options are applied.
+///
+/// Uses the configured HTTP client if any, otherwise the crate-wide shared
+/// one. A non-default connect timeout can't be applied to the shared client,
+/// so that case still gets a dedicated client.
+fn base_rdap_client(config: &CheckConfig) -> Result<RdapClient, DomainCheckError> {
+    let http_client = match &config.http_client {
+        Some(client) => client.clone(),
+        None if config.connect_timeout == DEFAULT_CONNECT_TIMEOUT => shared_http_client()?,
+        None => {
+            return RdapClient::with_config(
+                config.rdap_timeout,
+                config.connect_timeout,
+                config.enable_bootstrap,
+            )
+        }
+    };
+    Ok(RdapClient::with_http_client(
+        http_client,
+        config.rdap_timeout,
+        config.enable_bootstrap,
+    ))
+}
+
+/// RDAP client for a configuration with every per-feature option applied.
+fn configured_rdap_client(config: &CheckConfig) -> Result<RdapClient, DomainCheckError> {
+    Ok(base_rdap_client(config)?
+        .with_policy(config.classification_policy.clone())
+        .with_follow_registrar(config.follow_registrar_rdap && config.detailed_info)
+        .with_wildcard_detection(config.detect_wildcard_tlds)
+        .with_tld_timeouts(config.tld_timeouts.clone())
+        .with_max_response_bytes(config.max_rdap_response_bytes)
+        .with_timeout_retries(config.timeout_retries)
+        .with_server_override(config.rdap_server_override.as_deref())
+        .with_bootstrap_url(config.bootstrap_url.as_deref())
+        .with_concurrency_per_host(config.concurrency_per_host)
+        .with_endpoint_tracking(config.track_endpoints)
+        .with_skip_info(!rdap_record_needed(config)))
+}
+
+/// Whether anything downstream of an RDAP lookup reads the registration record.
+///
+/// When nothing does, registered domains are reported as taken straight from
+/// the HTTP status and the response body is never parsed.
+fn rdap_record_needed(config: &CheckConfig) -> bool {
+    config.detailed_info
+        || config.classification_policy.is_some()
+        || config.treat_dropping_as_available
+        || config.ambiguous_status_policy != AmbiguousStatusPolicy::TreatAsTaken
+}
 
 /// Check a single domain using the provided clients (for concurrent processing).
 ///
@@ -23,70 +140,56 @@ async fn check_single_domain_concurrent(
     whois_client: &WhoisClient,
     config: &CheckConfig,
 ) -> Result<DomainResult, DomainCheckError> {
-    // Validate domain format first
-    validate_domain(domain)?;
+    // Validate domain format first; `example.com.` is checked as `example.com`
+    let domain = normalize_domain_input(domain);
+    if config.strict_validation {
+        validate_domain_strict(domain)?;
+    } else {
+        validate_domain(domain)?;
+    }
+
+    // Race both protocols when asked (WHOIS must be allowed to run at all)
+    if config.race_protocols && config.enable_whois_fallback {
+        return match race_protocols(
+            rdap_client.check_domain(domain),
+            whois_with_discovery(domain, whois_client),
+        )
+        .await
+        {
+            Ok(result) => Ok(finalize_result(result, config)),
+            Err((rdap_error, whois_error)) => both_failed_result(domain, rdap_error, whois_error),
+        };
+    }
+
+    // TLDs configured to prefer WHOIS query it first, with RDAP as the fallback
+    if config.enable_whois_fallback && prefers_whois(domain, config) {
+        return match whois_then_rdap(
+            whois_with_discovery(domain, whois_client),
+            rdap_client.check_domain(domain),
+        )
+        .await
+        {
+            Ok(result) => Ok(finalize_result(result, config)),
+            Err((rdap_error, whois_error)) => both_failed_result(domain, rdap_error, whois_error),
+        };
+    }
 
     // Try RDAP first
     match rdap_client.check_domain(domain).await {
         Ok(result) => {
-            // RDAP succeeded, filter info based on configuration
-            let mut filtered_result = result;
-            if !config.detailed_info {
-                filtered_result.info = None;
-            }
-            Ok(filtered_result)
+            // RDAP succeeded, apply verdict policy and filter info
+            Ok(finalize_result(result, config))
         }
         Err(rdap_error) => {
             // RDAP failed, try WHOIS fallback if enabled
             if config.enable_whois_fallback {
                 // Discover WHOIS server for targeted query
-                let whois_result = whois_with_discovery(domain, whois_client).await;
-
-                match whois_result {
-                    Ok(whois_result) => {
-                        let mut filtered_result = whois_result;
-                        if !config.detailed_info {
-                            filtered_result.info = None;
-                        }
-                        Ok(filtered_result)
-                    }
-                    Err(whois_error) => {
-                        // Both RDAP and WHOIS failed, determine best response
-
-                        // Check if either error indicates the domain is available
-                        if rdap_error.indicates_available() || whois_error.indicates_available() {
-                            Ok(DomainResult {
-                                domain: domain.to_string(),
-                                available: Some(true),
-                                info: None,
-                                check_duration: None,
-                                method_used: CheckMethod::Rdap,
-                                error_message: None,
-                            })
-                        }
-                        // Check if it's an unknown TLD or truly ambiguous case
-                        else if matches!(rdap_error, DomainCheckError::BootstrapError { .. })
-                            || matches!(whois_error, DomainCheckError::BootstrapError { .. })
-                            || whois_error
-                                .to_string()
-                                .contains("Unable to determine domain status")
-                        {
-                            // Return unknown status for invalid TLDs or ambiguous cases
-                            Ok(DomainResult {
-                                domain: domain.to_string(),
-                                available: None, // Unknown status
-                                info: None,
-                                check_duration: None,
-                                method_used: CheckMethod::Unknown,
-                                error_message: Some(
-                                    "Unknown TLD or unable to determine status".to_string(),
-                                ),
-                            })
-                        } else {
-                            // Return the RDAP error as it's usually more informative
-                            Err(rdap_error)
-                        }
-                    }
+                match whois_with_discovery(domain, whois_client).await {
+                    Ok(whois_result) => Ok(finalize_result(
+                        keep_rdap_status(whois_result, &rdap_error),
+                        config,
+                    )),
+                    Err(whois_error) => both_failed_result(domain, rdap_error, whois_error),
                 }
             } else {
                 // No fallback enabled, return RDAP error
@@ -96,20 +199,317 @@ async fn check_single_domain_concurrent(
     }
 }
 
+/// Run RDAP and WHOIS concurrently and return the first successful result.
+///
+/// The slower lookup is dropped (cancelled) once the other succeeds. If one
+/// fails, the other is awaited; if both fail, both errors are returned.
+/// RDAP is polled first, so it wins when both are ready at the same time.
+/// A WHOIS answer after an RDAP failure keeps RDAP's HTTP status.
+async fn race_protocols<R, W>(
+    rdap: R,
+    whois: W,
+) -> Result<DomainResult, (DomainCheckError, DomainCheckError)>
+where
+    R: Future<Output = Result<DomainResult, DomainCheckError>>,
+    W: Future<Output = Result<DomainResult, DomainCheckError>>,
+{
+    tokio::pin!(rdap);
+    tokio::pin!(whois);
+
+    tokio::select! {
+        biased;
+        result = &mut rdap => match result {
+            Ok(result) => Ok(result),
+            Err(rdap_error) => match whois.await {
+                Ok(result) => Ok(keep_rdap_status(result, &rdap_error)),
+                Err(whois_error) => Err((rdap_error, whois_error)),
+            },
+        },
+        result = &mut whois => match result {
+            Ok(result) => Ok(result),
+            Err(whois_error) => rdap.await.map_err(|rdap_error| (rdap_error, whois_error)),
+        },
+    }
+}
+
+/// Whether the domain's TLD is configured to be checked over WHOIS first.
+fn prefers_whois(domain: &str, config: &CheckConfig) -> bool {
+    if config.tld_method_preference.is_empty() {
+        return false;
+    }
+    extract_tld(domain).is_ok_and(|tld| {
+        config.tld_method_preference.get(&tld.to_lowercase()) == Some(&CheckMethod::Whois)
+    })
+}
+
+/// Run WHOIS and only start RDAP if WHOIS fails.
+///
+/// If both fail, both errors are returned (RDAP's first, as with
+/// `race_protocols`).
+async fn whois_then_rdap<W, R>(
+    whois: W,
+    rdap: R,
+) -> Result<DomainResult, (DomainCheckError, DomainCheckError)>
+where
+    W: Future<Output = Result<DomainResult, DomainCheckError>>,
+    R: Future<Output = Result<DomainResult, DomainCheckError>>,
+{
+    match whois.await {
+        Ok(result) => Ok(result),
+        Err(whois_error) => rdap.await.map_err(|rdap_error| (rdap_error, whois_error)),
+    }
+}
+
+/// Decide the outcome when both RDAP and WHOIS failed for a domain.
+fn both_failed_result(
+    domain: &str,
+    rdap_error: DomainCheckError,
+    whois_error: DomainCheckError,
+) -> Result<DomainResult, DomainCheckError> {
+    // Check if either error indicates the domain is available
+    if rdap_error.indicates_available() || whois_error.indicates_available() {
+        Ok(DomainResult {
+            domain: domain.to_string(),
+            available: Some(true),
+            info: None,
+            check_duration: None,
+            method_used: CheckMethod::Rdap,
+            error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: rdap_error.indicates_available().then_some(404),
+            redirects_to: None,
+            attempts: None,
+        })
+    }
+    // Check if it's an unknown TLD or truly ambiguous case
+    else if matches!(rdap_error, DomainCheckError::BootstrapError { .. })
+        || matches!(whois_error, DomainCheckError::BootstrapError { .. })
+        || whois_error
+            .to_string()
+            .contains("Unable to determine domain status")
+    {
+        // Return unknown status for invalid TLDs or ambiguous cases
+        Ok(DomainResult {
+            domain: domain.to_string(),
+            available: None, // Unknown status
+            info: None,
+            check_duration: None,
+            method_used: CheckMethod::Unknown,
+            error_message: Some("Unknown TLD or unable to determine status".to_string()),
+            error_category: Some(ErrorCategory::UnknownTld),
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
+        })
+    } else {
+        // Return the RDAP error as it's usually more informative
+        Err(rdap_error)
+    }
+}
+
+/// Add a row to the configured timing log, if any.
+fn record_timing(
+    config: &CheckConfig,
+    domain: &str,
+    launched: Instant,
+    started: Instant,
+    result: Result<&DomainResult, &DomainCheckError>,
+) {
+    if let Some(log) = &config.timing_log {
+        let method = result.map_or(CheckMethod::Unknown, |r| r.method_used.clone());
+        log.record(
+            domain,
+            launched,
+            started,
+            method,
+            config.rdap_server_override.as_deref(),
+        );
+    }
+}
+
+/// Record on a WHOIS fallback result how RDAP answered before it failed
+/// (e.g. 429 or 503), so the RDAP outcome isn't lost.
+fn keep_rdap_status(mut result: DomainResult, rdap_error: &DomainCheckError) -> DomainResult {
+    if result.rdap_status.is_none() {
+        result.rdap_status = rdap_error.rdap_status();
+    }
+    result
+}
+
+/// Apply configuration-driven post-processing to a successful check.
+///
+/// Runs the optional ambiguous-status and drop-catching policies (which need
+/// registry status), then strips detailed info if it wasn't requested or
+/// carries no data.
+fn finalize_result(mut result: DomainResult, config: &CheckConfig) -> DomainResult {
+    if config.ambiguous_status_policy == AmbiguousStatusPolicy::TreatAsUnknown {
+        result = mark_ambiguous_unknown(result);
+    }
+    if config.treat_dropping_as_available {
+        result = mark_dropping_available(result);
+    }
+    result = note_idn_variants(result);
+    if !config.detailed_info || result.info.as_ref().is_some_and(DomainInfo::is_empty) {
+        result.info = None;
+    }
+    result
+}
+
+/// Flip a taken domain that is being deleted to available, with low confidence.
+///
+/// Matches `pendingDelete` and `redemptionPeriod` in either EPP (camelCase)
+/// or RDAP ("pending delete") spelling. Other results are returned unchanged.
+fn mark_dropping_available(mut result: DomainResult) -> DomainResult {
+    if result.available != Some(false) {
+        return result;
+    }
+
+    let dropping_status = result.info.as_ref().and_then(|info| {
+        info.status.iter().find(|status| {
+            let normalized = normalize_status(status);
+            normalized == "pendingdelete" || normalized == "redemptionperiod"
+        })
+    });
+
+    if let Some(status) = dropping_status {
+        result.note = Some(format!(
+            "Registered but in '{}'; expected to become available",
+            status
+        ));
+        result.available = Some(true);
+        result.confidence = Some(Confidence::Low);
+    }
+    result
+}
+
+/// Note registered IDN variants on an available result.
+///
+/// Registries that bundle variants can refuse a name whose variant is
+/// already registered, even though the name itself looks available. Variants
+/// are read from the name's own RDAP record, so this only fires for available
+/// results that still carry one: names flipped by
+/// `treat_dropping_as_available` or by a `ClassificationPolicy`. A plain 404
+/// has no record to list variants in.
+fn note_idn_variants(mut result: DomainResult) -> DomainResult {
+    let is_idn = result
+        .domain
+        .split('.')
+        .any(|label| label.starts_with("xn--"))
+        || !result.domain.is_ascii();
+    if result.available != Some(true) || !is_idn {
+        return result;
+    }
+    let registered: Vec<&str> = result
+        .info
+        .iter()
+        .flat_map(|info| &info.variants)
+        .filter(|v| v.is_registered())
+        .map(|v| v.name.as_str())
+        .collect();
+    if registered.is_empty() {
+        return result;
+    }
+    let variants = registered.join(", ");
+
+    let warning = format!(
+        "IDN variants exist ({}); registration may be blocked",
+        variants
+    );
+    result.note = Some(match result.note.take() {
+        Some(note) => format!("{}; {}", note, warning),
+        None => warning,
+    });
+    result
+}
+
+/// Statuses that don't say whether a name is actually registered.
+const AMBIGUOUS_STATUSES: &[&str] = &["serverhold", "clienthold", "inactive"];
+
+/// Downgrade a taken RDAP result to unknown when its record is ambiguous.
+///
+/// Applies only when every status is ambiguous and the record has no
+/// registration, expiration or last-changed date; anything else stays taken.
+fn mark_ambiguous_unknown(mut result: DomainResult) -> DomainResult {
+    if result.available != Some(false) || result.method_used != CheckMethod::Rdap {
+        return result;
+    }
+    let Some(info) = result.info.as_ref() else {
+        return result;
+    };
+
+    let only_ambiguous = !info.status.is_empty()
+        && info
+            .status
+            .iter()
+            .all(|status| AMBIGUOUS_STATUSES.contains(&normalize_status(status).as_str()));
+    let no_events = info.creation_date.is_none()
+        && info.expiration_date.is_none()
+        && info.updated_date.is_none();
+
+    if only_ambiguous && no_events {
+        result.note = Some(format!(
+            "Registry shows only '{}' and no registration dates; may be held or deleted",
+            info.status.join(", ")
+        ));
+        result.available = None;
+        result.confidence = Some(Confidence::Low);
+    }
+    result
+}
+
+/// Lowercase a status and drop separators, so EPP `serverHold` and RDAP
+/// `server hold` compare equal.
+fn normalize_status(status: &str) -> String {
+    status
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
 /// Perform WHOIS check with server discovery for targeted queries.
 ///
-/// If the TLD's authoritative WHOIS server can be discovered via IANA referral,
-/// uses `whois -h <server> <domain>` for a more reliable query. Falls back to
-/// bare `whois <domain>` otherwise.
+/// TLDs with bundled WHOIS servers are queried directly over TCP first,
+/// trying each of the TLD's servers in turn. If none answers (or the TLD has
+/// none bundled) and the system `whois` command is installed, the TLD's
+/// authoritative server is discovered via IANA referral and queried with
+/// `whois -h <server> <domain>`, falling back to bare `whois <domain>`.
 async fn whois_with_discovery(
     domain: &str,
     whois_client: &WhoisClient,
 ) -> Result<DomainResult, DomainCheckError> {
-    let tld = extract_tld(domain).ok();
-    let whois_server = if let Some(ref t) = tld {
-        get_whois_server(t).await
-    } else {
-        None
+    let servers = extract_tld(domain)
+        .map(|tld| get_whois_servers(&tld))
+        .unwrap_or_default();
+    whois_with_failover(domain, whois_client, &servers).await
+}
+
+/// `whois_with_discovery` against an explicit list of bundled servers.
+async fn whois_with_failover(
+    domain: &str,
+    whois_client: &WhoisClient,
+    servers: &[String],
+) -> Result<DomainResult, DomainCheckError> {
+    if !servers.is_empty() {
+        match whois_client
+            .check_domain_with_servers(domain, servers)
+            .await
+        {
+            Ok(result) => return Ok(result),
+            Err(e) if !has_whois_command().await => return Err(e),
+            Err(_) => {}
+        }
+    }
+
+    let whois_server = match extract_tld(domain) {
+        Ok(tld) => get_whois_server(&tld).await,
+        Err(_) => None,
     };
 
     if let Some(server) = whois_server {
@@ -119,6 +519,39 @@ async fn whois_with_discovery(
     }
 }
 
+/// Compute a launch order that round-robins across endpoint groups.
+///
+/// Domains are grouped by `key_of` (first-seen group order, input order within
+/// each group), then one domain is taken from each group in turn. Returns the
+/// original indices in launch order.
+fn interleave_order<F>(domains: &[String], key_of: F) -> Vec<usize>
+where
+    F: Fn(&str) -> String,
+{
+    let mut group_index: std::collections::HashMap<String, usize> =
+        std::collections::HashMap::new();
+    let mut groups: Vec<std::collections::VecDeque<usize>> = Vec::new();
+
+    for (index, domain) in domains.iter().enumerate() {
+        let key = key_of(domain);
+        let slot = *group_index.entry(key).or_insert_with(|| {
+            groups.push(std::collections::VecDeque::new());
+            groups.len() - 1
+        });
+        groups[slot].push_back(index);
+    }
+
+    let mut order = Vec::with_capacity(domains.len());
+    while order.len() < domains.len() {
+        for group in groups.iter_mut() {
+            if let Some(index) = group.pop_front() {
+                order.push(index);
+            }
+        }
+    }
+    order
+}
+
 /// Main domain checker that coordinates availability checking operations.
 ///
 /// The `DomainChecker` handles all aspects of domain checking including:
@@ -159,17 +592,22 @@ impl DomainChecker {
     /// - WHOIS fallback: enabled
     /// - Bootstrap: enabled
     /// - Detailed info: disabled
+    ///
+    /// A configuration installed with `init_with` replaces these defaults.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client can't be built; use [`Self::try_new`] to
+    /// get the error instead.
     pub fn new() -> Self {
-        let config = CheckConfig::default();
-        let rdap_client = RdapClient::with_config(config.rdap_timeout, config.enable_bootstrap)
-            .expect("Failed to create RDAP client");
-        let whois_client = WhoisClient::with_timeout(config.whois_timeout);
+        Self::with_config(crate::init::default_config())
+    }
 
-        Self {
-            config,
-            rdap_client,
-            whois_client,
-        }
+    /// Create a new domain checker with default configuration, returning an
+    /// error if the HTTP client can't be built (e.g. the TLS backend fails
+    /// to initialise).
+    pub fn try_new() -> Result<Self, DomainCheckError> {
+        Self::try_with_config(crate::init::default_config())
     }
 
     /// Create a new domain checker with custom configuration.
@@ -187,16 +625,36 @@ impl DomainChecker {
     ///     
     /// let checker = DomainChecker::with_config(config);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client can't be built; use
+    /// [`Self::try_with_config`] to get the error instead.
     pub fn with_config(config: CheckConfig) -> Self {
-        let rdap_client = RdapClient::with_config(config.rdap_timeout, config.enable_bootstrap)
-            .expect("Failed to create RDAP client");
-        let whois_client = WhoisClient::with_timeout(config.whois_timeout);
+        Self::try_with_config(config).expect("Failed to create RDAP client")
+    }
+
+    /// Create a new domain checker with custom configuration, returning an
+    /// error if the HTTP client can't be built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use domain_check_lib::{CheckConfig, DomainChecker};
+    ///
+    /// let checker = DomainChecker::try_with_config(CheckConfig::default())?;
+    /// # Ok::<(), domain_check_lib::DomainCheckError>(())
+    /// ```
+    pub fn try_with_config(config: CheckConfig) -> Result<Self, DomainCheckError> {
+        let rdap_client = configured_rdap_client(&config)?;
+        let whois_client =
+            WhoisClient::with_timeout(config.whois_timeout).with_strict(config.whois_strict);
 
-        Self {
+        Ok(Self {
             config,
             rdap_client,
             whois_client,
-        }
+        })
     }
 
     /// Check availability of a single domain.
@@ -208,6 +666,8 @@ impl DomainChecker {
     /// 1. Validates the domain format
     /// 2. Attempts RDAP check first (modern protocol)
     /// 3. Falls back to WHOIS if RDAP fails and fallback is enabled
+    ///    (or runs both at once when `race_protocols` is set); TLDs set to
+    ///    `CheckMethod::Whois` in `tld_method_preference` try WHOIS first
     /// 4. Returns comprehensive result with timing and method information
     ///
     /// # Arguments
@@ -225,76 +685,51 @@ impl DomainChecker {
     /// - Network errors occur
     /// - All checking methods fail
     pub async fn check_domain(&self, domain: &str) -> Result<DomainResult, DomainCheckError> {
-        // Validate domain format first
-        validate_domain(domain)?;
-
-        // Try RDAP first
-        match self.rdap_client.check_domain(domain).await {
-            Ok(result) => {
-                // RDAP succeeded, filter info based on configuration
-                Ok(self.filter_result_info(result))
-            }
-            Err(rdap_error) => {
-                // RDAP failed, try WHOIS fallback if enabled
-                if self.config.enable_whois_fallback {
-                    // Use WHOIS with server discovery for targeted queries
-                    match whois_with_discovery(domain, &self.whois_client).await {
-                        Ok(whois_result) => Ok(self.filter_result_info(whois_result)),
-                        Err(whois_error) => {
-                            // Both RDAP and WHOIS failed, determine best response
-
-                            // Check if either error indicates the domain is available
-                            if rdap_error.indicates_available() || whois_error.indicates_available()
-                            {
-                                Ok(DomainResult {
-                                    domain: domain.to_string(),
-                                    available: Some(true),
-                                    info: None,
-                                    check_duration: None,
-                                    method_used: CheckMethod::Rdap,
-                                    error_message: None,
-                                })
-                            }
-                            // Check if it's an unknown TLD or truly ambiguous case
-                            else if matches!(rdap_error, DomainCheckError::BootstrapError { .. })
-                                || matches!(whois_error, DomainCheckError::BootstrapError { .. })
-                                || whois_error
-                                    .to_string()
-                                    .contains("Unable to determine domain status")
-                            {
-                                // Return unknown status for invalid TLDs or ambiguous cases
-                                Ok(DomainResult {
-                                    domain: domain.to_string(),
-                                    available: None, // Unknown status
-                                    info: None,
-                                    check_duration: None,
-                                    method_used: CheckMethod::Unknown,
-                                    error_message: Some(
-                                        "Unknown TLD or unable to determine status".to_string(),
-                                    ),
-                                })
-                            } else {
-                                // Return the most informative error
-                                Err(rdap_error)
-                            }
-                        }
-                    }
-                } else {
-                    // No fallback enabled, return RDAP error
-                    Err(rdap_error)
-                }
-            }
-        }
+        check_single_domain_concurrent(domain, &self.rdap_client, &self.whois_client, &self.config)
+            .await
     }
 
-    /// Filter domain result info based on configuration.
+    /// Check a single domain and keep its registration details.
+    ///
+    /// Same as `check_domain`, but `info` is returned even when the
+    /// checker's `detailed_info` is off, so one lookup can get details
+    /// without building a second checker. Following registrar RDAP links
+    /// still depends on the checker's own configuration.
+    ///
+    /// # Example
     ///
-    /// If detailed_info is disabled, removes the info field to keep results clean.
-    fn filter_result_info(&self, mut result: DomainResult) -> DomainResult {
-        if !self.config.detailed_info {
-            result.info = None;
+    /// ```rust,no_run
+    /// use domain_check_lib::DomainChecker;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let checker = DomainChecker::new();
+    ///     let result = checker.check_domain_detailed("example.com").await?;
+    ///     if let Some(info) = result.info {
+    ///         println!("Registrar: {:?}", info.registrar);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn check_domain_detailed(
+        &self,
+        domain: &str,
+    ) -> Result<DomainResult, DomainCheckError> {
+        check_single_domain_concurrent(
+            domain,
+            &self.rdap_client.clone().with_skip_info(false),
+            &self.whois_client,
+            &self.detailed_config(),
+        )
+        .await
+    }
+
+    /// This checker's configuration with `detailed_info` forced on.
+    fn detailed_config(&self) -> CheckConfig {
+        CheckConfig {
+            detailed_info: true,
+            ..self.config.clone()
         }
-        result
     }
 
     /// Check availability of multiple domains concurrently.
@@ -330,33 +765,154 @@ impl DomainChecker {
     pub async fn check_domains(
         &self,
         domains: &[String],
+    ) -> Result<Vec<DomainResult>, DomainCheckError> {
+        self.check_domains_inner(domains, None).await
+    }
+
+    /// Check multiple domains, reporting lifecycle events as they happen.
+    ///
+    /// Behaves like `check_domains`, and additionally sends a `CheckEvent`
+    /// over `events` when each check starts and completes, when an RDAP
+    /// request is retried, and when a server rate limits us. Sending never
+    /// blocks; if the receiver is dropped, events are silently discarded.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use domain_check_lib::{CheckEvent, DomainChecker};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let checker = DomainChecker::new();
+    ///     let domains = vec!["example.com".to_string(), "example.org".to_string()];
+    ///     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    ///
+    ///     let progress = tokio::spawn(async move {
+    ///         while let Some(event) = rx.recv().await {
+    ///             if let CheckEvent::Completed { result } = event {
+    ///                 println!("done: {}", result.domain);
+    ///             }
+    ///         }
+    ///     });
+    ///
+    ///     let results = checker.check_domains_with_events(&domains, tx).await?;
+    ///     progress.await?;
+    ///     println!("{} results", results.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn check_domains_with_events(
+        &self,
+        domains: &[String],
+        events: UnboundedSender<CheckEvent>,
+    ) -> Result<Vec<DomainResult>, DomainCheckError> {
+        self.check_domains_inner(domains, Some(events)).await
+    }
+
+    /// Check domains in batches of `chunk_size`, calling `on_chunk` with each
+    /// batch's results before starting the next.
+    ///
+    /// Each batch is checked concurrently like `check_domains` and its results
+    /// are dropped once `on_chunk` returns, so memory is bounded by the batch
+    /// size. The callback is a natural checkpoint: persist the results there
+    /// and a later run can skip the domains already done. A `chunk_size` of 0
+    /// is treated as 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use domain_check_lib::DomainChecker;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let checker = DomainChecker::new();
+    ///     let domains: Vec<String> = (0..10_000).map(|i| format!("name{}.com", i)).collect();
+    ///
+    ///     checker
+    ///         .check_domains_chunked(&domains, 500, |results| {
+    ///             // e.g. append to a checkpoint file
+    ///             println!("{} more done", results.len());
+    ///         })
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn check_domains_chunked<F>(
+        &self,
+        domains: &[String],
+        chunk_size: usize,
+        mut on_chunk: F,
+    ) -> Result<(), DomainCheckError>
+    where
+        F: FnMut(&[DomainResult]),
+    {
+        for chunk in domains.chunks(chunk_size.max(1)) {
+            let results = self.check_domains_inner(chunk, None).await?;
+            on_chunk(&results);
+        }
+        Ok(())
+    }
+
+    /// Shared implementation of `check_domains` with an optional event sink.
+    async fn check_domains_inner(
+        &self,
+        domains: &[String],
+        events: Option<UnboundedSender<CheckEvent>>,
     ) -> Result<Vec<DomainResult>, DomainCheckError> {
         if domains.is_empty() {
             return Ok(Vec::new());
         }
 
         // Create semaphore to limit concurrent operations
-        let semaphore = Arc::new(Semaphore::new(self.config.concurrency));
+        let semaphore = concurrency_limiter(self.config.concurrency, self.config.concurrency_ramp);
         let mut handles = Vec::new();
 
+        // Launch order: input order, or round-robin across RDAP endpoints.
+        // The semaphore is fair, so permits follow spawn order.
+        let launch_order: Vec<usize> = if self.config.interleave_by_endpoint {
+            interleave_order(domains, endpoint_key)
+        } else {
+            (0..domains.len()).collect()
+        };
+
         // Spawn concurrent tasks for each domain
-        for (index, domain) in domains.iter().enumerate() {
-            let domain = domain.clone();
+        for index in launch_order {
+            let domain = domains[index].clone();
             let semaphore = Arc::clone(&semaphore);
 
             // Clone the checker components we need
-            let rdap_client = self.rdap_client.clone();
+            let rdap_client = match &events {
+                Some(events) => self.rdap_client.clone().with_events(events.clone()),
+                None => self.rdap_client.clone(),
+            };
             let whois_client = self.whois_client.clone();
             let config = self.config.clone();
+            let events = events.clone();
+            let launched = Instant::now();
 
             let handle = tokio::spawn(async move {
                 // Acquire semaphore permit
                 let _permit = semaphore.acquire().await.unwrap();
+                let started = Instant::now();
 
-                // Check this domain
+                if let Some(events) = &events {
+                    let _ = events.send(CheckEvent::Started {
+                        domain: domain.clone(),
+                    });
+                }
+
+                // Check this domain, converting errors to a DomainResult with error info
                 let result =
                     check_single_domain_concurrent(&domain, &rdap_client, &whois_client, &config)
-                        .await;
+                        .await
+                        .unwrap_or_else(|e| DomainResult::from_error(domain.as_str(), &e));
+                record_timing(&config, &domain, launched, started, Ok(&result));
+
+                if let Some(events) = &events {
+                    let _ = events.send(CheckEvent::Completed {
+                        result: Box::new(result.clone()),
+                    });
+                }
 
                 // Return with original index to maintain order
                 (index, result)
@@ -382,23 +938,10 @@ impl DomainChecker {
         // Sort by original index to maintain input order
         indexed_results.sort_by_key(|(index, _)| *index);
 
-        // Extract results, converting errors to DomainResult with error info
-        let results = indexed_results
+        Ok(indexed_results
             .into_iter()
-            .map(|(index, result)| match result {
-                Ok(domain_result) => domain_result,
-                Err(e) => DomainResult {
-                    domain: domains[index].clone(),
-                    available: None,
-                    info: None,
-                    check_duration: None,
-                    method_used: CheckMethod::Unknown,
-                    error_message: Some(e.to_string()),
-                },
-            })
-            .collect();
-
-        Ok(results)
+            .map(|(_, result)| result)
+            .collect())
     }
 
     /// Check domains and return results as a stream.
@@ -440,50 +983,181 @@ impl DomainChecker {
         &self,
         domains: &[String],
     ) -> Pin<Box<dyn Stream<Item = Result<DomainResult, DomainCheckError>> + Send + '_>> {
-        let domains = domains.to_vec();
-        let semaphore = Arc::new(Semaphore::new(self.config.concurrency));
+        let domains: Vec<String> = if self.config.interleave_by_endpoint {
+            interleave_order(domains, endpoint_key)
+                .into_iter()
+                .map(|i| domains[i].clone())
+                .collect()
+        } else {
+            domains.to_vec()
+        };
+        let semaphore = concurrency_limiter(self.config.concurrency, self.config.concurrency_ramp);
+
+        if let Some(capacity) = self.config.result_buffer {
+            let rdap_client = self.rdap_client.clone();
+            let whois_client = self.whois_client.clone();
+            let config = self.config.clone();
+            return bounded_result_stream(
+                domains,
+                self.config.concurrency,
+                capacity,
+                self.config.deterministic_order,
+                move |domain| {
+                    let semaphore = Arc::clone(&semaphore);
+                    let rdap_client = rdap_client.clone();
+                    let whois_client = whois_client.clone();
+                    let config = config.clone();
+                    async move {
+                        let launched = Instant::now();
+                        let _permit = semaphore.acquire().await.unwrap();
+                        let started = Instant::now();
+                        let result = check_single_domain_concurrent(
+                            &domain,
+                            &rdap_client,
+                            &whois_client,
+                            &config,
+                        )
+                        .await;
+                        record_timing(&config, &domain, launched, started, result.as_ref());
+                        result
+                    }
+                },
+            );
+        }
 
         // Create stream of futures
-        let stream = futures_util::stream::iter(domains)
-            .map(move |domain| {
-                let semaphore = Arc::clone(&semaphore);
-                let rdap_client = self.rdap_client.clone();
-                let whois_client = self.whois_client.clone();
-                let config = self.config.clone();
+        let checks = futures_util::stream::iter(domains).map(move |domain| {
+            let semaphore = Arc::clone(&semaphore);
+            let rdap_client = self.rdap_client.clone();
+            let whois_client = self.whois_client.clone();
+            let config = self.config.clone();
 
-                async move {
-                    // Acquire semaphore permit
-                    let _permit = semaphore.acquire().await.unwrap();
+            async move {
+                // Acquire semaphore permit
+                let launched = Instant::now();
+                let _permit = semaphore.acquire().await.unwrap();
+                let started = Instant::now();
 
-                    // Check domain
+                // Check domain
+                let result =
                     check_single_domain_concurrent(&domain, &rdap_client, &whois_client, &config)
-                        .await
-                }
-            })
-            // Buffer unordered allows concurrent execution while maintaining the stream interface
-            .buffer_unordered(self.config.concurrency);
-
-        Box::pin(stream)
+                        .await;
+                record_timing(&config, &domain, launched, started, result.as_ref());
+                result
+            }
+        });
+
+        // Both run checks concurrently; `buffered` holds finished results
+        // back until every earlier domain has been yielded
+        if self.config.deterministic_order {
+            Box::pin(checks.buffered(self.config.concurrency))
+        } else {
+            Box::pin(checks.buffer_unordered(self.config.concurrency))
+        }
     }
 
-    /// Read domain names from a file and check their availability.
-    ///
-    /// The file should contain one domain name per line. Empty lines and
-    /// lines starting with '#' are ignored as comments.
-    ///
-    /// # Arguments
+    /// Stream domain checks, yielding only results that pass `filter`.
     ///
-    /// * `file_path` - Path to the file containing domain names
+    /// Same as `check_domains_stream`, but filtered-out results are dropped
+    /// before they reach the caller. Errors are always yielded.
     ///
-    /// # Returns
+    /// # Example
     ///
-    /// Vector of `DomainResult` for all valid domains in the file.
+    /// ```rust,no_run
+    /// use domain_check_lib::{DomainChecker, StreamFilter};
+    /// use futures_util::StreamExt;
     ///
-    /// # Errors
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let checker = DomainChecker::new();
+    ///     let domains = vec!["example.com".to_string(), "example.org".to_string()];
     ///
-    /// Returns `DomainCheckError` if:
-    /// - The file cannot be read
-    /// - The file contains too many domains (over limit)
+    ///     let mut stream = checker.check_domains_stream_filtered(&domains, StreamFilter::AvailableOnly);
+    ///     while let Some(Ok(result)) = stream.next().await {
+    ///         println!("available: {}", result.domain);
+    ///     }
+    /// }
+    /// ```
+    pub fn check_domains_stream_filtered(
+        &self,
+        domains: &[String],
+        filter: StreamFilter,
+    ) -> Pin<Box<dyn Stream<Item = Result<DomainResult, DomainCheckError>> + Send + '_>> {
+        filter_stream(self.check_domains_stream(domains), filter)
+    }
+
+    /// Check domains and deliver each result to a `ResultSink` as it completes.
+    ///
+    /// Runs with the configured concurrency like `check_domains_stream`, but
+    /// failed checks are delivered as unknown-status results (as in
+    /// `check_domains`) so the sink sees exactly one result per domain.
+    /// Results arrive in completion order.
+    ///
+    /// # Returns
+    ///
+    /// The number of results delivered to the sink.
+    pub async fn check_domains_to_sink<S: ResultSink>(
+        &self,
+        domains: &[String],
+        sink: &S,
+    ) -> Result<usize, DomainCheckError> {
+        let domains: Vec<String> = if self.config.interleave_by_endpoint {
+            interleave_order(domains, endpoint_key)
+                .into_iter()
+                .map(|i| domains[i].clone())
+                .collect()
+        } else {
+            domains.to_vec()
+        };
+
+        let semaphore = concurrency_limiter(self.config.concurrency, self.config.concurrency_ramp);
+        let mut stream = futures_util::stream::iter(domains)
+            .map(|domain| {
+                let semaphore = Arc::clone(&semaphore);
+                async move {
+                    let launched = Instant::now();
+                    let _permit = semaphore.acquire().await.unwrap();
+                    let started = Instant::now();
+                    let result = check_single_domain_concurrent(
+                        &domain,
+                        &self.rdap_client,
+                        &self.whois_client,
+                        &self.config,
+                    )
+                    .await
+                    .unwrap_or_else(|e| DomainResult::from_error(domain.as_str(), &e));
+                    record_timing(&self.config, &domain, launched, started, Ok(&result));
+                    result
+                }
+            })
+            .buffer_unordered(self.config.concurrency);
+
+        let mut delivered = 0;
+        while let Some(result) = stream.next().await {
+            sink.on_result(result).await;
+            delivered += 1;
+        }
+        Ok(delivered)
+    }
+
+    /// Read domain names from a file and check their availability.
+    ///
+    /// The file should contain one domain name per line. Empty lines and
+    /// lines starting with '#' are ignored as comments.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to the file containing domain names
+    ///
+    /// # Returns
+    ///
+    /// Vector of `DomainResult` for all valid domains in the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainCheckError` if:
+    /// - The file cannot be read
+    /// - The file contains too many domains (over limit)
     /// - No valid domains are found in the file
     pub async fn check_domains_from_file(
         &self,
@@ -545,6 +1219,24 @@ impl DomainChecker {
         self.check_domains(&domains).await
     }
 
+    /// TLDs flagged as possibly wildcarded so far, sorted.
+    ///
+    /// Populated only when `detect_wildcard_tlds` is enabled: each TLD whose
+    /// random-name probe was reported taken. Results for these TLDs carry
+    /// unknown availability, low confidence and an explanatory note.
+    pub fn wildcard_tlds(&self) -> Vec<String> {
+        self.rdap_client.wildcard_tlds()
+    }
+
+    /// RDAP outcome counts per endpoint host so far, most failures first.
+    ///
+    /// Populated only when `track_endpoints` is enabled. Clones of a checker
+    /// share one tally, so concurrent checks all contribute. Useful for
+    /// spotting a registry that fails every lookup during a large run.
+    pub fn endpoint_stats(&self) -> Vec<EndpointStats> {
+        self.rdap_client.endpoint_stats()
+    }
+
     /// Get the current configuration for this checker.
     pub fn config(&self) -> &CheckConfig {
         &self.config
@@ -555,12 +1247,25 @@ impl DomainChecker {
     /// This allows modifying settings like concurrency or timeout
     /// after the checker has been created. Note that this will recreate
     /// the internal protocol clients with the new settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client can't be built; use
+    /// [`Self::try_set_config`] to get the error instead.
     pub fn set_config(&mut self, config: CheckConfig) {
-        // Recreate clients with new configuration
-        self.rdap_client = RdapClient::with_config(config.rdap_timeout, config.enable_bootstrap)
+        self.try_set_config(config)
             .expect("Failed to recreate RDAP client");
-        self.whois_client = WhoisClient::with_timeout(config.whois_timeout);
+    }
+
+    /// Update the configuration for this checker, returning an error (and
+    /// leaving the checker unchanged) if the HTTP client can't be built.
+    pub fn try_set_config(&mut self, config: CheckConfig) -> Result<(), DomainCheckError> {
+        // Recreate clients with new configuration
+        self.rdap_client = configured_rdap_client(&config)?;
+        self.whois_client =
+            WhoisClient::with_timeout(config.whois_timeout).with_strict(config.whois_strict);
         self.config = config;
+        Ok(())
     }
 }
 
@@ -573,6 +1278,7 @@ impl Default for DomainChecker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_http;
     use crate::types::DomainInfo;
     use std::time::Duration;
 
@@ -608,6 +1314,21 @@ mod tests {
         assert!(!checker.config().enable_whois_fallback);
     }
 
+    #[test]
+    fn test_domain_checker_try_constructors() {
+        let config = CheckConfig::default()
+            .with_concurrency(5)
+            .with_connect_timeout(Duration::from_secs(1));
+        let mut checker = DomainChecker::try_with_config(config).unwrap();
+        assert_eq!(checker.config().concurrency, 5);
+
+        checker
+            .try_set_config(CheckConfig::default().with_concurrency(7))
+            .unwrap();
+        assert_eq!(checker.config().concurrency, 7);
+        assert!(DomainChecker::try_new().is_ok());
+    }
+
     // ── config() and set_config() ───────────────────────────────────────
 
     #[test]
@@ -627,7 +1348,7 @@ mod tests {
         assert_eq!(checker.config().concurrency, 75);
     }
 
-    // ── filter_result_info ──────────────────────────────────────────────
+    // ── finalize_result info filtering ──────────────────────────────────
 
     #[test]
     fn test_filter_result_info_removes_when_disabled() {
@@ -642,9 +1363,16 @@ mod tests {
             check_duration: None,
             method_used: CheckMethod::Rdap,
             error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
         };
 
-        let filtered = checker.filter_result_info(result);
+        let filtered = finalize_result(result, checker.config());
         assert!(filtered.info.is_none());
     }
 
@@ -663,9 +1391,16 @@ mod tests {
             check_duration: None,
             method_used: CheckMethod::Rdap,
             error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
         };
 
-        let filtered = checker.filter_result_info(result);
+        let filtered = finalize_result(result, checker.config());
         assert!(filtered.info.is_some());
         assert_eq!(
             filtered.info.unwrap().registrar,
@@ -673,6 +1408,173 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detailed_config_keeps_info_when_disabled() {
+        let checker = DomainChecker::new(); // detailed_info = false by default
+        let result = DomainResult {
+            domain: "test.com".to_string(),
+            available: Some(false),
+            info: Some(DomainInfo {
+                registrar: Some("Test Registrar".to_string()),
+                ..Default::default()
+            }),
+            check_duration: None,
+            method_used: CheckMethod::Rdap,
+            error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
+        };
+
+        let kept = finalize_result(result, &checker.detailed_config());
+        assert_eq!(
+            kept.info.and_then(|i| i.registrar),
+            Some("Test Registrar".to_string())
+        );
+        // The checker's own configuration is untouched
+        assert!(!checker.config().detailed_info);
+    }
+
+    #[tokio::test]
+    async fn test_check_domain_detailed_returns_info_when_disabled() {
+        let addr = test_http::serve(vec![test_http::rdap_json(
+            r#"{"objectClassName":"domain","ldhName":"detailed.com","status":["active"],
+                "entities":[{"roles":["registrar"],
+                    "vcardArray":["vcard",[["fn",{},"text","Stub Registrar"]]]}]}"#,
+        )])
+        .await;
+
+        let checker = DomainChecker::with_config(
+            CheckConfig::default()
+                .with_detailed_info(false)
+                .with_whois_fallback(false)
+                .with_bootstrap(false)
+                .with_rdap_server_override(format!("http://{}/domain/", addr)),
+        );
+        let result = checker.check_domain_detailed("detailed.com").await.unwrap();
+
+        assert_eq!(result.available, Some(false));
+        assert_eq!(
+            result.info.and_then(|i| i.registrar),
+            Some("Stub Registrar".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_rdap_info_is_dropped() {
+        // A registered domain whose record has no registrar, dates,
+        // status, nameservers or DNSSEC data
+        let addr = test_http::serve(vec![test_http::rdap_json(
+            r#"{"objectClassName":"domain","ldhName":"sparse.com"}"#,
+        )])
+        .await;
+
+        let checker = DomainChecker::with_config(
+            CheckConfig::default()
+                .with_detailed_info(true)
+                .with_whois_fallback(false)
+                .with_bootstrap(false)
+                .with_rdap_server_override(format!("http://{}/domain/", addr)),
+        );
+        let result = checker.check_domain("sparse.com").await.unwrap();
+
+        assert_eq!(result.available, Some(false));
+        assert!(result.info.is_none(), "{:?}", result.info);
+    }
+
+    /// Treats server-hold-only records as available.
+    #[derive(Debug)]
+    struct HoldIsAvailable;
+
+    impl crate::ClassificationPolicy for HoldIsAvailable {
+        fn classify(&self, rdap_json: &serde_json::Value) -> Option<bool> {
+            let status = rdap_json["status"].as_array()?;
+            Some(status.iter().all(|s| s == "server hold"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_classification_policy_applied_by_checker() {
+        let addr = test_http::serve(vec![test_http::rdap_json(
+            r#"{"objectClassName":"domain","ldhName":"held.com","status":["server hold"]}"#,
+        )])
+        .await;
+
+        let checker = DomainChecker::with_config(
+            CheckConfig::default()
+                .with_classification_policy(HoldIsAvailable)
+                .with_whois_fallback(false)
+                .with_bootstrap(false)
+                .with_rdap_server_override(format!("http://{}/domain/", addr)),
+        );
+        let result = checker.check_domain("held.com").await.unwrap();
+
+        assert_eq!(result.available, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_follow_registrar_rdap_applied_by_checker() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let registry = serde_json::json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "status": ["active"],
+            "links": [{
+                "rel": "related",
+                "type": "application/rdap+json",
+                "href": format!("http://{}/registrar/domain/example.com", addr)
+            }]
+        });
+        let registrar = serde_json::json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "entities": [{
+                "roles": ["registrar"],
+                "vcardArray": ["vcard", [["fn", {}, "text", "Example Registrar, Inc."]]]
+            }]
+        });
+        test_http::serve_on(
+            listener,
+            vec![
+                test_http::rdap_json(&registry.to_string()),
+                test_http::rdap_json(&registrar.to_string()),
+            ],
+        );
+
+        let checker = DomainChecker::with_config(
+            CheckConfig::default()
+                .with_detailed_info(true)
+                .with_follow_registrar_rdap(true)
+                .with_whois_fallback(false)
+                .with_bootstrap(false)
+                .with_rdap_server_override(format!("http://{}/domain/", addr)),
+        );
+        let result = checker.check_domain("example.com").await.unwrap();
+
+        let info = result.info.unwrap();
+        assert_eq!(info.registrar.as_deref(), Some("Example Registrar, Inc."));
+    }
+
+    #[test]
+    fn test_rdap_record_needed_only_when_something_reads_it() {
+        assert!(!rdap_record_needed(&CheckConfig::default()));
+        assert!(rdap_record_needed(
+            &CheckConfig::default().with_detailed_info(true)
+        ));
+        assert!(rdap_record_needed(
+            &CheckConfig::default().with_treat_dropping_as_available(true)
+        ));
+        assert!(rdap_record_needed(
+            &CheckConfig::default()
+                .with_ambiguous_status_policy(AmbiguousStatusPolicy::TreatAsUnknown)
+        ));
+    }
+
     #[test]
     fn test_filter_result_info_no_info_noop() {
         let checker = DomainChecker::new();
@@ -683,13 +1585,758 @@ mod tests {
             check_duration: None,
             method_used: CheckMethod::Rdap,
             error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
         };
 
-        let filtered = checker.filter_result_info(result);
+        let filtered = finalize_result(result, checker.config());
         assert!(filtered.info.is_none());
         assert_eq!(filtered.available, Some(true));
     }
 
+    // ── treat_dropping_as_available ─────────────────────────────────────
+
+    fn rdap_taken_result(json: serde_json::Value) -> DomainResult {
+        DomainResult {
+            domain: "dropping.com".to_string(),
+            available: Some(false),
+            info: Some(crate::protocols::rdap::extract_domain_info(&json)),
+            check_duration: None,
+            method_used: CheckMethod::Rdap,
+            error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
+        }
+    }
+
+    #[test]
+    fn test_dropping_idn_with_registered_variant_is_noted() {
+        let json = serde_json::json!({
+            "status": ["pending delete"],
+            "variants": [{
+                "relation": ["registered"],
+                "variantNames": [{"ldhName": "cafe.example"}]
+            }]
+        });
+        let config = CheckConfig::default()
+            .with_treat_dropping_as_available(true)
+            .with_detailed_info(true);
+
+        let mut result = rdap_taken_result(json.clone());
+        result.domain = "xn--caf-dma.example".to_string();
+        let result = finalize_result(result, &config);
+        assert_eq!(result.available, Some(true));
+        let note = result.note.unwrap();
+        assert!(note.contains("pending delete"), "{}", note);
+        assert!(
+            note.contains("IDN variants exist (cafe.example)"),
+            "{}",
+            note
+        );
+
+        // ASCII names and taken results get no variant note
+        let result = finalize_result(rdap_taken_result(json.clone()), &config);
+        assert!(!result.note.unwrap().contains("variants"));
+        let mut taken = rdap_taken_result(json);
+        taken.domain = "xn--caf-dma.example".to_string();
+        assert!(finalize_result(taken, &CheckConfig::default())
+            .note
+            .is_none());
+    }
+
+    #[test]
+    fn test_unregistered_idn_variants_not_noted() {
+        let json = serde_json::json!({
+            "status": ["pending delete"],
+            "variants": [{
+                "relation": ["unregistered", "registration restricted"],
+                "variantNames": [{"ldhName": "cafe.example"}]
+            }]
+        });
+        let config = CheckConfig::default().with_treat_dropping_as_available(true);
+
+        let mut result = rdap_taken_result(json);
+        result.domain = "xn--caf-dma.example".to_string();
+        let result = finalize_result(result, &config);
+        assert_eq!(result.available, Some(true));
+        assert!(!result.note.unwrap().contains("variants"));
+    }
+
+    #[tokio::test]
+    async fn test_policy_available_idn_with_registered_variant_is_noted() {
+        // The policy reports the held name available; its record lists a
+        // registered variant
+        let addr = test_http::serve(vec![test_http::rdap_json(
+            &serde_json::json!({
+                "objectClassName": "domain",
+                "ldhName": "xn--caf-dma.com",
+                "status": ["server hold"],
+                "variants": [{
+                    "relation": ["registered"],
+                    "variantNames": [{"ldhName": "cafe.com"}]
+                }]
+            })
+            .to_string(),
+        )])
+        .await;
+
+        let checker = DomainChecker::with_config(
+            CheckConfig::default()
+                .with_classification_policy(HoldIsAvailable)
+                .with_whois_fallback(false)
+                .with_bootstrap(false)
+                .with_rdap_server_override(format!("http://{}/domain/", addr)),
+        );
+        let result = checker.check_domain("xn--caf-dma.com").await.unwrap();
+
+        assert_eq!(result.available, Some(true));
+        let note = result.note.unwrap();
+        assert!(note.contains("IDN variants exist (cafe.com)"), "{}", note);
+    }
+
+    #[test]
+    fn test_redemption_period_flipped_when_enabled() {
+        let json = serde_json::json!({
+            "status": ["redemption period", "client transfer prohibited"]
+        });
+        let config = CheckConfig::default().with_treat_dropping_as_available(true);
+
+        let result = finalize_result(rdap_taken_result(json), &config);
+        assert_eq!(result.available, Some(true));
+        assert_eq!(result.confidence, Some(Confidence::Low));
+        assert!(result.note.unwrap().contains("redemption period"));
+        // Info is still filtered after the policy reads the status
+        assert!(result.info.is_none());
+    }
+
+    #[test]
+    fn test_pending_delete_camel_case_flipped() {
+        let json = serde_json::json!({ "status": ["pendingDelete"] });
+        let result = mark_dropping_available(rdap_taken_result(json));
+        assert_eq!(result.available, Some(true));
+        assert_eq!(result.confidence, Some(Confidence::Low));
+    }
+
+    #[test]
+    fn test_dropping_not_flipped_by_default() {
+        let json = serde_json::json!({ "status": ["redemption period"] });
+        let result = finalize_result(rdap_taken_result(json), &CheckConfig::default());
+        assert_eq!(result.available, Some(false));
+        assert!(result.confidence.is_none());
+        assert!(result.note.is_none());
+    }
+
+    #[test]
+    fn test_ordinary_taken_not_flipped() {
+        let json = serde_json::json!({ "status": ["client delete prohibited"] });
+        let config = CheckConfig::default().with_treat_dropping_as_available(true);
+        let result = finalize_result(rdap_taken_result(json), &config);
+        assert_eq!(result.available, Some(false));
+        assert!(result.confidence.is_none());
+    }
+
+    // ── ambiguous_status_policy ─────────────────────────────────────────
+
+    #[test]
+    fn test_server_hold_only_taken_by_default() {
+        let json = serde_json::json!({ "status": ["server hold"] });
+        let result = finalize_result(rdap_taken_result(json), &CheckConfig::default());
+        assert_eq!(result.available, Some(false));
+        assert!(result.confidence.is_none());
+    }
+
+    #[test]
+    fn test_server_hold_only_unknown_when_cautious() {
+        let json = serde_json::json!({ "status": ["serverHold"] });
+        let config = CheckConfig::default()
+            .with_ambiguous_status_policy(AmbiguousStatusPolicy::TreatAsUnknown);
+        let result = finalize_result(rdap_taken_result(json), &config);
+        assert_eq!(result.available, None);
+        assert_eq!(result.confidence, Some(Confidence::Low));
+        assert!(result.note.unwrap().contains("serverHold"));
+    }
+
+    #[test]
+    fn test_server_hold_with_registration_event_stays_taken() {
+        let json = serde_json::json!({
+            "status": ["server hold"],
+            "events": [{"eventAction": "registration", "eventDate": "2020-01-01T00:00:00Z"}]
+        });
+        let config = CheckConfig::default()
+            .with_ambiguous_status_policy(AmbiguousStatusPolicy::TreatAsUnknown);
+        let result = finalize_result(rdap_taken_result(json), &config);
+        assert_eq!(result.available, Some(false));
+    }
+
+    #[test]
+    fn test_server_hold_with_other_status_stays_taken() {
+        let json = serde_json::json!({ "status": ["server hold", "client transfer prohibited"] });
+        let config = CheckConfig::default()
+            .with_ambiguous_status_policy(AmbiguousStatusPolicy::TreatAsUnknown);
+        let result = finalize_result(rdap_taken_result(json), &config);
+        assert_eq!(result.available, Some(false));
+    }
+
+    // ── interleave_order ────────────────────────────────────────────────
+
+    #[test]
+    fn test_interleave_order_round_robins_endpoints() {
+        let domains: Vec<String> = [
+            "a.com", "b.com", "c.com", "a.io", "b.io", "a.org", "c.io", "d.com",
+        ]
+        .iter()
+        .map(|d| d.to_string())
+        .collect();
+
+        // Known mapping: endpoint is the TLD
+        let order = interleave_order(&domains, |d| d.rsplit('.').next().unwrap().to_string());
+        let launched: Vec<&str> = order.iter().map(|&i| domains[i].as_str()).collect();
+        assert_eq!(
+            launched,
+            vec!["a.com", "a.io", "a.org", "b.com", "b.io", "c.com", "c.io", "d.com"]
+        );
+    }
+
+    #[test]
+    fn test_interleave_order_groups_shared_hosts() {
+        // io and ai share a registry host, so they form one group
+        let domains: Vec<String> = ["x1.io", "x1.ai", "x1.com", "x2.io"]
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        let order = interleave_order(&domains, endpoint_key);
+        let launched: Vec<&str> = order.iter().map(|&i| domains[i].as_str()).collect();
+        assert_eq!(launched, vec!["x1.io", "x1.com", "x1.ai", "x2.io"]);
+    }
+
+    #[test]
+    fn test_interleave_order_is_permutation() {
+        let domains: Vec<String> = (0..20).map(|i| format!("n{}.t{}", i, i % 3)).collect();
+        let mut order = interleave_order(&domains, |d| d.rsplit('.').next().unwrap().to_string());
+        order.sort();
+        assert_eq!(order, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_interleave_order_single_endpoint_keeps_input_order() {
+        let domains: Vec<String> = ["a.com", "b.com", "c.com"]
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(interleave_order(&domains, |_| "same".into()), vec![0, 1, 2]);
+    }
+
+    // ── check_domains_with_events ───────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_check_domains_with_events_started_and_completed() {
+        // Invalid domains fail validation locally, so no network is needed
+        let checker = DomainChecker::new();
+        let domains = vec!["-bad-.com".to_string(), "x".to_string()];
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let results = checker
+            .check_domains_with_events(&domains, tx)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let mut started = Vec::new();
+        let mut completed = Vec::new();
+        while let Some(event) = rx.recv().await {
+            match event {
+                CheckEvent::Started { domain } => started.push(domain),
+                CheckEvent::Completed { result } => {
+                    assert!(
+                        started.contains(&result.domain),
+                        "Completed before Started for {}",
+                        result.domain
+                    );
+                    completed.push(result.domain);
+                }
+                other => panic!("unexpected event: {:?}", other),
+            }
+        }
+
+        started.sort();
+        completed.sort();
+        let mut expected = domains.clone();
+        expected.sort();
+        assert_eq!(started, expected);
+        assert_eq!(completed, expected);
+    }
+
+    #[tokio::test]
+    async fn test_check_domains_with_events_empty_closes_channel() {
+        let checker = DomainChecker::new();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let results = checker.check_domains_with_events(&[], tx).await.unwrap();
+        assert!(results.is_empty());
+        assert!(rx.recv().await.is_none());
+    }
+
+    // ── timing log ──────────────────────────────────────────────────────
+
+    /// RDAP server that answers every request with 404 after `delay`.
+    async fn slow_not_found_server(delay: std::time::Duration) -> std::net::SocketAddr {
+        test_http::serve_with(move |_| async move {
+            tokio::time::sleep(delay).await;
+            Some(test_http::empty("404 Not Found"))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_check_domains_chunked_calls_back_per_chunk() {
+        let addr = slow_not_found_server(std::time::Duration::ZERO).await;
+        let checker = DomainChecker::with_config(
+            CheckConfig::default()
+                .with_whois_fallback(false)
+                .with_bootstrap(false)
+                .with_rdap_server_override(format!("http://{}/domain/", addr)),
+        );
+        let domains: Vec<String> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| format!("{}.com", name))
+            .collect();
+
+        let mut chunks: Vec<Vec<String>> = Vec::new();
+        checker
+            .check_domains_chunked(&domains, 2, |results| {
+                assert!(results.iter().all(|r| r.available == Some(true)));
+                chunks.push(results.iter().map(|r| r.domain.clone()).collect());
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            chunks,
+            vec![
+                vec!["a.com".to_string(), "b.com".to_string()],
+                vec!["c.com".to_string(), "d.com".to_string()],
+                vec!["e.com".to_string()],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timing_log_records_one_row_per_domain() {
+        let addr = slow_not_found_server(std::time::Duration::from_millis(50)).await;
+        let run_start = Instant::now();
+        let log = crate::timings::TimingLog::new();
+        let checker = DomainChecker::with_config(
+            CheckConfig::default()
+                .with_concurrency(2)
+                .with_whois_fallback(false)
+                .with_bootstrap(false)
+                .with_rdap_server_override(format!("http://{}/domain/", addr))
+                .with_timing_log(log.clone()),
+        );
+        let domains: Vec<String> = ["a", "b", "c", "d", "e", "f"]
+            .iter()
+            .map(|name| format!("{}.com", name))
+            .collect();
+
+        let results = checker.check_domains(&domains).await.unwrap();
+        let elapsed_ms = run_start.elapsed().as_millis() as u64;
+        assert!(results.iter().all(|r| r.available == Some(true)));
+
+        let rows = log.rows();
+        let mut logged: Vec<&str> = rows.iter().map(|r| r.domain.as_str()).collect();
+        logged.sort();
+        assert_eq!(
+            logged,
+            ["a.com", "b.com", "c.com", "d.com", "e.com", "f.com"]
+        );
+
+        for row in &rows {
+            assert!(row.launched_ms <= row.started_ms, "{:?}", row);
+            assert!(row.started_ms <= row.completed_ms, "{:?}", row);
+            assert_eq!(row.duration_ms, row.completed_ms - row.started_ms);
+            assert!(row.duration_ms >= 50, "{:?}", row);
+            assert!(row.completed_ms <= elapsed_ms, "{:?}", row);
+            assert_eq!(row.method, CheckMethod::Rdap);
+        }
+        // Only two run at once, so later launches wait for a slot
+        assert!(
+            rows.iter().any(|r| r.started_ms - r.launched_ms >= 50),
+            "{:?}",
+            rows
+        );
+    }
+
+    // ── check_domains_to_sink ───────────────────────────────────────────
+
+    struct CountingSink {
+        count: std::sync::atomic::AtomicUsize,
+        domains: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl ResultSink for CountingSink {
+        async fn on_result(&self, result: DomainResult) {
+            self.count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.domains.lock().unwrap().push(result.domain);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_domains_to_sink_receives_every_result() {
+        // Invalid domains fail validation locally, so no network is needed
+        let checker = DomainChecker::new();
+        let domains = vec!["-a-.com".to_string(), "x".to_string(), "y".to_string()];
+        let sink = CountingSink {
+            count: std::sync::atomic::AtomicUsize::new(0),
+            domains: std::sync::Mutex::new(Vec::new()),
+        };
+
+        let delivered = checker
+            .check_domains_to_sink(&domains, &sink)
+            .await
+            .unwrap();
+        assert_eq!(delivered, 3);
+        assert_eq!(sink.count.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        let mut received = sink.domains.into_inner().unwrap();
+        received.sort();
+        let mut expected = domains.clone();
+        expected.sort();
+        assert_eq!(received, expected);
+    }
+
+    #[tokio::test]
+    async fn test_check_domains_to_sink_vec_sink() {
+        let checker = DomainChecker::new();
+        let sink = crate::sink::VecSink::new();
+        let delivered = checker
+            .check_domains_to_sink(&["x".to_string()], &sink)
+            .await
+            .unwrap();
+        assert_eq!(delivered, 1);
+        let results = sink.into_results();
+        assert_eq!(results[0].available, None);
+        assert!(results[0].error_message.is_some());
+    }
+
+    // ── filter_stream ───────────────────────────────────────────────────
+
+    fn status_result(domain: &str, available: Option<bool>) -> DomainResult {
+        DomainResult {
+            domain: domain.to_string(),
+            available,
+            info: None,
+            check_duration: None,
+            method_used: CheckMethod::Rdap,
+            error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
+        }
+    }
+
+    fn mixed_stream(
+    ) -> Pin<Box<dyn Stream<Item = Result<DomainResult, DomainCheckError>> + Send + 'static>> {
+        Box::pin(futures_util::stream::iter(vec![
+            Ok(status_result("free.com", Some(true))),
+            Ok(status_result("taken.com", Some(false))),
+            Ok(status_result("unknown.com", None)),
+            Err(DomainCheckError::invalid_domain("bad", "no TLD")),
+            Ok(status_result("free.org", Some(true))),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_filter_stream_available_only() {
+        let items: Vec<_> = filter_stream(mixed_stream(), StreamFilter::AvailableOnly)
+            .collect()
+            .await;
+        let domains: Vec<String> = items
+            .iter()
+            .filter_map(|r| r.as_ref().ok())
+            .map(|r| r.domain.clone())
+            .collect();
+        assert_eq!(domains, vec!["free.com", "free.org"]);
+        assert!(items
+            .iter()
+            .filter_map(|r| r.as_ref().ok())
+            .all(|r| r.available == Some(true)));
+        // The error still comes through
+        assert_eq!(items.iter().filter(|r| r.is_err()).count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_filter_stream_taken_only_and_all() {
+        let taken: Vec<_> = filter_stream(mixed_stream(), StreamFilter::TakenOnly)
+            .filter_map(|r| std::future::ready(r.ok()))
+            .collect()
+            .await;
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].domain, "taken.com");
+
+        let all: Vec<_> = filter_stream(mixed_stream(), StreamFilter::default())
+            .collect()
+            .await;
+        assert_eq!(all.len(), 5);
+    }
+
+    // ── bounded_result_stream ───────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_bounded_result_stream_applies_backpressure() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let started = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&started);
+        let items: Vec<String> = (0..30).map(|i| format!("d{}.com", i)).collect();
+
+        let mut stream = bounded_result_stream(items, 2, 1, false, move |domain| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { domain }
+        });
+
+        let mut consumed = 0;
+        let mut max_outstanding = 0;
+        while stream.next().await.is_some() {
+            consumed += 1;
+            // Deliberately slow consumer: the producer would race ahead if unbounded
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            let outstanding = started.load(Ordering::SeqCst) - consumed;
+            max_outstanding = max_outstanding.max(outstanding);
+        }
+
+        assert_eq!(consumed, 30);
+        // concurrency (2) + channel capacity (1) + one result waiting on send
+        assert!(max_outstanding <= 4, "outstanding {}", max_outstanding);
+    }
+
+    #[tokio::test]
+    async fn test_stream_with_result_buffer_yields_every_domain() {
+        let checker = DomainChecker::with_config(CheckConfig::default().with_result_buffer(1));
+        let domains = vec!["invalid".to_string(), "also-invalid".to_string()];
+
+        let results: Vec<_> = checker.check_domains_stream(&domains).collect().await;
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.is_err()));
+    }
+
+    /// RDAP server that answers 404 after a delay that shrinks with each
+    /// domain's number (`d0.com` slowest), so completion order reverses
+    /// input order.
+    async fn reverse_delay_server() -> std::net::SocketAddr {
+        test_http::serve_with(|request| async move {
+            let index: u64 = test_http::request_path(&request)
+                .trim_start_matches("/domain/d")
+                .split('.')
+                .next()
+                .and_then(|i| i.parse().ok())
+                .unwrap_or(0);
+            let delay = std::time::Duration::from_millis((6 - index.min(6)) * 40);
+            tokio::time::sleep(delay).await;
+            Some(test_http::empty("404 Not Found"))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_order_streams_in_input_order() {
+        let addr = reverse_delay_server().await;
+        let domains: Vec<String> = (0..6).map(|i| format!("d{}.com", i)).collect();
+        let base = CheckConfig::default()
+            .with_concurrency(6)
+            .with_whois_fallback(false)
+            .with_bootstrap(false)
+            .with_rdap_server_override(format!("http://{}/domain/", addr));
+
+        let order_of = |config: CheckConfig| {
+            let domains = domains.clone();
+            async move {
+                let checker = DomainChecker::with_config(config);
+                checker
+                    .check_domains_stream(&domains)
+                    .map(|r| r.unwrap().domain)
+                    .collect::<Vec<_>>()
+                    .await
+            }
+        };
+
+        let ordered = base.clone().with_deterministic_order(true);
+        for _ in 0..3 {
+            assert_eq!(order_of(ordered.clone()).await, domains);
+        }
+        // Bounded handoff keeps the same order
+        let buffered = ordered.with_result_buffer(1);
+        assert_eq!(order_of(buffered).await, domains);
+
+        // Completion order: the slowest (first) domain arrives last
+        let unordered = order_of(base).await;
+        assert_eq!(unordered.last().map(String::as_str), Some("d0.com"));
+    }
+
+    // ── race_protocols ──────────────────────────────────────────────────
+
+    fn method_result(method: CheckMethod) -> DomainResult {
+        DomainResult {
+            method_used: method,
+            ..status_result("example.com", Some(false))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_race_returns_whois_when_rdap_is_slow() {
+        let rdap = async {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            Ok(method_result(CheckMethod::Rdap))
+        };
+        let whois = async { Ok(method_result(CheckMethod::Whois)) };
+
+        let start = std::time::Instant::now();
+        let result = race_protocols(rdap, whois).await.unwrap();
+        assert_eq!(result.method_used, CheckMethod::Whois);
+        // The delayed RDAP lookup was cancelled rather than awaited
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_race_prefers_rdap_on_tie() {
+        let rdap = async { Ok(method_result(CheckMethod::Rdap)) };
+        let whois = async { Ok(method_result(CheckMethod::Whois)) };
+
+        let result = race_protocols(rdap, whois).await.unwrap();
+        assert_eq!(result.method_used, CheckMethod::Rdap);
+    }
+
+    #[tokio::test]
+    async fn test_race_waits_for_other_protocol_after_error() {
+        let rdap = async { Err(DomainCheckError::rdap("example.com", "server error")) };
+        let whois = async {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            Ok(method_result(CheckMethod::Whois))
+        };
+
+        let result = race_protocols(rdap, whois).await.unwrap();
+        assert_eq!(result.method_used, CheckMethod::Whois);
+    }
+
+    #[tokio::test]
+    async fn test_whois_answer_keeps_rdap_status() {
+        let rdap = async {
+            Err(DomainCheckError::rdap_with_status(
+                "example.com",
+                "service unavailable",
+                503,
+            ))
+        };
+        let whois = async { Ok(method_result(CheckMethod::Whois)) };
+
+        let result = race_protocols(rdap, whois).await.unwrap();
+        assert_eq!(result.method_used, CheckMethod::Whois);
+        assert_eq!(result.rdap_status, Some(503));
+
+        // Sequential fallback path
+        let error = DomainCheckError::rdap_with_status("example.com", "rate limited", 429);
+        let kept = keep_rdap_status(method_result(CheckMethod::Whois), &error);
+        assert_eq!(kept.rdap_status, Some(429));
+        let unreached = DomainCheckError::rdap("example.com", "connection refused");
+        let kept = keep_rdap_status(method_result(CheckMethod::Whois), &unreached);
+        assert_eq!(kept.rdap_status, None);
+    }
+
+    #[tokio::test]
+    async fn test_race_returns_both_errors() {
+        let rdap = async { Err(DomainCheckError::rdap("example.com", "server error")) };
+        let whois = async { Err(DomainCheckError::whois("example.com", "no answer")) };
+
+        let (rdap_error, whois_error) = race_protocols(rdap, whois).await.unwrap_err();
+        assert!(matches!(rdap_error, DomainCheckError::RdapError { .. }));
+        assert!(matches!(whois_error, DomainCheckError::WhoisError { .. }));
+    }
+
+    // ── Per-TLD method preference ───────────────────────────────────────
+
+    #[test]
+    fn test_prefers_whois_only_for_configured_tlds() {
+        let config = CheckConfig::default()
+            .with_tld_method("de", CheckMethod::Whois)
+            .with_tld_method("com", CheckMethod::Rdap);
+        assert!(prefers_whois("example.de", &config));
+        assert!(prefers_whois("EXAMPLE.DE", &config));
+        assert!(!prefers_whois("example.com", &config));
+        assert!(!prefers_whois("example.org", &config));
+        assert!(!prefers_whois("example.de", &CheckConfig::default()));
+    }
+
+    #[tokio::test]
+    async fn test_whois_preferred_tld_skips_rdap() {
+        let rdap_called = std::sync::atomic::AtomicBool::new(false);
+        let whois = async { Ok(method_result(CheckMethod::Whois)) };
+        let rdap = async {
+            rdap_called.store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(method_result(CheckMethod::Rdap))
+        };
+
+        let result = whois_then_rdap(whois, rdap).await.unwrap();
+        assert_eq!(result.method_used, CheckMethod::Whois);
+        assert!(!rdap_called.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_whois_preferred_tld_falls_back_to_rdap() {
+        let whois = async { Err(DomainCheckError::whois("example.de", "no answer")) };
+        let rdap = async { Ok(method_result(CheckMethod::Rdap)) };
+        let result = whois_then_rdap(whois, rdap).await.unwrap();
+        assert_eq!(result.method_used, CheckMethod::Rdap);
+
+        let whois = async { Err(DomainCheckError::whois("example.de", "no answer")) };
+        let rdap = async { Err(DomainCheckError::rdap("example.de", "server error")) };
+        let (rdap_error, whois_error) = whois_then_rdap(whois, rdap).await.unwrap_err();
+        assert!(matches!(rdap_error, DomainCheckError::RdapError { .. }));
+        assert!(matches!(whois_error, DomainCheckError::WhoisError { .. }));
+    }
+
+    // ── WHOIS failover ──────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_whois_failover_tries_each_bundled_server() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let dead = test_http::closed_addr().await;
+        let live = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live_addr = live.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = live.accept().await.unwrap();
+            let mut query = [0u8; 256];
+            let _ = stream.read(&mut query).await;
+            let _ = stream
+                .write_all(b"No match for domain \"EXAMPLE-UNUSED.COM\".\r\n")
+                .await;
+        });
+
+        // The first bundled server refuses; the second answers, whether or
+        // not a system `whois` is installed
+        let result = whois_with_failover(
+            "example-unused.com",
+            &WhoisClient::new(),
+            &[dead.to_string(), live_addr.to_string()],
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.available, Some(true));
+        assert_eq!(result.method_used, CheckMethod::Whois);
+    }
+
     // ── check_domains with empty list ───────────────────────────────────
 
     #[tokio::test]
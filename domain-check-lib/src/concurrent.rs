@@ -1,9 +1,168 @@
 //! Concurrent processing utilities for domain checking.
 //!
-//! This module provides utilities for managing concurrent domain checks,
-//! including rate limiting, semaphore management, and result collection.
-//!
-//! Currently, concurrent processing is handled directly in the checker module
-//! using tokio primitives. This module is reserved for future enhancements.
+//! This module provides the semaphore that bounds in-flight domain checks,
+//! including the optional warm-up ramp applied at the start of a run, and
+//! the per-host limiter that caps requests to any single RDAP server.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Build the semaphore that caps in-flight checks at `concurrency`.
+///
+/// With a `ramp` (see `CheckConfig::concurrency_ramp`), the semaphore starts
+/// with a single permit and a background task adds the rest at even
+/// intervals across the window, so callers that run their own check loop can
+/// warm up the same way `DomainChecker` does. Spawning the ramp task requires
+/// a Tokio runtime.
+pub fn concurrency_limiter(concurrency: usize, ramp: Option<Duration>) -> Arc<Semaphore> {
+    let concurrency = concurrency.max(1);
+    let ramp = match ramp {
+        Some(ramp) if !ramp.is_zero() && concurrency > 1 => ramp,
+        _ => return Arc::new(Semaphore::new(concurrency)),
+    };
+
+    let semaphore = Arc::new(Semaphore::new(1));
+    let growing = Arc::clone(&semaphore);
+    let step = ramp / (concurrency - 1) as u32;
+    tokio::spawn(async move {
+        for _ in 1..concurrency {
+            tokio::time::sleep(step).await;
+            growing.add_permits(1);
+        }
+    });
+    semaphore
+}
+
+/// Caps in-flight requests per host, on top of the global limit.
+///
+/// Each host gets its own semaphore the first time it is seen; clones of a
+/// client share the limiter through an `Arc`.
+#[derive(Debug)]
+pub(crate) struct HostLimiter {
+    per_host: usize,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl HostLimiter {
+    pub(crate) fn new(per_host: usize) -> Self {
+        Self {
+            per_host: per_host.max(1),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait for a request slot on `host`; the slot is freed when the permit drops.
+    pub(crate) async fn acquire(&self, host: &str) -> OwnedSemaphorePermit {
+        let semaphore = {
+            let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+            Arc::clone(
+                hosts
+                    .entry(host.to_lowercase())
+                    .or_insert_with(|| Arc::new(Semaphore::new(self.per_host))),
+            )
+        };
+        semaphore
+            .acquire_owned()
+            .await
+            .expect("host semaphore is never closed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── concurrency_limiter ─────────────────────────────────────────────
+
+    /// Run `calls` mock checks that each hold a permit for `hold`, returning
+    /// the peak number in flight before `window` elapses and overall.
+    async fn peak_in_flight(
+        semaphore: Arc<Semaphore>,
+        calls: usize,
+        hold: Duration,
+        window: Duration,
+    ) -> (usize, usize) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let early_peak = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let start = tokio::time::Instant::now();
+
+        let handles: Vec<_> = (0..calls)
+            .map(|_| {
+                let semaphore = Arc::clone(&semaphore);
+                let (in_flight, early_peak, peak) = (
+                    Arc::clone(&in_flight),
+                    Arc::clone(&early_peak),
+                    Arc::clone(&peak),
+                );
+                tokio::spawn(async move {
+                    let _permit = semaphore.acquire().await.unwrap();
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    if start.elapsed() < window {
+                        early_peak.fetch_max(now, Ordering::SeqCst);
+                    }
+                    tokio::time::sleep(hold).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        (
+            early_peak.load(Ordering::SeqCst),
+            peak.load(Ordering::SeqCst),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_ramp_limits_early_in_flight() {
+        let semaphore = concurrency_limiter(10, Some(Duration::from_millis(450)));
+        let (early, peak) = peak_in_flight(
+            semaphore,
+            10,
+            Duration::from_millis(700),
+            Duration::from_millis(150),
+        )
+        .await;
+
+        assert!(early < 10, "{} in flight during the ramp", early);
+        assert_eq!(peak, 10);
+    }
+
+    #[tokio::test]
+    async fn test_no_ramp_starts_at_full_concurrency() {
+        let semaphore = concurrency_limiter(10, None);
+        let (early, _) = peak_in_flight(
+            semaphore,
+            10,
+            Duration::from_millis(200),
+            Duration::from_millis(100),
+        )
+        .await;
+        assert_eq!(early, 10);
+    }
+
+    // ── HostLimiter ─────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_host_limiter_caps_each_host_separately() {
+        let limiter = HostLimiter::new(2);
+        let a1 = limiter.acquire("rdap.a.test").await;
+        let _a2 = limiter.acquire("RDAP.A.test").await;
+
+        // A third slot on the same host waits; other hosts are unaffected
+        let third = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("rdap.a.test"));
+        assert!(third.await.is_err());
+        let _b = limiter.acquire("rdap.b.test").await;
 
-// Future: Implement advanced concurrent processing utilities
+        drop(a1);
+        let third = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("rdap.a.test"));
+        assert!(third.await.is_ok());
+    }
+}
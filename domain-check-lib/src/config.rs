@@ -4,6 +4,7 @@
 //! configurations with proper precedence rules.
 
 use crate::error::DomainCheckError;
+use crate::types::CheckMethod;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
@@ -35,6 +36,19 @@ pub struct FileConfig {
     /// Domain generation defaults (prefixes/suffixes)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub generation: Option<GenerationConfig>,
+
+    /// TLD alias groups: primary TLD → alternatives to try when it's taken
+    /// (e.g. `com = ["co", "io"]`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tld_aliases: Option<HashMap<String, Vec<String>>>,
+
+    /// Per-TLD RDAP timeouts for slow registries (e.g. `ch = "15s"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tld_timeouts: Option<HashMap<String, String>>,
+
+    /// Per-TLD protocol to try first, "rdap" or "whois" (e.g. `de = "whois"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tld_methods: Option<HashMap<String, String>>,
 }
 
 /// Default configuration values that map to CLI options.
@@ -71,16 +85,20 @@ pub struct DefaultsConfig {
     /// Default detailed info setting
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detailed_info: Option<bool>,
+
+    /// TLDs to always drop from the expanded domain set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_tlds: Option<Vec<String>>,
 }
 
-/// Monitoring configuration (placeholder for future features).
+/// Monitoring configuration used by `--watch`.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct MonitoringConfig {
     /// Monitoring interval
     #[serde(skip_serializing_if = "Option::is_none")]
     pub interval: Option<String>,
 
-    /// Command to run on changes
+    /// Shell command run for each domain that becomes available
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notify_command: Option<String>,
 }
@@ -151,11 +169,14 @@ impl ConfigManager {
             )
         })?;
 
-        let config: FileConfig =
+        let mut config: FileConfig =
             toml::from_str(&content).map_err(|e| DomainCheckError::ConfigError {
                 message: format!("Failed to parse TOML configuration: {}", e),
             })?;
 
+        // Accept ".com" / "COM" and store them as "com"
+        normalize_config_tlds(&mut config);
+
         // Validate the loaded configuration
         self.validate_config(&config)?;
 
@@ -275,10 +296,11 @@ impl ConfigManager {
                     if higher_defaults.concurrency.is_some() {
                         lower_defaults.concurrency = higher_defaults.concurrency;
                     }
-                    if higher_defaults.preset.is_some() {
+                    // `preset` and `tlds` are one TLD source: a file that sets
+                    // either replaces the lower file's choice entirely, so a
+                    // local preset isn't shadowed by a global TLD list
+                    if higher_defaults.preset.is_some() || higher_defaults.tlds.is_some() {
                         lower_defaults.preset = higher_defaults.preset;
-                    }
-                    if higher_defaults.tlds.is_some() {
                         lower_defaults.tlds = higher_defaults.tlds;
                     }
                     if higher_defaults.pretty.is_some() {
@@ -296,6 +318,9 @@ impl ConfigManager {
                     if higher_defaults.detailed_info.is_some() {
                         lower_defaults.detailed_info = higher_defaults.detailed_info;
                     }
+                    if higher_defaults.exclude_tlds.is_some() {
+                        lower_defaults.exclude_tlds = higher_defaults.exclude_tlds;
+                    }
                     Some(lower_defaults)
                 }
                 (None, Some(higher_defaults)) => Some(higher_defaults),
@@ -328,6 +353,36 @@ impl ConfigManager {
                 (Some(lower_gen), None) => Some(lower_gen),
                 (None, None) => None,
             },
+            tld_aliases: match (lower.tld_aliases, higher.tld_aliases) {
+                (Some(mut lower_aliases), Some(higher_aliases)) => {
+                    // Merge alias groups, higher precedence wins for conflicts
+                    lower_aliases.extend(higher_aliases);
+                    Some(lower_aliases)
+                }
+                (None, Some(higher_aliases)) => Some(higher_aliases),
+                (Some(lower_aliases), None) => Some(lower_aliases),
+                (None, None) => None,
+            },
+            tld_timeouts: match (lower.tld_timeouts, higher.tld_timeouts) {
+                (Some(mut lower_timeouts), Some(higher_timeouts)) => {
+                    // Merge per-TLD timeouts, higher precedence wins for conflicts
+                    lower_timeouts.extend(higher_timeouts);
+                    Some(lower_timeouts)
+                }
+                (None, Some(higher_timeouts)) => Some(higher_timeouts),
+                (Some(lower_timeouts), None) => Some(lower_timeouts),
+                (None, None) => None,
+            },
+            tld_methods: match (lower.tld_methods, higher.tld_methods) {
+                (Some(mut lower_methods), Some(higher_methods)) => {
+                    // Merge per-TLD methods, higher precedence wins for conflicts
+                    lower_methods.extend(higher_methods);
+                    Some(lower_methods)
+                }
+                (None, Some(higher_methods)) => Some(higher_methods),
+                (Some(lower_methods), None) => Some(lower_methods),
+                (None, None) => None,
+            },
         }
     }
 
@@ -355,6 +410,15 @@ impl ConfigManager {
                 }
             }
 
+            // Validate excluded TLD format
+            for tld in defaults.exclude_tlds.iter().flatten() {
+                if !is_valid_tld(tld) {
+                    return Err(DomainCheckError::ConfigError {
+                        message: format!("Invalid TLD '{}' in exclude_tlds", tld),
+                    });
+                }
+            }
+
             // Validate that preset and tlds are not both specified
             if defaults.preset.is_some() && defaults.tlds.is_some() {
                 return Err(DomainCheckError::ConfigError {
@@ -380,7 +444,7 @@ impl ConfigManager {
 
                 // Basic TLD format validation
                 for tld in tlds {
-                    if tld.is_empty() || tld.contains('.') || tld.contains(' ') {
+                    if !is_valid_tld(tld) {
                         return Err(DomainCheckError::ConfigError {
                             message: format!("Invalid TLD '{}' in preset '{}'", tld, name),
                         });
@@ -389,6 +453,66 @@ impl ConfigManager {
             }
         }
 
+        // Validate TLD aliases
+        if let Some(aliases) = &config.tld_aliases {
+            for (primary, alternatives) in aliases {
+                if !is_valid_tld(primary) {
+                    return Err(DomainCheckError::ConfigError {
+                        message: format!("Invalid primary TLD '{}' in tld_aliases", primary),
+                    });
+                }
+
+                for tld in alternatives {
+                    if !is_valid_tld(tld) {
+                        return Err(DomainCheckError::ConfigError {
+                            message: format!(
+                                "Invalid TLD '{}' in tld_aliases for '{}'",
+                                tld, primary
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        // Validate per-TLD timeouts
+        if let Some(timeouts) = &config.tld_timeouts {
+            for (tld, timeout_str) in timeouts {
+                if !is_valid_tld(tld) {
+                    return Err(DomainCheckError::ConfigError {
+                        message: format!("Invalid TLD '{}' in tld_timeouts", tld),
+                    });
+                }
+                if parse_timeout_string(timeout_str).is_none() {
+                    return Err(DomainCheckError::ConfigError {
+                        message: format!(
+                            "Invalid timeout '{}' for '{}' in tld_timeouts. Use format like '5s', '30s', '2m'",
+                            timeout_str, tld
+                        ),
+                    });
+                }
+            }
+        }
+
+        // Validate per-TLD method preferences
+        if let Some(methods) = &config.tld_methods {
+            for (tld, method) in methods {
+                if !is_valid_tld(tld) {
+                    return Err(DomainCheckError::ConfigError {
+                        message: format!("Invalid TLD '{}' in tld_methods", tld),
+                    });
+                }
+                if parse_check_method(method).is_none() {
+                    return Err(DomainCheckError::ConfigError {
+                        message: format!(
+                            "Invalid method '{}' for '{}' in tld_methods. Use 'rdap' or 'whois'",
+                            method, tld
+                        ),
+                    });
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -701,6 +825,86 @@ impl EnvConfig {
     pub fn has_output_format_conflict(&self) -> bool {
         matches!((self.json, self.csv), (Some(true), Some(true)))
     }
+
+    /// Check if both TLD sources are set (DC_TLD and DC_PRESET).
+    pub fn has_tld_source_conflict(&self) -> bool {
+        self.tlds.is_some() && self.preset.is_some()
+    }
+}
+
+/// Normalize a TLD from a config file: strip one leading dot and lowercase.
+fn normalize_tld(tld: &str) -> String {
+    tld.strip_prefix('.').unwrap_or(tld).to_lowercase()
+}
+
+/// Check whether a config TLD is a single label once normalized.
+fn is_valid_tld(tld: &str) -> bool {
+    let tld = normalize_tld(tld);
+    !tld.is_empty() && !tld.contains('.') && !tld.contains(char::is_whitespace)
+}
+
+/// Normalize every TLD list in a loaded config file in place.
+fn normalize_config_tlds(config: &mut FileConfig) {
+    let normalize_all = |tlds: &mut Vec<String>| {
+        for tld in tlds.iter_mut() {
+            *tld = normalize_tld(tld);
+        }
+    };
+
+    if let Some(tlds) = config.defaults.as_mut().and_then(|d| d.tlds.as_mut()) {
+        normalize_all(tlds);
+    }
+
+    if let Some(tlds) = config
+        .defaults
+        .as_mut()
+        .and_then(|d| d.exclude_tlds.as_mut())
+    {
+        normalize_all(tlds);
+    }
+
+    if let Some(presets) = config.custom_presets.as_mut() {
+        presets.values_mut().for_each(normalize_all);
+    }
+
+    if let Some(timeouts) = config.tld_timeouts.take() {
+        config.tld_timeouts = Some(
+            timeouts
+                .into_iter()
+                .map(|(tld, timeout)| (normalize_tld(&tld), timeout))
+                .collect(),
+        );
+    }
+
+    if let Some(methods) = config.tld_methods.take() {
+        config.tld_methods = Some(
+            methods
+                .into_iter()
+                .map(|(tld, method)| (normalize_tld(&tld), method))
+                .collect(),
+        );
+    }
+
+    if let Some(aliases) = config.tld_aliases.take() {
+        config.tld_aliases = Some(
+            aliases
+                .into_iter()
+                .map(|(primary, mut alternatives)| {
+                    normalize_all(&mut alternatives);
+                    (normalize_tld(&primary), alternatives)
+                })
+                .collect(),
+        );
+    }
+}
+
+/// Parse a `[tld_methods]` value ("rdap" or "whois", any case).
+pub fn parse_check_method(method: &str) -> Option<CheckMethod> {
+    match method.trim().to_lowercase().as_str() {
+        "rdap" => Some(CheckMethod::Rdap),
+        "whois" => Some(CheckMethod::Whois),
+        _ => None,
+    }
 }
 
 /// Parse a timeout string like "5s", "30s", "2m" into seconds.
@@ -898,6 +1102,35 @@ concurrency = 50
         assert!(err.to_string().contains("TOML"));
     }
 
+    #[test]
+    fn test_load_exclude_tlds_normalized() {
+        let f = write_temp_config(
+            r#"
+[defaults]
+exclude_tlds = [".LOAN", "top"]
+"#,
+        );
+
+        let config = ConfigManager::new(false).load_file(f.path()).unwrap();
+        assert_eq!(
+            config.defaults.unwrap().exclude_tlds,
+            Some(vec!["loan".to_string(), "top".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_load_exclude_tlds_rejects_multi_label() {
+        let f = write_temp_config(
+            r#"
+[defaults]
+exclude_tlds = ["co.uk"]
+"#,
+        );
+
+        let err = ConfigManager::new(false).load_file(f.path()).unwrap_err();
+        assert!(err.to_string().contains("exclude_tlds"));
+    }
+
     // ── Validation: concurrency ─────────────────────────────────────────
 
     #[test]
@@ -1072,6 +1305,64 @@ tlds = ["com", "org"]
         assert!(result.unwrap_err().to_string().contains("Invalid TLD"));
     }
 
+    #[test]
+    fn test_validate_custom_preset_leading_dot_accepted() {
+        let manager = ConfigManager::new(false);
+        let config = FileConfig {
+            custom_presets: Some(HashMap::from([(
+                "mine".to_string(),
+                vec![".com".to_string(), ".IO".to_string()],
+            )])),
+            ..Default::default()
+        };
+        assert!(manager.validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_custom_preset_rejects_dotted_after_strip() {
+        let manager = ConfigManager::new(false);
+        for bad in ["..com", ".co.uk", ".co m"] {
+            let config = FileConfig {
+                custom_presets: Some(HashMap::from([("bad".to_string(), vec![bad.to_string()])])),
+                ..Default::default()
+            };
+            let result = manager.validate_config(&config);
+            assert!(result.is_err(), "'{}' should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn test_load_normalizes_leading_dot_tlds() {
+        let f = write_temp_config(
+            "[defaults]\ntlds = [\".Com\", \"org\"]\n\n\
+             [custom_presets]\nmine = [\".com\", \".io\"]\n\n\
+             [tld_aliases]\n\".com\" = [\".net\", \"CO\"]\n",
+        );
+        let manager = ConfigManager::new(false);
+        let config = manager.load_file(f.path()).unwrap();
+
+        assert_eq!(
+            config.defaults.unwrap().tlds.unwrap(),
+            vec!["com".to_string(), "org".to_string()]
+        );
+        assert_eq!(
+            config.custom_presets.unwrap()["mine"],
+            vec!["com".to_string(), "io".to_string()]
+        );
+        assert_eq!(
+            config.tld_aliases.unwrap()["com"],
+            vec!["net".to_string(), "co".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_tld_with_space() {
+        let f = write_temp_config("[custom_presets]\nmine = [\"co m\"]\n");
+        let manager = ConfigManager::new(false);
+        let err = manager.load_file(f.path()).unwrap_err();
+        assert!(err.to_string().contains("Invalid TLD 'co m'"));
+    }
+
     #[test]
     fn test_validate_valid_custom_preset() {
         let manager = ConfigManager::new(false);
@@ -1154,6 +1445,31 @@ tlds = ["com", "org"]
         assert!(merged.defaults.is_none());
     }
 
+    #[test]
+    fn test_merge_higher_preset_replaces_lower_tlds() {
+        let manager = ConfigManager::new(false);
+        let lower = FileConfig {
+            defaults: Some(DefaultsConfig {
+                tlds: Some(vec!["com".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let higher = FileConfig {
+            defaults: Some(DefaultsConfig {
+                preset: Some("startup".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let merged = manager.merge_configs(lower, higher);
+        assert!(manager.validate_config(&merged).is_ok());
+        let d = merged.defaults.unwrap();
+        assert_eq!(d.preset, Some("startup".to_string()));
+        assert_eq!(d.tlds, None);
+    }
+
     #[test]
     fn test_merge_all_default_fields() {
         let manager = ConfigManager::new(false);
@@ -1167,6 +1483,7 @@ tlds = ["com", "org"]
                 whois_fallback: Some(true),
                 bootstrap: Some(false),
                 detailed_info: Some(false),
+                exclude_tlds: Some(vec!["loan".to_string()]),
             }),
             ..Default::default()
         };
@@ -1180,6 +1497,7 @@ tlds = ["com", "org"]
                 whois_fallback: Some(false),
                 bootstrap: Some(true),
                 detailed_info: Some(true),
+                exclude_tlds: Some(vec!["top".to_string()]),
             }),
             ..Default::default()
         };
@@ -1194,6 +1512,7 @@ tlds = ["com", "org"]
         assert_eq!(d.whois_fallback, Some(false));
         assert_eq!(d.bootstrap, Some(true));
         assert_eq!(d.detailed_info, Some(true));
+        assert_eq!(d.exclude_tlds, Some(vec!["top".to_string()]));
     }
 
     #[test]
@@ -1414,6 +1733,113 @@ notify_command = "echo done"
         assert_eq!(mon.notify_command, Some("echo done".to_string()));
     }
 
+    #[test]
+    fn test_load_tld_aliases_config() {
+        let f = write_temp_config(
+            r#"
+[tld_aliases]
+com = ["co", "io"]
+net = ["org"]
+"#,
+        );
+        let manager = ConfigManager::new(false);
+        let config = manager.load_file(f.path()).unwrap();
+        let aliases = config.tld_aliases.unwrap();
+        assert_eq!(aliases.get("com"), Some(&vec!["co".into(), "io".into()]));
+        assert_eq!(aliases.get("net"), Some(&vec!["org".into()]));
+    }
+
+    #[test]
+    fn test_validate_tld_aliases_invalid_tld() {
+        let manager = ConfigManager::new(false);
+        let config = FileConfig {
+            tld_aliases: Some(HashMap::from([(
+                "com".to_string(),
+                vec!["co".to_string(), "bad.tld".to_string()],
+            )])),
+            ..Default::default()
+        };
+        let err = manager.validate_config(&config).unwrap_err();
+        assert!(format!("{:?}", err).contains("tld_aliases"));
+    }
+
+    #[test]
+    fn test_merge_tld_aliases_higher_wins() {
+        let manager = ConfigManager::new(false);
+        let lower = FileConfig {
+            tld_aliases: Some(HashMap::from([
+                ("com".to_string(), vec!["net".to_string()]),
+                ("org".to_string(), vec!["ngo".to_string()]),
+            ])),
+            ..Default::default()
+        };
+        let higher = FileConfig {
+            tld_aliases: Some(HashMap::from([(
+                "com".to_string(),
+                vec!["co".to_string(), "io".to_string()],
+            )])),
+            ..Default::default()
+        };
+        let merged = manager.merge_configs(lower, higher);
+        let aliases = merged.tld_aliases.unwrap();
+        assert_eq!(aliases.get("com"), Some(&vec!["co".into(), "io".into()]));
+        assert_eq!(aliases.get("org"), Some(&vec!["ngo".into()]));
+    }
+
+    #[test]
+    fn test_load_tld_timeouts_config() {
+        let f = write_temp_config(
+            r#"
+[tld_timeouts]
+".CH" = "15s"
+de = "1m"
+"#,
+        );
+        let manager = ConfigManager::new(false);
+        let config = manager.load_file(f.path()).unwrap();
+        let timeouts = config.tld_timeouts.unwrap();
+        assert_eq!(timeouts.get("ch"), Some(&"15s".to_string()));
+        assert_eq!(timeouts.get("de"), Some(&"1m".to_string()));
+    }
+
+    #[test]
+    fn test_validate_tld_timeouts_invalid_timeout() {
+        let manager = ConfigManager::new(false);
+        let config = FileConfig {
+            tld_timeouts: Some(HashMap::from([("ch".to_string(), "soon".to_string())])),
+            ..Default::default()
+        };
+        let err = manager.validate_config(&config).unwrap_err();
+        assert!(format!("{:?}", err).contains("tld_timeouts"));
+    }
+
+    #[test]
+    fn test_load_tld_methods_config() {
+        let f = write_temp_config(
+            r#"
+[tld_methods]
+".DE" = "whois"
+com = "RDAP"
+"#,
+        );
+        let manager = ConfigManager::new(false);
+        let config = manager.load_file(f.path()).unwrap();
+        let methods = config.tld_methods.unwrap();
+        assert_eq!(methods.get("de"), Some(&"whois".to_string()));
+        assert_eq!(parse_check_method(&methods["com"]), Some(CheckMethod::Rdap));
+    }
+
+    #[test]
+    fn test_validate_tld_methods_invalid_method() {
+        let manager = ConfigManager::new(false);
+        let config = FileConfig {
+            tld_methods: Some(HashMap::from([("de".to_string(), "dns".to_string())])),
+            ..Default::default()
+        };
+        let err = manager.validate_config(&config).unwrap_err();
+        assert!(format!("{:?}", err).contains("tld_methods"));
+    }
+
     // ── TOML serialization round-trip ───────────────────────────────────
 
     #[test]
@@ -1538,6 +1964,17 @@ notify_command = "echo done"
         assert!(!env.has_output_format_conflict());
     }
 
+    #[test]
+    fn test_has_tld_source_conflict() {
+        let mut env = EnvConfig {
+            tlds: Some(vec!["com".to_string()]),
+            ..Default::default()
+        };
+        assert!(!env.has_tld_source_conflict());
+        env.preset = Some("startup".to_string());
+        assert!(env.has_tld_source_conflict());
+    }
+
     #[test]
     fn test_has_output_format_conflict_none() {
         let env = EnvConfig::default();
@@ -0,0 +1,144 @@
+//! Mixed-script and homoglyph detection for internationalized domain names.
+//!
+//! Punycode (`xn--`) labels are decoded before analysis, so both raw Unicode
+//! input and its ASCII-compatible form are checked the same way. Detection
+//! is deliberately conservative: only Latin, Greek and Cyrillic are compared,
+//! since those are the scripts whose letters are routinely mistaken for one
+//! another. Labels mixing Latin with CJK or other scripts are not flagged.
+
+/// Scripts relevant to homoglyph detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+}
+
+impl Script {
+    fn name(self) -> &'static str {
+        match self {
+            Script::Latin => "Latin",
+            Script::Greek => "Greek",
+            Script::Cyrillic => "Cyrillic",
+        }
+    }
+}
+
+/// Cyrillic letters that render like Latin letters.
+const CYRILLIC_LOOKALIKES: &[char] = &[
+    'а', 'в', 'е', 'к', 'м', 'н', 'о', 'р', 'с', 'т', 'у', 'х', 'ѕ', 'і', 'ј', 'ԁ', 'ӏ', 'ԛ', 'ԝ',
+    'һ', 'ь', 'ү',
+];
+
+/// Greek letters that render like Latin letters.
+const GREEK_LOOKALIKES: &[char] = &['α', 'ο', 'ρ', 'ν', 'ι', 'κ', 'τ', 'υ', 'χ', 'ε'];
+
+fn script_of(c: char) -> Option<Script> {
+    match c as u32 {
+        0x41..=0x5A | 0x61..=0x7A => Some(Script::Latin),
+        0xC0..=0x24F if c != '×' && c != '÷' => Some(Script::Latin),
+        0x1E00..=0x1EFF => Some(Script::Latin),
+        0x370..=0x3FF | 0x1F00..=0x1FFF => Some(Script::Greek),
+        0x400..=0x52F => Some(Script::Cyrillic),
+        _ => None,
+    }
+}
+
+/// Check a domain for mixed-script or whole-script homoglyph labels.
+///
+/// Returns a human-readable warning for the first suspicious label, or
+/// None for domains that look safe (including all plain ASCII domains).
+///
+/// # Example
+///
+/// ```rust
+/// use domain_check_lib::confusable_warning;
+///
+/// assert!(confusable_warning("apple.com").is_none());
+/// // Cyrillic "а" followed by Latin "pple"
+/// assert!(confusable_warning("xn--pple-43d.com").is_some());
+/// ```
+pub fn confusable_warning(domain: &str) -> Option<String> {
+    for raw in domain.trim().split('.') {
+        let label = match raw.get(..4) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("xn--") => {
+                idna::punycode::decode_to_string(&raw[4..]).unwrap_or_else(|| raw.to_string())
+            }
+            _ => raw.to_string(),
+        };
+
+        let mut scripts: Vec<Script> = Vec::new();
+        for script in label.chars().filter_map(script_of) {
+            if !scripts.contains(&script) {
+                scripts.push(script);
+            }
+        }
+
+        match scripts.as_slice() {
+            [] | [Script::Latin] => {}
+            [single] => {
+                let all_lookalikes = label.chars().filter(|c| script_of(*c).is_some()).all(|c| {
+                    let lower = c.to_lowercase().next().unwrap_or(c);
+                    CYRILLIC_LOOKALIKES.contains(&lower) || GREEK_LOOKALIKES.contains(&lower)
+                });
+                if all_lookalikes {
+                    return Some(format!(
+                        "label '{}' is all {} letters that look Latin",
+                        label,
+                        single.name()
+                    ));
+                }
+            }
+            mixed => {
+                let names: Vec<&str> = mixed.iter().map(|s| s.name()).collect();
+                return Some(format!(
+                    "label '{}' mixes {} scripts",
+                    label,
+                    names.join(" and ")
+                ));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── confusable_warning ──────────────────────────────────────────────
+
+    #[test]
+    fn test_pure_latin_no_warning() {
+        assert!(confusable_warning("example.com").is_none());
+        assert!(confusable_warning("my-brand2.io").is_none());
+        assert!(confusable_warning("xn--mnchen-3ya.de").is_none()); // münchen
+    }
+
+    #[test]
+    fn test_undecodable_punycode_label_checked_as_is() {
+        assert!(confusable_warning("xn--!!.com").is_none());
+    }
+
+    #[test]
+    fn test_cyrillic_latin_mix_warns() {
+        let warning = confusable_warning("xn--pple-43d.com").unwrap();
+        assert!(warning.contains("Latin"), "{}", warning);
+        assert!(warning.contains("Cyrillic"), "{}", warning);
+
+        // Same check for raw Unicode input
+        assert!(confusable_warning("\u{430}pple.com").is_some());
+    }
+
+    #[test]
+    fn test_whole_script_lookalike_warns() {
+        let warning = confusable_warning("xn--80ak6aa92e.com").unwrap();
+        assert!(warning.contains("look Latin"), "{}", warning);
+    }
+
+    #[test]
+    fn test_genuine_cyrillic_word_no_warning() {
+        // "пример" contains letters with no Latin lookalike (п, и)
+        assert!(confusable_warning("пример.рф").is_none());
+    }
+}
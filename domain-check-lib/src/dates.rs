@@ -0,0 +1,469 @@
+//! Parsing and reformatting of registry dates.
+//!
+//! RDAP servers return RFC 3339 timestamps with varying precision and
+//! offsets, while WHOIS servers use whatever their registry prefers
+//! (`2020-03-18T10:35:29+01:00`, `18.03.2020`, `14-aug-1995`, `2020/03/18`).
+//! [`parse_registry_date`] accepts the common forms and normalizes them to
+//! UTC so they can be compared, filtered and printed consistently.
+
+use std::str::FromStr;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// A registry date normalized to UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RegistryDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+/// Output style for registry dates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateFormat {
+    /// RFC 3339 in UTC, e.g. `2020-03-18T09:35:29Z`
+    Iso,
+    /// Calendar date only, e.g. `2020-03-18`
+    DateOnly,
+    /// strftime-style pattern (`%Y`, `%m`, `%d`, `%H`, `%M`, `%S`, `%y`,
+    /// `%b`, `%B`, `%F`, `%T`, `%s`, `%%`)
+    Strftime(String),
+}
+
+impl FromStr for DateFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "iso" => Ok(DateFormat::Iso),
+            "date-only" => Ok(DateFormat::DateOnly),
+            pattern if pattern.contains('%') => Ok(DateFormat::Strftime(pattern.to_string())),
+            other => Err(format!(
+                "Invalid date format '{}': use 'iso', 'date-only', or a strftime pattern such as '%d/%m/%Y'",
+                other
+            )),
+        }
+    }
+}
+
+impl RegistryDate {
+    /// Seconds since the Unix epoch.
+    pub fn unix_timestamp(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day) * 86_400
+            + i64::from(self.hour) * 3_600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+    }
+
+    /// The UTC date and time `timestamp` seconds after the Unix epoch.
+    pub fn from_unix_timestamp(timestamp: i64) -> Self {
+        let days = timestamp.div_euclid(86_400);
+        let secs = timestamp.rem_euclid(86_400) as u32;
+        let (year, month, day) = civil_from_days(days);
+        RegistryDate {
+            year,
+            month,
+            day,
+            hour: secs / 3_600,
+            minute: secs % 3_600 / 60,
+            second: secs % 60,
+        }
+    }
+
+    /// Render the date in the requested format.
+    pub fn format(&self, format: &DateFormat) -> String {
+        match format {
+            DateFormat::Iso => format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                self.year, self.month, self.day, self.hour, self.minute, self.second
+            ),
+            DateFormat::DateOnly => {
+                format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+            }
+            DateFormat::Strftime(pattern) => self.strftime(pattern),
+        }
+    }
+
+    fn strftime(&self, pattern: &str) -> String {
+        let mut out = String::with_capacity(pattern.len() + 8);
+        // The fields are public, so a hand-built date may carry a month
+        // outside 1..=12; `%b`/`%B` then fall back to the month number.
+        let month_name = self
+            .month
+            .checked_sub(1)
+            .and_then(|index| MONTH_NAMES.get(index as usize));
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => out.push_str(&format!("{:04}", self.year)),
+                Some('y') => out.push_str(&format!("{:02}", self.year.rem_euclid(100))),
+                Some('m') => out.push_str(&format!("{:02}", self.month)),
+                Some('d') => out.push_str(&format!("{:02}", self.day)),
+                Some('H') => out.push_str(&format!("{:02}", self.hour)),
+                Some('M') => out.push_str(&format!("{:02}", self.minute)),
+                Some('S') => out.push_str(&format!("{:02}", self.second)),
+                Some('b') => match month_name {
+                    Some(name) => out.push_str(&name[..3]),
+                    None => out.push_str(&format!("{:02}", self.month)),
+                },
+                Some('B') => match month_name {
+                    Some(name) => out.push_str(name),
+                    None => out.push_str(&format!("{:02}", self.month)),
+                },
+                Some('F') => out.push_str(&self.format(&DateFormat::DateOnly)),
+                Some('T') => out.push_str(&format!(
+                    "{:02}:{:02}:{:02}",
+                    self.hour, self.minute, self.second
+                )),
+                Some('s') => out.push_str(&self.unix_timestamp().to_string()),
+                Some('%') => out.push('%'),
+                // Unknown specifiers are kept as written
+                Some(other) => {
+                    out.push('%');
+                    out.push(other);
+                }
+                None => out.push('%'),
+            }
+        }
+        out
+    }
+}
+
+/// Parse a date as returned by an RDAP or WHOIS server.
+///
+/// Recognized date parts are `YYYY-MM-DD`, `YYYY/MM/DD`, `YYYY.MM.DD`,
+/// `YYYYMMDD`, `DD.MM.YYYY`, `DD/MM/YYYY` and `DD-Mon-YYYY`, optionally
+/// followed (after `T` or a space) by `HH:MM[:SS[.fraction]]` and a zone
+/// (`Z`, `±HH:MM`, `±HHMM`, `UTC`, `GMT`). Times with an offset are
+/// converted to UTC; times without one are taken as UTC. Returns None for
+/// anything else.
+///
+/// # Example
+///
+/// ```rust
+/// use domain_check_lib::{parse_registry_date, DateFormat};
+///
+/// let date = parse_registry_date("2020-03-18T00:35:29+01:00").unwrap();
+/// assert_eq!(date.format(&DateFormat::Iso), "2020-03-17T23:35:29Z");
+///
+/// let date = parse_registry_date("18.03.2020").unwrap();
+/// assert_eq!(date.format(&DateFormat::DateOnly), "2020-03-18");
+/// ```
+pub fn parse_registry_date(input: &str) -> Option<RegistryDate> {
+    let input = input.trim();
+    // The time starts after whitespace or a `T` that follows a digit
+    // (month names such as `oct` may contain a `t` themselves)
+    let split = input
+        .char_indices()
+        .find(|&(i, c)| {
+            c.is_whitespace()
+                || (matches!(c, 'T' | 't') && input[..i].ends_with(|p: char| p.is_ascii_digit()))
+        })
+        .map_or(input.len(), |(i, _)| i);
+    let (date_part, rest) = input.split_at(split);
+    let (year, month, day) = parse_date_part(date_part)?;
+    if month == 0 || month > 12 || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let rest = rest.get(1..).unwrap_or("").trim();
+    let (hour, minute, second, offset_minutes) = if rest.is_empty() {
+        (0, 0, 0, 0)
+    } else {
+        parse_time_part(rest)?
+    };
+
+    let local = RegistryDate {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+    };
+    Some(RegistryDate::from_unix_timestamp(
+        local.unix_timestamp() - i64::from(offset_minutes) * 60,
+    ))
+}
+
+fn parse_date_part(part: &str) -> Option<(i32, u32, u32)> {
+    if part.len() == 8 && part.bytes().all(|b| b.is_ascii_digit()) {
+        return Some((
+            part[..4].parse().ok()?,
+            part[4..6].parse().ok()?,
+            part[6..].parse().ok()?,
+        ));
+    }
+
+    let fields: Vec<&str> = part.split(['-', '/', '.']).collect();
+    let [first, second, third] = fields.as_slice() else {
+        return None;
+    };
+
+    if first.len() == 4 {
+        // YYYY-MM-DD and friends
+        Some((
+            first.parse().ok()?,
+            second.parse().ok()?,
+            third.parse().ok()?,
+        ))
+    } else if third.len() == 4 {
+        // DD.MM.YYYY, DD/MM/YYYY or DD-Mon-YYYY
+        let month = match second.parse() {
+            Ok(month) => month,
+            Err(_) => month_from_name(second)?,
+        };
+        Some((third.parse().ok()?, month, first.parse().ok()?))
+    } else {
+        None
+    }
+}
+
+fn month_from_name(name: &str) -> Option<u32> {
+    let name = name.to_ascii_lowercase();
+    if name.len() < 3 {
+        return None;
+    }
+    MONTH_NAMES
+        .iter()
+        .position(|m| m.to_ascii_lowercase().starts_with(&name))
+        .map(|i| i as u32 + 1)
+}
+
+fn parse_time_part(part: &str) -> Option<(u32, u32, u32, i32)> {
+    let zone_start = part
+        .find(|c: char| !(c.is_ascii_digit() || c == ':' || c == '.'))
+        .unwrap_or(part.len());
+    let (clock, zone) = part.split_at(zone_start);
+
+    let mut fields = clock.split(':');
+    let hour: u32 = fields.next()?.parse().ok()?;
+    let minute: u32 = fields.next()?.parse().ok()?;
+    let second: u32 = match fields.next() {
+        // Fractional seconds are dropped
+        Some(s) => s.split('.').next()?.parse().ok()?,
+        None => 0,
+    };
+    if fields.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    Some((hour, minute, second.min(59), parse_zone(zone.trim())?))
+}
+
+/// Offset from UTC in minutes.
+fn parse_zone(zone: &str) -> Option<i32> {
+    match zone {
+        "" | "Z" | "z" | "UTC" | "GMT" => return Some(0),
+        _ => {}
+    }
+
+    let sign = match zone.as_bytes()[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits: String = zone[1..].chars().filter(|c| *c != ':').collect();
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i32>().ok()?, 0),
+        4 => (digits[..2].parse::<i32>().ok()?, digits[2..].parse().ok()?),
+        _ => return None,
+    };
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 60 + minutes))
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Howard Hinnant's civil calendar algorithms.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(input: &str) -> RegistryDate {
+        parse_registry_date(input).unwrap_or_else(|| panic!("failed to parse {:?}", input))
+    }
+
+    // ── parse_registry_date ─────────────────────────────────────────────
+
+    #[test]
+    fn test_rfc3339_to_date_only() {
+        let parsed = date("1995-08-14T04:00:00Z");
+        assert_eq!(parsed.format(&DateFormat::DateOnly), "1995-08-14");
+    }
+
+    #[test]
+    fn test_de_style_to_date_only() {
+        assert_eq!(
+            date("18.03.2020").format(&DateFormat::DateOnly),
+            "2020-03-18"
+        );
+        // DENIC WHOIS `Changed:` lines carry a local offset
+        assert_eq!(
+            date("2020-03-18T10:35:29+01:00").format(&DateFormat::DateOnly),
+            "2020-03-18"
+        );
+    }
+
+    #[test]
+    fn test_offset_converted_to_utc() {
+        assert_eq!(
+            date("2020-01-01T00:30:00+01:00").format(&DateFormat::Iso),
+            "2019-12-31T23:30:00Z"
+        );
+        assert_eq!(
+            date("2020-02-28T23:00:00-0200").format(&DateFormat::Iso),
+            "2020-02-29T01:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_fractional_seconds_and_named_zones() {
+        assert_eq!(
+            date("2024-01-01T12:00:00.123Z").format(&DateFormat::Iso),
+            "2024-01-01T12:00:00Z"
+        );
+        assert_eq!(
+            date("2024-01-01 12:00:00 UTC").format(&DateFormat::Iso),
+            "2024-01-01T12:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_whois_date_variants() {
+        assert_eq!(
+            date("14-aug-1995").format(&DateFormat::DateOnly),
+            "1995-08-14"
+        );
+        assert_eq!(
+            date("01-OCT-2001").format(&DateFormat::DateOnly),
+            "2001-10-01"
+        );
+        assert_eq!(
+            date("2020/03/18").format(&DateFormat::DateOnly),
+            "2020-03-18"
+        );
+        assert_eq!(
+            date("2020.03.18").format(&DateFormat::DateOnly),
+            "2020-03-18"
+        );
+        assert_eq!(date("20200318").format(&DateFormat::DateOnly), "2020-03-18");
+    }
+
+    #[test]
+    fn test_unparseable_dates() {
+        for input in [
+            "",
+            "not a date",
+            "2020-13-01",
+            "2021-02-29",
+            "2020-01-01T25:00:00Z",
+        ] {
+            assert!(parse_registry_date(input).is_none(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_dates_order_chronologically() {
+        assert!(date("2020-01-01") < date("2020-01-02T00:00:00+01:00"));
+        assert_eq!(date("1970-01-01T00:00:00Z").unix_timestamp(), 0);
+    }
+
+    // ── DateFormat ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_date_format_from_str() {
+        assert_eq!("iso".parse::<DateFormat>(), Ok(DateFormat::Iso));
+        assert_eq!("date-only".parse::<DateFormat>(), Ok(DateFormat::DateOnly));
+        assert_eq!(
+            "%d/%m/%Y".parse::<DateFormat>(),
+            Ok(DateFormat::Strftime("%d/%m/%Y".to_string()))
+        );
+        assert!("yesterday".parse::<DateFormat>().is_err());
+    }
+
+    #[test]
+    fn test_strftime() {
+        let parsed = date("2020-03-08T07:05:09Z");
+        let format = |p: &str| parsed.format(&DateFormat::Strftime(p.to_string()));
+        assert_eq!(format("%d/%m/%Y"), "08/03/2020");
+        assert_eq!(format("%b %d, %y"), "Mar 08, 20");
+        assert_eq!(format("%B %F %T"), "March 2020-03-08 07:05:09");
+        assert_eq!(format("100%% %Q"), "100% %Q");
+    }
+
+    #[test]
+    fn test_strftime_out_of_range_month() {
+        for month in [0, 13] {
+            let built = RegistryDate {
+                year: 2020,
+                month,
+                day: 8,
+                hour: 0,
+                minute: 0,
+                second: 0,
+            };
+            let formatted = built.format(&DateFormat::Strftime("%b|%B".to_string()));
+            assert_eq!(formatted, format!("{:02}|{:02}", month, month));
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Resolver shared by the DNS-dependent enrichment features.
+//!
+//! By default hostnames go through the system resolver. When
+//! `CheckConfig::dns_servers` is set, HTTP clients built through
+//! [`configure_resolver`] send A/AAAA queries to those nameservers instead,
+//! for networks where the system resolver is restricted or wrong. Queries
+//! go through hickory-resolver over UDP, retrying over TCP when a reply is
+//! truncated.
+
+use hickory_resolver::config::{
+    LookupIpStrategy, NameServerConfig, NameServerConfigGroup, Protocol, ResolverConfig,
+    ResolverOpts, ServerOrderingStrategy,
+};
+use hickory_resolver::TokioAsyncResolver;
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Time allowed for one query to one nameserver.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Point an HTTP client at the configured nameservers, if any.
+///
+/// Every DNS-dependent feature builds its client through here so they all
+/// resolve the same way.
+pub(crate) fn configure_resolver(
+    builder: reqwest::ClientBuilder,
+    dns_servers: Option<&[SocketAddr]>,
+) -> reqwest::ClientBuilder {
+    match dns_servers {
+        Some(servers) if !servers.is_empty() => {
+            builder.dns_resolver(Arc::new(NameserverResolver::new(servers.to_vec())))
+        }
+        _ => builder,
+    }
+}
+
+/// Resolves hostnames by querying a fixed list of nameservers in order.
+#[derive(Clone)]
+pub(crate) struct NameserverResolver {
+    resolver: Arc<TokioAsyncResolver>,
+}
+
+impl std::fmt::Debug for NameserverResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NameserverResolver").finish_non_exhaustive()
+    }
+}
+
+impl NameserverResolver {
+    pub(crate) fn new(servers: Vec<SocketAddr>) -> Self {
+        let mut group = NameServerConfigGroup::with_capacity(servers.len() * 2);
+        for server in servers {
+            group.push(NameServerConfig::new(server, Protocol::Udp));
+            group.push(NameServerConfig::new(server, Protocol::Tcp));
+        }
+
+        let mut opts = ResolverOpts::default();
+        opts.timeout = QUERY_TIMEOUT;
+        opts.attempts = 1;
+        opts.ip_strategy = LookupIpStrategy::Ipv4AndIpv6;
+        opts.server_ordering_strategy = ServerOrderingStrategy::UserProvidedOrder;
+        opts.num_concurrent_reqs = 1;
+        opts.use_hosts_file = false;
+
+        let config = ResolverConfig::from_parts(None, Vec::new(), group);
+        Self {
+            resolver: Arc::new(TokioAsyncResolver::tokio(config, opts)),
+        }
+    }
+
+    /// Look up A and AAAA records, trying each nameserver until one answers.
+    pub(crate) async fn lookup(&self, host: &str) -> Result<Vec<IpAddr>, String> {
+        // A trailing dot keeps the name from being treated as relative
+        let fqdn = format!("{}.", host.trim_end_matches('.'));
+        let lookup = self
+            .resolver
+            .lookup_ip(fqdn.as_str())
+            .await
+            .map_err(|e| format!("could not resolve {}: {}", host, e))?;
+        Ok(lookup.iter().collect())
+    }
+}
+
+impl Resolve for NameserverResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.clone();
+        Box::pin(async move {
+            let addrs = resolver.lookup(name.as_str()).await?;
+            // reqwest fills in the port of the URL being fetched
+            let addrs: Addrs = Box::new(addrs.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, UdpSocket};
+
+    const TYPE_A: u16 = 1;
+
+    /// Answer an A query with 127.0.0.1 and anything else with no records.
+    /// With `truncated`, send the TC bit and no answers instead.
+    fn reply_to(query: &[u8], truncated: bool) -> Vec<u8> {
+        let len = query.len();
+        let qtype = u16::from_be_bytes([query[len - 4], query[len - 3]]);
+        let mut reply = query.to_vec();
+        reply[2] = if truncated { 0x83 } else { 0x81 }; // response (+ TC), recursion desired
+        reply[3] = 0x80; // recursion available, NOERROR
+        if qtype == TYPE_A && !truncated {
+            reply[7] = 1; // one answer
+            reply.extend_from_slice(&[0xC0, 12]); // name → question
+            reply.extend_from_slice(&TYPE_A.to_be_bytes());
+            reply.extend_from_slice(&1u16.to_be_bytes());
+            reply.extend_from_slice(&60u32.to_be_bytes());
+            reply.extend_from_slice(&4u16.to_be_bytes());
+            reply.extend_from_slice(&[127, 0, 0, 1]);
+        }
+        reply
+    }
+
+    /// A UDP nameserver built on `reply_to`.
+    async fn mock_nameserver(truncated: bool) -> (SocketAddr, tokio::task::JoinHandle<()>) {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        let handle = tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            loop {
+                let Ok((len, peer)) = socket.recv_from(&mut buf).await else {
+                    return;
+                };
+                let _ = socket
+                    .send_to(&reply_to(&buf[..len], truncated), peer)
+                    .await;
+            }
+        });
+        (addr, handle)
+    }
+
+    /// A TCP nameserver on `addr`'s port that answers every query in full.
+    async fn mock_tcp_nameserver(addr: SocketAddr) -> tokio::task::JoinHandle<()> {
+        let listener = TcpListener::bind(addr).await.unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    loop {
+                        let mut len = [0u8; 2];
+                        if stream.read_exact(&mut len).await.is_err() {
+                            return;
+                        }
+                        let mut query = vec![0u8; u16::from_be_bytes(len) as usize];
+                        if stream.read_exact(&mut query).await.is_err() {
+                            return;
+                        }
+                        let reply = reply_to(&query, false);
+                        let mut framed = (reply.len() as u16).to_be_bytes().to_vec();
+                        framed.extend_from_slice(&reply);
+                        if stream.write_all(&framed).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        })
+    }
+
+    // ── NameserverResolver ──────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_custom_nameserver_is_used() {
+        let (server, handle) = mock_nameserver(false).await;
+        let resolver = NameserverResolver::new(vec![server]);
+
+        let addrs = resolver.lookup("parked.test").await.unwrap();
+        assert_eq!(addrs, vec![IpAddr::from([127, 0, 0, 1])]);
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_truncated_reply_retried_over_tcp() {
+        let (server, udp) = mock_nameserver(true).await;
+        let tcp = mock_tcp_nameserver(server).await;
+        let resolver = NameserverResolver::new(vec![server]);
+
+        let addrs = resolver.lookup("parked.test").await.unwrap();
+        assert_eq!(addrs, vec![IpAddr::from([127, 0, 0, 1])]);
+        udp.abort();
+        tcp.abort();
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_nameserver_is_an_error() {
+        let server = UdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let resolver = NameserverResolver::new(vec![server]);
+        assert!(resolver.lookup("parked.test").await.is_err());
+    }
+    #[tokio::test]
+    async fn test_configured_client_resolves_through_nameserver() {
+        let (server, dns) = mock_nameserver(false).await;
+        let port = test_http::serve(vec![test_http::response("200 OK", None, "ok")])
+            .await
+            .port();
+
+        let client = configure_resolver(reqwest::Client::builder(), Some(&[server]))
+            .build()
+            .unwrap();
+        let body = client
+            .get(format!("http://parked.test:{}/", port))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "ok");
+        dns.abort();
+    }
+}
@@ -0,0 +1,122 @@
+//! JSON representation of `DomainResult::check_duration`.
+//!
+//! serde's default `Duration` encoding (`{"secs": 0, "nanos": 234567890}`)
+//! is awkward to consume, so durations are written as integer milliseconds
+//! (`234`). Two features change the written form:
+//!
+//! - `duration-float-secs`: float seconds (`0.234567890`)
+//! - `duration-struct`: the `{secs, nanos}` object (wins if both are enabled)
+//!
+//! Reading accepts all three forms regardless of features, so results saved
+//! by any build can be loaded by any other.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::time::Duration;
+
+/// Any of the written forms, for deserialization.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyDuration {
+    Millis(u64),
+    Secs(f64),
+    Struct { secs: u64, nanos: u32 },
+}
+
+impl AnyDuration {
+    fn into_duration<E: serde::de::Error>(self) -> Result<Duration, E> {
+        match self {
+            AnyDuration::Millis(ms) => Ok(Duration::from_millis(ms)),
+            AnyDuration::Secs(secs) => Duration::try_from_secs_f64(secs).map_err(E::custom),
+            AnyDuration::Struct { secs, nanos } => Ok(Duration::new(secs, nanos)),
+        }
+    }
+}
+
+/// Serialize `Duration` in the form selected by features.
+fn serialize_duration<S: Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if cfg!(feature = "duration-struct") {
+        duration.serialize(serializer)
+    } else if cfg!(feature = "duration-float-secs") {
+        serializer.serialize_f64(duration.as_secs_f64())
+    } else {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+}
+
+pub(crate) fn serialize<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serialize_duration(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    Option::<AnyDuration>::deserialize(deserializer)?
+        .map(AnyDuration::into_duration)
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Timed {
+        #[serde(default, with = "super")]
+        took: Option<Duration>,
+    }
+
+    fn to_json(duration: Duration) -> String {
+        serde_json::to_string(&Timed {
+            took: Some(duration),
+        })
+        .unwrap()
+    }
+
+    #[cfg(not(any(feature = "duration-struct", feature = "duration-float-secs")))]
+    #[test]
+    fn test_default_writes_integer_millis() {
+        assert_eq!(
+            to_json(Duration::from_nanos(234_567_890)),
+            r#"{"took":234}"#
+        );
+    }
+
+    #[cfg(all(feature = "duration-float-secs", not(feature = "duration-struct")))]
+    #[test]
+    fn test_float_secs_feature_writes_seconds() {
+        assert_eq!(to_json(Duration::from_millis(1_500)), r#"{"took":1.5}"#);
+    }
+
+    #[cfg(feature = "duration-struct")]
+    #[test]
+    fn test_struct_feature_writes_secs_and_nanos() {
+        assert_eq!(
+            to_json(Duration::from_nanos(1_234_567_890)),
+            r#"{"took":{"secs":1,"nanos":234567890}}"#
+        );
+    }
+
+    #[test]
+    fn test_reads_every_form() {
+        let read = |json: &str| serde_json::from_str::<Timed>(json).unwrap().took;
+        assert_eq!(read(r#"{"took":234}"#), Some(Duration::from_millis(234)));
+        assert_eq!(read(r#"{"took":1.5}"#), Some(Duration::from_millis(1_500)));
+        assert_eq!(
+            read(r#"{"took":{"secs":1,"nanos":234567890}}"#),
+            Some(Duration::from_nanos(1_234_567_890))
+        );
+        assert_eq!(read(r#"{"took":null}"#), None);
+        assert_eq!(read("{}"), None);
+        assert!(serde_json::from_str::<Timed>(r#"{"took":-1.0}"#).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+//! Per-endpoint RDAP outcome tallies.
+//!
+//! When `CheckConfig::track_endpoints` is enabled, every RDAP lookup is
+//! counted against the host of the endpoint it was sent to. The report turns
+//! "lots of unknowns" during a large run into something actionable, such as
+//! one registry timing out on every request.
+
+use crate::error::DomainCheckError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Outcome counts for one RDAP endpoint host.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointStats {
+    /// Endpoint host, e.g. `rdap.verisign.com`
+    pub host: String,
+    /// Lookups answered with a registration record (HTTP 200)
+    pub success: usize,
+    /// Lookups answered with "not found" (HTTP 404)
+    pub not_found: usize,
+    /// Lookups that failed with anything other than a timeout
+    pub errors: usize,
+    /// Lookups that ran out of time
+    pub timeouts: usize,
+}
+
+impl EndpointStats {
+    /// Number of lookups sent to this endpoint.
+    pub fn total(&self) -> usize {
+        self.success + self.not_found + self.errors + self.timeouts
+    }
+
+    /// Number of lookups that got no usable answer.
+    pub fn failures(&self) -> usize {
+        self.errors + self.timeouts
+    }
+}
+
+/// Shared tally of RDAP outcomes keyed by endpoint host.
+#[derive(Debug, Default)]
+pub(crate) struct EndpointTracker {
+    stats: Mutex<HashMap<String, EndpointStats>>,
+}
+
+impl EndpointTracker {
+    /// Count one lookup against `host`: the HTTP status it was answered
+    /// with, or the error it failed with.
+    pub(crate) fn record(&self, host: &str, outcome: Result<u16, &DomainCheckError>) {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = stats
+            .entry(host.to_string())
+            .or_insert_with(|| EndpointStats {
+                host: host.to_string(),
+                ..Default::default()
+            });
+        match outcome {
+            Ok(404) => entry.not_found += 1,
+            Ok(_) => entry.success += 1,
+            Err(e) if e.indicates_available() => entry.not_found += 1,
+            Err(DomainCheckError::Timeout { .. }) => entry.timeouts += 1,
+            Err(_) => entry.errors += 1,
+        }
+    }
+
+    /// Current tallies, endpoints with the most failures first.
+    ///
+    /// Ties are broken by host so the report is stable across runs.
+    pub(crate) fn snapshot(&self) -> Vec<EndpointStats> {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let mut report: Vec<EndpointStats> = stats.values().cloned().collect();
+        report.sort_by(|a, b| {
+            b.failures()
+                .cmp(&a.failures())
+                .then_with(|| a.host.cmp(&b.host))
+        });
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_tallies_match_outcomes_per_endpoint() {
+        let tracker = EndpointTracker::default();
+        let timeout = DomainCheckError::timeout("RDAP request", Duration::from_secs(3));
+        let refused = DomainCheckError::rdap_with_status("x.foo", "HTTP 503", 503);
+        let gone = DomainCheckError::rdap_with_status("x.com", "HTTP 404", 404);
+
+        tracker.record("rdap.verisign.com", Ok(200));
+        tracker.record("rdap.verisign.com", Ok(404));
+        tracker.record("rdap.verisign.com", Err(&gone));
+        for _ in 0..3 {
+            tracker.record("rdap.nic.foo", Err(&timeout));
+        }
+        tracker.record("rdap.nic.foo", Err(&refused));
+
+        let report = tracker.snapshot();
+        assert_eq!(
+            report,
+            vec![
+                EndpointStats {
+                    host: "rdap.nic.foo".to_string(),
+                    success: 0,
+                    not_found: 0,
+                    errors: 1,
+                    timeouts: 3,
+                },
+                EndpointStats {
+                    host: "rdap.verisign.com".to_string(),
+                    success: 1,
+                    not_found: 2,
+                    errors: 0,
+                    timeouts: 0,
+                },
+            ]
+        );
+        assert_eq!(report[0].total(), 4);
+        assert_eq!(report[0].failures(), 4);
+    }
+
+    #[test]
+    fn test_empty_tracker_reports_nothing() {
+        assert!(EndpointTracker::default().snapshot().is_empty());
+    }
+}
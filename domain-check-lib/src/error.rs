@@ -3,24 +3,31 @@
 //! This module defines a comprehensive error type that covers all the different
 //! ways domain checking can fail, from network issues to invalid input.
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Main error type for domain checking operations.
 ///
 /// This enum covers all possible failure modes in the domain checking process,
 /// providing detailed context for debugging and user-friendly error messages.
+/// The enum and its variants are `#[non_exhaustive]`: build errors with the
+/// constructors below and match variants with `{ .. }`.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum DomainCheckError {
     /// Invalid domain name format
+    #[non_exhaustive]
     InvalidDomain { domain: String, reason: String },
 
     /// Network-related errors (connection, timeout, etc.)
+    #[non_exhaustive]
     NetworkError {
         message: String,
         source: Option<String>,
     },
 
     /// RDAP protocol specific errors
+    #[non_exhaustive]
     RdapError {
         domain: String,
         message: String,
@@ -28,30 +35,39 @@ pub enum DomainCheckError {
     },
 
     /// WHOIS protocol specific errors
+    #[non_exhaustive]
     WhoisError { domain: String, message: String },
 
     /// Bootstrap registry lookup failures
+    #[non_exhaustive]
     BootstrapError { tld: String, message: String },
 
     /// JSON parsing errors for RDAP responses
+    #[non_exhaustive]
     ParseError {
         message: String,
         content: Option<String>,
     },
 
     /// Configuration errors (invalid settings, etc.)
+    #[non_exhaustive]
     ConfigError { message: String },
 
     /// File I/O errors when reading domain lists
+    #[non_exhaustive]
     FileError { path: String, message: String },
 
     /// Timeout errors when operations take too long
+    #[non_exhaustive]
     Timeout {
         operation: String,
         duration: std::time::Duration,
+        /// How many times the operation was tried, each under `duration`
+        attempts: u8,
     },
 
     /// Rate limiting errors when servers reject requests
+    #[non_exhaustive]
     RateLimited {
         service: String,
         message: String,
@@ -59,12 +75,119 @@ pub enum DomainCheckError {
     },
 
     /// Invalid pattern syntax in domain generation
+    #[non_exhaustive]
     InvalidPattern { pattern: String, reason: String },
 
     /// Generic internal errors that don't fit other categories
+    #[non_exhaustive]
     Internal { message: String },
 }
 
+/// Coarse failure category for a `DomainCheckError`.
+///
+/// Derived from the error variant rather than its message, so that display
+/// code and statistics don't need to pattern-match on strings. The one
+/// exception is file descriptor exhaustion, which only surfaces as OS error
+/// text inside otherwise ordinary network errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The operation exceeded its time budget
+    Timeout,
+    /// Connection, DNS, or transport failure
+    Network,
+    /// The server response could not be understood
+    Parse,
+    /// The registry or WHOIS server is throttling requests
+    RateLimited,
+    /// The registry refused the lookup (HTTP 403 or 451), usually by policy
+    /// or for legal reasons rather than because anything failed
+    Blocked,
+    /// No RDAP/WHOIS source is known for the TLD
+    UnknownTld,
+    /// The process ran out of file descriptors (EMFILE), usually from
+    /// concurrency set higher than `ulimit -n` allows
+    TooManyOpenFiles,
+    /// Anything else (invalid input, config, internal errors, ...)
+    Other,
+}
+
+/// Machine-readable form of an error, for JSON output.
+///
+/// `kind` is a stable snake_case name for the error variant (`file`,
+/// `config`, `network`, ...; see `DomainCheckError::kind`) that scripts can
+/// branch on. `message` is a single-line description without the hints
+/// included in the `Display` output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorReport {
+    pub kind: String,
+    pub message: String,
+}
+
+impl ErrorReport {
+    /// Create a report for an error that isn't a `DomainCheckError`.
+    pub fn new<K: Into<String>, M: Into<String>>(kind: K, message: M) -> Self {
+        Self {
+            kind: kind.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl From<&DomainCheckError> for ErrorReport {
+    fn from(err: &DomainCheckError) -> Self {
+        use DomainCheckError::*;
+
+        let message = match err {
+            InvalidDomain { domain, reason } => format!("invalid domain '{}': {}", domain, reason),
+            NetworkError { message, .. } => message.clone(),
+            RdapError {
+                domain, message, ..
+            } => format!("RDAP error for {}: {}", domain, message),
+            WhoisError { domain, message } => format!("WHOIS error for {}: {}", domain, message),
+            BootstrapError { tld, message } => format!("no registry for .{}: {}", tld, message),
+            ParseError { message, .. } => message.clone(),
+            ConfigError { message } => message.clone(),
+            FileError { path, message } => format!("{}: {}", path, message),
+            Timeout {
+                operation,
+                duration,
+                attempts,
+            } => match attempts {
+                0 | 1 => format!("{} timed out after {:?}", operation, duration),
+                n => format!(
+                    "{} timed out after {:?} ({} attempts)",
+                    operation, duration, n
+                ),
+            },
+            RateLimited {
+                service, message, ..
+            } => format!("rate limited by {}: {}", service, message),
+            InvalidPattern { pattern, reason } => {
+                format!("invalid pattern '{}': {}", pattern, reason)
+            }
+            Internal { message } => message.clone(),
+        };
+        Self::new(err.kind(), message)
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Timeout => "timeout",
+            Self::Network => "network error",
+            Self::Parse => "parsing error",
+            Self::RateLimited => "rate limited",
+            Self::Blocked => "blocked by registry policy",
+            Self::UnknownTld => "unknown TLD",
+            Self::TooManyOpenFiles => "too many open files",
+            Self::Other => "error",
+        };
+        write!(f, "{}", label)
+    }
+}
+
 impl DomainCheckError {
     /// Create a new invalid domain error.
     pub fn invalid_domain<D: Into<String>, R: Into<String>>(domain: D, reason: R) -> Self {
@@ -133,6 +256,49 @@ impl DomainCheckError {
         Self::Timeout {
             operation: operation.into(),
             duration,
+            attempts: 1,
+        }
+    }
+
+    /// Create a timeout error for an operation that timed out on every one
+    /// of `attempts` tries.
+    pub fn timeout_after_attempts<O: Into<String>>(
+        operation: O,
+        duration: std::time::Duration,
+        attempts: u8,
+    ) -> Self {
+        Self::Timeout {
+            operation: operation.into(),
+            duration,
+            attempts,
+        }
+    }
+
+    /// Create a new configuration error.
+    pub fn config<M: Into<String>>(message: M) -> Self {
+        Self::ConfigError {
+            message: message.into(),
+        }
+    }
+
+    /// Create a new parse error.
+    pub fn parse<M: Into<String>>(message: M) -> Self {
+        Self::ParseError {
+            message: message.into(),
+            content: None,
+        }
+    }
+
+    /// Create a new rate-limit error.
+    pub fn rate_limited<S: Into<String>, M: Into<String>>(
+        service: S,
+        message: M,
+        retry_after: Option<std::time::Duration>,
+    ) -> Self {
+        Self::RateLimited {
+            service: service.into(),
+            message: message.into(),
+            retry_after,
         }
     }
 
@@ -179,6 +345,95 @@ impl DomainCheckError {
         }
     }
 
+    /// Stable snake_case name of this error's variant, used as `kind` in
+    /// `ErrorReport`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::InvalidDomain { .. } => "invalid_domain",
+            Self::NetworkError { .. } => "network",
+            Self::RdapError { .. } => "rdap",
+            Self::WhoisError { .. } => "whois",
+            Self::BootstrapError { .. } => "bootstrap",
+            Self::ParseError { .. } => "parse",
+            Self::ConfigError { .. } => "config",
+            Self::FileError { .. } => "file",
+            Self::Timeout { .. } => "timeout",
+            Self::RateLimited { .. } => "rate_limited",
+            Self::InvalidPattern { .. } => "invalid_pattern",
+            Self::Internal { .. } => "internal",
+        }
+    }
+
+    /// Classify this error into a coarse category based on its variant.
+    pub fn category(&self) -> ErrorCategory {
+        if self.is_fd_exhaustion() {
+            return ErrorCategory::TooManyOpenFiles;
+        }
+
+        match self {
+            Self::Timeout { .. } => ErrorCategory::Timeout,
+            Self::NetworkError { .. } => ErrorCategory::Network,
+            Self::ParseError { .. } => ErrorCategory::Parse,
+            Self::RateLimited { .. } => ErrorCategory::RateLimited,
+            Self::BootstrapError { .. } => ErrorCategory::UnknownTld,
+            Self::RdapError {
+                status_code: Some(429),
+                ..
+            } => ErrorCategory::RateLimited,
+            Self::RdapError {
+                status_code: Some(403 | 451),
+                ..
+            } => ErrorCategory::Blocked,
+            // No status code means the request never got an HTTP response
+            Self::RdapError {
+                status_code: None, ..
+            } => ErrorCategory::Network,
+            Self::RdapError { .. }
+            | Self::WhoisError { .. }
+            | Self::InvalidDomain { .. }
+            | Self::ConfigError { .. }
+            | Self::FileError { .. }
+            | Self::InvalidPattern { .. }
+            | Self::Internal { .. } => ErrorCategory::Other,
+        }
+    }
+
+    /// How many tries a timed-out operation got (None for other errors).
+    pub fn attempts(&self) -> Option<u8> {
+        match self {
+            Self::Timeout { attempts, .. } => Some(*attempts),
+            _ => None,
+        }
+    }
+
+    /// HTTP status of the RDAP response behind this error (None if the
+    /// server wasn't reached or this isn't an RDAP error).
+    pub fn rdap_status(&self) -> Option<u16> {
+        match self {
+            Self::RdapError { status_code, .. } => *status_code,
+            _ => None,
+        }
+    }
+
+    /// Check whether the underlying OS error was EMFILE ("too many open files").
+    ///
+    /// Socket and process creation errors are stringified by the time they
+    /// reach us, so this matches the OS message text.
+    fn is_fd_exhaustion(&self) -> bool {
+        let texts: [Option<&str>; 2] = match self {
+            Self::NetworkError { message, source } => [Some(message), source.as_deref()],
+            Self::RdapError { message, .. } | Self::WhoisError { message, .. } => {
+                [Some(message), None]
+            }
+            _ => [None, None],
+        };
+
+        texts.iter().flatten().any(|text| {
+            let text = text.to_lowercase();
+            text.contains("too many open files") || text.contains("os error 24")
+        })
+    }
+
     /// Check if this error suggests the operation should be retried.
     pub fn is_retryable(&self) -> bool {
         matches!(
@@ -215,6 +470,7 @@ impl fmt::Display for DomainCheckError {
                 match status_code {
                     Some(404) => write!(f, "✅ {}: Domain appears to be available", domain),
                     Some(429) => write!(f, "⏳ {}: Registry is rate limiting requests\n   💡 Please wait a moment and try again", domain),
+                    Some(code @ (403 | 451)) => write!(f, "🚫 {}: Registry refused the lookup (HTTP {})\n   💡 Blocked by registry policy; retrying won't help", domain, code),
                     Some(500..=599) => write!(f, "⚠️ {}: Registry server is temporarily unavailable\n   💡 Trying backup method...", domain),
                     Some(code) => write!(f, "⚠️ {}: Registry returned error (HTTP {})\n   💡 This domain registry may be temporarily unavailable", domain, code),
                     None => write!(f, "⚠️ {}: {}\n   💡 Trying alternative checking method...", domain, message),
@@ -251,8 +507,12 @@ impl fmt::Display for DomainCheckError {
                     write!(f, "📁 File error ({}): {}\n   💡 Please check the file and try again", path, message)
                 }
             }
-            Self::Timeout { operation, duration } => {
-                write!(f, "⏱️ Operation timed out after {:?}: {}\n   💡 Try reducing the number of domains or check your internet connection", duration, operation)
+            Self::Timeout { operation, duration, attempts } => {
+                if *attempts > 1 {
+                    write!(f, "⏱️ Operation timed out after {:?} on all {} attempts: {}\n   💡 The server may be down; try again later or raise the timeout", duration, attempts, operation)
+                } else {
+                    write!(f, "⏱️ Operation timed out after {:?}: {}\n   💡 Try reducing the number of domains or check your internet connection", duration, operation)
+                }
             }
             Self::RateLimited { service, message, retry_after } => {
                 match retry_after {
@@ -409,9 +669,11 @@ mod tests {
             DomainCheckError::Timeout {
                 operation,
                 duration,
+                attempts,
             } => {
                 assert_eq!(operation, "RDAP lookup");
                 assert_eq!(duration, std::time::Duration::from_secs(10));
+                assert_eq!(attempts, 1);
             }
             _ => panic!("wrong variant"),
         }
@@ -612,6 +874,184 @@ mod tests {
         assert!(!err.is_retryable());
     }
 
+    // ── category ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_category_timeout() {
+        let err = DomainCheckError::timeout("RDAP request", std::time::Duration::from_secs(3));
+        assert_eq!(err.category(), ErrorCategory::Timeout);
+    }
+
+    #[test]
+    fn test_category_network() {
+        assert_eq!(
+            DomainCheckError::network("connection refused").category(),
+            ErrorCategory::Network
+        );
+    }
+
+    #[test]
+    fn test_category_parse() {
+        let err = DomainCheckError::ParseError {
+            message: "bad json".into(),
+            content: None,
+        };
+        assert_eq!(err.category(), ErrorCategory::Parse);
+    }
+
+    #[test]
+    fn test_category_rate_limited() {
+        let err = DomainCheckError::RateLimited {
+            service: "RDAP".into(),
+            message: "slow down".into(),
+            retry_after: None,
+        };
+        assert_eq!(err.category(), ErrorCategory::RateLimited);
+    }
+
+    #[test]
+    fn test_category_rdap_429_is_rate_limited() {
+        let err = DomainCheckError::rdap_with_status("a.com", "too many", 429);
+        assert_eq!(err.category(), ErrorCategory::RateLimited);
+    }
+
+    #[test]
+    fn test_timeout_attempts() {
+        let once = DomainCheckError::timeout("RDAP request", std::time::Duration::from_secs(2));
+        assert_eq!(once.attempts(), Some(1));
+
+        let retried = DomainCheckError::timeout_after_attempts(
+            "RDAP request",
+            std::time::Duration::from_secs(2),
+            3,
+        );
+        assert_eq!(retried.attempts(), Some(3));
+        assert_eq!(retried.category(), ErrorCategory::Timeout);
+        assert!(retried.to_string().contains("all 3 attempts"));
+        assert_eq!(
+            ErrorReport::from(&retried).message,
+            "RDAP request timed out after 2s (3 attempts)"
+        );
+
+        assert_eq!(DomainCheckError::rdap("a.com", "boom").attempts(), None);
+    }
+
+    #[test]
+    fn test_category_rdap_403_and_451_are_blocked() {
+        for status in [403, 451] {
+            let err = DomainCheckError::rdap_with_status("a.com", "refused", status);
+            assert_eq!(err.category(), ErrorCategory::Blocked, "{}", status);
+        }
+    }
+
+    #[test]
+    fn test_category_rdap_without_status_is_network() {
+        let err = DomainCheckError::rdap("a.com", "Request failed: connection reset");
+        assert_eq!(err.category(), ErrorCategory::Network);
+    }
+
+    #[test]
+    fn test_category_rdap_server_error_is_other() {
+        let err = DomainCheckError::rdap_with_status("a.com", "server error", 503);
+        assert_eq!(err.category(), ErrorCategory::Other);
+    }
+
+    #[test]
+    fn test_category_emfile_is_too_many_open_files() {
+        let err = DomainCheckError::rdap(
+            "a.com",
+            "Request failed: error sending request for url (https://rdap.verisign.com/com/v1/domain/a.com): \
+             error trying to connect: tcp open error: Too many open files (os error 24)",
+        );
+        assert_eq!(err.category(), ErrorCategory::TooManyOpenFiles);
+
+        let err = DomainCheckError::network_with_source(
+            "Connection to RDAP server for a.com failed",
+            "Too many open files (os error 24)",
+        );
+        assert_eq!(err.category(), ErrorCategory::TooManyOpenFiles);
+
+        let err = DomainCheckError::whois("a.com", "Failed to execute whois command: os error 24");
+        assert_eq!(err.category(), ErrorCategory::TooManyOpenFiles);
+    }
+
+    #[test]
+    fn test_category_emfile_label() {
+        assert_eq!(
+            ErrorCategory::TooManyOpenFiles.to_string(),
+            "too many open files"
+        );
+    }
+
+    #[test]
+    fn test_category_bootstrap_is_unknown_tld() {
+        let err = DomainCheckError::bootstrap("zzz", "no endpoint");
+        assert_eq!(err.category(), ErrorCategory::UnknownTld);
+    }
+
+    #[test]
+    fn test_category_other_variants() {
+        let others = vec![
+            DomainCheckError::whois("a.com", "lookup failed"),
+            DomainCheckError::invalid_domain("bad", "too short"),
+            DomainCheckError::ConfigError {
+                message: "bad".into(),
+            },
+            DomainCheckError::file_error("x.txt", "not found"),
+            DomainCheckError::invalid_pattern("\\x", "unknown escape"),
+            DomainCheckError::internal("oops"),
+        ];
+        for err in others {
+            assert_eq!(err.category(), ErrorCategory::Other, "{:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_category_ignores_message_content() {
+        // A registrar literally named "Network Solutions" must not look like a network error
+        let err = DomainCheckError::whois("a.com", "Registrar: Network Solutions, LLC timeout");
+        assert_eq!(err.category(), ErrorCategory::Other);
+    }
+
+    #[test]
+    fn test_category_display_labels() {
+        assert_eq!(ErrorCategory::Timeout.to_string(), "timeout");
+        assert_eq!(ErrorCategory::Network.to_string(), "network error");
+        assert_eq!(ErrorCategory::Parse.to_string(), "parsing error");
+        assert_eq!(ErrorCategory::RateLimited.to_string(), "rate limited");
+        assert_eq!(ErrorCategory::UnknownTld.to_string(), "unknown TLD");
+        assert_eq!(
+            ErrorCategory::Blocked.to_string(),
+            "blocked by registry policy"
+        );
+        assert_eq!(ErrorCategory::Other.to_string(), "error");
+    }
+
+    // ── ErrorReport ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_error_report_from_file_error() {
+        let err = DomainCheckError::file_error("domains.txt", "file not found");
+        let report = ErrorReport::from(&err);
+        assert_eq!(report.kind, "file");
+        assert_eq!(report.message, "domains.txt: file not found");
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({"kind": "file", "message": "domains.txt: file not found"})
+        );
+    }
+
+    #[test]
+    fn test_error_report_message_is_single_line() {
+        let err = DomainCheckError::ConfigError {
+            message: "concurrency must be between 1 and 100".to_string(),
+        };
+        assert!(err.to_string().contains('\n'));
+        let report = ErrorReport::from(&err);
+        assert_eq!(report.kind, "config");
+        assert_eq!(report.message, "concurrency must be between 1 and 100");
+    }
+
     // ── Display for every variant ───────────────────────────────────────
 
     #[test]
@@ -30,7 +30,7 @@
 
 use crate::error::DomainCheckError;
 use crate::types::{GenerateConfig, GenerationResult};
-use crate::utils::is_valid_base_name;
+use crate::utils::{is_valid_base_name, is_valid_generated_name};
 
 /// A single slot in a parsed pattern — either a fixed character or a set of possibilities.
 #[derive(Debug, Clone)]
@@ -124,6 +124,14 @@ pub fn estimate_pattern_count(pattern: &str) -> Result<usize, DomainCheckError>
 /// Names are filtered through `is_valid_base_name` (removes leading/trailing
 /// hyphens, names shorter than 2 chars, etc.).
 pub fn expand_pattern(pattern: &str) -> Result<Vec<String>, DomainCheckError> {
+    expand_pattern_with(pattern, is_valid_base_name)
+}
+
+/// `expand_pattern` with a caller-chosen name validator.
+fn expand_pattern_with(
+    pattern: &str,
+    is_valid: impl Fn(&str) -> bool,
+) -> Result<Vec<String>, DomainCheckError> {
     let slots = parse_pattern(pattern)?;
 
     // Build the list of char options per slot
@@ -152,7 +160,7 @@ pub fn expand_pattern(pattern: &str) -> Result<Vec<String>, DomainCheckError> {
             .map(|(i, &c)| options[i][c])
             .collect();
 
-        if is_valid_base_name(&name) {
+        if is_valid(&name) {
             results.push(name);
         }
 
@@ -187,6 +195,23 @@ pub fn apply_affixes<'a>(
     prefixes: &'a [String],
     suffixes: &'a [String],
     include_bare: bool,
+) -> impl Iterator<Item = String> + 'a {
+    apply_affixes_with(
+        base_names,
+        prefixes,
+        suffixes,
+        include_bare,
+        is_valid_base_name,
+    )
+}
+
+/// `apply_affixes` with a caller-chosen name validator.
+fn apply_affixes_with<'a>(
+    base_names: &'a [String],
+    prefixes: &'a [String],
+    suffixes: &'a [String],
+    include_bare: bool,
+    is_valid: impl Fn(&str) -> bool + 'a,
 ) -> impl Iterator<Item = String> + 'a {
     base_names.iter().flat_map(move |name| {
         let mut variants = Vec::new();
@@ -195,14 +220,14 @@ pub fn apply_affixes<'a>(
         for prefix in prefixes {
             for suffix in suffixes {
                 let candidate = format!("{}{}{}", prefix, name, suffix);
-                if is_valid_base_name(&candidate) {
+                if is_valid(&candidate) {
                     variants.push(candidate);
                 }
             }
             // prefix + name (no suffix)
             if suffixes.is_empty() || !suffixes.is_empty() {
                 let candidate = format!("{}{}", prefix, name);
-                if is_valid_base_name(&candidate) {
+                if is_valid(&candidate) {
                     variants.push(candidate);
                 }
             }
@@ -211,13 +236,13 @@ pub fn apply_affixes<'a>(
         // name + suffix (no prefix)
         for suffix in suffixes {
             let candidate = format!("{}{}", name, suffix);
-            if is_valid_base_name(&candidate) {
+            if is_valid(&candidate) {
                 variants.push(candidate);
             }
         }
 
         // bare name
-        if include_bare && is_valid_base_name(name) {
+        if include_bare && is_valid(name) {
             variants.push(name.clone());
         }
 
@@ -225,13 +250,44 @@ pub fn apply_affixes<'a>(
     })
 }
 
+/// Estimate how many names `generate_names` will produce, without expanding anything.
+///
+/// Sums the literal names and each pattern's Cartesian estimate, then
+/// multiplies by the variants affixes create per base name: every
+/// prefix × suffix pair, each prefix alone, each suffix alone, and the bare
+/// name when `include_bare` is set. Like `estimate_pattern_count`, this is an
+/// upper bound: validation may filter some names out.
+pub fn estimate_generation(
+    config: &GenerateConfig,
+    literal_names: &[String],
+) -> Result<usize, DomainCheckError> {
+    let mut base_count: usize = literal_names.len();
+    for pattern in &config.patterns {
+        base_count = base_count.saturating_add(estimate_pattern_count(pattern)?);
+    }
+
+    if !config.has_affixes() {
+        return Ok(base_count);
+    }
+
+    let prefixes = config.prefixes.len();
+    let suffixes = config.suffixes.len();
+    let per_name = prefixes
+        .saturating_mul(suffixes)
+        .saturating_add(prefixes)
+        .saturating_add(suffixes)
+        .saturating_add(usize::from(config.include_bare));
+    Ok(base_count.saturating_mul(per_name))
+}
+
 /// Run the full generation pipeline: patterns → affixes → validated names.
 ///
 /// This is the main entry point for domain name generation. It:
 /// 1. Expands all patterns into base names
 /// 2. Combines with any literal base names (from `config.patterns` treated as patterns)
 /// 3. Applies prefix/suffix permutations
-/// 4. Filters all results through domain name validation
+/// 4. Filters all results through domain name validation (dropping Unicode
+///    names when `config.ascii_only` is set)
 ///
 /// Returns a `GenerationResult` with the names and a pre-filter estimate.
 pub fn generate_names(
@@ -239,32 +295,29 @@ pub fn generate_names(
     literal_names: &[String],
 ) -> Result<GenerationResult, DomainCheckError> {
     // Step 1: Estimate total count (for informational purposes)
-    let mut estimated_count: usize = literal_names.len();
-    for pattern in &config.patterns {
-        estimated_count = estimated_count.saturating_add(estimate_pattern_count(pattern)?);
-    }
+    let estimated_count = estimate_generation(config, literal_names)?;
+
+    let is_valid = |name: &str| is_valid_generated_name(name, config.ascii_only);
 
     // Step 2: Expand patterns into base names
     let mut base_names: Vec<String> = literal_names.to_vec();
     for pattern in &config.patterns {
-        base_names.extend(expand_pattern(pattern)?);
+        base_names.extend(expand_pattern_with(pattern, is_valid)?);
     }
 
     // Step 3: Apply affixes if configured
     let names = if config.has_affixes() {
-        apply_affixes(
+        apply_affixes_with(
             &base_names,
             &config.prefixes,
             &config.suffixes,
             config.include_bare,
+            is_valid,
         )
         .collect()
     } else {
         // Still filter through validation
-        base_names
-            .into_iter()
-            .filter(|n| is_valid_base_name(n))
-            .collect()
+        base_names.into_iter().filter(|n| is_valid(n)).collect()
     };
 
     Ok(GenerationResult {
@@ -415,6 +468,61 @@ mod tests {
         assert_eq!(estimate_pattern_count("\\w\\w\\w").unwrap(), 19683);
     }
 
+    #[test]
+    fn test_estimate_generation_matches_pattern_with_affixes() {
+        let config = GenerateConfig {
+            patterns: vec!["app\\d".to_string()],
+            prefixes: vec!["get".to_string(), "try".to_string()],
+            suffixes: vec!["ly".to_string()],
+            include_bare: true,
+            ascii_only: false,
+        };
+        let actual = generate_names(&config, &[]).unwrap().names.len();
+        // 10 bases × (2×1 + 2 + 1 + bare)
+        assert_eq!(estimate_generation(&config, &[]).unwrap(), 60);
+        assert_eq!(actual, 60);
+    }
+
+    #[test]
+    fn test_estimate_generation_counts_literals_without_bare() {
+        let config = GenerateConfig {
+            patterns: vec![],
+            prefixes: vec!["my".to_string()],
+            suffixes: vec!["hub".to_string()],
+            include_bare: false,
+            ascii_only: false,
+        };
+        let literals = vec!["cloud".to_string(), "data".to_string()];
+        let actual = generate_names(&config, &literals).unwrap().names.len();
+        assert_eq!(estimate_generation(&config, &literals).unwrap(), 6);
+        assert_eq!(actual, 6);
+    }
+
+    #[test]
+    fn test_estimate_generation_is_upper_bound_after_filtering() {
+        // \w includes a hyphen, which is dropped in the last position
+        let config = GenerateConfig {
+            patterns: vec!["ab\\w".to_string()],
+            prefixes: vec![],
+            suffixes: vec!["x".to_string()],
+            include_bare: true,
+            ascii_only: false,
+        };
+        let estimate = estimate_generation(&config, &[]).unwrap();
+        let actual = generate_names(&config, &[]).unwrap().names.len();
+        assert_eq!(estimate, 54);
+        assert!(actual <= estimate && actual >= 52);
+    }
+
+    #[test]
+    fn test_estimate_generation_invalid_pattern() {
+        let config = GenerateConfig {
+            patterns: vec!["bad\\x".to_string()],
+            ..Default::default()
+        };
+        assert!(estimate_generation(&config, &[]).is_err());
+    }
+
     // ── Affixes ─────────────────────────────────────────────────────
 
     #[test]
@@ -510,6 +618,7 @@ mod tests {
             prefixes: vec!["get".to_string()],
             suffixes: vec![],
             include_bare: true,
+            ascii_only: false,
         };
         let result = generate_names(&config, &[]).unwrap();
         // 10 patterns → each gets: getappN + appN = 20
@@ -525,6 +634,7 @@ mod tests {
             prefixes: vec!["my".to_string()],
             suffixes: vec!["hub".to_string()],
             include_bare: true,
+            ascii_only: false,
         };
         let literals = vec!["cloud".to_string()];
         let result = generate_names(&config, &literals).unwrap();
@@ -553,6 +663,39 @@ mod tests {
         assert_eq!(result.estimated_count, 101); // 1 literal + 100 pattern estimate
     }
 
+    #[test]
+    fn test_pipeline_keeps_unicode_unless_ascii_only() {
+        let literals = vec!["café".to_string()];
+        let default = GenerateConfig {
+            patterns: vec!["caf\\d".to_string()],
+            ..Default::default()
+        };
+        let names = generate_names(&default, &literals).unwrap().names;
+        assert!(names.contains(&"café".to_string()));
+        assert_eq!(names.len(), 11);
+
+        let ascii = GenerateConfig {
+            ascii_only: true,
+            ..default
+        };
+        let names = generate_names(&ascii, &literals).unwrap().names;
+        assert!(!names.contains(&"café".to_string()));
+        assert_eq!(names.len(), 10);
+    }
+
+    #[test]
+    fn test_pipeline_unicode_affixes() {
+        let config = GenerateConfig {
+            prefixes: vec!["le".to_string()],
+            include_bare: true,
+            ..Default::default()
+        };
+        let names = generate_names(&config, &["café".to_string()])
+            .unwrap()
+            .names;
+        assert_eq!(names, vec!["lecafé", "café"]);
+    }
+
     // ── GenerateConfig helpers ──────────────────────────────────────
 
     #[test]
@@ -670,6 +813,7 @@ mod tests {
             prefixes: vec!["my".to_string()],
             suffixes: vec![],
             include_bare: true,
+            ascii_only: false,
         };
         let literals = vec!["app".to_string()];
         let result = generate_names(&config, &literals).unwrap();
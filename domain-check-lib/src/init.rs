@@ -0,0 +1,117 @@
+//! Process-wide initialization for long-running hosts.
+//!
+//! `init_with` lets a server do its setup once at startup: preload the
+//! bootstrap cache from a saved IANA registry, install a default
+//! `CheckConfig` for every `DomainChecker::new()`, and (with the `tracing`
+//! feature) install a log subscriber.
+
+use crate::error::DomainCheckError;
+use crate::protocols::registry::load_bootstrap_snapshot;
+use crate::types::CheckConfig;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Configuration installed by `init_with`, used by `DomainChecker::new()`.
+static DEFAULT_CONFIG: RwLock<Option<CheckConfig>> = RwLock::new(None);
+
+/// Where `init_with` reads the bootstrap snapshot from.
+#[derive(Debug, Clone)]
+enum SnapshotSource {
+    Json(String),
+    File(PathBuf),
+}
+
+/// Options for [`init_with`](crate::init_with).
+///
+/// Every option is off by default, so `InitOptions::default()` does nothing.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use domain_check_lib::{init_with, CheckConfig, InitOptions};
+///
+/// init_with(
+///     InitOptions::default()
+///         .with_bootstrap_snapshot_file("/etc/domain-check/dns.json")
+///         .with_default_config(CheckConfig::default().with_concurrency(50)),
+/// )?;
+/// # Ok::<(), domain_check_lib::DomainCheckError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InitOptions {
+    bootstrap_snapshot: Option<SnapshotSource>,
+    default_config: Option<CheckConfig>,
+    #[cfg(feature = "tracing")]
+    tracing_filter: Option<String>,
+}
+
+impl InitOptions {
+    /// Preload the bootstrap cache from the contents of an IANA `dns.json`.
+    pub fn with_bootstrap_snapshot<S: Into<String>>(mut self, json: S) -> Self {
+        self.bootstrap_snapshot = Some(SnapshotSource::Json(json.into()));
+        self
+    }
+
+    /// Preload the bootstrap cache from a saved IANA `dns.json` file.
+    pub fn with_bootstrap_snapshot_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.bootstrap_snapshot = Some(SnapshotSource::File(path.into()));
+        self
+    }
+
+    /// Use `config` for every checker created with `DomainChecker::new()`.
+    pub fn with_default_config(mut self, config: CheckConfig) -> Self {
+        self.default_config = Some(config);
+        self
+    }
+
+    /// Install a stderr `tracing` subscriber with the given filter
+    /// (e.g. `"domain_check_lib=debug"`). `RUST_LOG` takes precedence.
+    #[cfg(feature = "tracing")]
+    pub fn with_tracing<S: Into<String>>(mut self, filter: S) -> Self {
+        self.tracing_filter = Some(filter.into());
+        self
+    }
+}
+
+/// Apply `options`. See [`crate::init_with`].
+pub(crate) fn init_with(options: InitOptions) -> Result<(), DomainCheckError> {
+    #[cfg(feature = "tracing")]
+    if let Some(filter) = options.tracing_filter {
+        // A subscriber installed by the host application wins
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter(
+                tracing_subscriber::EnvFilter::try_from_default_env()
+                    .unwrap_or_else(|_| filter.into()),
+            )
+            .with_writer(std::io::stderr)
+            .try_init();
+    }
+
+    match options.bootstrap_snapshot {
+        Some(SnapshotSource::Json(json)) => {
+            load_bootstrap_snapshot(&json)?;
+        }
+        Some(SnapshotSource::File(path)) => {
+            let json = std::fs::read_to_string(&path).map_err(|e| {
+                DomainCheckError::file_error(path.display().to_string(), e.to_string())
+            })?;
+            load_bootstrap_snapshot(&json)?;
+        }
+        None => {}
+    }
+
+    if let Some(config) = options.default_config {
+        *DEFAULT_CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(config);
+    }
+
+    Ok(())
+}
+
+/// The configuration installed by `init_with`, or `CheckConfig::default()`.
+pub(crate) fn default_config() -> CheckConfig {
+    DEFAULT_CONFIG
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
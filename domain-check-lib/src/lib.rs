@@ -31,28 +31,71 @@
 // Re-export main public API types and functions
 // This makes them available as domain_check_lib::TypeName
 pub use checker::DomainChecker;
-pub use config::{load_env_config, ConfigManager, FileConfig, GenerationConfig};
-pub use error::DomainCheckError;
+pub use concurrent::concurrency_limiter;
+pub use config::{
+    load_env_config, parse_check_method, ConfigManager, FileConfig, GenerationConfig,
+};
+pub use confusables::confusable_warning;
+pub use dates::{parse_registry_date, DateFormat, RegistryDate};
+pub use endpoints::EndpointStats;
+pub use error::{DomainCheckError, ErrorCategory, ErrorReport};
+pub use init::InitOptions;
+#[cfg(feature = "parking")]
+pub use parking::{detect_parking, enrich_parking, is_parking_page, parking_client};
+pub use policy::{ClassificationPolicy, DefaultClassificationPolicy};
+pub use protocols::rdap::shared_http_client;
 pub use protocols::registry::{
-    get_all_known_tlds, get_available_presets, get_preset_tlds, get_preset_tlds_with_custom,
-    get_whois_server, initialize_bootstrap,
+    fetch_all_bootstrap_endpoints, get_all_known_tlds, get_available_presets, get_preset_tlds,
+    get_preset_tlds_with_custom, get_rdap_endpoint, get_tld_variants, get_top_tlds,
+    get_whois_server, get_whois_servers, initialize_bootstrap, initialize_bootstrap_with,
+    load_bootstrap_snapshot,
+};
+pub use protocols::RdapClient;
+#[cfg(feature = "redirects")]
+pub use redirects::{detect_redirect, enrich_redirects, redirect_client};
+pub use sink::{FnSink, ResultSink, VecSink};
+pub use timings::{CheckTiming, TimingLog};
+pub use types::{
+    AmbiguousStatusPolicy, CheckConfig, CheckEvent, CheckMethod, Confidence, DomainInfo,
+    DomainResult, IdnVariant, OutputMode, StreamFilter,
+};
+pub use utils::{
+    alias_domains, auto_concurrency, deduplicate_domains, distinct_endpoints, expand_domain_inputs,
+    expand_tld_variants, extract_domain_parts, reserved_label, sample_tlds_per_registry,
+    validate_tlds,
 };
-pub use types::{CheckConfig, CheckMethod, DomainInfo, DomainResult, OutputMode};
-pub use utils::expand_domain_inputs;
 
 // Public modules
 pub mod generate;
 
 // Re-export generation types for convenience
-pub use generate::{apply_affixes, estimate_pattern_count, expand_pattern, generate_names};
+pub use generate::{
+    apply_affixes, estimate_generation, estimate_pattern_count, expand_pattern, generate_names,
+};
 pub use types::{GenerateConfig, GenerationResult};
 
 // Internal modules - these are not part of the public API
 mod checker;
 mod concurrent;
 mod config;
+mod confusables;
+mod dates;
+#[cfg(any(feature = "parking", feature = "redirects"))]
+mod dns;
+mod duration_serde;
+mod endpoints;
 mod error;
+mod init;
+#[cfg(feature = "parking")]
+mod parking;
+mod policy;
 mod protocols;
+#[cfg(feature = "redirects")]
+mod redirects;
+mod sink;
+#[cfg(test)]
+mod test_http;
+mod timings;
 mod types;
 mod utils;
 
@@ -65,11 +108,27 @@ pub const AUTHOR: &str = env!("CARGO_PKG_AUTHORS");
 
 /// Initialize the library with default settings.
 ///
-/// This function can be called to set up global state like logging,
-/// registry caches, etc. It's optional - the library will work without it.
+/// Equivalent to `init_with(InitOptions::default())`, which changes nothing.
+/// It's optional - the library will work without it.
 pub fn init() {
-    // Future: Initialize global caches, logging, etc.
-    // For now, this is a no-op but provides future extensibility
+    // Default options touch no files, so this cannot fail
+    let _ = init_with(InitOptions::default());
+}
+
+/// Initialize global state so later checkers start warm.
+///
+/// Intended for server startup: preload the bootstrap cache from a saved
+/// IANA registry (no fetch is needed for its TLDs for 24 hours), set the
+/// configuration used by `DomainChecker::new()`, and optionally install a
+/// `tracing` subscriber. Safe to call more than once; later calls replace
+/// what they set.
+///
+/// # Errors
+///
+/// Returns an error if the bootstrap snapshot can't be read or parsed. Nothing
+/// else is changed in that case.
+pub fn init_with(options: InitOptions) -> Result<()> {
+    init::init_with(options)
 }
 
 /// Get library information for debugging or display purposes.
@@ -107,6 +166,21 @@ fn get_enabled_features() -> Vec<&'static str> {
     #[cfg(feature = "debug")]
     features.push("debug");
 
+    #[cfg(feature = "parking")]
+    features.push("parking");
+
+    #[cfg(feature = "redirects")]
+    features.push("redirects");
+
+    #[cfg(feature = "duration-float-secs")]
+    features.push("duration-float-secs");
+
+    #[cfg(feature = "duration-struct")]
+    features.push("duration-struct");
+
+    #[cfg(feature = "tracing")]
+    features.push("tracing");
+
     features
 }
 
@@ -221,6 +295,13 @@ mod tests {
             check_duration: None,
             method_used: CheckMethod::Rdap,
             error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
         };
         assert_eq!(result.domain, "example.com");
         assert_eq!(result.available, Some(true));
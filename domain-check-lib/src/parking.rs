@@ -0,0 +1,189 @@
+//! Parked-domain detection for taken domains (enabled by the `parking` feature).
+//!
+//! This is enrichment, not availability: it fetches the root page of an
+//! already-registered domain and looks for signatures of common parking and
+//! domain-marketplace providers. Any fetch failure yields `None` ("don't
+//! know") rather than an error.
+
+use crate::error::DomainCheckError;
+use crate::types::{CheckConfig, DomainResult};
+
+/// Maximum bytes of a page body inspected for signatures.
+const MAX_BODY_BYTES: usize = 256 * 1024;
+
+/// Lowercase substrings that identify parking providers or parked-page copy.
+const PARKING_SIGNATURES: &[&str] = &[
+    "sedoparking",
+    "sedo domain parking",
+    "parkingcrew",
+    "bodis.com",
+    "above.com",
+    "parklogic",
+    "domainmarket",
+    "hugedomains",
+    "afternic",
+    "dan.com",
+    "this domain is parked",
+    "this domain may be for sale",
+    "this domain is for sale",
+    "buy this domain",
+    "domain is parked free",
+    "parked free, courtesy of",
+];
+
+/// Hosts that parked domains commonly redirect to.
+const MARKETPLACE_HOSTS: &[&str] = &[
+    "sedo.com",
+    "dan.com",
+    "afternic.com",
+    "hugedomains.com",
+    "bodis.com",
+    "parkingcrew.net",
+    "above.com",
+];
+
+/// Heuristically decide whether an HTML page is a parking page.
+pub fn is_parking_page(html: &str) -> bool {
+    let lower = html.to_lowercase();
+    PARKING_SIGNATURES.iter().any(|sig| lower.contains(sig))
+}
+
+/// Whether a URL host belongs to a known domain marketplace.
+fn is_marketplace_host(host: &str) -> bool {
+    let host = host.to_lowercase();
+    MARKETPLACE_HOSTS
+        .iter()
+        .any(|m| host == *m || host.ends_with(&format!(".{}", m)))
+}
+
+/// Build the HTTP client used by [`detect_parking`].
+///
+/// Requests time out after `config.timeout`, follow up to five redirects,
+/// and resolve hostnames through `config.dns_servers` when set. Build it
+/// once per run and share it across domains.
+pub fn parking_client(config: &CheckConfig) -> Result<reqwest::Client, DomainCheckError> {
+    let builder = reqwest::Client::builder()
+        .timeout(config.timeout)
+        .redirect(reqwest::redirect::Policy::limited(5));
+    crate::dns::configure_resolver(builder, config.dns_servers.as_deref())
+        .build()
+        .map_err(|e| DomainCheckError::internal(format!("Failed to build parking client: {}", e)))
+}
+
+/// Fetch a domain's root page through `client` (see [`parking_client`]) and
+/// check it for parking signatures.
+///
+/// Follows redirects; landing on a known marketplace counts as parked.
+/// Returns None when the page can't be fetched.
+pub async fn detect_parking(client: &reqwest::Client, domain: &str) -> Option<bool> {
+    let response = client
+        .get(format!("http://{}/", domain))
+        .send()
+        .await
+        .ok()?;
+
+    if let Some(host) = response.url().host_str() {
+        if is_marketplace_host(host) {
+            return Some(true);
+        }
+    }
+
+    let bytes = response.bytes().await.ok()?;
+    let body = String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_BODY_BYTES)]);
+    Some(is_parking_page(&body))
+}
+
+/// Fill in `parked` for every taken result, checking up to `config.concurrency` at once.
+///
+/// Available and unknown results are left untouched, as are all results if
+/// the client can't be built.
+pub async fn enrich_parking(results: &mut [DomainResult], config: &CheckConfig) {
+    use futures_util::StreamExt;
+
+    let Ok(client) = parking_client(config) else {
+        return;
+    };
+
+    let taken: Vec<(usize, String)> = results
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.available == Some(false))
+        .map(|(i, r)| (i, r.domain.clone()))
+        .collect();
+
+    let verdicts: Vec<(usize, Option<bool>)> = futures_util::stream::iter(taken)
+        .map(|(i, domain)| {
+            let client = &client;
+            async move { (i, detect_parking(client, &domain).await) }
+        })
+        .buffer_unordered(config.concurrency.max(1))
+        .collect()
+        .await;
+
+    for (i, parked) in verdicts {
+        results[i].parked = parked;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http;
+
+    const SEDO_PAGE: &str = r#"<!DOCTYPE html>
+<html><head><title>example-brand.com</title>
+<script src="//img.sedoparking.com/js/park.js"></script></head>
+<body><div class="banner">This domain may be for sale!</div>
+<a href="https://sedo.com/search/details/?domain=example-brand.com">Buy this domain</a>
+</body></html>"#;
+
+    const NORMAL_PAGE: &str = r#"<!DOCTYPE html>
+<html><head><title>Example Brand — Handmade Furniture</title></head>
+<body><nav><a href="/shop">Shop</a><a href="/about">About us</a></nav>
+<h1>Handmade furniture since 1998</h1><p>Visit our workshop in Portland.</p>
+</body></html>"#;
+
+    #[test]
+    fn test_parking_page_detected() {
+        assert!(is_parking_page(SEDO_PAGE));
+        assert!(is_parking_page("<title>THIS DOMAIN IS PARKED</title>"));
+    }
+
+    #[test]
+    fn test_normal_page_not_detected() {
+        assert!(!is_parking_page(NORMAL_PAGE));
+    }
+
+    #[test]
+    fn test_marketplace_host() {
+        assert!(is_marketplace_host("sedo.com"));
+        assert!(is_marketplace_host("www.HugeDomains.com"));
+        assert!(!is_marketplace_host("notsedo.com"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_parking_classifies_pages() {
+        let client = parking_client(&CheckConfig::default()).unwrap();
+
+        let page = |body| vec![test_http::response("200 OK", Some("text/html"), body)];
+        let parked = test_http::serve(page(SEDO_PAGE)).await;
+        assert_eq!(
+            detect_parking(&client, &parked.to_string()).await,
+            Some(true)
+        );
+
+        let normal = test_http::serve(page(NORMAL_PAGE)).await;
+        assert_eq!(
+            detect_parking(&client, &normal.to_string()).await,
+            Some(false)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_failure_is_unknown() {
+        let addr = test_http::closed_addr().await;
+
+        let client = parking_client(&CheckConfig::default()).unwrap();
+        assert_eq!(detect_parking(&client, &addr.to_string()).await, None);
+    }
+}
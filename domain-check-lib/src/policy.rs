@@ -0,0 +1,65 @@
+//! Pluggable availability classification for RDAP responses.
+//!
+//! By default a domain with an RDAP record is taken. Registries disagree on
+//! edge cases (e.g. `serverHold`), so library users can attach a
+//! `ClassificationPolicy` to `CheckConfig` to decide availability from the
+//! raw RDAP JSON themselves.
+
+use serde_json::Value;
+use std::fmt::Debug;
+
+/// Decides availability from a successful (HTTP 200) RDAP response.
+///
+/// Return `Some(true)` for available, `Some(false)` for taken, or `None`
+/// to fall back to the default decision (taken).
+///
+/// # Example
+///
+/// ```rust
+/// use domain_check_lib::{CheckConfig, ClassificationPolicy};
+/// use serde_json::Value;
+///
+/// #[derive(Debug)]
+/// struct ServerHoldIsAvailable;
+///
+/// impl ClassificationPolicy for ServerHoldIsAvailable {
+///     fn classify(&self, rdap_json: &Value) -> Option<bool> {
+///         let statuses = rdap_json["status"].as_array()?;
+///         statuses
+///             .iter()
+///             .any(|s| s.as_str() == Some("server hold"))
+///             .then_some(true)
+///     }
+/// }
+///
+/// let config = CheckConfig::default().with_classification_policy(ServerHoldIsAvailable);
+/// ```
+pub trait ClassificationPolicy: Debug + Send + Sync {
+    /// Classify an RDAP domain object.
+    fn classify(&self, rdap_json: &Value) -> Option<bool>;
+}
+
+/// The built-in policy: any RDAP record means the domain is taken.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultClassificationPolicy;
+
+impl ClassificationPolicy for DefaultClassificationPolicy {
+    fn classify(&self, _rdap_json: &Value) -> Option<bool> {
+        Some(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_is_taken() {
+        let json = serde_json::json!({"status": ["server hold"]});
+        assert_eq!(DefaultClassificationPolicy.classify(&json), Some(false));
+        assert_eq!(
+            DefaultClassificationPolicy.classify(&serde_json::json!({})),
+            Some(false)
+        );
+    }
+}
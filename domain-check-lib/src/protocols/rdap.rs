@@ -4,11 +4,69 @@
 //! which is the modern replacement for WHOIS. RDAP provides structured JSON responses
 //! with standardized data formats.
 
+use crate::concurrent::HostLimiter;
+use crate::endpoints::{EndpointStats, EndpointTracker};
 use crate::error::DomainCheckError;
-use crate::protocols::registry::{extract_tld, get_rdap_endpoint};
-use crate::types::{CheckMethod, DomainInfo, DomainResult};
+use crate::policy::ClassificationPolicy;
+use crate::protocols::registry::{endpoint_host, extract_tld, get_rdap_endpoint_via};
+use crate::types::{CheckEvent, CheckMethod, Confidence, DomainInfo, DomainResult, IdnVariant};
 use reqwest::StatusCode;
-use std::time::{Duration, Instant};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::OnceCell;
+
+/// Default time allowed for DNS resolution and TCP/TLS connection setup.
+pub(crate) const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Default cap on an RDAP response body; real records are a few kilobytes.
+pub(crate) const DEFAULT_MAX_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Slack added to the RDAP timeout for the HTTP client's own request timeout.
+const HTTP_TIMEOUT_BUFFER: Duration = Duration::from_secs(2);
+
+/// Process-wide HTTP client shared by checkers using the default connect timeout.
+///
+/// Cloning a `reqwest::Client` shares its connection pool, so every checker
+/// built from this client reuses the same pooled connections and TLS sessions.
+/// Request timeouts are applied per request, so only the connect timeout is
+/// fixed here. Compressed responses (gzip, brotli, deflate) are decoded
+/// transparently, as some registries compress RDAP JSON.
+///
+/// The client is built on first use; if that fails (e.g. the TLS backend
+/// can't be initialised) every call returns the same error.
+pub fn shared_http_client() -> Result<reqwest::Client, DomainCheckError> {
+    static CLIENT: OnceLock<Result<reqwest::Client, DomainCheckError>> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| build_http_client(DEFAULT_CONNECT_TIMEOUT, None))
+        .clone()
+}
+
+/// Build an HTTP client for RDAP requests.
+///
+/// Every client decodes compressed responses (gzip, brotli, deflate) and
+/// bounds connection setup by `connect_timeout`; `timeout`, when given, caps
+/// each whole request.
+fn build_http_client(
+    connect_timeout: Duration,
+    timeout: Option<Duration>,
+) -> Result<reqwest::Client, DomainCheckError> {
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(connect_timeout)
+        .gzip(true)
+        .brotli(true)
+        .deflate(true);
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    builder.build().map_err(|e| {
+        DomainCheckError::network_with_source("Failed to create RDAP HTTP client", e.to_string())
+    })
+}
+
+/// Per-TLD wildcard probe outcomes, each computed at most once.
+type WildcardProbes = Mutex<HashMap<String, Arc<OnceCell<bool>>>>;
 
 /// RDAP client for checking domain availability.
 ///
@@ -18,51 +76,128 @@ use std::time::{Duration, Instant};
 pub struct RdapClient {
     /// HTTP client for making RDAP requests
     http_client: reqwest::Client,
-    /// Timeout for RDAP requests
+    /// Timeout for RDAP requests (total, including body read)
     timeout: Duration,
+    /// Timeout for establishing the connection (DNS + TCP + TLS)
+    connect_timeout: Duration,
     /// Whether to use IANA bootstrap for unknown TLDs
     use_bootstrap: bool,
+    /// Optional sink for retry / rate-limit lifecycle events
+    events: Option<UnboundedSender<CheckEvent>>,
+    /// Optional override for classifying RDAP records as available/taken
+    policy: Option<Arc<dyn ClassificationPolicy>>,
+    /// Whether to follow one `related` link to the registrar's RDAP server
+    follow_registrar: bool,
+    /// Wildcard probe results shared by clones (None = detection disabled)
+    wildcard_probes: Option<Arc<WildcardProbes>>,
+    /// Per-TLD overrides of `timeout` for registries known to be slow
+    tld_timeouts: Arc<HashMap<String, Duration>>,
+    /// Largest response body read before giving up
+    max_response_bytes: usize,
+    /// Extra requests made after a timeout before reporting it
+    timeout_retries: u8,
+    /// Endpoint used for every TLD instead of the registry's (None = resolve per TLD)
+    server_override: Option<String>,
+    /// Bootstrap registry fetched for unknown TLDs (None = IANA)
+    bootstrap_url: Option<String>,
+    /// Per-host request cap shared by clones (None = only the global limit)
+    host_limiter: Option<Arc<HostLimiter>>,
+    /// Per-endpoint outcome tallies shared by clones (None = not tracked)
+    endpoint_tracker: Option<Arc<EndpointTracker>>,
+    /// Drain 200 bodies without parsing them when nothing needs the record
+    skip_info: bool,
 }
 
 impl RdapClient {
     /// Create a new RDAP client with default settings.
     pub fn new() -> Result<Self, DomainCheckError> {
-        let http_client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(5))
-            .build()
-            .map_err(|e| {
-                DomainCheckError::network_with_source(
-                    "Failed to create RDAP HTTP client",
-                    e.to_string(),
-                )
-            })?;
+        let http_client = build_http_client(DEFAULT_CONNECT_TIMEOUT, Some(Duration::from_secs(5)))?;
 
         Ok(Self {
             http_client,
             timeout: Duration::from_secs(3),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
             use_bootstrap: false,
+            events: None,
+            policy: None,
+            follow_registrar: false,
+            wildcard_probes: None,
+            tld_timeouts: Arc::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            timeout_retries: 0,
+            server_override: None,
+            bootstrap_url: None,
+            host_limiter: None,
+            endpoint_tracker: None,
+            skip_info: false,
         })
     }
 
     /// Create a new RDAP client with custom settings.
-    pub fn with_config(timeout: Duration, use_bootstrap: bool) -> Result<Self, DomainCheckError> {
-        let http_client = reqwest::Client::builder()
-            .timeout(timeout + Duration::from_secs(2)) // Add buffer for HTTP timeout
-            .build()
-            .map_err(|e| {
-                DomainCheckError::network_with_source(
-                    "Failed to create RDAP HTTP client",
-                    e.to_string(),
-                )
-            })?;
+    ///
+    /// `connect_timeout` bounds DNS resolution and connection setup, so a dead
+    /// host fails fast. `timeout` bounds the whole request including the body
+    /// read, so a slow-but-responsive registry still gets the full budget.
+    pub fn with_config(
+        timeout: Duration,
+        connect_timeout: Duration,
+        use_bootstrap: bool,
+    ) -> Result<Self, DomainCheckError> {
+        // Add buffer for HTTP timeout
+        let http_client = build_http_client(connect_timeout, Some(timeout + HTTP_TIMEOUT_BUFFER))?;
 
         Ok(Self {
             http_client,
             timeout,
+            connect_timeout,
             use_bootstrap,
+            events: None,
+            policy: None,
+            follow_registrar: false,
+            wildcard_probes: None,
+            tld_timeouts: Arc::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            timeout_retries: 0,
+            server_override: None,
+            bootstrap_url: None,
+            host_limiter: None,
+            endpoint_tracker: None,
+            skip_info: false,
         })
     }
 
+    /// Create an RDAP client on top of an existing HTTP client.
+    ///
+    /// Clients created from clones of the same `reqwest::Client` share its
+    /// connection pool, which avoids repeated connection and TLS setup when
+    /// many checkers are created (e.g. one per request in a server). The
+    /// provided client's own connect timeout applies; `timeout` is enforced
+    /// per request.
+    pub fn with_http_client(
+        http_client: reqwest::Client,
+        timeout: Duration,
+        use_bootstrap: bool,
+    ) -> Self {
+        Self {
+            http_client,
+            timeout,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            use_bootstrap,
+            events: None,
+            policy: None,
+            follow_registrar: false,
+            wildcard_probes: None,
+            tld_timeouts: Arc::default(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            timeout_retries: 0,
+            server_override: None,
+            bootstrap_url: None,
+            host_limiter: None,
+            endpoint_tracker: None,
+            skip_info: false,
+        }
+    }
+
     /// Check domain availability using RDAP.
     ///
     /// # Arguments
@@ -85,8 +220,26 @@ impl RdapClient {
 
         // Extract TLD and get RDAP endpoint
         let tld = extract_tld(domain)?;
-        let endpoint = get_rdap_endpoint(&tld, self.use_bootstrap).await?;
+        let endpoint = match &self.server_override {
+            Some(server) => server.clone(),
+            None => {
+                get_rdap_endpoint_via(&tld, self.use_bootstrap, self.bootstrap_url.as_deref())
+                    .await?
+            }
+        };
 
+        self.check_domain_at(domain, &tld, &endpoint, start_time)
+            .await
+    }
+
+    /// Check a domain against a resolved RDAP endpoint.
+    async fn check_domain_at(
+        &self,
+        domain: &str,
+        tld: &str,
+        endpoint: &str,
+        start_time: Instant,
+    ) -> Result<DomainResult, DomainCheckError> {
         // Build RDAP URL
         let rdap_url = format!("{}{}", endpoint, domain);
 
@@ -95,26 +248,47 @@ impl RdapClient {
             println!("🔍 Attempting RDAP request to: {}", rdap_url);
         }
 
-        // Make RDAP request with timeout
-        let result =
-            tokio::time::timeout(self.timeout, self.make_rdap_request(&rdap_url, domain)).await;
+        // Make RDAP request with timeout, within the host's request cap
+        let host_permit = match &self.host_limiter {
+            Some(limiter) => Some(limiter.acquire(endpoint_host(&rdap_url)).await),
+            None => None,
+        };
+        let result = self.fetch_with_timeout(&rdap_url, domain).await;
+        drop(host_permit);
+        if let Some(tracker) = &self.endpoint_tracker {
+            let outcome = result.as_ref().map(|(_, _, status)| *status);
+            tracker.record(endpoint_host(&rdap_url), outcome);
+        }
 
         let check_duration = start_time.elapsed();
 
         match result {
-            Ok(Ok((available, info))) => Ok(DomainResult {
-                domain: domain.to_string(),
-                available: Some(available),
-                info,
-                check_duration: Some(check_duration),
-                method_used: if self.use_bootstrap {
-                    CheckMethod::Bootstrap
-                } else {
-                    CheckMethod::Rdap
-                },
-                error_message: None,
-            }),
-            Ok(Err(e)) => {
+            Ok((available, info, status)) => {
+                let mut result = DomainResult {
+                    domain: domain.to_string(),
+                    available: Some(available),
+                    info,
+                    check_duration: Some(check_duration),
+                    method_used: if self.use_bootstrap {
+                        CheckMethod::Bootstrap
+                    } else {
+                        CheckMethod::Rdap
+                    },
+                    error_message: None,
+                    error_category: None,
+                    confidence: None,
+                    note: None,
+                    parked: None,
+                    rdap_status: Some(status),
+                    redirects_to: None,
+                    attempts: None,
+                };
+                if !available && self.tld_is_wildcarded(tld, endpoint).await {
+                    mark_wildcarded(&mut result, tld);
+                }
+                Ok(result)
+            }
+            Err(e) => {
                 // 🔍 DEBUG: Log RDAP errors
                 if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
                     println!("🔍 RDAP Error for {}: {}", domain, e);
@@ -129,31 +303,334 @@ impl RdapClient {
                         check_duration: Some(check_duration),
                         method_used: CheckMethod::Rdap,
                         error_message: None,
+                        error_category: None,
+                        confidence: None,
+                        note: None,
+                        parked: None,
+                        rdap_status: Some(404),
+                        redirects_to: None,
+                        attempts: None,
                     })
                 } else {
                     Err(e)
                 }
             }
-            Err(_) => {
-                // 🔍 DEBUG: Log timeout
-                if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
-                    println!("🔍 RDAP Timeout for {} after {:?}", domain, self.timeout);
-                }
+        }
+    }
+
+    /// Attach a channel that receives retry and rate-limit events.
+    pub(crate) fn with_events(mut self, events: UnboundedSender<CheckEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Attach a policy that decides availability for RDAP records.
+    pub(crate) fn with_policy(mut self, policy: Option<Arc<dyn ClassificationPolicy>>) -> Self {
+        self.policy = policy;
+        self
+    }
 
-                Err(DomainCheckError::timeout("RDAP request", self.timeout))
+    /// Follow registry `related` links to the registrar's RDAP server for richer info.
+    pub(crate) fn with_follow_registrar(mut self, enabled: bool) -> Self {
+        self.follow_registrar = enabled;
+        self
+    }
+
+    /// Probe one random name per TLD to detect registries where every name is taken.
+    pub(crate) fn with_wildcard_detection(mut self, enabled: bool) -> Self {
+        self.wildcard_probes = enabled.then(|| Arc::new(Mutex::new(HashMap::new())));
+        self
+    }
+
+    /// Count each lookup's outcome against its endpoint host.
+    pub(crate) fn with_endpoint_tracking(mut self, enabled: bool) -> Self {
+        self.endpoint_tracker = enabled.then(Arc::default);
+        self
+    }
+
+    /// Report any 200 response as taken without parsing its body.
+    ///
+    /// Only valid when no caller needs the registration record: no
+    /// detailed info, classification policy or status-based post-processing.
+    pub(crate) fn with_skip_info(mut self, skip: bool) -> Self {
+        self.skip_info = skip;
+        self
+    }
+
+    /// Outcome tallies per endpoint so far (empty when tracking is off).
+    pub(crate) fn endpoint_stats(&self) -> Vec<EndpointStats> {
+        self.endpoint_tracker
+            .as_ref()
+            .map(|tracker| tracker.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Use longer (or shorter) timeouts for specific TLDs.
+    pub(crate) fn with_tld_timeouts(mut self, tld_timeouts: HashMap<String, Duration>) -> Self {
+        self.tld_timeouts = Arc::new(tld_timeouts);
+        self
+    }
+
+    /// Refuse RDAP response bodies larger than `max_bytes`.
+    pub(crate) fn with_max_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_response_bytes = max_bytes;
+        self
+    }
+
+    /// Send every query to `server` instead of resolving the TLD's endpoint.
+    ///
+    /// Queries go to `<server>/<domain>`; a trailing slash on `server` is optional.
+    pub(crate) fn with_server_override(mut self, server: Option<&str>) -> Self {
+        self.server_override = server.map(|url| format!("{}/", url.trim_end_matches('/')));
+        self
+    }
+
+    /// Fetch the bootstrap registry from `url` instead of IANA.
+    pub(crate) fn with_bootstrap_url(mut self, url: Option<&str>) -> Self {
+        self.bootstrap_url = url.map(str::to_string);
+        self
+    }
+
+    /// Allow at most `per_host` requests in flight to any one RDAP host.
+    pub(crate) fn with_concurrency_per_host(mut self, per_host: Option<usize>) -> Self {
+        self.host_limiter = per_host.map(|n| Arc::new(HostLimiter::new(n)));
+        self
+    }
+
+    /// Retry a timed-out request up to `retries` more times.
+    pub(crate) fn with_timeout_retries(mut self, retries: u8) -> Self {
+        self.timeout_retries = retries;
+        self
+    }
+
+    /// Read a JSON response body, refusing bodies over `max_response_bytes`.
+    ///
+    /// The declared Content-Length is checked first; chunked or unlabelled
+    /// bodies are read incrementally and abandoned once they pass the cap, so
+    /// an oversized body is never buffered in full.
+    async fn read_json(
+        &self,
+        response: reqwest::Response,
+        domain: &str,
+    ) -> Result<serde_json::Value, DomainCheckError> {
+        let body = self.read_body(response, domain).await?;
+        serde_json::from_slice(&body).map_err(|e| DomainCheckError::ParseError {
+            message: format!("Failed to parse RDAP JSON for {}: {}", domain, e),
+            content: None,
+        })
+    }
+
+    /// Read a response body, failing once it exceeds `max_response_bytes`.
+    async fn read_body(
+        &self,
+        mut response: reqwest::Response,
+        domain: &str,
+    ) -> Result<Vec<u8>, DomainCheckError> {
+        let limit = self.max_response_bytes;
+        let too_large = || {
+            DomainCheckError::rdap(
+                domain,
+                format!("RDAP response exceeds the {} byte size limit", limit),
+            )
+        };
+        if response
+            .content_length()
+            .is_some_and(|len| len > limit as u64)
+        {
+            return Err(too_large());
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| self.request_error(domain, "Failed to read RDAP response", e))?
+        {
+            if body.len() + chunk.len() > limit {
+                return Err(too_large());
             }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+
+    /// The total timeout for a domain: its TLD's override, or the global one.
+    fn timeout_for(&self, domain: &str) -> Duration {
+        if self.tld_timeouts.is_empty() {
+            return self.timeout;
+        }
+        let tld = domain.rsplit('.').next().unwrap_or_default().to_lowercase();
+        self.tld_timeouts.get(&tld).copied().unwrap_or(self.timeout)
+    }
+
+    /// TLDs whose random-name probe was reported taken, sorted.
+    pub(crate) fn wildcard_tlds(&self) -> Vec<String> {
+        let Some(probes) = &self.wildcard_probes else {
+            return Vec::new();
+        };
+        let probes = probes.lock().unwrap_or_else(|e| e.into_inner());
+        let mut tlds: Vec<String> = probes
+            .iter()
+            .filter(|(_, probe)| probe.get() == Some(&true))
+            .map(|(tld, _)| tld.clone())
+            .collect();
+        tlds.sort();
+        tlds
+    }
+
+    /// Whether a random name under `tld` is also reported taken.
+    ///
+    /// The probe runs at most once per TLD; concurrent callers wait for the
+    /// first probe's outcome. A probe that fails counts as not wildcarded.
+    async fn tld_is_wildcarded(&self, tld: &str, endpoint: &str) -> bool {
+        let Some(probes) = &self.wildcard_probes else {
+            return false;
+        };
+        let probe = {
+            let mut probes = probes.lock().unwrap_or_else(|e| e.into_inner());
+            Arc::clone(probes.entry(tld.to_lowercase()).or_default())
+        };
+
+        *probe
+            .get_or_init(|| async {
+                let probe_domain = format!("{}.{}", random_probe_label(), tld);
+                let probe_url = format!("{}{}", endpoint, probe_domain);
+                matches!(
+                    self.fetch_with_timeout(&probe_url, &probe_domain).await,
+                    Ok((false, _, _))
+                )
+            })
+            .await
+    }
+
+    /// Fill gaps in registry info from the registrar's RDAP record (one hop).
+    ///
+    /// Any failure fetching or parsing the registrar response leaves the
+    /// registry info unchanged.
+    async fn enrich_from_registrar(
+        &self,
+        json: &serde_json::Value,
+        rdap_url: &str,
+        info: DomainInfo,
+    ) -> DomainInfo {
+        if !self.follow_registrar {
+            return info;
+        }
+        let Some(link) = registrar_rdap_link(json, rdap_url) else {
+            return info;
+        };
+
+        let registrar_json = match self
+            .http_client
+            .get(&link)
+            .timeout(self.timeout + HTTP_TIMEOUT_BUFFER)
+            .send()
+            .await
+        {
+            Ok(response) if response.status() == StatusCode::OK => {
+                self.read_json(response, &link).await.ok()
+            }
+            _ => None,
+        };
+
+        match registrar_json {
+            Some(registrar_json) => merge_domain_info(info, extract_domain_info(&registrar_json)),
+            None => info,
+        }
+    }
+
+    /// Availability for a domain with an RDAP record (taken unless a policy says otherwise).
+    fn classify(&self, json: &serde_json::Value) -> bool {
+        self.policy
+            .as_ref()
+            .and_then(|policy| policy.classify(json))
+            .unwrap_or(false)
+    }
+
+    /// Send a lifecycle event if a listener is attached (a closed channel is ignored).
+    fn emit(&self, event: CheckEvent) {
+        if let Some(events) = &self.events {
+            let _ = events.send(event);
+        }
+    }
+
+    /// Run an RDAP request under the total timeout.
+    ///
+    /// The tokio timeout guards the whole exchange (connect + headers + body);
+    /// connection setup is separately bounded by the HTTP client's connect timeout.
+    /// The total is the domain's per-TLD override when one is configured.
+    /// A timed-out request is retried `timeout_retries` times; the final
+    /// error records how many attempts were made.
+    async fn fetch_with_timeout(
+        &self,
+        rdap_url: &str,
+        domain: &str,
+    ) -> Result<(bool, Option<DomainInfo>, u16), DomainCheckError> {
+        let timeout = self.timeout_for(domain);
+        let attempts = self.timeout_retries.saturating_add(1);
+        for attempt in 1..=attempts {
+            match tokio::time::timeout(timeout, self.make_rdap_request(rdap_url, domain)).await {
+                Ok(result) => return result,
+                Err(_) => {
+                    // 🔍 DEBUG: Log timeout
+                    if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
+                        println!(
+                            "🔍 RDAP Timeout for {} after {:?} (attempt {}/{})",
+                            domain, timeout, attempt, attempts
+                        );
+                    }
+
+                    if attempt < attempts {
+                        self.emit(CheckEvent::Retrying {
+                            domain: domain.to_string(),
+                            attempt: u32::from(attempt) + 1,
+                        });
+                    }
+                }
+            }
+        }
+
+        Err(DomainCheckError::timeout_after_attempts(
+            "RDAP request",
+            timeout,
+            attempts,
+        ))
+    }
+
+    /// Convert a failed HTTP send into a `DomainCheckError`.
+    ///
+    /// Connect timeouts become network errors (the host is unreachable), which
+    /// keeps them distinct from a registry that is merely slow to respond.
+    fn request_error(&self, domain: &str, context: &str, e: reqwest::Error) -> DomainCheckError {
+        if e.is_connect() && e.is_timeout() {
+            DomainCheckError::network_with_source(
+                format!(
+                    "Connection to RDAP server for {} timed out after {:?}",
+                    domain, self.connect_timeout
+                ),
+                e.to_string(),
+            )
+        } else {
+            DomainCheckError::rdap(domain, format!("{}: {}", context, e))
         }
     }
 
     /// Make an RDAP request to the specified URL.
-    /// Make an RDAP request to the specified URL.
+    ///
+    /// Returns the availability, any parsed registration info and the HTTP
+    /// status that decided them (the retry's status after a 429).
     async fn make_rdap_request(
         &self,
         rdap_url: &str,
         domain: &str,
-    ) -> Result<(bool, Option<DomainInfo>), DomainCheckError> {
+    ) -> Result<(bool, Option<DomainInfo>, u16), DomainCheckError> {
+        // The client-wide HTTP timeout is sized for the global timeout, so
+        // stretch it for TLDs with a longer override
+        let http_timeout = self.timeout_for(domain) + HTTP_TIMEOUT_BUFFER;
+
         // First attempt
-        let response = self.http_client.get(rdap_url).send().await.map_err(|e| {
+        let request = self.http_client.get(rdap_url).timeout(http_timeout);
+        let response = request.send().await.map_err(|e| {
             // 🔍 DEBUG: Log request errors
             if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
                 println!("🔍 HTTP Request failed for {}: {}", rdap_url, e);
@@ -165,7 +642,7 @@ impl RdapClient {
                     println!("   └─ Request error");
                 }
             }
-            DomainCheckError::rdap(domain, format!("Request failed: {}", e))
+            self.request_error(domain, "Request failed", e)
         })?;
 
         // 🔍 DEBUG: Log response status
@@ -175,10 +652,15 @@ impl RdapClient {
 
         match response.status() {
             StatusCode::OK => {
+                ensure_rdap_content_type(&response, domain)?;
+                if self.skip_info {
+                    // Drained so the connection can be reused
+                    self.read_body(response, domain).await?;
+                    return Ok((false, None, 200));
+                }
+
                 // Domain exists, parse the response
-                let json = response.json::<serde_json::Value>().await.map_err(|e| {
-                    DomainCheckError::rdap(domain, format!("Failed to parse JSON: {}", e))
-                })?;
+                let json = self.read_json(response, domain).await?;
 
                 // 🔍 DEBUG: Print the actual JSON response for analysis
                 if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
@@ -191,6 +673,9 @@ impl RdapClient {
                 }
 
                 let domain_info = extract_domain_info(&json);
+                let domain_info = self
+                    .enrich_from_registrar(&json, rdap_url, domain_info)
+                    .await;
 
                 // 🔍 DEBUG: Print extracted info
                 if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
@@ -202,14 +687,14 @@ impl RdapClient {
                     println!("--- End Extracted Info ---\n");
                 }
 
-                Ok((false, Some(domain_info)))
+                Ok((self.classify(&json), Some(domain_info), 200))
             }
             StatusCode::NOT_FOUND => {
                 // Domain is available
                 if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
                     println!("🔍 Domain {} is available (404)", domain);
                 }
-                Ok((true, None))
+                Ok((true, None, 404))
             }
             StatusCode::TOO_MANY_REQUESTS => {
                 // Rate limited, try once more after a short delay
@@ -217,29 +702,40 @@ impl RdapClient {
                     println!("🔍 Rate limited for {}, retrying after 500ms...", domain);
                 }
 
+                self.emit(CheckEvent::RateLimited {
+                    host: endpoint_host(rdap_url).to_string(),
+                });
+                self.emit(CheckEvent::Retrying {
+                    domain: domain.to_string(),
+                    attempt: 2,
+                });
+
                 tokio::time::sleep(Duration::from_millis(500)).await;
 
-                let retry_response = self.http_client.get(rdap_url).send().await.map_err(|e| {
-                    DomainCheckError::rdap(domain, format!("Retry request failed: {}", e))
-                })?;
+                let retry_response = self
+                    .http_client
+                    .get(rdap_url)
+                    .timeout(http_timeout)
+                    .send()
+                    .await
+                    .map_err(|e| self.request_error(domain, "Retry request failed", e))?;
 
                 match retry_response.status() {
                     StatusCode::OK => {
-                        let json =
-                            retry_response
-                                .json::<serde_json::Value>()
-                                .await
-                                .map_err(|e| {
-                                    DomainCheckError::rdap(
-                                        domain,
-                                        format!("Failed to parse retry JSON: {}", e),
-                                    )
-                                })?;
+                        ensure_rdap_content_type(&retry_response, domain)?;
+                        if self.skip_info {
+                            self.read_body(retry_response, domain).await?;
+                            return Ok((false, None, 200));
+                        }
+                        let json = self.read_json(retry_response, domain).await?;
 
                         let domain_info = extract_domain_info(&json);
-                        Ok((false, Some(domain_info)))
+                        let domain_info = self
+                            .enrich_from_registrar(&json, rdap_url, domain_info)
+                            .await;
+                        Ok((self.classify(&json), Some(domain_info), 200))
                     }
-                    StatusCode::NOT_FOUND => Ok((true, None)),
+                    StatusCode::NOT_FOUND => Ok((true, None, 404)),
                     code => {
                         if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
                             println!("🔍 Retry failed for {} with status: {}", domain, code);
@@ -252,6 +748,21 @@ impl RdapClient {
                     }
                 }
             }
+            code @ (StatusCode::FORBIDDEN | StatusCode::UNAVAILABLE_FOR_LEGAL_REASONS) => {
+                // Refused by policy; retrying won't help. The status code
+                // makes this ErrorCategory::Blocked rather than a server error.
+                if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
+                    println!(
+                        "🔍 RDAP lookup for {} blocked with status: {}",
+                        domain, code
+                    );
+                }
+                Err(DomainCheckError::rdap_with_status(
+                    domain,
+                    format!("RDAP server refused the lookup: {}", code),
+                    code.as_u16(),
+                ))
+            }
             code => {
                 if std::env::var("DOMAIN_CHECK_DEBUG_RDAP").is_ok() {
                     println!("🔍 RDAP server error for {} with status: {}", domain, code);
@@ -266,6 +777,56 @@ impl RdapClient {
     }
 }
 
+/// A label that is vanishingly unlikely to be registered under any TLD.
+fn random_probe_label() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    format!("dc-probe-{:08x}{:x}", nanos, std::process::id())
+}
+
+/// Downgrade a taken result on a TLD whose random-name probe was also taken.
+fn mark_wildcarded(result: &mut DomainResult, tld: &str) {
+    result.available = None;
+    result.confidence = Some(Confidence::Low);
+    result.note = Some(format!(
+        "TLD .{} may be wildcarded: a random name was also reported taken",
+        tld
+    ));
+}
+
+/// Reject 200 responses whose `Content-Type` isn't JSON.
+///
+/// Misconfigured servers, captive portals and WAF blocks often answer with
+/// an HTML page; reporting the media type is clearer than a JSON parse
+/// error. A missing header is tolerated.
+fn ensure_rdap_content_type(
+    response: &reqwest::Response,
+    domain: &str,
+) -> Result<(), DomainCheckError> {
+    let Some(value) = response.headers().get(reqwest::header::CONTENT_TYPE) else {
+        return Ok(());
+    };
+    let media_type = value
+        .to_str()
+        .unwrap_or("")
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+
+    if media_type.ends_with("json") {
+        Ok(())
+    } else {
+        Err(DomainCheckError::rdap(
+            domain,
+            format!("server returned non-RDAP content: {}", media_type),
+        ))
+    }
+}
+
 impl Default for RdapClient {
     fn default() -> Self {
         Self::new().expect("Failed to create default RDAP client")
@@ -346,9 +907,98 @@ pub fn extract_domain_info(json: &serde_json::Value) -> DomainInfo {
         }
     }
 
+    // Extract DNSSEC delegation state
+    info.dnssec = json
+        .get("secureDNS")
+        .and_then(|d| d.get("delegationSigned"))
+        .and_then(|v| v.as_bool());
+
+    // Extract IDN variants with their relation (RFC 9083 section 5.3)
+    if let Some(variants) = json.get("variants").and_then(|v| v.as_array()) {
+        for variant in variants {
+            let relation: Vec<String> = variant
+                .get("relation")
+                .and_then(|r| r.as_array())
+                .map(|r| {
+                    r.iter()
+                        .filter_map(|s| s.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let names = variant
+                .get("variantNames")
+                .and_then(|n| n.as_array())
+                .into_iter()
+                .flatten();
+            for name in names {
+                let ldh_name = name
+                    .get("ldhName")
+                    .or_else(|| name.get("unicodeName"))
+                    .and_then(|n| n.as_str());
+                let Some(ldh_name) = ldh_name else { continue };
+                // A name listed in several groups collects every relation
+                match info.variants.iter_mut().find(|v| v.name == ldh_name) {
+                    Some(existing) => {
+                        for r in &relation {
+                            if !existing.relation.contains(r) {
+                                existing.relation.push(r.clone());
+                            }
+                        }
+                    }
+                    None => info.variants.push(IdnVariant {
+                        name: ldh_name.to_string(),
+                        relation: relation.clone(),
+                    }),
+                }
+            }
+        }
+    }
+
     info
 }
 
+/// Find the registrar RDAP URL in a registry response's `links`.
+///
+/// Looks for a `rel: "related"` link that is RDAP (by media type or a
+/// `/domain/` path) and isn't the URL that was just queried.
+fn registrar_rdap_link(json: &serde_json::Value, rdap_url: &str) -> Option<String> {
+    json.get("links")?
+        .as_array()?
+        .iter()
+        .filter(|link| link.get("rel").and_then(|r| r.as_str()) == Some("related"))
+        .filter_map(|link| {
+            let href = link.get("href")?.as_str()?;
+            let is_rdap = link
+                .get("type")
+                .and_then(|t| t.as_str())
+                .is_some_and(|t| t.contains("rdap"))
+                || href.contains("/domain/");
+            let is_http = href.starts_with("https://") || href.starts_with("http://");
+            (is_rdap && is_http && href != rdap_url).then(|| href.to_string())
+        })
+        .next()
+}
+
+/// Merge registrar-level info into registry info, keeping registry values
+/// and filling only what the registry left empty.
+fn merge_domain_info(mut registry: DomainInfo, registrar: DomainInfo) -> DomainInfo {
+    registry.registrar = registry.registrar.or(registrar.registrar);
+    registry.creation_date = registry.creation_date.or(registrar.creation_date);
+    registry.expiration_date = registry.expiration_date.or(registrar.expiration_date);
+    registry.updated_date = registry.updated_date.or(registrar.updated_date);
+    if registry.status.is_empty() {
+        registry.status = registrar.status;
+    }
+    if registry.nameservers.is_empty() {
+        registry.nameservers = registrar.nameservers;
+    }
+    registry.dnssec = registry.dnssec.or(registrar.dnssec);
+    if registry.variants.is_empty() {
+        registry.variants = registrar.variants;
+    }
+    registry
+}
+
 /// Extract organization name from vCard format in RDAP entity.
 fn extract_vcard_name(entity: &serde_json::Value) -> Option<String> {
     entity
@@ -403,6 +1053,8 @@ fn extract_entity_identifier(entity: &serde_json::Value) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ErrorCategory;
+    use crate::test_http;
 
     // ── RdapClient creation ─────────────────────────────────────────────
 
@@ -417,7 +1069,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_rdap_client_with_config() {
-        let client = RdapClient::with_config(Duration::from_secs(10), true).unwrap();
+        let client =
+            RdapClient::with_config(Duration::from_secs(10), DEFAULT_CONNECT_TIMEOUT, true)
+                .unwrap();
         assert_eq!(client.timeout, Duration::from_secs(10));
         assert!(client.use_bootstrap);
     }
@@ -426,6 +1080,829 @@ mod tests {
     fn test_rdap_client_default() {
         let client = RdapClient::default();
         assert_eq!(client.timeout, Duration::from_secs(3));
+        assert_eq!(client.connect_timeout, DEFAULT_CONNECT_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn test_rdap_client_with_connect_timeout() {
+        let client =
+            RdapClient::with_config(Duration::from_secs(4), Duration::from_secs(1), false).unwrap();
+        assert_eq!(client.timeout, Duration::from_secs(4));
+        assert_eq!(client.connect_timeout, Duration::from_secs(1));
+    }
+
+    // ── Connect vs read timeouts (local mock servers) ───────────────────
+
+    /// Listener whose accept queue is full, so new connections hang in SYN.
+    async fn saturated_listener() -> (tokio::net::TcpListener, Vec<tokio::net::TcpStream>) {
+        let socket = tokio::net::TcpSocket::new_v4().unwrap();
+        socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let listener = socket.listen(0).unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Fill the backlog without ever accepting
+        let mut fillers = Vec::new();
+        for _ in 0..4 {
+            if let Ok(Ok(stream)) = tokio::time::timeout(
+                Duration::from_millis(100),
+                tokio::net::TcpStream::connect(addr),
+            )
+            .await
+            {
+                fillers.push(stream);
+            }
+        }
+        (listener, fillers)
+    }
+
+    /// Server that sends headers immediately but stalls before the body.
+    async fn slow_body_server(delay: Duration) -> std::net::SocketAddr {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            if let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let body = r#"{"objectClassName":"domain"}"#;
+                let headers = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/rdap+json\r\nContent-Length: {}\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(headers.as_bytes()).await;
+                let _ = stream.flush().await;
+                tokio::time::sleep(delay).await;
+                let _ = stream.write_all(body.as_bytes()).await;
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_connect_delay_is_network_error() {
+        let (listener, _fillers) = saturated_listener().await;
+        let addr = listener.local_addr().unwrap();
+        let client =
+            RdapClient::with_config(Duration::from_secs(5), Duration::from_millis(300), false)
+                .unwrap();
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+
+        match err {
+            DomainCheckError::NetworkError { message, .. } => {
+                assert!(message.contains("timed out"), "message: {}", message);
+            }
+            other => panic!("expected network error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_body_delay_is_timeout_error() {
+        let addr = slow_body_server(Duration::from_secs(3)).await;
+        let client =
+            RdapClient::with_config(Duration::from_millis(500), Duration::from_secs(2), false)
+                .unwrap();
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, DomainCheckError::Timeout { .. }),
+            "expected timeout error, got {:?}",
+            err
+        );
+    }
+
+    /// Server that reads every request and never answers, counting requests.
+    async fn silent_server() -> (std::net::SocketAddr, Arc<std::sync::atomic::AtomicUsize>) {
+        let requests = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let addr = test_http::serve_with(move |request| {
+            if !request.is_empty() {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            }
+            async { None }
+        })
+        .await;
+        (addr, requests)
+    }
+
+    #[tokio::test]
+    async fn test_timeout_retries_record_attempts() {
+        let (addr, requests) = silent_server().await;
+        let client =
+            RdapClient::with_config(Duration::from_millis(200), Duration::from_secs(2), false)
+                .unwrap()
+                .with_timeout_retries(2);
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+
+        // Retry budget of 2 plus the first attempt
+        assert_eq!(err.attempts(), Some(3), "{:?}", err);
+        assert_eq!(err.category(), ErrorCategory::Timeout);
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_without_retries_is_one_attempt() {
+        let (addr, _) = silent_server().await;
+        let client =
+            RdapClient::with_config(Duration::from_millis(200), Duration::from_secs(2), false)
+                .unwrap();
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+        assert_eq!(err.attempts(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_slow_body_within_budget_succeeds() {
+        // Body arrives after the connect timeout would have fired, but within total
+        let addr = slow_body_server(Duration::from_millis(400)).await;
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_millis(200), false)
+                .unwrap();
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let (available, info, _) = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap();
+        assert!(!available);
+        assert!(info.is_some());
+    }
+
+    /// Server that answers 429 to the first request and 404 to the next.
+    async fn rate_limit_then_404_server() -> std::net::SocketAddr {
+        test_http::serve(vec![
+            test_http::empty("429 Too Many Requests"),
+            test_http::empty("404 Not Found"),
+        ])
+        .await
+    }
+
+    /// Answer a single request with an empty response of the given status.
+    async fn status_server(status: &'static str) -> std::net::SocketAddr {
+        test_http::serve(vec![test_http::empty(status)]).await
+    }
+
+    #[tokio::test]
+    async fn test_403_is_blocked() {
+        let addr = status_server("403 Forbidden").await;
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false).unwrap();
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+        assert_eq!(err.category(), ErrorCategory::Blocked);
+        assert!(err.to_string().contains("HTTP 403"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_451_is_blocked() {
+        let addr = status_server("451 Unavailable For Legal Reasons").await;
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false).unwrap();
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+        assert_eq!(err.category(), ErrorCategory::Blocked);
+    }
+
+    #[tokio::test]
+    async fn test_gzip_encoded_response_is_decoded() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let json = r#"{"objectClassName":"domain","ldhName":"example.com","status":["active"]}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        let body = encoder.finish().unwrap();
+
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/rdap+json\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(&body);
+        let addr = test_http::serve(vec![response]).await;
+
+        let server = format!("http://{}/domain/", addr);
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_server_override(Some(&server));
+        let result = client.check_domain("example.com").await.unwrap();
+        assert_eq!(result.available, Some(false));
+        assert_eq!(result.rdap_status, Some(200));
+    }
+
+    /// Answer every request with 404, recording each request path.
+    async fn path_recording_server() -> (std::net::SocketAddr, Arc<Mutex<Vec<String>>>) {
+        let paths = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&paths);
+        let addr = test_http::serve_with(move |request| {
+            recorded
+                .lock()
+                .unwrap()
+                .push(test_http::request_path(&request).to_string());
+            async { Some(test_http::empty("404 Not Found")) }
+        })
+        .await;
+        (addr, paths)
+    }
+
+    #[tokio::test]
+    async fn test_server_override_receives_every_tld() {
+        let (addr, paths) = path_recording_server().await;
+        let server = format!("http://{}/rdap/domain/", addr);
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_server_override(Some(&server));
+
+        // .com is built in, .zzstaging is unknown and bootstrap is off
+        for domain in ["example.com", "example.io", "example.zzstaging"] {
+            let result = client.check_domain(domain).await.unwrap();
+            assert_eq!(result.available, Some(true), "{}", domain);
+        }
+
+        assert_eq!(
+            *paths.lock().unwrap(),
+            vec![
+                "/rdap/domain/example.com",
+                "/rdap/domain/example.io",
+                "/rdap/domain/example.zzstaging",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_emits_events() {
+        let addr = rate_limit_then_404_server().await;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_events(tx);
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let (available, _, _) = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap();
+        assert!(available);
+        drop(client);
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        assert!(matches!(
+            &events[0],
+            CheckEvent::RateLimited { host } if *host == addr.to_string()
+        ));
+        assert!(matches!(
+            &events[1],
+            CheckEvent::Retrying { domain, attempt: 2 } if domain == "example.com"
+        ));
+    }
+
+    /// Server that answers 200 with the given content type and body.
+    async fn content_type_server(
+        content_type: &'static str,
+        body: &'static str,
+    ) -> std::net::SocketAddr {
+        test_http::serve(vec![test_http::response(
+            "200 OK",
+            Some(content_type),
+            body,
+        )])
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_html_response_is_descriptive_rdap_error() {
+        let addr = content_type_server(
+            "text/html; charset=utf-8",
+            "<html><body>Please log in to the network</body></html>",
+        )
+        .await;
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false).unwrap();
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+
+        match err {
+            DomainCheckError::RdapError { message, .. } => {
+                assert_eq!(message, "server returned non-RDAP content: text/html");
+            }
+            other => panic!("expected RDAP error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plain_json_content_type_accepted() {
+        let addr = content_type_server("application/json", r#"{"objectClassName":"domain"}"#).await;
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false).unwrap();
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let (available, _, _) = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap();
+        assert!(!available);
+    }
+
+    // ── Response size limit ─────────────────────────────────────────────
+
+    fn assert_size_limit_error(err: DomainCheckError) {
+        match err {
+            DomainCheckError::RdapError { message, .. } => {
+                assert_eq!(message, "RDAP response exceeds the 64 byte size limit");
+            }
+            other => panic!("expected size-limit error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_response_is_rejected() {
+        let body =
+            r#"{"objectClassName":"domain","port43":"whois.example.com","status":["active"]}"#;
+        assert!(body.len() > 64);
+        let addr = content_type_server("application/rdap+json", body).await;
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_max_response_bytes(64);
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+        assert_size_limit_error(err);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_chunked_response_is_rejected() {
+        // No Content-Length: the cap must trip while reading
+        let mut response = "HTTP/1.1 200 OK\r\nContent-Type: application/rdap+json\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n".to_string();
+        for _ in 0..10 {
+            response.push_str(&format!("20\r\n{}\r\n", "x".repeat(32)));
+        }
+        response.push_str("0\r\n\r\n");
+        let addr = test_http::serve(vec![response]).await;
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_max_response_bytes(64);
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+        assert_size_limit_error(err);
+    }
+
+    #[derive(Debug)]
+    struct ServerHoldIsAvailable;
+
+    impl ClassificationPolicy for ServerHoldIsAvailable {
+        fn classify(&self, rdap_json: &serde_json::Value) -> Option<bool> {
+            let statuses = rdap_json["status"].as_array()?;
+            statuses
+                .iter()
+                .any(|s| s.as_str() == Some("server hold"))
+                .then_some(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_skip_info_does_not_parse_body() {
+        // Not JSON: parsing it would fail, so a taken verdict proves the
+        // body was never deserialized or passed to extract_domain_info
+        let addr = content_type_server("application/rdap+json", "not json {").await;
+        let url = format!("http://{}/domain/example.com", addr);
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false).unwrap();
+
+        let (available, info, status) = client
+            .clone()
+            .with_skip_info(true)
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap();
+        assert!(!available);
+        assert!(info.is_none());
+        assert_eq!(status, 200);
+
+        let addr = content_type_server("application/rdap+json", "not json {").await;
+        let url = format!("http://{}/domain/example.com", addr);
+        let err = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainCheckError::ParseError { .. }),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_classification_policy_overrides_taken() {
+        let body = r#"{"objectClassName":"domain","status":["server hold"]}"#;
+        let addr = content_type_server("application/rdap+json", body).await;
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_policy(Some(Arc::new(ServerHoldIsAvailable)));
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let (available, info, _) = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap();
+        assert!(available, "serverHold should be available under the policy");
+        assert_eq!(info.unwrap().status, vec!["server hold".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_classification_policy_none_falls_back_to_taken() {
+        let body = r#"{"objectClassName":"domain","status":["active"]}"#;
+        let addr = content_type_server("application/rdap+json", body).await;
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_policy(Some(Arc::new(ServerHoldIsAvailable)));
+
+        let url = format!("http://{}/domain/example.com", addr);
+        let (available, _, _) = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap();
+        assert!(!available);
+    }
+
+    // ── Registrar RDAP referral ─────────────────────────────────────────
+
+    /// Server answering each connection with the next body in order.
+    fn json_sequence_server(
+        listener: tokio::net::TcpListener,
+        bodies: Vec<String>,
+    ) -> tokio::task::JoinHandle<()> {
+        let responses = bodies
+            .iter()
+            .map(|body| test_http::rdap_json(body))
+            .collect();
+        test_http::serve_on(listener, responses)
+    }
+
+    fn registry_fixture(registrar_url: &str) -> String {
+        serde_json::json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "status": ["client transfer prohibited"],
+            "events": [
+                {"eventAction": "registration", "eventDate": "1995-08-14T04:00:00Z"}
+            ],
+            "links": [
+                {"rel": "self", "href": "https://rdap.example-registry.test/domain/example.com"},
+                {"rel": "related", "type": "application/rdap+json", "href": registrar_url}
+            ]
+        })
+        .to_string()
+    }
+
+    fn registrar_fixture() -> String {
+        serde_json::json!({
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "status": ["active"],
+            "events": [
+                {"eventAction": "registration", "eventDate": "2001-01-01T00:00:00Z"},
+                {"eventAction": "expiration", "eventDate": "2030-08-13T04:00:00Z"}
+            ],
+            "entities": [{
+                "roles": ["registrar"],
+                "vcardArray": ["vcard", [["version", {}, "text", "4.0"], ["fn", {}, "text", "Example Registrar, Inc."]]]
+            }],
+            "nameservers": [{"ldhName": "ns1.example.net"}, {"ldhName": "ns2.example.net"}]
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_follow_registrar_merges_info() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let registrar_url = format!("http://{}/registrar/domain/example.com", addr);
+        json_sequence_server(
+            listener,
+            vec![registry_fixture(&registrar_url), registrar_fixture()],
+        );
+
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_follow_registrar(true);
+        let url = format!("http://{}/domain/example.com", addr);
+        let (available, info, _) = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap();
+        let info = info.unwrap();
+
+        assert!(!available);
+        // Filled in from the registrar response
+        assert_eq!(info.registrar.as_deref(), Some("Example Registrar, Inc."));
+        assert_eq!(
+            info.expiration_date.as_deref(),
+            Some("2030-08-13T04:00:00Z")
+        );
+        assert_eq!(info.nameservers, vec!["ns1.example.net", "ns2.example.net"]);
+        // Registry values win where present
+        assert_eq!(info.creation_date.as_deref(), Some("1995-08-14T04:00:00Z"));
+        assert_eq!(info.status, vec!["client transfer prohibited"]);
+    }
+
+    #[tokio::test]
+    async fn test_registrar_link_not_followed_by_default() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let registrar_url = format!("http://{}/registrar/domain/example.com", addr);
+        let server = json_sequence_server(
+            listener,
+            vec![registry_fixture(&registrar_url), registrar_fixture()],
+        );
+
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false).unwrap();
+        let url = format!("http://{}/domain/example.com", addr);
+        let (_, info, _) = client
+            .fetch_with_timeout(&url, "example.com")
+            .await
+            .unwrap();
+
+        assert!(info.unwrap().registrar.is_none());
+        server.abort();
+    }
+
+    #[test]
+    fn test_registrar_rdap_link_skips_self_and_non_rdap() {
+        let json = serde_json::json!({
+            "links": [
+                {"rel": "related", "href": "https://registrar.test/about.html", "type": "text/html"},
+                {"rel": "related", "href": "https://registry.test/domain/x.com"},
+                {"rel": "related", "href": "https://registrar.test/rdap/domain/x.com"}
+            ]
+        });
+        assert_eq!(
+            registrar_rdap_link(&json, "https://registry.test/domain/x.com").as_deref(),
+            Some("https://registrar.test/rdap/domain/x.com")
+        );
+        assert!(registrar_rdap_link(&serde_json::json!({}), "https://r.test/").is_none());
+    }
+
+    // ── Per-TLD timeouts ────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_tld_timeout_override_allows_slow_registry() {
+        let client =
+            RdapClient::with_config(Duration::from_millis(200), Duration::from_secs(1), false)
+                .unwrap()
+                .with_tld_timeouts(HashMap::from([("zz".to_string(), Duration::from_secs(3))]));
+
+        // The override outlasts the delayed body
+        let addr = slow_body_server(Duration::from_millis(600)).await;
+        let result = client
+            .check_domain_at(
+                "example.zz",
+                "zz",
+                &format!("http://{}/domain/", addr),
+                Instant::now(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.available, Some(false));
+
+        // Other TLDs keep the short global timeout
+        let addr = slow_body_server(Duration::from_millis(600)).await;
+        let err = client
+            .check_domain_at(
+                "example.yy",
+                "yy",
+                &format!("http://{}/domain/", addr),
+                Instant::now(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DomainCheckError::Timeout { .. }), "{:?}", err);
+    }
+
+    #[test]
+    fn test_timeout_for_falls_back_to_global() {
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_tld_timeouts(HashMap::from([("ch".to_string(), Duration::from_secs(10))]));
+
+        assert_eq!(client.timeout_for("example.CH"), Duration::from_secs(10));
+        assert_eq!(client.timeout_for("example.com"), Duration::from_secs(3));
+    }
+
+    // ── Wildcard TLD detection ──────────────────────────────────────────
+
+    /// Serve an RDAP record for every request, or 404 for probe names when
+    /// `wildcard` is false. Returns the address and a request counter.
+    async fn wildcard_server(
+        wildcard: bool,
+    ) -> (std::net::SocketAddr, Arc<std::sync::atomic::AtomicUsize>) {
+        let requests = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let addr = test_http::serve_with(move |request| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let response = if !wildcard && request.contains("dc-probe-") {
+                test_http::empty("404 Not Found")
+            } else {
+                test_http::rdap_json(r#"{"objectClassName":"domain","status":["active"]}"#)
+            };
+            async move { Some(response) }
+        })
+        .await;
+        (addr, requests)
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_tld_marks_results_unknown() {
+        use std::sync::atomic::Ordering;
+
+        let (addr, requests) = wildcard_server(true).await;
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_wildcard_detection(true);
+        let endpoint = format!("http://{}/domain/", addr);
+
+        for domain in ["first.zz", "second.zz"] {
+            let result = client
+                .check_domain_at(domain, "zz", &endpoint, Instant::now())
+                .await
+                .unwrap();
+            assert_eq!(result.available, None, "{}", domain);
+            assert_eq!(result.confidence, Some(Confidence::Low));
+            let note = result.note.unwrap();
+            assert!(note.contains("TLD .zz may be wildcarded"), "{}", note);
+        }
+
+        assert_eq!(client.wildcard_tlds(), vec!["zz".to_string()]);
+        // Two checks plus a single probe for the TLD
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_probe_available_keeps_taken() {
+        let (addr, _) = wildcard_server(false).await;
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_wildcard_detection(true);
+        let endpoint = format!("http://{}/domain/", addr);
+
+        let result = client
+            .check_domain_at("example.zz", "zz", &endpoint, Instant::now())
+            .await
+            .unwrap();
+        assert_eq!(result.available, Some(false));
+        assert!(result.note.is_none());
+        assert!(client.wildcard_tlds().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_detection_disabled_skips_probe() {
+        use std::sync::atomic::Ordering;
+
+        let (addr, requests) = wildcard_server(true).await;
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false).unwrap();
+        let endpoint = format!("http://{}/domain/", addr);
+
+        let result = client
+            .check_domain_at("example.zz", "zz", &endpoint, Instant::now())
+            .await
+            .unwrap();
+        assert_eq!(result.available, Some(false));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    // ── RDAP status ─────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_taken_domain_reports_200() {
+        let (addr, _) = wildcard_server(false).await;
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false).unwrap();
+        let endpoint = format!("http://{}/domain/", addr);
+
+        let result = client
+            .check_domain_at("example.zz", "zz", &endpoint, Instant::now())
+            .await
+            .unwrap();
+        assert_eq!(result.available, Some(false));
+        assert_eq!(result.rdap_status, Some(200));
+    }
+
+    #[tokio::test]
+    async fn test_available_domain_reports_404_after_retry() {
+        let addr = rate_limit_then_404_server().await;
+        let client =
+            RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false).unwrap();
+        let endpoint = format!("http://{}/domain/", addr);
+
+        let result = client
+            .check_domain_at("example.zz", "zz", &endpoint, Instant::now())
+            .await
+            .unwrap();
+        assert_eq!(result.available, Some(true));
+        assert_eq!(result.rdap_status, Some(404));
+    }
+
+    // ── Endpoint tracking ───────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_lookups_tallied_per_endpoint_host() {
+        let (live, _) = wildcard_server(false).await;
+        let dead = test_http::closed_addr().await;
+        let client = RdapClient::with_config(Duration::from_secs(3), Duration::from_secs(1), false)
+            .unwrap()
+            .with_endpoint_tracking(true);
+
+        for (addr, domain) in [(live, "a.zz"), (live, "dc-probe-b.zz"), (dead, "c.zz")] {
+            let endpoint = format!("http://{}/domain/", addr);
+            let _ = client
+                .check_domain_at(domain, "zz", &endpoint, Instant::now())
+                .await;
+        }
+
+        let stats = client.endpoint_stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].host, dead.to_string());
+        assert_eq!(stats[0].errors, 1);
+        assert_eq!(stats[1].host, live.to_string());
+        assert_eq!((stats[1].success, stats[1].not_found), (1, 1));
+    }
+
+    #[test]
+    fn test_endpoint_tracking_off_by_default() {
+        assert!(RdapClient::default().endpoint_stats().is_empty());
+    }
+
+    // ── Shared HTTP client ──────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_clients_share_one_http_client() {
+        let (addr, _) = wildcard_server(false).await;
+        let shared = reqwest::Client::new();
+        let first = RdapClient::with_http_client(shared.clone(), Duration::from_secs(3), false);
+        let second = RdapClient::with_http_client(shared, Duration::from_secs(3), true);
+        assert!(second.use_bootstrap);
+        let endpoint = format!("http://{}/domain/", addr);
+
+        for client in [&first, &second] {
+            let result = client
+                .check_domain_at("example.zz", "zz", &endpoint, Instant::now())
+                .await
+                .unwrap();
+            assert_eq!(result.available, Some(false));
+        }
+    }
+
+    #[test]
+    fn test_rdap_client_on_shared_http_client_keeps_timeouts() {
+        let client = RdapClient::with_http_client(
+            shared_http_client().unwrap(),
+            Duration::from_secs(7),
+            false,
+        );
+        assert_eq!(client.timeout, Duration::from_secs(7));
+        assert_eq!(client.connect_timeout, DEFAULT_CONNECT_TIMEOUT);
     }
 
     // ── extract_domain_info ─────────────────────────────────────────────
@@ -452,6 +1929,13 @@ mod tests {
             .contains(&"client delete prohibited".to_string()));
     }
 
+    #[test]
+    fn test_extract_domain_info_dnssec() {
+        let signed = serde_json::json!({"secureDNS": {"delegationSigned": true}});
+        assert_eq!(extract_domain_info(&signed).dnssec, Some(true));
+        assert_eq!(extract_domain_info(&serde_json::json!({})).dnssec, None);
+    }
+
     #[test]
     fn test_extract_domain_info_updated_date() {
         let json = serde_json::json!({
@@ -593,6 +2077,52 @@ mod tests {
         assert_eq!(info.nameservers.len(), 3);
     }
 
+    #[test]
+    fn test_extract_idn_variants() {
+        let json = serde_json::json!({
+            "ldhName": "xn--caf-dma.example",
+            "variants": [
+                {
+                    "relation": ["registered", "conjoined"],
+                    "idnTable": ".EXAMPLE French",
+                    "variantNames": [
+                        {"ldhName": "cafe.example", "unicodeName": "cafe.example"}
+                    ]
+                },
+                {
+                    "relation": ["unregistered", "registration restricted"],
+                    "variantNames": [
+                        {"ldhName": "xn--caf-8oa.example"},
+                        {"ldhName": "cafe.example"}
+                    ]
+                }
+            ]
+        });
+        let info = extract_domain_info(&json);
+        let names: Vec<&str> = info.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["cafe.example", "xn--caf-8oa.example"]);
+        assert_eq!(
+            info.variants[0].relation,
+            vec![
+                "registered",
+                "conjoined",
+                "unregistered",
+                "registration restricted"
+            ]
+        );
+        assert!(info.variants[0].is_registered());
+        assert!(!info.variants[1].is_registered());
+        assert!(!info.is_empty());
+
+        // No variants key: nothing captured and nothing serialized
+        let info = extract_domain_info(&serde_json::json!({"status": ["active"]}));
+        assert!(info.variants.is_empty());
+        assert!(serde_json::to_value(&info)
+            .unwrap()
+            .get("variants")
+            .is_none());
+    }
+
     // ── extract_vcard_name ──────────────────────────────────────────────
 
     #[test]
@@ -115,6 +115,129 @@ pub fn get_rdap_registry_map() -> HashMap<&'static str, &'static str> {
     ])
 }
 
+/// Get the built-in WHOIS server mappings.
+///
+/// Used to target `whois -h <server>` directly for common TLDs, so the
+/// system `whois` client doesn't have to guess or follow referrals.
+///
+/// # Returns
+///
+/// A HashMap mapping TLD strings (like "com", "org") to WHOIS server hostnames.
+pub fn get_whois_server_map() -> HashMap<&'static str, &'static str> {
+    HashMap::from([
+        // Popular gTLDs
+        ("com", "whois.verisign-grs.com"),
+        ("net", "whois.verisign-grs.com"),
+        ("org", "whois.publicinterestregistry.org"),
+        ("info", "whois.nic.info"),
+        ("biz", "whois.nic.biz"),
+        ("xyz", "whois.nic.xyz"),
+        // Google TLDs
+        ("app", "whois.nic.google"),
+        ("dev", "whois.nic.google"),
+        ("page", "whois.nic.google"),
+        // Popular ccTLDs
+        ("ai", "whois.nic.ai"),
+        ("io", "whois.nic.io"),
+        ("me", "whois.nic.me"),
+        ("co", "whois.nic.co"),
+        ("us", "whois.nic.us"),
+        ("uk", "whois.nic.uk"),
+        ("de", "whois.denic.de"),
+        ("fr", "whois.nic.fr"),
+        ("nl", "whois.domain-registry.nl"),
+        ("eu", "whois.eu"),
+        ("it", "whois.nic.it"),
+        ("es", "whois.nic.es"),
+        ("jp", "whois.jprs.jp"),
+        ("cn", "whois.cnnic.cn"),
+        ("ca", "whois.cira.ca"),
+        ("au", "whois.auda.org.au"),
+        ("br", "whois.registro.br"),
+        ("in", "whois.registry.in"),
+        ("tv", "whois.nic.tv"),
+        ("cc", "ccwhois.verisign-grs.com"),
+    ])
+}
+
+/// Alternate WHOIS servers, tried in order after the primary server in
+/// `get_whois_server_map` when it refuses or drops the connection.
+const WHOIS_FALLBACK_SERVERS: &[(&str, &[&str])] = &[
+    ("com", &["whois.crsnic.net"]),
+    ("net", &["whois.crsnic.net"]),
+    ("org", &["whois.pir.org"]),
+];
+
+/// Get every bundled WHOIS server for a TLD, primary first.
+///
+/// Returns an empty vector when the TLD has no bundled WHOIS server.
+///
+/// # Examples
+///
+/// ```rust
+/// use domain_check_lib::get_whois_servers;
+///
+/// assert_eq!(
+///     get_whois_servers("org"),
+///     vec!["whois.publicinterestregistry.org", "whois.pir.org"]
+/// );
+/// assert!(get_whois_servers("zzzz").is_empty());
+/// ```
+pub fn get_whois_servers(tld: &str) -> Vec<String> {
+    let tld = tld.trim().trim_start_matches('.').to_lowercase();
+    let Some(primary) = get_whois_server_map().get(tld.as_str()).copied() else {
+        return Vec::new();
+    };
+    let fallbacks = WHOIS_FALLBACK_SERVERS
+        .iter()
+        .find(|(t, _)| *t == tld)
+        .map(|(_, servers)| *servers)
+        .unwrap_or_default();
+
+    std::iter::once(primary)
+        .chain(fallbacks.iter().copied())
+        .map(str::to_string)
+        .collect()
+}
+
+/// TLD variant bundles: alternate script forms of the same TLD, run by
+/// one registry. A name registered under one variant is typically bundled
+/// with (or blocked under) the others. TLDs are in A-label (`xn--`) form.
+const TLD_VARIANT_GROUPS: &[&[&str]] = &[
+    // .中国 / .中國 (CNNIC)
+    &["xn--fiqs8s", "xn--fiqz9s"],
+    // .台湾 / .台灣 (TWNIC)
+    &["xn--kprw13d", "xn--kpry57d"],
+];
+
+/// Get the variant TLDs bundled with a TLD.
+///
+/// Returns the other members of the TLD's variant group, or an empty
+/// vector when the TLD has no known variants (true for most TLDs).
+///
+/// # Examples
+///
+/// ```rust
+/// use domain_check_lib::get_tld_variants;
+///
+/// assert_eq!(get_tld_variants("xn--fiqs8s"), vec!["xn--fiqz9s"]);
+/// assert!(get_tld_variants("com").is_empty());
+/// ```
+pub fn get_tld_variants(tld: &str) -> Vec<String> {
+    let tld = tld.trim().trim_start_matches('.').to_lowercase();
+    TLD_VARIANT_GROUPS
+        .iter()
+        .find(|group| group.contains(&tld.as_str()))
+        .map(|group| {
+            group
+                .iter()
+                .filter(|variant| **variant != tld)
+                .map(|variant| variant.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Get all TLDs that we have RDAP endpoints for.
 ///
 /// Returns the union of hardcoded registry keys and bootstrap cache keys,
@@ -139,6 +262,91 @@ pub fn get_all_known_tlds() -> Vec<String> {
     tlds
 }
 
+/// Built-in registry TLDs in popularity order, most popular first.
+const BUILTIN_TLD_RANKING: &[&str] = &[
+    "com", "net", "org", "io", "ai", "app", "dev", "xyz", "info", "me", "tech", "online", "site",
+    "shop", "us", "uk", "de", "ca", "au", "fr", "nl", "br", "in", "biz", "tv", "cc", "cloud",
+    "page", "blog", "website", "digital", "zone",
+];
+
+/// Widely used TLDs outside the built-in registry, most popular first.
+const POPULAR_TLD_RANKING: &[&str] = &[
+    "co",
+    "store",
+    "eu",
+    "ch",
+    "es",
+    "it",
+    "jp",
+    "pl",
+    "se",
+    "be",
+    "at",
+    "dk",
+    "no",
+    "fi",
+    "nz",
+    "mx",
+    "sg",
+    "club",
+    "live",
+    "pro",
+    "space",
+    "fun",
+    "top",
+    "vip",
+    "life",
+    "world",
+    "agency",
+    "studio",
+    "design",
+    "email",
+    "network",
+    "solutions",
+    "media",
+    "company",
+    "group",
+    "news",
+    "art",
+    "one",
+    "gg",
+    "sh",
+];
+
+/// Get the `limit` most popular known TLDs.
+///
+/// Built-in registry TLDs come first in popularity order, then bootstrap
+/// TLDs from a curated popularity list, then every other known TLD
+/// alphabetically. Only TLDs returned by [`get_all_known_tlds`] are
+/// considered, so without a bootstrap fetch the result is built-ins only.
+///
+/// # Examples
+///
+/// ```rust
+/// use domain_check_lib::get_top_tlds;
+///
+/// assert_eq!(get_top_tlds(3), vec!["com", "net", "org"]);
+/// ```
+pub fn get_top_tlds(limit: usize) -> Vec<String> {
+    let builtin = get_rdap_registry_map();
+    let rank = |tld: &str| -> (u8, usize) {
+        if builtin.contains_key(tld) {
+            let pos = BUILTIN_TLD_RANKING.iter().position(|t| *t == tld);
+            (0, pos.unwrap_or(usize::MAX))
+        } else if let Some(pos) = POPULAR_TLD_RANKING.iter().position(|t| *t == tld) {
+            (1, pos)
+        } else {
+            (2, 0)
+        }
+    };
+
+    // Stable sort keeps unranked TLDs in alphabetical order
+    let mut tlds = get_all_known_tlds();
+    tlds.sort_by_key(|tld| rank(tld));
+    tlds.truncate(limit);
+    tlds
+}
+
 /// Get predefined TLD presets for common use cases.
 ///
 /// This function provides curated TLD lists for common scenarios.
@@ -329,6 +537,16 @@ pub fn validate_preset_tlds(preset_tlds: &[String]) -> bool {
 ///
 /// The RDAP endpoint URL if found, or an error if not available.
 pub async fn get_rdap_endpoint(tld: &str, use_bootstrap: bool) -> Result<String, DomainCheckError> {
+    get_rdap_endpoint_via(tld, use_bootstrap, None).await
+}
+
+/// Look up RDAP endpoint for a given TLD, fetching the bootstrap registry
+/// from `bootstrap_url` instead of IANA when the cache needs filling.
+pub(crate) async fn get_rdap_endpoint_via(
+    tld: &str,
+    use_bootstrap: bool,
+    bootstrap_url: Option<&str>,
+) -> Result<String, DomainCheckError> {
     let tld_lower = tld.to_lowercase();
 
     // 1. Check built-in registry (instant, offline)
@@ -370,7 +588,7 @@ pub async fn get_rdap_endpoint(tld: &str, use_bootstrap: bool) -> Result<String,
         };
 
         if needs_fetch {
-            fetch_full_bootstrap().await?;
+            fetch_full_bootstrap(bootstrap_url.unwrap_or(BOOTSTRAP_URL)).await?;
         }
 
         // Re-check after fetch
@@ -403,14 +621,39 @@ pub async fn get_rdap_endpoint(tld: &str, use_bootstrap: bool) -> Result<String,
     }
 }
 
-/// Fetch the full IANA bootstrap registry and populate the cache.
+/// IANA RDAP bootstrap registry for DNS.
+const BOOTSTRAP_URL: &str = "https://data.iana.org/rdap/dns.json";
+
+/// Fetch the full IANA bootstrap registry (or a mirror at `url`) and
+/// populate the cache.
 ///
 /// Instead of fetching per-TLD, this downloads the complete IANA RDAP bootstrap
 /// JSON and parses all service entries at once. Much more efficient for bulk
 /// operations and provides coverage for ~1,180 TLDs.
-async fn fetch_full_bootstrap() -> Result<(), DomainCheckError> {
-    const BOOTSTRAP_URL: &str = "https://data.iana.org/rdap/dns.json";
+async fn fetch_full_bootstrap(url: &str) -> Result<(), DomainCheckError> {
+    fetch_bootstrap_endpoints_from(url).await.map(|_| ())
+}
+
+/// Fetch the IANA bootstrap registry and return every TLD → endpoint mapping.
+///
+/// Endpoints are in the same `.../domain/` form as [`get_rdap_registry_map`],
+/// in the order IANA lists them, so the result can be diffed against the
+/// built-in map or used to build a TLD file. The bootstrap cache is refreshed
+/// as a side effect, using the first endpoint for each TLD.
+///
+/// # Returns
+///
+/// A HashMap from lowercase TLD to its RDAP endpoint URLs, or an error if
+/// the registry cannot be fetched or parsed.
+pub async fn fetch_all_bootstrap_endpoints(
+) -> Result<HashMap<String, Vec<String>>, DomainCheckError> {
+    fetch_bootstrap_endpoints_from(BOOTSTRAP_URL).await
+}
 
+/// Fetch a bootstrap registry in IANA's `dns.json` format from `url`.
+async fn fetch_bootstrap_endpoints_from(
+    url: &str,
+) -> Result<HashMap<String, Vec<String>>, DomainCheckError> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(10))
         .build()
@@ -418,7 +661,7 @@ async fn fetch_full_bootstrap() -> Result<(), DomainCheckError> {
             DomainCheckError::network_with_source("Failed to create HTTP client", e.to_string())
         })?;
 
-    let response = client.get(BOOTSTRAP_URL).send().await.map_err(|e| {
+    let response = client.get(url).send().await.map_err(|e| {
         DomainCheckError::bootstrap("*", format!("Failed to fetch bootstrap registry: {}", e))
     })?;
 
@@ -433,6 +676,58 @@ async fn fetch_full_bootstrap() -> Result<(), DomainCheckError> {
         DomainCheckError::bootstrap("*", format!("Failed to parse bootstrap JSON: {}", e))
     })?;
 
+    let endpoints = parse_bootstrap_endpoints(&json)?;
+    store_bootstrap_endpoints(&endpoints)?;
+
+    Ok(endpoints)
+}
+
+/// Replace the cached bootstrap endpoints, using the first endpoint for each TLD.
+fn store_bootstrap_endpoints(
+    endpoints: &HashMap<String, Vec<String>>,
+) -> Result<(), DomainCheckError> {
+    // Update cache atomically
+    let mut cache = bootstrap_cache()
+        .lock()
+        .map_err(|_| DomainCheckError::internal("Failed to acquire bootstrap cache lock"))?;
+
+    cache.rdap_endpoints = endpoints
+        .iter()
+        .filter_map(|(tld, urls)| urls.first().map(|url| (tld.clone(), url.clone())))
+        .collect();
+    cache.rdap_loaded = true;
+    cache.last_fetch = Some(Instant::now());
+    cache.no_rdap.clear(); // Reset negative cache on fresh fetch
+
+    Ok(())
+}
+
+/// Populate the bootstrap cache from a saved copy of the IANA registry.
+///
+/// `json` is the contents of `https://data.iana.org/rdap/dns.json` (or a
+/// trimmed copy in the same format). The snapshot counts as a fresh fetch,
+/// so no network request is made for its TLDs until the 24-hour TTL expires.
+///
+/// # Returns
+///
+/// The number of TLDs loaded, or an error if the document can't be parsed.
+pub fn load_bootstrap_snapshot(json: &str) -> Result<usize, DomainCheckError> {
+    let json: serde_json::Value = serde_json::from_str(json).map_err(|e| {
+        DomainCheckError::bootstrap("*", format!("Failed to parse bootstrap JSON: {}", e))
+    })?;
+
+    let endpoints = parse_bootstrap_endpoints(&json)?;
+    store_bootstrap_endpoints(&endpoints)?;
+
+    Ok(endpoints.len())
+}
+
+/// Parse an IANA bootstrap document into TLD → endpoint URLs.
+///
+/// Service entries without TLDs or URLs are skipped.
+fn parse_bootstrap_endpoints(
+    json: &serde_json::Value,
+) -> Result<HashMap<String, Vec<String>>, DomainCheckError> {
     // Validate structure
     let services = json
         .get("services")
@@ -444,44 +739,32 @@ async fn fetch_full_bootstrap() -> Result<(), DomainCheckError> {
             )
         })?;
 
-    let mut endpoints: HashMap<String, String> = HashMap::new();
+    let mut endpoints: HashMap<String, Vec<String>> = HashMap::new();
 
     for service in services {
-        if let Some(service_array) = service.as_array() {
-            if service_array.len() >= 2 {
-                // Get the endpoint URL(s)
-                let url = service_array[1]
-                    .as_array()
-                    .and_then(|urls| urls.first())
-                    .and_then(|u| u.as_str());
-
-                if let Some(url) = url {
-                    let endpoint = format!("{}/domain/", url.trim_end_matches('/'));
-
-                    // Get all TLDs served by this endpoint
-                    if let Some(tlds) = service_array[0].as_array() {
-                        for t in tlds {
-                            if let Some(tld_str) = t.as_str() {
-                                endpoints.insert(tld_str.to_lowercase(), endpoint.clone());
-                            }
-                        }
-                    }
-                }
+        let Some([tlds, urls, ..]) = service.as_array().map(Vec::as_slice) else {
+            continue;
+        };
+        let urls: Vec<String> = urls
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|u| u.as_str())
+            .map(|url| format!("{}/domain/", url.trim_end_matches('/')))
+            .collect();
+        if urls.is_empty() {
+            continue;
+        }
+
+        // Every TLD served by this set of endpoints
+        for tld in tlds.as_array().into_iter().flatten() {
+            if let Some(tld) = tld.as_str() {
+                endpoints.insert(tld.to_lowercase(), urls.clone());
             }
         }
     }
 
-    // Update cache atomically
-    let mut cache = bootstrap_cache()
-        .lock()
-        .map_err(|_| DomainCheckError::internal("Failed to acquire bootstrap cache lock"))?;
-
-    cache.rdap_endpoints = endpoints;
-    cache.rdap_loaded = true;
-    cache.last_fetch = Some(Instant::now());
-    cache.no_rdap.clear(); // Reset negative cache on fresh fetch
-
-    Ok(())
+    Ok(endpoints)
 }
 
 /// Pre-warm the bootstrap cache by fetching the full IANA registry.
@@ -492,6 +775,16 @@ async fn fetch_full_bootstrap() -> Result<(), DomainCheckError> {
 /// This is safe to call multiple times — subsequent calls are no-ops if the
 /// cache is still fresh (within the 24-hour TTL).
 pub async fn initialize_bootstrap() -> Result<(), DomainCheckError> {
+    initialize_bootstrap_with(None).await
+}
+
+/// Pre-warm the bootstrap cache from `bootstrap_url`, or from IANA when None.
+///
+/// Like [`initialize_bootstrap`], for mirrors of the IANA registry and test
+/// environments (see `CheckConfig::with_bootstrap_url`).
+pub async fn initialize_bootstrap_with(
+    bootstrap_url: Option<&str>,
+) -> Result<(), DomainCheckError> {
     let needs_fetch = {
         let cache = bootstrap_cache()
             .lock()
@@ -500,7 +793,7 @@ pub async fn initialize_bootstrap() -> Result<(), DomainCheckError> {
     };
 
     if needs_fetch {
-        fetch_full_bootstrap().await?;
+        fetch_full_bootstrap(bootstrap_url.unwrap_or(BOOTSTRAP_URL)).await?;
     }
 
     Ok(())
@@ -613,12 +906,52 @@ pub fn extract_tld(domain: &str) -> Result<String, DomainCheckError> {
         ));
     }
 
+    if parts.last().is_some_and(|tld| tld.trim().is_empty()) {
+        return Err(DomainCheckError::invalid_domain(
+            domain,
+            "empty TLD (domain ends with a dot)",
+        ));
+    }
+
     // Return the last part as TLD
     // Note: This is simplified and doesn't handle multi-level TLDs like .co.uk
     // For production use, consider using a library like publicsuffix
     Ok(parts.last().unwrap().to_lowercase())
 }
 
+/// Get a key identifying the RDAP endpoint host that will serve a domain.
+///
+/// Uses only the built-in registry and already-cached bootstrap entries, so it
+/// never touches the network. Domains whose endpoint isn't known yet fall back
+/// to their TLD, which still groups them sensibly.
+pub(crate) fn endpoint_key(domain: &str) -> String {
+    let tld = match extract_tld(domain) {
+        Ok(tld) => tld,
+        Err(_) => return domain.to_lowercase(),
+    };
+
+    let endpoint = get_rdap_registry_map()
+        .get(tld.as_str())
+        .map(|e| e.to_string())
+        .or_else(|| {
+            bootstrap_cache()
+                .lock()
+                .ok()
+                .and_then(|cache| cache.rdap_endpoints.get(&tld).cloned())
+        });
+
+    match endpoint {
+        Some(url) => endpoint_host(&url).to_string(),
+        None => tld,
+    }
+}
+
+/// Extract the host portion of an endpoint URL.
+pub(crate) fn endpoint_host(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    without_scheme.split('/').next().unwrap_or(without_scheme)
+}
+
 /// Clear the bootstrap cache (useful for testing).
 #[allow(dead_code)]
 pub fn clear_bootstrap_cache() -> Result<(), DomainCheckError> {
@@ -675,12 +1008,42 @@ mod tests {
         assert!(extract_tld("").is_err());
     }
 
+    #[test]
+    fn test_extract_tld_trailing_dot() {
+        let err = extract_tld("brand.").unwrap_err();
+        assert!(err.to_string().contains("empty TLD"), "{}", err);
+    }
+
     #[test]
     fn test_extract_tld_multi_level() {
         // Returns last part only (simplified — doesn't handle co.uk)
         assert_eq!(extract_tld("example.co.uk").unwrap(), "uk");
     }
 
+    // ── endpoint_key ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_endpoint_key_shared_registry_host() {
+        // io and ai share the Identity Digital RDAP host
+        assert_eq!(endpoint_key("a.io"), "rdap.identitydigital.services");
+        assert_eq!(endpoint_key("a.io"), endpoint_key("b.ai"));
+        assert_ne!(endpoint_key("a.com"), endpoint_key("a.io"));
+    }
+
+    #[test]
+    fn test_endpoint_key_unknown_tld_falls_back_to_tld() {
+        assert_eq!(endpoint_key("example.zzunknown"), "zzunknown");
+    }
+
+    #[test]
+    fn test_endpoint_host() {
+        assert_eq!(
+            endpoint_host("https://rdap.verisign.com/com/v1/domain/"),
+            "rdap.verisign.com"
+        );
+        assert_eq!(endpoint_host("rdap.example/domain/"), "rdap.example");
+    }
+
     // ── get_rdap_registry_map ───────────────────────────────────────────
 
     #[test]
@@ -790,6 +1153,51 @@ mod tests {
         assert!(!cache.is_stale()); // Just fetched = not stale
     }
 
+    // ── Bootstrap parsing ───────────────────────────────────────────────
+
+    fn bootstrap_snapshot() -> HashMap<String, Vec<String>> {
+        let json: serde_json::Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/rdap_dns_bootstrap.json"))
+                .unwrap();
+        parse_bootstrap_endpoints(&json).unwrap()
+    }
+
+    #[test]
+    fn test_bootstrap_snapshot_maps_known_tld() {
+        let endpoints = bootstrap_snapshot();
+        assert_eq!(
+            endpoints["com"],
+            vec!["https://rdap.verisign.com/com/v1/domain/".to_string()]
+        );
+        // Agrees with the built-in map
+        assert_eq!(
+            endpoints["com"][0],
+            get_rdap_registry_map()["com"].to_string()
+        );
+    }
+
+    #[test]
+    fn test_bootstrap_snapshot_shared_and_multiple_endpoints() {
+        let endpoints = bootstrap_snapshot();
+        assert_eq!(endpoints["app"], endpoints["dev"]);
+        // TLDs are lowercased; every listed URL is kept in order
+        assert_eq!(
+            endpoints["nl"],
+            vec![
+                "https://rdap.sidn.nl/domain/".to_string(),
+                "http://rdap.sidn.nl/domain/".to_string(),
+            ]
+        );
+        // Entries without URLs are skipped
+        assert!(!endpoints.contains_key("malformed"));
+    }
+
+    #[test]
+    fn test_bootstrap_rejects_missing_services() {
+        let json = serde_json::json!({ "version": "1.0" });
+        assert!(parse_bootstrap_endpoints(&json).is_err());
+    }
+
     // ── WHOIS server caching ────────────────────────────────────────────
 
     #[test]
@@ -876,6 +1284,52 @@ mod tests {
         assert!(validate_preset_tlds(&[]));
     }
 
+    // ── get_tld_variants ────────────────────────────────────────────────
+
+    #[test]
+    fn test_tld_variants_returns_other_group_members() {
+        assert_eq!(get_tld_variants("xn--fiqs8s"), vec!["xn--fiqz9s"]);
+        assert_eq!(get_tld_variants(".XN--FIQZ9S"), vec!["xn--fiqs8s"]);
+    }
+
+    #[test]
+    fn test_tld_variants_empty_for_plain_tld() {
+        assert!(get_tld_variants("com").is_empty());
+    }
+
+    // ── get_top_tlds ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_builtin_ranking_covers_only_builtin_tlds() {
+        let registry = get_rdap_registry_map();
+        for tld in BUILTIN_TLD_RANKING {
+            assert!(registry.contains_key(tld), "{} is not built in", tld);
+        }
+        for tld in POPULAR_TLD_RANKING {
+            assert!(!registry.contains_key(tld), "{} is built in", tld);
+        }
+    }
+
+    #[test]
+    fn test_top_tlds_in_priority_order() {
+        assert_eq!(
+            get_top_tlds(10),
+            vec!["com", "net", "org", "io", "ai", "app", "dev", "xyz", "info", "me"]
+        );
+        assert!(get_top_tlds(0).is_empty());
+    }
+
+    // ── get_whois_servers ───────────────────────────────────────────────
+
+    #[test]
+    fn test_whois_servers_primary_first() {
+        assert_eq!(
+            get_whois_servers(".COM"),
+            vec!["whois.verisign-grs.com", "whois.crsnic.net"]
+        );
+        assert_eq!(get_whois_servers("io"), vec!["whois.nic.io"]);
+    }
+
     // ── get_preset_tlds_with_custom ─────────────────────────────────────
 
     #[test]
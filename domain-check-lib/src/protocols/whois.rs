@@ -5,10 +5,54 @@
 //! unstructured text responses that require parsing.
 
 use crate::error::DomainCheckError;
+use crate::protocols::registry::{extract_tld, get_whois_server_map};
 use crate::types::{CheckMethod, DomainResult};
 use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
 use tokio::process::Command;
 
+/// Port WHOIS servers listen on (RFC 3912).
+const WHOIS_PORT: u16 = 43;
+
+/// Largest WHOIS response read from a server; anything past it is dropped.
+const MAX_WHOIS_RESPONSE_BYTES: u64 = 256 * 1024;
+
+/// Registry wording for names that can't be registered, with the note
+/// attached to the result. Checked before the availability patterns, since
+/// these responses often also say "not found" or are very short. Each phrase
+/// ties the state to the registry, so disclaimers or registrant names that
+/// merely mention "reserved" or "premium" don't match.
+const RESERVED_PATTERNS: &[(&str, &str)] = &[
+    ("reserved by registry", "reserved by the registry"),
+    ("reserved by the registry", "reserved by the registry"),
+    ("reserved for the registry", "reserved by the registry"),
+    ("registry reserved", "reserved by the registry"),
+    ("this name is reserved", "reserved by the registry"),
+    ("blocked by registry", "blocked by the registry"),
+    ("blocked by the registry", "blocked by the registry"),
+    ("blocked by the dpml", "blocked by the registry"),
+    (
+        "premium name held by the registry",
+        "held by the registry as a premium name",
+    ),
+];
+
+/// Values of a `Status:` / `Domain Status:` line that mark a name as
+/// unregistrable, with the note attached to the result.
+const RESERVED_STATUSES: &[(&str, &str)] = &[
+    ("reserved", "reserved by the registry"),
+    ("blocked", "blocked by the registry"),
+    ("premium", "held by the registry as a premium name"),
+];
+
+/// Availability parsed from one WHOIS response.
+struct WhoisVerdict {
+    available: bool,
+    /// Set when the name is reserved, blocked or premium
+    note: Option<String>,
+}
+
 /// WHOIS client for checking domain availability using the system's whois command.
 ///
 /// This client uses the system's `whois` command-line tool to query domain information.
@@ -17,6 +61,8 @@ use tokio::process::Command;
 pub struct WhoisClient {
     /// Timeout for WHOIS requests
     timeout: Duration,
+    /// Report short unrecognised responses as unknown rather than available
+    strict: bool,
 }
 
 impl WhoisClient {
@@ -24,12 +70,22 @@ impl WhoisClient {
     pub fn new() -> Self {
         Self {
             timeout: Duration::from_secs(5),
+            strict: false,
         }
     }
 
     /// Create a new WHOIS client with custom timeout.
     pub fn with_timeout(timeout: Duration) -> Self {
-        Self { timeout }
+        Self {
+            timeout,
+            strict: false,
+        }
+    }
+
+    /// Disable the short-output heuristic (see `CheckConfig::whois_strict`).
+    pub(crate) fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     /// Check domain availability using WHOIS.
@@ -61,14 +117,21 @@ impl WhoisClient {
         let check_duration = start_time.elapsed();
 
         match result {
-            Ok(Ok(available)) => {
+            Ok(Ok(verdict)) => {
                 Ok(DomainResult {
                     domain: domain.to_string(),
-                    available: Some(available),
+                    available: Some(verdict.available),
                     info: None, // WHOIS parsing for detailed info is complex and inconsistent
                     check_duration: Some(check_duration),
                     method_used: CheckMethod::Whois,
                     error_message: None,
+                    error_category: None,
+                    confidence: None,
+                    note: verdict.note,
+                    parked: None,
+                    rdap_status: None,
+                    redirects_to: None,
+                    attempts: None,
                 })
             }
             Ok(Err(e)) => Err(e),
@@ -101,13 +164,20 @@ impl WhoisClient {
         let check_duration = start_time.elapsed();
 
         match result {
-            Ok(Ok(available)) => Ok(DomainResult {
+            Ok(Ok(verdict)) => Ok(DomainResult {
                 domain: domain.to_string(),
-                available: Some(available),
+                available: Some(verdict.available),
                 info: None,
                 check_duration: Some(check_duration),
                 method_used: CheckMethod::Whois,
                 error_message: None,
+                error_category: None,
+                confidence: None,
+                note: verdict.note,
+                parked: None,
+                rdap_status: None,
+                redirects_to: None,
+                attempts: None,
             }),
             Ok(Err(_)) => {
                 // Targeted query failed, fall back to bare whois
@@ -117,11 +187,82 @@ impl WhoisClient {
         }
     }
 
+    /// Check domain availability against a list of WHOIS servers.
+    ///
+    /// Each server is a hostname (port 43) or `host:port`. Servers are tried
+    /// in order; a refused or timed-out connection moves on to the next one,
+    /// while a server that accepts the connection gives the final answer.
+    pub async fn check_domain_with_servers(
+        &self,
+        domain: &str,
+        servers: &[String],
+    ) -> Result<DomainResult, DomainCheckError> {
+        let start_time = Instant::now();
+        let mut last_error = None;
+
+        for server in servers {
+            let address = whois_address(server);
+            let stream =
+                match tokio::time::timeout(self.timeout, TcpStream::connect(&address)).await {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(e)) => {
+                        last_error = Some(format!("{}: {}", server, e));
+                        continue;
+                    }
+                    Err(_) => {
+                        last_error = Some(format!("{}: connection timed out", server));
+                        continue;
+                    }
+                };
+
+            let response = tokio::time::timeout(self.timeout, query_whois(stream, domain))
+                .await
+                .map_err(|_| DomainCheckError::timeout("WHOIS query", self.timeout))?
+                .map_err(|e| {
+                    DomainCheckError::whois(
+                        domain,
+                        format!("WHOIS query to {} failed: {}", server, e),
+                    )
+                })?;
+
+            let verdict = self.classify(&response.to_lowercase())?;
+            return Ok(DomainResult {
+                domain: domain.to_string(),
+                available: Some(verdict.available),
+                info: None,
+                check_duration: Some(start_time.elapsed()),
+                method_used: CheckMethod::Whois,
+                error_message: None,
+                error_category: None,
+                confidence: None,
+                note: verdict.note,
+                parked: None,
+                rdap_status: None,
+                redirects_to: None,
+                attempts: None,
+            });
+        }
+
+        Err(DomainCheckError::whois(
+            domain,
+            format!(
+                "Could not connect to any WHOIS server ({})",
+                last_error.unwrap_or_else(|| "none given".to_string())
+            ),
+        ))
+    }
+
     /// Execute the system whois command and parse the result.
-    async fn execute_whois_command(&self, domain: &str) -> Result<bool, DomainCheckError> {
+    ///
+    /// Targets the bundled WHOIS server for the domain's TLD with `-h` when
+    /// one is known, otherwise runs plain `whois <domain>`.
+    async fn execute_whois_command(&self, domain: &str) -> Result<WhoisVerdict, DomainCheckError> {
+        let args = whois_command_args(domain);
+
         // First attempt
         let output = Command::new("whois")
-            .arg(domain)
+            .args(&args)
+            .kill_on_drop(true)
             .output()
             .await
             .map_err(|e| {
@@ -137,12 +278,13 @@ impl WhoisClient {
         let output_text = String::from_utf8_lossy(&output.stdout).to_lowercase();
 
         // Check for rate limiting first
-        if self.is_rate_limited(&output_text) {
+        if self.is_rate_limited(&output_text) && reserved_note(&output_text).is_none() {
             // Wait and retry once
             tokio::time::sleep(Duration::from_millis(1000)).await;
 
             let retry_output = Command::new("whois")
-                .arg(domain)
+                .args(&args)
+                .kill_on_drop(true)
                 .output()
                 .await
                 .map_err(|e| {
@@ -150,9 +292,9 @@ impl WhoisClient {
                 })?;
 
             let retry_text = String::from_utf8_lossy(&retry_output.stdout).to_lowercase();
-            self.parse_whois_availability(&retry_text)
+            self.classify(&retry_text)
         } else {
-            self.parse_whois_availability(&output_text)
+            self.classify(&output_text)
         }
     }
 
@@ -161,11 +303,12 @@ impl WhoisClient {
         &self,
         domain: &str,
         server: &str,
-    ) -> Result<bool, DomainCheckError> {
+    ) -> Result<WhoisVerdict, DomainCheckError> {
         let output = Command::new("whois")
             .arg("-h")
             .arg(server)
             .arg(domain)
+            .kill_on_drop(true)
             .output()
             .await
             .map_err(|e| {
@@ -177,13 +320,14 @@ impl WhoisClient {
 
         let output_text = String::from_utf8_lossy(&output.stdout).to_lowercase();
 
-        if self.is_rate_limited(&output_text) {
+        if self.is_rate_limited(&output_text) && reserved_note(&output_text).is_none() {
             tokio::time::sleep(Duration::from_millis(1000)).await;
 
             let retry_output = Command::new("whois")
                 .arg("-h")
                 .arg(server)
                 .arg(domain)
+                .kill_on_drop(true)
                 .output()
                 .await
                 .map_err(|e| {
@@ -191,12 +335,20 @@ impl WhoisClient {
                 })?;
 
             let retry_text = String::from_utf8_lossy(&retry_output.stdout).to_lowercase();
-            self.parse_whois_availability(&retry_text)
+            self.classify(&retry_text)
         } else {
-            self.parse_whois_availability(&output_text)
+            self.classify(&output_text)
         }
     }
 
+    /// Parse a WHOIS response into availability plus any reserved-name note.
+    fn classify(&self, whois_output: &str) -> Result<WhoisVerdict, DomainCheckError> {
+        Ok(WhoisVerdict {
+            available: self.parse_whois_availability(whois_output)?,
+            note: reserved_note(whois_output).map(|reason| format!("{} (WHOIS)", reason)),
+        })
+    }
+
     /// Parse WHOIS output to determine domain availability.
     ///
     /// This function looks for common patterns in WHOIS responses that indicate
@@ -226,6 +378,12 @@ impl WhoisClient {
             }
         }
 
+        // Reserved, blocked and premium names can't be registered even when
+        // the response otherwise reads like "not found"
+        if reserved_note(&output_lower).is_some() {
+            return Ok(false);
+        }
+
         // Patterns that typically indicate domain availability
         let available_patterns = [
             "no match",
@@ -284,8 +442,9 @@ impl WhoisClient {
             return Ok(false);
         }
 
-        // If the output is very short, it might indicate availability
-        if output_lower.trim().len() < 50 {
+        // If the output is very short, it might indicate availability (unless
+        // strict, where a terse error or greeting shouldn't read as available)
+        if !self.strict && output_lower.trim().len() < 50 {
             return Ok(true);
         }
 
@@ -407,6 +566,24 @@ pub async fn is_whois_available() -> bool {
     }
 }
 
+/// Whether the system `whois` command can be run, checked once per process.
+///
+/// Unlike `is_whois_available` this never sends a query; it only checks
+/// that the binary can be started.
+pub(crate) async fn has_whois_command() -> bool {
+    static AVAILABLE: tokio::sync::OnceCell<bool> = tokio::sync::OnceCell::const_new();
+    *AVAILABLE
+        .get_or_init(|| async {
+            Command::new("whois")
+                .arg("--version")
+                .kill_on_drop(true)
+                .output()
+                .await
+                .is_ok()
+        })
+        .await
+}
+
 /// Get the version of the system's whois command.
 ///
 /// This is useful for debugging and ensuring compatibility.
@@ -427,6 +604,66 @@ pub async fn get_whois_version() -> Result<String, DomainCheckError> {
     }
 }
 
+/// Why a WHOIS response marks a name as unregistrable, if it does.
+fn reserved_note(whois_output: &str) -> Option<&'static str> {
+    let output_lower = whois_output.to_lowercase();
+    let status_note = output_lower.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if !key.trim().ends_with("status") {
+            return None;
+        }
+        let first_word = value.split_whitespace().next()?;
+        RESERVED_STATUSES
+            .iter()
+            .find(|(status, _)| first_word == *status)
+            .map(|(_, note)| *note)
+    });
+    status_note.or_else(|| {
+        RESERVED_PATTERNS
+            .iter()
+            .find(|(pattern, _)| output_lower.contains(pattern))
+            .map(|(_, note)| *note)
+    })
+}
+
+/// Build the argument list for a plain WHOIS query.
+///
+/// Returns `["-h", <server>, <domain>]` when the TLD has a bundled WHOIS
+/// server, or just `[<domain>]` otherwise.
+fn whois_command_args(domain: &str) -> Vec<String> {
+    let server = extract_tld(domain)
+        .ok()
+        .and_then(|tld| get_whois_server_map().get(tld.as_str()).copied());
+
+    match server {
+        Some(host) => vec!["-h".to_string(), host.to_string(), domain.to_string()],
+        None => vec![domain.to_string()],
+    }
+}
+
+/// Socket address for a WHOIS server given as `host` or `host:port`.
+fn whois_address(server: &str) -> String {
+    if server.contains(':') {
+        server.to_string()
+    } else {
+        format!("{}:{}", server, WHOIS_PORT)
+    }
+}
+
+/// Send one WHOIS query and read the response until the server closes,
+/// keeping at most `MAX_WHOIS_RESPONSE_BYTES`.
+async fn query_whois(mut stream: TcpStream, domain: &str) -> std::io::Result<String> {
+    stream
+        .write_all(format!("{}\r\n", domain).as_bytes())
+        .await?;
+    let mut response = Vec::new();
+    stream
+        .take(MAX_WHOIS_RESPONSE_BYTES)
+        .read_to_end(&mut response)
+        .await?;
+    Ok(String::from_utf8_lossy(&response).into_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -605,6 +842,34 @@ mod tests {
         assert!(client.parse_whois_availability("").unwrap());
     }
 
+    #[test]
+    fn test_short_greeting_available_when_lenient() {
+        let client = WhoisClient::new().with_strict(false);
+        assert!(client
+            .parse_whois_availability("Welcome to the WHOIS service")
+            .unwrap());
+    }
+
+    #[test]
+    fn test_short_greeting_unknown_when_strict() {
+        let client = WhoisClient::new().with_strict(true);
+        let err = client
+            .parse_whois_availability("Welcome to the WHOIS service")
+            .unwrap_err();
+        match err {
+            DomainCheckError::WhoisError { message, .. } => {
+                assert!(message.contains("Unable to determine domain status"));
+            }
+            other => panic!("expected WHOIS error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_strict_still_recognises_patterns() {
+        let client = WhoisClient::new().with_strict(true);
+        assert!(client.parse_whois_availability("No match").unwrap());
+    }
+
     // ── parse_whois_availability: ambiguous = error ─────────────────────
 
     #[test]
@@ -621,6 +886,84 @@ mod tests {
             .contains("WHOIS lookup failed"));
     }
 
+    // ── parse_whois_availability: reserved names ────────────────────────
+
+    #[test]
+    fn test_reserved_by_registry_not_available() {
+        let client = WhoisClient::new();
+        let output = "Domain not found.\n\
+            >>> This name is reserved by the Registry in accordance with ICANN Policy. <<<";
+        assert!(!client.parse_whois_availability(output).unwrap());
+        assert_eq!(reserved_note(output), Some("reserved by the registry"));
+    }
+
+    #[test]
+    fn test_short_reserved_output_not_available() {
+        let client = WhoisClient::new();
+        // Short enough that the length heuristic would call it available
+        assert!(!client.parse_whois_availability("Status: RESERVED").unwrap());
+        assert!(!client
+            .parse_whois_availability("Reserved by Registry Operator")
+            .unwrap());
+    }
+
+    #[test]
+    fn test_blocked_name_not_available() {
+        let client = WhoisClient::new();
+        let output = "The registration of this domain is restricted, as it is \
+            blocked by the DPML Brand Protection policy.";
+        assert!(!client.parse_whois_availability(output).unwrap());
+        assert_eq!(reserved_note(output), Some("blocked by the registry"));
+    }
+
+    #[test]
+    fn test_premium_name_not_available() {
+        let client = WhoisClient::new();
+        let output = "No match for \"CARS.XYZ\".\nThis is a premium name held by the registry.";
+        assert!(!client.parse_whois_availability(output).unwrap());
+        let verdict = client.classify(output).unwrap();
+        assert!(!verdict.available);
+        assert_eq!(
+            verdict.note.as_deref(),
+            Some("held by the registry as a premium name (WHOIS)")
+        );
+    }
+
+    #[test]
+    fn test_copyright_notice_is_not_reserved() {
+        let client = WhoisClient::new();
+        let output = "No match for \"EXAMPLE-FREE.COM\".\n(c) 2024 Registry. All rights reserved.";
+        assert!(client.parse_whois_availability(output).unwrap());
+        assert_eq!(reserved_note(output), None);
+    }
+
+    #[test]
+    fn test_reserved_status_line() {
+        assert_eq!(
+            reserved_note("Domain Name: EXAMPLE.TEST\nDomain Status: Blocked"),
+            Some("blocked by the registry")
+        );
+        assert_eq!(
+            reserved_note("Registration status: premium"),
+            Some("held by the registry as a premium name")
+        );
+    }
+
+    #[test]
+    fn test_incidental_mentions_are_not_reserved() {
+        let client = WhoisClient::new();
+        let output = "No match for \"SHOP-NOW.COM\".\n\
+            Looking for a premium name? Our reserved name program can help.\n\
+            Note: the domain is reserved for 5 days after payment.";
+        assert!(client.parse_whois_availability(output).unwrap());
+        assert_eq!(reserved_note(output), None);
+
+        let output = "Domain Name: EXAMPLE.COM\n\
+            Domain Status: clientTransferProhibited\n\
+            Registrant Organization: Reserved Name Holdings";
+        assert_eq!(reserved_note(output), None);
+    }
+
     // ── is_rate_limited ─────────────────────────────────────────────────
 
     #[test]
@@ -703,6 +1046,106 @@ mod tests {
         assert_eq!(parse_iana_refer_response(""), None);
     }
 
+    // ── whois_command_args ──────────────────────────────────────────────
+
+    #[test]
+    fn test_whois_args_known_tld_uses_host() {
+        assert_eq!(
+            whois_command_args("example.com"),
+            vec!["-h", "whois.verisign-grs.com", "example.com"]
+        );
+        assert_eq!(
+            whois_command_args("example.IO"),
+            vec!["-h", "whois.nic.io", "example.IO"]
+        );
+    }
+
+    #[test]
+    fn test_whois_args_unknown_tld_is_plain() {
+        assert_eq!(whois_command_args("example.zzzz"), vec!["example.zzzz"]);
+        assert_eq!(whois_command_args("localhost"), vec!["localhost"]);
+    }
+
+    // ── Native WHOIS server failover ────────────────────────────────────
+
+    #[test]
+    fn test_whois_address_defaults_to_port_43() {
+        assert_eq!(whois_address("whois.nic.io"), "whois.nic.io:43");
+        assert_eq!(whois_address("127.0.0.1:4343"), "127.0.0.1:4343");
+    }
+
+    #[tokio::test]
+    async fn test_refused_server_falls_through_to_next() {
+        // Bind then drop to get a port with nothing listening
+        let dead = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let dead_addr = dead.local_addr().unwrap();
+        drop(dead);
+
+        let live = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live_addr = live.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = live.accept().await.unwrap();
+            let mut query = [0u8; 256];
+            let n = stream.read(&mut query).await.unwrap();
+            stream
+                .write_all(b"No match for domain \"EXAMPLE-UNUSED.COM\".\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&query[..n]).into_owned()
+        });
+
+        let result = WhoisClient::new()
+            .check_domain_with_servers(
+                "example-unused.com",
+                &[dead_addr.to_string(), live_addr.to_string()],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.available, Some(true));
+        assert_eq!(result.method_used, CheckMethod::Whois);
+        assert_eq!(server.await.unwrap(), "example-unused.com\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_all_servers_refused_is_whois_error() {
+        let dead = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let dead_addr = dead.local_addr().unwrap().to_string();
+        drop(dead);
+
+        let err = WhoisClient::new()
+            .check_domain_with_servers("example.com", &[dead_addr])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DomainCheckError::WhoisError { .. }),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_response_is_truncated() {
+        let live = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = live.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = live.accept().await.unwrap();
+            let mut query = [0u8; 256];
+            let _ = stream.read(&mut query).await;
+            let chunk = vec![b'x'; 64 * 1024];
+            // Keep sending well past the cap; the client stops reading
+            for _ in 0..64 {
+                if stream.write_all(&chunk).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let response = query_whois(stream, "example.com").await.unwrap();
+        assert_eq!(response.len() as u64, MAX_WHOIS_RESPONSE_BYTES);
+    }
+
     // ── Network-dependent test ──────────────────────────────────────────
 
     #[tokio::test]
@@ -0,0 +1,151 @@
+//! Redirect detection for taken domains (enabled by the `redirects` feature).
+//!
+//! This is enrichment, not availability: it requests the root URL of an
+//! already-registered domain, follows its redirects and reports the final
+//! host when it belongs to a different domain. Useful for spotting lookalikes
+//! that forward to a brand's real site or to a competitor. Any fetch failure
+//! yields `None` rather than an error.
+
+use crate::error::DomainCheckError;
+use crate::types::{CheckConfig, DomainResult};
+
+/// Redirect hops followed before giving up on a chain.
+const MAX_REDIRECTS: usize = 5;
+
+/// The final host, if it is outside `domain` (ignoring `www.` and subdomains).
+fn cross_domain_target(domain: &str, final_host: &str) -> Option<String> {
+    let domain = domain.trim_end_matches('.').to_lowercase();
+    let host = final_host.trim_end_matches('.').to_lowercase();
+    let bare = |h: &str| h.strip_prefix("www.").unwrap_or(h).to_string();
+    let (domain, bare_host) = (bare(&domain), bare(&host));
+
+    if bare_host == domain || bare_host.ends_with(&format!(".{}", domain)) {
+        None
+    } else {
+        Some(host)
+    }
+}
+
+/// Build the HTTP client used by [`detect_redirect`].
+///
+/// Requests time out after `config.timeout`, follow at most five redirect
+/// hops, and resolve hostnames through `config.dns_servers` when set. Build
+/// it once per run and share it across domains.
+pub fn redirect_client(config: &CheckConfig) -> Result<reqwest::Client, DomainCheckError> {
+    let builder = reqwest::Client::builder()
+        .timeout(config.timeout)
+        .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS));
+    crate::dns::configure_resolver(builder, config.dns_servers.as_deref())
+        .build()
+        .map_err(|e| DomainCheckError::internal(format!("Failed to build redirect client: {}", e)))
+}
+
+/// Fetch a domain's root URL and report where its redirects end up.
+///
+/// Makes one request chain through `client` (see [`redirect_client`]) and
+/// never reads the body. Returns None when the domain doesn't redirect
+/// off-site or the page can't be fetched.
+pub async fn detect_redirect(client: &reqwest::Client, domain: &str) -> Option<String> {
+    let response = client
+        .get(format!("http://{}/", domain))
+        .send()
+        .await
+        .ok()?;
+    let final_host = response.url().host_str()?;
+    cross_domain_target(domain, final_host)
+}
+
+/// Fill in `redirects_to` for every taken result, checking up to `config.concurrency` at once.
+///
+/// Available and unknown results are left untouched, as are all results if
+/// the client can't be built.
+pub async fn enrich_redirects(results: &mut [DomainResult], config: &CheckConfig) {
+    use futures_util::StreamExt;
+
+    let Ok(client) = redirect_client(config) else {
+        return;
+    };
+
+    let taken: Vec<(usize, String)> = results
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.available == Some(false))
+        .map(|(i, r)| (i, r.domain.clone()))
+        .collect();
+
+    let targets: Vec<(usize, Option<String>)> = futures_util::stream::iter(taken)
+        .map(|(i, domain)| {
+            let client = &client;
+            async move { (i, detect_redirect(client, &domain).await) }
+        })
+        .buffer_unordered(config.concurrency.max(1))
+        .collect()
+        .await;
+
+    for (i, target) in targets {
+        results[i].redirects_to = target;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_http;
+
+    #[test]
+    fn test_cross_domain_target() {
+        assert_eq!(
+            cross_domain_target("examp1e.com", "www.example.com"),
+            Some("www.example.com".to_string())
+        );
+        assert_eq!(cross_domain_target("example.com", "www.example.com"), None);
+        assert_eq!(
+            cross_domain_target("example.com", "shop.Example.com."),
+            None
+        );
+        assert_eq!(
+            cross_domain_target("example.com", "notexample.com"),
+            Some("notexample.com".to_string())
+        );
+    }
+
+    fn moved_to(location: &str) -> String {
+        format!(
+            "HTTP/1.1 301 Moved Permanently\r\nLocation: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            location
+        )
+    }
+
+    #[tokio::test]
+    async fn test_redirect_chain_reports_final_host() {
+        let origin = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let target = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let target_port = target.local_addr().unwrap().port();
+        let origin_addr = origin.local_addr().unwrap();
+
+        // 127.0.0.1 → localhost/ → localhost/home (200)
+        test_http::serve_on(
+            origin,
+            vec![moved_to(&format!("http://localhost:{}/", target_port))],
+        );
+        test_http::serve_on(
+            target,
+            vec![moved_to("/home"), test_http::response("200 OK", None, "ok")],
+        );
+
+        let client = redirect_client(&CheckConfig::default()).unwrap();
+        // The "domain" is the origin's address, so the final host is off-site
+        assert_eq!(
+            detect_redirect(&client, &origin_addr.to_string()).await,
+            Some("localhost".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_failure_is_unknown() {
+        let addr = test_http::closed_addr().await;
+
+        let client = redirect_client(&CheckConfig::default()).unwrap();
+        assert_eq!(detect_redirect(&client, &addr.to_string()).await, None);
+    }
+}
@@ -0,0 +1,150 @@
+//! Result sinks for embedding the checker in other systems.
+//!
+//! A `ResultSink` receives each `DomainResult` as soon as it completes, so
+//! callers can route results to a database, queue, or UI without collecting
+//! them into a `Vec` first. Drive a sink with
+//! `DomainChecker::check_domains_to_sink`.
+
+use crate::types::DomainResult;
+use std::future::Future;
+use std::sync::Mutex;
+
+/// Destination for domain check results.
+///
+/// Implementors can use `async fn on_result` directly in their impl block.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use domain_check_lib::{DomainChecker, DomainResult, ResultSink};
+///
+/// struct PrintSink;
+///
+/// impl ResultSink for PrintSink {
+///     async fn on_result(&self, result: DomainResult) {
+///         println!("{}: {:?}", result.domain, result.available);
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let checker = DomainChecker::new();
+///     let domains = vec!["example.com".to_string(), "example.org".to_string()];
+///     checker.check_domains_to_sink(&domains, &PrintSink).await?;
+///     Ok(())
+/// }
+/// ```
+pub trait ResultSink: Sync {
+    /// Handle one completed result.
+    fn on_result(&self, result: DomainResult) -> impl Future<Output = ()> + Send;
+}
+
+/// Sink that collects results into a vector, in completion order.
+#[derive(Debug, Default)]
+pub struct VecSink {
+    results: Mutex<Vec<DomainResult>>,
+}
+
+impl VecSink {
+    /// Create an empty sink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consume the sink and return the collected results.
+    pub fn into_results(self) -> Vec<DomainResult> {
+        self.results.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Number of results collected so far.
+    pub fn len(&self) -> usize {
+        self.results.lock().map(|r| r.len()).unwrap_or(0)
+    }
+
+    /// Whether no results have been collected yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ResultSink for VecSink {
+    async fn on_result(&self, result: DomainResult) {
+        if let Ok(mut results) = self.results.lock() {
+            results.push(result);
+        }
+    }
+}
+
+/// Sink that calls a synchronous closure for each result.
+///
+/// ```rust
+/// use domain_check_lib::FnSink;
+///
+/// let sink = FnSink::new(|result| println!("{}", result.domain));
+/// ```
+pub struct FnSink<F> {
+    f: F,
+}
+
+impl<F> FnSink<F>
+where
+    F: Fn(DomainResult) + Send + Sync,
+{
+    /// Wrap a closure as a sink.
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<F> ResultSink for FnSink<F>
+where
+    F: Fn(DomainResult) + Send + Sync,
+{
+    async fn on_result(&self, result: DomainResult) {
+        (self.f)(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CheckMethod;
+
+    fn make_result(domain: &str) -> DomainResult {
+        DomainResult {
+            domain: domain.to_string(),
+            available: Some(true),
+            info: None,
+            check_duration: None,
+            method_used: CheckMethod::Rdap,
+            error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_vec_sink_collects() {
+        let sink = VecSink::new();
+        assert!(sink.is_empty());
+        sink.on_result(make_result("a.com")).await;
+        sink.on_result(make_result("b.com")).await;
+        assert_eq!(sink.len(), 2);
+
+        let domains: Vec<String> = sink.into_results().into_iter().map(|r| r.domain).collect();
+        assert_eq!(domains, vec!["a.com", "b.com"]);
+    }
+
+    #[tokio::test]
+    async fn test_fn_sink_calls_closure() {
+        let seen = Mutex::new(Vec::new());
+        let sink = FnSink::new(|r: DomainResult| seen.lock().unwrap().push(r.domain));
+        sink.on_result(make_result("a.com")).await;
+        assert_eq!(*seen.lock().unwrap(), vec!["a.com".to_string()]);
+    }
+}
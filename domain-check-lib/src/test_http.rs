@@ -0,0 +1,111 @@
+//! Minimal HTTP/1.1 stub servers for tests.
+//!
+//! Every server binds `127.0.0.1:0`, reads one request per connection and
+//! answers with a complete `Connection: close` response, so reqwest never
+//! reuses a connection between requests. Also compiled into integration
+//! tests with `#[path]`, so nothing here may depend on crate internals.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A `Connection: close` response with `body`, and a `Content-Type` header
+/// when `content_type` is given.
+pub(crate) fn response(status: &str, content_type: Option<&str>, body: &str) -> String {
+    let content_type = content_type
+        .map(|ct| format!("Content-Type: {}\r\n", ct))
+        .unwrap_or_default();
+    format!(
+        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// A `Connection: close` response with no body, e.g. `empty("404 Not Found")`.
+pub(crate) fn empty(status: &str) -> String {
+    response(status, None, "")
+}
+
+/// A 200 response carrying an RDAP JSON `body`.
+pub(crate) fn rdap_json(body: &str) -> String {
+    response("200 OK", Some("application/rdap+json"), body)
+}
+
+async fn bind() -> TcpListener {
+    TcpListener::bind("127.0.0.1:0").await.unwrap()
+}
+
+/// Read the request head (and whatever body arrived with it).
+async fn read_request(stream: &mut TcpStream) -> String {
+    let mut buf = [0u8; 4096];
+    let n = stream.read(&mut buf).await.unwrap_or(0);
+    String::from_utf8_lossy(&buf[..n]).into_owned()
+}
+
+/// Answer one connection each with `responses`, in order, then stop.
+pub(crate) async fn serve<R: Into<Vec<u8>>>(responses: Vec<R>) -> SocketAddr {
+    let listener = bind().await;
+    let addr = listener.local_addr().unwrap();
+    serve_on(listener, responses);
+    addr
+}
+
+/// Like `serve`, on a listener the caller bound (for responses that need
+/// to know the server's own address).
+pub(crate) fn serve_on<R: Into<Vec<u8>>>(
+    listener: TcpListener,
+    responses: Vec<R>,
+) -> tokio::task::JoinHandle<()> {
+    let responses: Vec<Vec<u8>> = responses.into_iter().map(Into::into).collect();
+    tokio::spawn(async move {
+        for response in responses {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let _ = read_request(&mut stream).await;
+            let _ = stream.write_all(&response).await;
+        }
+    })
+}
+
+/// Answer every connection with `handler(request)`, handling connections
+/// concurrently. `None` holds the connection open without replying.
+pub(crate) async fn serve_with<F, Fut>(handler: F) -> SocketAddr
+where
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Option<String>> + Send + 'static,
+{
+    let listener = bind().await;
+    let addr = listener.local_addr().unwrap();
+    let handler = std::sync::Arc::new(handler);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let handler = std::sync::Arc::clone(&handler);
+            tokio::spawn(async move {
+                let request = read_request(&mut stream).await;
+                match handler(request).await {
+                    Some(response) => {
+                        let _ = stream.write_all(response.as_bytes()).await;
+                    }
+                    None => tokio::time::sleep(Duration::from_secs(30)).await,
+                }
+            });
+        }
+    });
+    addr
+}
+
+/// The path of a request (`GET /domain/x.com HTTP/1.1` → `/domain/x.com`).
+pub(crate) fn request_path(request: &str) -> &str {
+    request.split_whitespace().nth(1).unwrap_or("")
+}
+
+/// An address with nothing listening on it.
+pub(crate) async fn closed_addr() -> SocketAddr {
+    bind().await.local_addr().unwrap()
+}
@@ -0,0 +1,153 @@
+//! Per-check timing records for profiling concurrent runs.
+//!
+//! A `TimingLog` attached with `CheckConfig::with_timing_log` receives one
+//! row per domain: when its check was launched, when it got a concurrency
+//! slot, and when it completed. The gap between launch and start is time
+//! spent queued behind other checks, which `check_duration` alone can't show.
+
+use crate::protocols::registry::{endpoint_host, endpoint_key};
+use crate::types::CheckMethod;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Timing of one domain check, in milliseconds since the log was created.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckTiming {
+    pub domain: String,
+    /// When the check was scheduled
+    pub launched_ms: u64,
+    /// When it acquired a concurrency slot and started running
+    pub started_ms: u64,
+    /// When its result was ready
+    pub completed_ms: u64,
+    /// Time spent running (`completed_ms - started_ms`)
+    pub duration_ms: u64,
+    /// RDAP endpoint host for RDAP results (the `--rdap-server` host when one
+    /// is set), None for other methods
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    pub method: CheckMethod,
+}
+
+/// Shared, append-only log of `CheckTiming` rows.
+///
+/// Clones share the same rows and clock, so a log can be handed to a
+/// checker through its config and read back after the run.
+#[derive(Debug, Clone)]
+pub struct TimingLog {
+    epoch: Instant,
+    rows: Arc<Mutex<Vec<CheckTiming>>>,
+}
+
+impl TimingLog {
+    /// Create an empty log; timestamps are measured from now.
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            rows: Arc::default(),
+        }
+    }
+
+    /// Record a finished check that was launched at `launched`, started
+    /// running at `started` and was answered by `method` (`Unknown` for a
+    /// failed check). Completion is taken to be now.
+    ///
+    /// Pass the config's `rdap_server_override`: when set, RDAP rows name
+    /// that server instead of the domain's registry.
+    pub fn record(
+        &self,
+        domain: &str,
+        launched: Instant,
+        started: Instant,
+        method: CheckMethod,
+        rdap_server_override: Option<&str>,
+    ) {
+        let completed = Instant::now();
+        let since_epoch = |t: Instant| t.saturating_duration_since(self.epoch).as_millis() as u64;
+        let (started_ms, completed_ms) = (since_epoch(started), since_epoch(completed));
+        let row = CheckTiming {
+            domain: domain.to_string(),
+            launched_ms: since_epoch(launched),
+            started_ms,
+            completed_ms,
+            duration_ms: completed_ms - started_ms,
+            endpoint: (method == CheckMethod::Rdap).then(|| match rdap_server_override {
+                Some(url) => endpoint_host(url).to_string(),
+                None => endpoint_key(domain),
+            }),
+            method,
+        };
+        self.rows
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(row);
+    }
+
+    /// All rows so far, in launch order (ties broken by domain).
+    pub fn rows(&self) -> Vec<CheckTiming> {
+        let mut rows = self.rows.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        rows.sort_by(|a, b| {
+            a.launched_ms
+                .cmp(&b.launched_ms)
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+        rows
+    }
+}
+
+impl Default for TimingLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_record_measures_from_epoch() {
+        let log = TimingLog::new();
+        let launched = Instant::now();
+        std::thread::sleep(Duration::from_millis(20));
+        let started = Instant::now();
+        std::thread::sleep(Duration::from_millis(20));
+        log.record("a.com", launched, started, CheckMethod::Rdap, None);
+        log.record("b.zz", launched, launched, CheckMethod::Whois, None);
+
+        let rows = log.rows();
+        assert_eq!(rows.len(), 2);
+        let a = &rows[0];
+        assert_eq!(a.domain, "a.com");
+        assert!(a.started_ms >= a.launched_ms + 20, "{:?}", a);
+        assert!(a.duration_ms >= 20, "{:?}", a);
+        assert_eq!(a.completed_ms - a.started_ms, a.duration_ms);
+        assert_eq!(a.endpoint.as_deref(), Some("rdap.verisign.com"));
+        assert_eq!(rows[1].endpoint, None);
+        assert_eq!(rows[1].method, CheckMethod::Whois);
+    }
+
+    #[test]
+    fn test_clones_share_rows() {
+        let log = TimingLog::new();
+        let now = Instant::now();
+        log.clone()
+            .record("a.com", now, now, CheckMethod::Rdap, None);
+        assert_eq!(log.rows().len(), 1);
+    }
+
+    #[test]
+    fn test_rdap_server_override_is_the_endpoint() {
+        let log = TimingLog::new();
+        let now = Instant::now();
+        let server = Some("http://127.0.0.1:8080/domain/");
+        log.record("a.com", now, now, CheckMethod::Rdap, server);
+        log.record("b.com", now, now, CheckMethod::Whois, server);
+
+        let rows = log.rows();
+        assert_eq!(rows[0].endpoint.as_deref(), Some("127.0.0.1:8080"));
+        assert_eq!(rows[1].endpoint, None);
+    }
+}
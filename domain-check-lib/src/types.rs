@@ -3,15 +3,25 @@
 //! This module defines all the main data structures used throughout the library,
 //! including domain results, configuration options, and output formatting.
 
+use crate::error::{DomainCheckError, ErrorCategory};
+use crate::policy::ClassificationPolicy;
+use crate::timings::TimingLog;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Result of a domain availability check.
 ///
 /// Contains all information about a domain's availability status,
 /// registration details, and metadata about the check itself.
+///
+/// The struct is `#[non_exhaustive]` so fields can be added without breaking
+/// callers; build one with [`DomainResult::new`] or [`DomainResult::from_error`]
+/// and set the remaining fields directly.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct DomainResult {
     /// The domain name that was checked (e.g., "example.com")
     pub domain: String,
@@ -26,8 +36,13 @@ pub struct DomainResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub info: Option<DomainInfo>,
 
-    /// How long the domain check took to complete
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// How long the domain check took to complete. Serialized as integer
+    /// milliseconds unless a `duration-*` feature selects another form
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::duration_serde"
+    )]
     pub check_duration: Option<Duration>,
 
     /// Which method was used to check the domain
@@ -36,6 +51,96 @@ pub struct DomainResult {
     /// Any error message if the check failed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+
+    /// Category of the failure, if the check failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_category: Option<ErrorCategory>,
+
+    /// How far to trust `available` when the verdict was inferred rather
+    /// than reported by the registry (None means authoritative)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Confidence>,
+
+    /// Explanation of how an inferred verdict was reached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+
+    /// Whether a taken domain serves a parking page (None if not checked or
+    /// the page couldn't be fetched). Enrichment only; see the `parking` feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parked: Option<bool>,
+
+    /// HTTP status of the RDAP response behind this result (e.g. 200 taken,
+    /// 404 available, 429 or 503 on failure, also kept when WHOIS answered
+    /// after RDAP failed); None when RDAP wasn't reached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rdap_status: Option<u16>,
+
+    /// Host a taken domain's root URL finally redirects to, when that is a
+    /// different domain (None if not checked, no cross-domain redirect, or
+    /// the page couldn't be fetched). Enrichment only; see the `redirects` feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirects_to: Option<String>,
+
+    /// How many RDAP requests were made before the lookup gave up on a
+    /// timeout (None unless the result comes from a timeout)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u8>,
+}
+
+impl DomainResult {
+    /// A result with the given verdict and every optional field unset.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use domain_check_lib::{CheckMethod, DomainResult};
+    ///
+    /// let mut result = DomainResult::new("example.com", Some(false), CheckMethod::Rdap);
+    /// result.note = Some("imported".to_string());
+    /// assert_eq!(result.available, Some(false));
+    /// ```
+    pub fn new<D: Into<String>>(
+        domain: D,
+        available: Option<bool>,
+        method_used: CheckMethod,
+    ) -> Self {
+        DomainResult {
+            domain: domain.into(),
+            available,
+            info: None,
+            check_duration: None,
+            method_used,
+            error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
+        }
+    }
+
+    /// The unknown-status result reported for a check that failed outright.
+    pub fn from_error<D: Into<String>>(domain: D, error: &DomainCheckError) -> Self {
+        DomainResult {
+            error_message: Some(error.to_string()),
+            error_category: Some(error.category()),
+            rdap_status: error.rdap_status(),
+            attempts: error.attempts(),
+            ..DomainResult::new(domain, None, CheckMethod::Unknown)
+        }
+    }
+}
+
+/// Confidence in an inferred availability verdict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    High,
+    Medium,
+    Low,
 }
 
 /// Detailed information about a registered domain.
@@ -65,6 +170,52 @@ pub struct DomainInfo {
 
     /// Nameservers associated with the domain
     pub nameservers: Vec<String>,
+
+    /// Whether the delegation is DNSSEC-signed (RDAP `secureDNS.delegationSigned`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dnssec: Option<bool>,
+
+    /// IDN variants listed in the RDAP record (`variants[].variantNames`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<IdnVariant>,
+}
+
+/// An IDN variant of a domain, from an RDAP record's `variants` array.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdnVariant {
+    /// The variant's name (`ldhName`, or `unicodeName` when that's all there is)
+    pub name: String,
+
+    /// How the variant relates to the domain (`relation`), e.g. `registered`
+    /// or `unregistered`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relation: Vec<String>,
+}
+
+impl IdnVariant {
+    /// Whether the registry reports this variant as registered.
+    pub fn is_registered(&self) -> bool {
+        self.relation
+            .iter()
+            .any(|r| r.eq_ignore_ascii_case("registered"))
+    }
+}
+
+impl DomainInfo {
+    /// Whether no field carries any data.
+    ///
+    /// Sparse RDAP responses can yield an info block with every field unset;
+    /// checkers drop such blocks instead of returning them.
+    pub fn is_empty(&self) -> bool {
+        self.registrar.is_none()
+            && self.creation_date.is_none()
+            && self.expiration_date.is_none()
+            && self.status.is_empty()
+            && self.updated_date.is_none()
+            && self.nameservers.is_empty()
+            && self.dnssec.is_none()
+            && self.variants.is_empty()
+    }
 }
 
 /// Configuration options for domain checking operations.
@@ -103,6 +254,13 @@ pub struct CheckConfig {
     #[serde(skip)] // Don't serialize Duration directly
     pub rdap_timeout: Duration,
 
+    /// Timeout for DNS resolution and connection setup on RDAP requests.
+    /// Kept separate from `rdap_timeout` so dead hosts fail fast while slow
+    /// responses still get the full budget.
+    /// Default: 2 seconds
+    #[serde(skip)] // Don't serialize Duration directly
+    pub connect_timeout: Duration,
+
     /// Custom timeout for WHOIS requests
     /// Default: 5 seconds  
     #[serde(skip)] // Don't serialize Duration directly
@@ -112,6 +270,139 @@ pub struct CheckConfig {
     /// Default: empty
     #[serde(skip)] // Handled separately in config merging
     pub custom_presets: HashMap<String, Vec<String>>,
+
+    /// Whether to report taken domains in `pendingDelete` / `redemptionPeriod`
+    /// as available (with low confidence), for drop-catching workflows
+    /// Default: false
+    pub treat_dropping_as_available: bool,
+
+    /// How to classify RDAP records whose only statuses are ambiguous
+    /// (`serverHold`, `clientHold`, `inactive`) and that carry no
+    /// registration dates
+    /// Default: AmbiguousStatusPolicy::TreatAsTaken
+    pub ambiguous_status_policy: AmbiguousStatusPolicy,
+
+    /// Whether to reorder the work queue so consecutive checks hit different
+    /// RDAP endpoints (round-robin by registry host) instead of name-major order
+    /// Default: false
+    pub interleave_by_endpoint: bool,
+
+    /// Whether to follow the registry's `related` RDAP link to the registrar's
+    /// RDAP server (one hop) to fill in missing details; needs `detailed_info`
+    /// Default: false
+    pub follow_registrar_rdap: bool,
+
+    /// Whether to probe a random name once per TLD when a domain is reported
+    /// taken, and downgrade that TLD's results to unknown if the probe is
+    /// taken too (wildcarded registries)
+    /// Default: false
+    pub detect_wildcard_tlds: bool,
+
+    /// Whether to run RDAP and WHOIS at the same time and keep the first
+    /// successful answer instead of trying WHOIS only after RDAP fails
+    /// Default: false
+    pub race_protocols: bool,
+
+    /// Whether WHOIS responses matching no known available/taken pattern
+    /// are reported as unknown, even when they are very short
+    /// Default: false
+    pub whois_strict: bool,
+
+    /// Whether RDAP outcomes are tallied per endpoint host, for
+    /// `DomainChecker::endpoint_stats`
+    /// Default: false
+    pub track_endpoints: bool,
+
+    /// Whether domains are checked against the full RFC 1035 length and
+    /// label rules before lookup, instead of the minimal sanity check
+    /// Default: false
+    pub strict_validation: bool,
+
+    /// Warm-up window over which the number of in-flight checks grows from
+    /// one to `concurrency` (None = start at full concurrency)
+    /// Default: None
+    #[serde(skip)] // Don't serialize Duration directly
+    pub concurrency_ramp: Option<Duration>,
+
+    /// Maximum RDAP requests in flight to any one registry host, on top of
+    /// `concurrency` (None = no per-host limit)
+    /// Default: None
+    pub concurrency_per_host: Option<usize>,
+
+    /// Maximum number of completed results `check_domains_stream` holds for
+    /// a slow consumer before pausing new checks (None = unbounded handoff)
+    /// Default: None
+    pub result_buffer: Option<usize>,
+
+    /// Yield `check_domains_stream` results in input order instead of
+    /// completion order, for reproducible output in tests and NDJSON diffs
+    /// Default: false
+    pub deterministic_order: bool,
+
+    /// Nameservers used by DNS-dependent features such as parking detection
+    /// (None = system resolver)
+    /// Default: None
+    pub dns_servers: Option<Vec<SocketAddr>>,
+
+    /// Custom availability decision for RDAP records (None = any record is taken)
+    /// Default: None
+    #[serde(skip)]
+    pub classification_policy: Option<Arc<dyn ClassificationPolicy>>,
+
+    /// Largest RDAP response body accepted, in bytes; larger bodies fail the
+    /// RDAP lookup instead of being buffered
+    /// Default: 4 MiB
+    pub max_rdap_response_bytes: usize,
+
+    /// Extra RDAP requests made after a request times out, before the lookup
+    /// is reported as a timeout
+    /// Default: 0
+    pub timeout_retries: u8,
+
+    /// RDAP server that receives every query as `<url>/<domain>`, whatever
+    /// the TLD (None = each TLD's registry endpoint)
+    /// Default: None
+    pub rdap_server_override: Option<String>,
+
+    /// Bootstrap registry in IANA's `dns.json` format, fetched for TLDs
+    /// without a built-in endpoint (None = IANA's)
+    /// Default: None
+    pub bootstrap_url: Option<String>,
+
+    /// Log that receives launch, start and completion times for each check
+    /// run through `check_domains` and the streaming/sink variants
+    /// Default: None
+    #[serde(skip)]
+    pub timing_log: Option<TimingLog>,
+
+    /// HTTP client for RDAP requests (None = the crate's shared client, or a
+    /// dedicated one when `connect_timeout` differs from the default)
+    /// Default: None
+    #[serde(skip)]
+    pub http_client: Option<reqwest::Client>,
+
+    /// TLDs dropped from the expanded domain set by the CLI
+    /// Default: empty
+    #[serde(skip)] // Handled separately in config merging
+    pub exclude_tlds: Vec<String>,
+
+    /// Per-TLD RDAP timeout overrides (lowercase TLD without dot), used
+    /// instead of `rdap_timeout` for registries known to be slow
+    /// Default: empty
+    #[serde(skip)] // Handled separately in config merging
+    pub tld_timeouts: HashMap<String, Duration>,
+
+    /// Per-TLD protocol preference (lowercase TLD without dot). TLDs mapped
+    /// to `CheckMethod::Whois` are checked over WHOIS first with RDAP as the
+    /// fallback; any other TLD uses RDAP first as usual
+    /// Default: empty
+    #[serde(skip)] // Handled separately in config merging
+    pub tld_method_preference: HashMap<String, CheckMethod>,
+
+    /// TLD alias groups from config files (primary TLD → alternatives)
+    /// Default: empty
+    #[serde(skip)] // Handled separately in config merging
+    pub tld_aliases: HashMap<String, Vec<String>>,
 }
 
 /// Method used to check domain availability.
@@ -137,6 +428,61 @@ pub enum CheckMethod {
     Unknown,
 }
 
+/// Lifecycle event emitted while checking domains.
+///
+/// Sent over the channel passed to `DomainChecker::check_domains_with_events`
+/// so frontends can show richer progress than final results alone.
+#[derive(Debug, Clone)]
+pub enum CheckEvent {
+    /// A check acquired a concurrency slot and is starting
+    Started { domain: String },
+    /// A check finished (successfully or with an error result)
+    Completed { result: Box<DomainResult> },
+    /// A request is being retried; `attempt` counts from 1 for the first try
+    Retrying { domain: String, attempt: u32 },
+    /// An RDAP server responded with HTTP 429
+    RateLimited { host: String },
+}
+
+/// Which results a filtered stream yields.
+///
+/// Used with `DomainChecker::check_domains_stream_filtered`. Errors are
+/// always yielded so callers still see failed checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamFilter {
+    /// Yield only domains confirmed available
+    AvailableOnly,
+    /// Yield only domains confirmed taken
+    TakenOnly,
+    /// Yield every result (the default)
+    #[default]
+    All,
+}
+
+impl StreamFilter {
+    /// Whether a successful result passes this filter.
+    pub fn matches(&self, result: &DomainResult) -> bool {
+        match self {
+            StreamFilter::AvailableOnly => result.available == Some(true),
+            StreamFilter::TakenOnly => result.available == Some(false),
+            StreamFilter::All => true,
+        }
+    }
+}
+
+/// Classification of RDAP records that only carry ambiguous statuses.
+///
+/// See `CheckConfig::ambiguous_status_policy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AmbiguousStatusPolicy {
+    /// Any RDAP record means taken (the long-standing behavior)
+    #[default]
+    TreatAsTaken,
+
+    /// Report the domain as unknown with low confidence
+    TreatAsUnknown,
+}
+
 /// Output mode for displaying results.
 ///
 /// This controls how and when results are presented to the user,
@@ -167,8 +513,34 @@ impl Default for CheckConfig {
             detailed_info: false,
             tlds: None, // Will default to ["com"] when needed
             rdap_timeout: Duration::from_secs(3),
+            connect_timeout: Duration::from_secs(2),
             whois_timeout: Duration::from_secs(5),
             custom_presets: HashMap::new(),
+            treat_dropping_as_available: false,
+            ambiguous_status_policy: AmbiguousStatusPolicy::TreatAsTaken,
+            interleave_by_endpoint: false,
+            follow_registrar_rdap: false,
+            detect_wildcard_tlds: false,
+            race_protocols: false,
+            whois_strict: false,
+            strict_validation: false,
+            track_endpoints: false,
+            concurrency_ramp: None,
+            concurrency_per_host: None,
+            result_buffer: None,
+            deterministic_order: false,
+            dns_servers: None,
+            classification_policy: None,
+            max_rdap_response_bytes: 4 * 1024 * 1024,
+            timeout_retries: 0,
+            rdap_server_override: None,
+            bootstrap_url: None,
+            timing_log: None,
+            http_client: None,
+            exclude_tlds: Vec::new(),
+            tld_timeouts: HashMap::new(),
+            tld_method_preference: HashMap::new(),
+            tld_aliases: HashMap::new(),
         }
     }
 }
@@ -188,6 +560,12 @@ impl CheckConfig {
         self
     }
 
+    /// Set the connect timeout (DNS + TCP + TLS) for RDAP requests.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
     /// Enable or disable WHOIS fallback.
     pub fn with_whois_fallback(mut self, enabled: bool) -> Self {
         self.enable_whois_fallback = enabled;
@@ -206,6 +584,246 @@ impl CheckConfig {
         self
     }
 
+    /// Report domains that are being deleted as available.
+    ///
+    /// Taken domains whose registry status includes `pendingDelete` or
+    /// `redemptionPeriod` are flipped to `available: Some(true)` with
+    /// `Confidence::Low` and an explanatory note. Opt-in only.
+    pub fn with_treat_dropping_as_available(mut self, enabled: bool) -> Self {
+        self.treat_dropping_as_available = enabled;
+        self
+    }
+
+    /// Choose how records with only ambiguous statuses are classified.
+    ///
+    /// Some registries keep a held or near-deleted name visible with nothing
+    /// but `serverHold` and no registration events. `TreatAsUnknown` reports
+    /// those as unknown (low confidence) instead of taken.
+    pub fn with_ambiguous_status_policy(mut self, policy: AmbiguousStatusPolicy) -> Self {
+        self.ambiguous_status_policy = policy;
+        self
+    }
+
+    /// Interleave checks across RDAP endpoints to spread load between registries.
+    ///
+    /// Useful when checking many names against a few TLDs, where name-major
+    /// order would otherwise send every in-flight request to the same registry.
+    pub fn with_interleave_by_endpoint(mut self, enabled: bool) -> Self {
+        self.interleave_by_endpoint = enabled;
+        self
+    }
+
+    /// Follow registry RDAP referrals to the registrar's RDAP server.
+    ///
+    /// Registry responses are often thin; the registrar's record usually has
+    /// the registrar name, nameservers and dates. Registry values are kept
+    /// and the registrar record only fills gaps. Takes effect only together
+    /// with `with_detailed_info(true)`, and follows at most one hop.
+    pub fn with_follow_registrar_rdap(mut self, enabled: bool) -> Self {
+        self.follow_registrar_rdap = enabled;
+        self
+    }
+
+    /// Guard against TLDs where every name looks registered.
+    ///
+    /// Some registries answer every RDAP query with a record (or wildcard
+    /// every name in DNS), so all names appear taken. When enabled, the first
+    /// taken result for a TLD triggers one lookup of a random name; if that
+    /// is reported taken as well, results for the TLD are returned as unknown
+    /// with low confidence. See `DomainChecker::wildcard_tlds`.
+    pub fn with_detect_wildcard_tlds(mut self, enabled: bool) -> Self {
+        self.detect_wildcard_tlds = enabled;
+        self
+    }
+
+    /// Query RDAP and WHOIS concurrently and take the first successful answer.
+    ///
+    /// Lowers latency on TLDs with slow RDAP at the cost of doubling request
+    /// volume. The slower lookup is cancelled; RDAP wins ties. Has no effect
+    /// when WHOIS fallback is disabled.
+    pub fn with_race_protocols(mut self, enabled: bool) -> Self {
+        self.race_protocols = enabled;
+        self
+    }
+
+    /// Stop treating short, unrecognised WHOIS responses as available.
+    ///
+    /// By default a response under 50 characters with no known pattern is
+    /// read as "available", which misfires when a server answers with a
+    /// terse error or greeting. Strict mode reports such domains as unknown
+    /// instead, trading recall for precision.
+    pub fn with_whois_strict(mut self, enabled: bool) -> Self {
+        self.whois_strict = enabled;
+        self
+    }
+
+    /// Tally RDAP success/404/error/timeout counts per endpoint host.
+    ///
+    /// Read the tallies with `DomainChecker::endpoint_stats` after a run.
+    pub fn with_endpoint_tracking(mut self, enabled: bool) -> Self {
+        self.track_endpoints = enabled;
+        self
+    }
+
+    /// Reject malformed domains before lookup with a precise reason.
+    ///
+    /// The default check only rejects empty and one-character names, so a
+    /// bad name costs a registry round-trip. Strict validation enforces the
+    /// 253-character name limit, 1-63 character labels, the letter/digit/
+    /// hyphen charset and no leading or trailing hyphens per label.
+    pub fn with_strict_validation(mut self, enabled: bool) -> Self {
+        self.strict_validation = enabled;
+        self
+    }
+
+    /// Allow at most `per_host` RDAP requests in flight to any one host.
+    ///
+    /// Keeps a large run from overwhelming (and being rate limited by) a
+    /// single registry. Overall throughput is then bounded by the number of
+    /// distinct endpoints times `per_host`, whatever `concurrency` says.
+    pub fn with_concurrency_per_host(mut self, per_host: usize) -> Self {
+        self.concurrency_per_host = Some(per_host.max(1));
+        self
+    }
+
+    /// Ramp the number of in-flight checks up to `concurrency` over `ramp`.
+    ///
+    /// Starting every check at once sends a burst to registries that serve
+    /// many domains (e.g. `--all` against one endpoint), which often earns
+    /// 429s. With a ramp, checks start one at a time and a further slot opens
+    /// at even intervals until the full concurrency is reached; steady-state
+    /// throughput is unchanged. Requires a Tokio runtime when checks start.
+    pub fn with_concurrency_ramp(mut self, ramp: Duration) -> Self {
+        self.concurrency_ramp = Some(ramp);
+        self
+    }
+
+    /// Bound how many completed results the stream buffers for its consumer.
+    ///
+    /// When set, `check_domains_stream` runs checks on a background task and
+    /// hands results over a channel of this capacity; a slow consumer then
+    /// pauses new checks instead of letting results accumulate. Requires a
+    /// Tokio runtime when the stream is created.
+    pub fn with_result_buffer(mut self, capacity: usize) -> Self {
+        self.result_buffer = Some(capacity.max(1));
+        self
+    }
+
+    /// Make `check_domains_stream` yield results in input order.
+    ///
+    /// Checks still run concurrently, but a finished result is held back
+    /// until every earlier domain has been yielded, so one slow registry
+    /// delays everything after it and up to `concurrency` results sit in
+    /// memory. Meant for tests and reproducible output, not large runs.
+    pub fn with_deterministic_order(mut self, enabled: bool) -> Self {
+        self.deterministic_order = enabled;
+        self
+    }
+
+    /// Resolve hostnames through these nameservers instead of the system resolver.
+    ///
+    /// Applies to the DNS-dependent enrichment features (parking detection);
+    /// RDAP and WHOIS lookups keep using the system resolver.
+    pub fn with_dns_servers(mut self, servers: Vec<SocketAddr>) -> Self {
+        self.dns_servers = Some(servers);
+        self
+    }
+
+    /// Override how RDAP records are classified as available or taken.
+    ///
+    /// See `ClassificationPolicy`. Only successful RDAP lookups are passed to
+    /// the policy; 404s stay available and WHOIS results are unaffected.
+    pub fn with_classification_policy<P: ClassificationPolicy + 'static>(
+        mut self,
+        policy: P,
+    ) -> Self {
+        self.classification_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Cap the size of RDAP response bodies.
+    ///
+    /// Guards against a misbehaving server returning an enormous body. A
+    /// response over the limit is abandoned mid-read and reported as an RDAP
+    /// error, so the usual WHOIS fallback still applies.
+    pub fn with_max_rdap_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_rdap_response_bytes = max_bytes;
+        self
+    }
+
+    /// Retry an RDAP request up to `retries` more times when it times out.
+    ///
+    /// Each attempt gets the full RDAP timeout. A lookup that still times
+    /// out records the number of attempts in `DomainResult::attempts`, so a
+    /// registry that never answers can be told apart from a one-off blip.
+    pub fn with_timeout_retries(mut self, retries: u8) -> Self {
+        self.timeout_retries = retries;
+        self
+    }
+
+    /// Fetch the RDAP bootstrap registry from `url` instead of
+    /// `https://data.iana.org/rdap/dns.json`.
+    ///
+    /// For mirrors of the IANA registry and test environments. The document
+    /// must use IANA's format. The bootstrap cache is process-wide, so the
+    /// first registry fetched serves every checker until it goes stale.
+    pub fn with_bootstrap_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.bootstrap_url = Some(url.into());
+        self
+    }
+
+    /// Send every RDAP query to `url` instead of the TLD's registry.
+    ///
+    /// Endpoint resolution (built-in map and IANA bootstrap) is skipped and
+    /// each domain is queried at `<url>/<domain>`, which makes it possible
+    /// to exercise a staging RDAP deployment with names under any TLD.
+    pub fn with_rdap_server_override<S: Into<String>>(mut self, url: S) -> Self {
+        self.rdap_server_override = Some(url.into());
+        self
+    }
+
+    /// Record per-check timings into `log` (see `TimingLog`).
+    ///
+    /// Each check run by `check_domains`, `check_domains_stream` or
+    /// `check_domains_to_sink` adds one row. Keep a clone of the log to read
+    /// the rows back after the run.
+    pub fn with_timing_log(mut self, log: TimingLog) -> Self {
+        self.timing_log = Some(log);
+        self
+    }
+
+    /// Send RDAP requests through this HTTP client.
+    ///
+    /// By default, checkers share one lazily-built client so creating many of
+    /// them (e.g. one per request in a server) reuses pooled connections. Use
+    /// this to supply a client with custom proxy, TLS or pool settings; its
+    /// own connect timeout then applies instead of `connect_timeout`.
+    pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Override the RDAP timeout for one TLD.
+    ///
+    /// Useful for registries that are reliably slower than the rest, so they
+    /// don't time out into unknowns while other TLDs keep the short default.
+    pub fn with_tld_timeout(mut self, tld: &str, timeout: Duration) -> Self {
+        let tld = tld.trim().trim_start_matches('.').to_lowercase();
+        self.tld_timeouts.insert(tld, timeout);
+        self
+    }
+
+    /// Prefer `method` when checking domains under one TLD.
+    ///
+    /// `CheckMethod::Whois` queries WHOIS first and falls back to RDAP, for
+    /// registries whose RDAP service is missing or unreliable. WHOIS must
+    /// still be enabled with `enable_whois_fallback`.
+    pub fn with_tld_method(mut self, tld: &str, method: CheckMethod) -> Self {
+        let tld = tld.trim().trim_start_matches('.').to_lowercase();
+        self.tld_method_preference.insert(tld, method);
+        self
+    }
+
     /// Set TLDs to check for base domain names.
     pub fn with_tlds(mut self, tlds: Vec<String>) -> Self {
         self.tlds = Some(tlds);
@@ -232,6 +850,11 @@ pub struct GenerateConfig {
     /// Whether to include the bare base name when prefixes/suffixes are provided.
     /// Default: true. When false, only affixed variants are generated.
     pub include_bare: bool,
+
+    /// Whether `generate_names` drops names with Unicode letters (e.g. `café`).
+    /// Unicode names that are kept must fit a 63-byte DNS label in punycode form.
+    /// Default: false (Unicode names are kept)
+    pub ascii_only: bool,
 }
 
 /// Result of the domain name generation pipeline.
@@ -240,8 +863,8 @@ pub struct GenerationResult {
     /// Generated base names (validated, ready for TLD expansion)
     pub names: Vec<String>,
 
-    /// Pre-filter estimate of how many names the patterns would produce.
-    /// May be higher than `names.len()` due to validation filtering.
+    /// Pre-filter estimate of how many names the patterns and affixes would
+    /// produce. May be higher than `names.len()` due to validation filtering.
     pub estimated_count: usize,
 }
 
@@ -253,6 +876,7 @@ impl GenerateConfig {
             prefixes: Vec::new(),
             suffixes: Vec::new(),
             include_bare: true,
+            ascii_only: false,
         }
     }
 
@@ -278,6 +902,47 @@ impl std::fmt::Display for CheckMethod {
     }
 }
 
+impl Confidence {
+    /// Whether this confidence is at or above `min` (high > medium > low).
+    pub fn at_least(self, min: Confidence) -> bool {
+        self.rank() >= min.rank()
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            Confidence::High => 2,
+            Confidence::Medium => 1,
+            Confidence::Low => 0,
+        }
+    }
+}
+
+impl std::str::FromStr for Confidence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "high" => Ok(Confidence::High),
+            "medium" => Ok(Confidence::Medium),
+            "low" => Ok(Confidence::Low),
+            other => Err(format!(
+                "Invalid confidence level '{}': use 'high', 'medium' or 'low'",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Confidence::High => write!(f, "high"),
+            Confidence::Medium => write!(f, "medium"),
+            Confidence::Low => write!(f, "low"),
+        }
+    }
+}
+
 impl std::fmt::Display for OutputMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -304,6 +969,7 @@ mod tests {
         assert!(!config.detailed_info);
         assert!(config.tlds.is_none());
         assert_eq!(config.rdap_timeout, Duration::from_secs(3));
+        assert_eq!(config.connect_timeout, Duration::from_secs(2));
         assert_eq!(config.whois_timeout, Duration::from_secs(5));
         assert!(config.custom_presets.is_empty());
     }
@@ -343,6 +1009,231 @@ mod tests {
         assert_eq!(config.timeout, Duration::from_secs(30));
     }
 
+    #[test]
+    fn test_with_connect_timeout() {
+        let config = CheckConfig::default().with_connect_timeout(Duration::from_millis(750));
+        assert_eq!(config.connect_timeout, Duration::from_millis(750));
+        // Total timeout is untouched
+        assert_eq!(config.timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_with_ambiguous_status_policy() {
+        assert_eq!(
+            CheckConfig::default().ambiguous_status_policy,
+            AmbiguousStatusPolicy::TreatAsTaken
+        );
+        let config = CheckConfig::default()
+            .with_ambiguous_status_policy(AmbiguousStatusPolicy::TreatAsUnknown);
+        assert_eq!(
+            config.ambiguous_status_policy,
+            AmbiguousStatusPolicy::TreatAsUnknown
+        );
+    }
+
+    #[test]
+    fn test_with_treat_dropping_as_available() {
+        assert!(!CheckConfig::default().treat_dropping_as_available);
+        let config = CheckConfig::default().with_treat_dropping_as_available(true);
+        assert!(config.treat_dropping_as_available);
+    }
+
+    #[test]
+    fn test_with_follow_registrar_rdap() {
+        assert!(!CheckConfig::default().follow_registrar_rdap);
+        assert!(
+            CheckConfig::default()
+                .with_follow_registrar_rdap(true)
+                .follow_registrar_rdap
+        );
+    }
+
+    #[test]
+    fn test_with_detect_wildcard_tlds() {
+        assert!(!CheckConfig::default().detect_wildcard_tlds);
+        assert!(
+            CheckConfig::default()
+                .with_detect_wildcard_tlds(true)
+                .detect_wildcard_tlds
+        );
+    }
+
+    #[test]
+    fn test_with_race_protocols() {
+        assert!(!CheckConfig::default().race_protocols);
+        assert!(
+            CheckConfig::default()
+                .with_race_protocols(true)
+                .race_protocols
+        );
+    }
+
+    #[test]
+    fn test_with_whois_strict() {
+        assert!(!CheckConfig::default().whois_strict);
+        assert!(CheckConfig::default().with_whois_strict(true).whois_strict);
+    }
+
+    #[test]
+    fn test_with_endpoint_tracking() {
+        assert!(!CheckConfig::default().track_endpoints);
+        assert!(
+            CheckConfig::default()
+                .with_endpoint_tracking(true)
+                .track_endpoints
+        );
+    }
+
+    #[test]
+    fn test_with_strict_validation() {
+        assert!(!CheckConfig::default().strict_validation);
+        assert!(
+            CheckConfig::default()
+                .with_strict_validation(true)
+                .strict_validation
+        );
+    }
+
+    #[test]
+    fn test_with_dns_servers() {
+        let server: SocketAddr = "10.0.0.53:53".parse().unwrap();
+        let config = CheckConfig::default().with_dns_servers(vec![server]);
+        assert_eq!(config.dns_servers, Some(vec![server]));
+        assert_eq!(CheckConfig::default().dns_servers, None);
+    }
+
+    #[test]
+    fn test_with_tld_method_normalizes_tld() {
+        let config = CheckConfig::default().with_tld_method(".DE", CheckMethod::Whois);
+        assert_eq!(
+            config.tld_method_preference.get("de"),
+            Some(&CheckMethod::Whois)
+        );
+    }
+
+    #[test]
+    fn test_with_tld_timeout_normalizes_tld() {
+        let config = CheckConfig::default().with_tld_timeout(".CH", Duration::from_secs(12));
+        assert_eq!(
+            config.tld_timeouts.get("ch"),
+            Some(&Duration::from_secs(12))
+        );
+    }
+
+    #[test]
+    fn test_with_concurrency_per_host() {
+        assert_eq!(CheckConfig::default().concurrency_per_host, None);
+        assert_eq!(
+            CheckConfig::default()
+                .with_concurrency_per_host(2)
+                .concurrency_per_host,
+            Some(2)
+        );
+        assert_eq!(
+            CheckConfig::default()
+                .with_concurrency_per_host(0)
+                .concurrency_per_host,
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_with_concurrency_ramp() {
+        assert_eq!(CheckConfig::default().concurrency_ramp, None);
+        assert_eq!(
+            CheckConfig::default()
+                .with_concurrency_ramp(Duration::from_secs(2))
+                .concurrency_ramp,
+            Some(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn test_with_timeout_retries() {
+        assert_eq!(CheckConfig::default().timeout_retries, 0);
+        assert_eq!(
+            CheckConfig::default()
+                .with_timeout_retries(2)
+                .timeout_retries,
+            2
+        );
+    }
+
+    #[test]
+    fn test_with_bootstrap_url() {
+        assert_eq!(CheckConfig::default().bootstrap_url, None);
+        assert_eq!(
+            CheckConfig::default()
+                .with_bootstrap_url("http://mirror.local/dns.json")
+                .bootstrap_url
+                .as_deref(),
+            Some("http://mirror.local/dns.json")
+        );
+    }
+
+    #[test]
+    fn test_with_rdap_server_override() {
+        assert_eq!(CheckConfig::default().rdap_server_override, None);
+        assert_eq!(
+            CheckConfig::default()
+                .with_rdap_server_override("http://localhost:8080/rdap/domain")
+                .rdap_server_override
+                .as_deref(),
+            Some("http://localhost:8080/rdap/domain")
+        );
+    }
+
+    #[test]
+    fn test_with_max_rdap_response_bytes() {
+        assert_eq!(
+            CheckConfig::default().max_rdap_response_bytes,
+            4 * 1024 * 1024
+        );
+        assert_eq!(
+            CheckConfig::default()
+                .with_max_rdap_response_bytes(1024)
+                .max_rdap_response_bytes,
+            1024
+        );
+    }
+
+    #[test]
+    fn test_with_http_client() {
+        assert!(CheckConfig::default().http_client.is_none());
+        let config = CheckConfig::default().with_http_client(reqwest::Client::new());
+        assert!(config.http_client.is_some());
+    }
+
+    #[test]
+    fn test_with_result_buffer() {
+        assert_eq!(CheckConfig::default().result_buffer, None);
+        assert_eq!(
+            CheckConfig::default().with_result_buffer(8).result_buffer,
+            Some(8)
+        );
+        assert_eq!(
+            CheckConfig::default().with_result_buffer(0).result_buffer,
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_with_deterministic_order() {
+        assert!(!CheckConfig::default().deterministic_order);
+        assert!(
+            CheckConfig::default()
+                .with_deterministic_order(true)
+                .deterministic_order
+        );
+    }
+
+    #[test]
+    fn test_with_interleave_by_endpoint() {
+        assert!(!CheckConfig::default().interleave_by_endpoint);
+        let config = CheckConfig::default().with_interleave_by_endpoint(true);
+        assert!(config.interleave_by_endpoint);
+    }
+
     #[test]
     fn test_with_whois_fallback() {
         let config = CheckConfig::default().with_whois_fallback(false);
@@ -459,6 +1350,67 @@ mod tests {
         assert_eq!(format!("{}", OutputMode::Auto), "Auto");
     }
 
+    // ── DomainResult constructors ───────────────────────────────────────
+
+    #[test]
+    fn test_domain_result_new_leaves_optional_fields_unset() {
+        let result = DomainResult::new("example.com", Some(true), CheckMethod::Rdap);
+        assert_eq!(result.domain, "example.com");
+        assert_eq!(result.available, Some(true));
+        assert_eq!(result.method_used, CheckMethod::Rdap);
+        assert!(result.info.is_none());
+        assert!(result.error_message.is_none());
+        assert!(result.rdap_status.is_none());
+    }
+
+    #[test]
+    fn test_domain_result_from_error() {
+        let error = DomainCheckError::rdap_with_status("example.com", "rate limited", 429);
+        let result = DomainResult::from_error("example.com", &error);
+        assert_eq!(result.available, None);
+        assert_eq!(result.method_used, CheckMethod::Unknown);
+        assert_eq!(result.error_message, Some(error.to_string()));
+        assert_eq!(result.error_category, Some(error.category()));
+        assert_eq!(result.rdap_status, Some(429));
+
+        let error = DomainCheckError::timeout_after_attempts("RDAP", Duration::from_secs(3), 2);
+        assert_eq!(DomainResult::from_error("a.com", &error).attempts, Some(2));
+    }
+
+    // ── DomainInfo ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_domain_info_is_empty() {
+        assert!(DomainInfo::default().is_empty());
+        assert!(!DomainInfo {
+            nameservers: vec!["ns1.example.com".to_string()],
+            ..Default::default()
+        }
+        .is_empty());
+        assert!(!DomainInfo {
+            dnssec: Some(false),
+            ..Default::default()
+        }
+        .is_empty());
+    }
+
+    // ── Confidence ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_confidence_at_least() {
+        assert!(Confidence::High.at_least(Confidence::Medium));
+        assert!(Confidence::Medium.at_least(Confidence::Medium));
+        assert!(!Confidence::Low.at_least(Confidence::Medium));
+        assert!(Confidence::Low.at_least(Confidence::Low));
+    }
+
+    #[test]
+    fn test_confidence_from_str() {
+        assert_eq!("HIGH".parse::<Confidence>(), Ok(Confidence::High));
+        assert_eq!(" low ".parse::<Confidence>(), Ok(Confidence::Low));
+        assert!("certain".parse::<Confidence>().is_err());
+    }
+
     // ── Serialization ───────────────────────────────────────────────────
 
     #[test]
@@ -484,6 +1436,13 @@ mod tests {
             check_duration: None,
             method_used: CheckMethod::Rdap,
             error_message: None,
+            error_category: None,
+            confidence: None,
+            note: None,
+            parked: None,
+            rdap_status: None,
+            redirects_to: None,
+            attempts: None,
         };
         let json = serde_json::to_string(&result).unwrap();
         // None fields with skip_serializing_if should be absent
@@ -4,6 +4,7 @@
 //! parsing, and other common operations used throughout the library.
 
 use crate::error::DomainCheckError;
+use std::collections::HashMap;
 
 /// Validate a domain name format.
 ///
@@ -18,7 +19,7 @@ use crate::error::DomainCheckError;
 ///
 /// `Ok(())` if valid, `Err(DomainCheckError)` if invalid.
 pub fn validate_domain(domain: &str) -> Result<(), DomainCheckError> {
-    let domain = domain.trim();
+    let domain = normalize_domain_input(domain);
 
     if domain.is_empty() {
         return Err(DomainCheckError::invalid_domain(
@@ -37,6 +38,77 @@ pub fn validate_domain(domain: &str) -> Result<(), DomainCheckError> {
     Ok(())
 }
 
+/// Validate a domain name against the RFC 1035 length and label rules.
+///
+/// Stricter than `validate_domain`: the whole name must be at most 253
+/// characters and every label 1-63 characters of letters, digits and
+/// hyphens, not starting or ending with a hyphen. Non-ASCII letters are
+/// accepted so IDNs can be given in Unicode form. The error names the
+/// offending label and rule.
+///
+/// # Arguments
+///
+/// * `domain` - The domain name to validate
+///
+/// # Returns
+///
+/// `Ok(())` if valid, `Err(DomainCheckError::InvalidDomain)` if not.
+pub fn validate_domain_strict(domain: &str) -> Result<(), DomainCheckError> {
+    validate_domain(domain)?;
+    let domain = normalize_domain_input(domain);
+
+    let length = domain.chars().count();
+    if length > 253 {
+        return Err(DomainCheckError::invalid_domain(
+            domain,
+            format!("Domain name is {} characters; the limit is 253", length),
+        ));
+    }
+
+    for label in domain.split('.') {
+        if label.is_empty() {
+            return Err(DomainCheckError::invalid_domain(
+                domain,
+                "Domain name contains an empty label (leading or repeated dot)",
+            ));
+        }
+        let length = label.chars().count();
+        if length > 63 {
+            return Err(DomainCheckError::invalid_domain(
+                domain,
+                format!(
+                    "Label '{}' is {} characters; the limit is 63",
+                    label, length
+                ),
+            ));
+        }
+        if let Some(c) = label.chars().find(|&c| !(c.is_alphanumeric() || c == '-')) {
+            return Err(DomainCheckError::invalid_domain(
+                domain,
+                format!("Label '{}' contains invalid character '{}'", label, c),
+            ));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(DomainCheckError::invalid_domain(
+                domain,
+                format!("Label '{}' starts or ends with a hyphen", label),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Trim whitespace and a single trailing dot from a domain input.
+///
+/// DNS-canonical names such as `example.com.` carry a root dot; stripping it
+/// makes them identical to `example.com`. Only one dot is removed, so
+/// `example.com..` stays malformed.
+pub(crate) fn normalize_domain_input(domain: &str) -> &str {
+    let domain = domain.trim();
+    domain.strip_suffix('.').unwrap_or(domain)
+}
+
 /// Expand domain inputs based on smart detection rules.
 ///
 /// Implements the smart expansion logic:
@@ -56,7 +128,7 @@ pub fn expand_domain_inputs(domains: &[String], tlds: &Option<Vec<String>>) -> V
     let mut results = Vec::new();
 
     for domain in domains {
-        let trimmed = domain.trim();
+        let trimmed = normalize_domain_input(domain);
 
         // Skip empty or invalid domains
         if trimmed.is_empty() {
@@ -94,6 +166,219 @@ pub fn expand_domain_inputs(domains: &[String], tlds: &Option<Vec<String>>) -> V
     results
 }
 
+/// Reject TLD lists containing empty or whitespace-only entries.
+///
+/// An empty TLD would expand `brand` into the malformed `brand.`, so it is
+/// reported up front as a `ConfigError` ("empty TLD in TLD list").
+///
+/// # Example
+///
+/// ```rust
+/// use domain_check_lib::validate_tlds;
+///
+/// assert!(validate_tlds(&["com".to_string(), "io".to_string()]).is_ok());
+/// assert!(validate_tlds(&["com".to_string(), " ".to_string()]).is_err());
+/// ```
+pub fn validate_tlds(tlds: &[String]) -> Result<(), DomainCheckError> {
+    match tlds
+        .iter()
+        .position(|tld| tld.trim().trim_start_matches('.').is_empty())
+    {
+        Some(index) if tlds.len() > 1 => Err(DomainCheckError::ConfigError {
+            message: format!(
+                "empty TLD in TLD list (entry {} of {})",
+                index + 1,
+                tlds.len()
+            ),
+        }),
+        Some(_) => Err(DomainCheckError::ConfigError {
+            message: "empty TLD in TLD list".to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Remove duplicate domains while preserving first-seen order.
+///
+/// Domain names are compared case-insensitively, so `Example.com` and
+/// `example.com` collapse into a single entry (the first spelling wins).
+///
+/// # Arguments
+///
+/// * `domains` - Fully qualified domain names, possibly containing duplicates
+///
+/// # Returns
+///
+/// A tuple of `(unique_domains, duplicate_count)`.
+pub fn deduplicate_domains(domains: Vec<String>) -> (Vec<String>, usize) {
+    let mut seen = std::collections::HashSet::with_capacity(domains.len());
+    let original_len = domains.len();
+
+    let unique: Vec<String> = domains
+        .into_iter()
+        .filter(|d| seen.insert(d.to_lowercase()))
+        .collect();
+
+    let duplicate_count = original_len - unique.len();
+    (unique, duplicate_count)
+}
+
+/// Split a fully qualified domain into its base name and TLD.
+///
+/// The base name is the first label; everything after it is the TLD, so
+/// multi-level suffixes stay together (`brand.co.uk` → `("brand", "co.uk")`).
+/// Both parts are lowercased. Returns None when the domain has no dot.
+pub fn extract_domain_parts(domain: &str) -> Option<(String, String)> {
+    let (base, tld) = domain.trim().split_once('.')?;
+    if base.is_empty() || tld.is_empty() {
+        return None;
+    }
+    Some((base.to_lowercase(), tld.to_lowercase()))
+}
+
+/// Second-level labels that registries commonly reserve for their own use.
+const RESERVED_LABELS: &[&str] = &["nic", "www", "whois", "rdap", "registry"];
+
+/// Return the base label if it is commonly reserved by registries.
+///
+/// Accepts a base name (`nic`) or a fully qualified domain (`nic.com`).
+/// Such names generally can't be registered, so checking them wastes a
+/// request and may produce a confusing result.
+///
+/// # Example
+///
+/// ```rust
+/// use domain_check_lib::reserved_label;
+///
+/// assert_eq!(reserved_label("nic.com"), Some("nic".to_string()));
+/// assert_eq!(reserved_label("brand.com"), None);
+/// ```
+pub fn reserved_label(domain: &str) -> Option<String> {
+    let base = domain.trim().split('.').next()?.to_lowercase();
+    RESERVED_LABELS.contains(&base.as_str()).then_some(base)
+}
+
+/// Build the alias domains for a fully qualified domain.
+///
+/// Looks up the domain's TLD in `aliases` and swaps in each alternative,
+/// e.g. `example.com` with `com → [co, io]` yields `example.co`, `example.io`.
+/// Returns an empty vector when the TLD has no aliases.
+pub fn alias_domains(domain: &str, aliases: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let Some((base, tld)) = domain.rsplit_once('.') else {
+        return Vec::new();
+    };
+
+    let tld = tld.to_lowercase();
+    match aliases.get(&tld) {
+        Some(alternatives) => alternatives
+            .iter()
+            .map(|alt| alt.trim().trim_start_matches('.').to_lowercase())
+            .filter(|alt| !alt.is_empty() && *alt != tld)
+            .map(|alt| format!("{}.{}", base, alt))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Add the variant-TLD forms of each domain (see [`get_tld_variants`]).
+///
+/// Each domain is followed by its variants, e.g. `brand.xn--fiqs8s` yields
+/// `brand.xn--fiqs8s`, `brand.xn--fiqz9s`. Domains whose TLD has no
+/// variants pass through unchanged, and duplicates are dropped.
+///
+/// [`get_tld_variants`]: crate::get_tld_variants
+pub fn expand_tld_variants(domains: &[String]) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut expanded = Vec::with_capacity(domains.len());
+    for domain in domains {
+        let variants = match domain.rsplit_once('.') {
+            Some((base, tld)) => crate::protocols::registry::get_tld_variants(tld)
+                .into_iter()
+                .map(|variant| format!("{}.{}", base, variant))
+                .collect(),
+            None => Vec::new(),
+        };
+        for candidate in std::iter::once(domain.clone()).chain(variants) {
+            if seen.insert(candidate.to_lowercase()) {
+                expanded.push(candidate);
+            }
+        }
+    }
+    expanded
+}
+
+/// Concurrent checks allowed per distinct registry endpoint under `auto`.
+const AUTO_PER_ENDPOINT: usize = 4;
+
+/// Concurrent checks allowed per available CPU under `auto`.
+const AUTO_PER_CPU: usize = 8;
+
+/// Upper bound for `auto`, well below the hard limit of 100.
+const AUTO_MAX: usize = 50;
+
+/// Pick a conservative concurrency for checking `domains`.
+///
+/// The heuristic is `min(endpoints × 4, cpus × 8, 50)`, never more than the
+/// number of domains and never less than 1. Endpoints are the distinct RDAP
+/// hosts that will serve the domains, so a single-registry workload stays at
+/// 4 in-flight requests instead of hammering one host into rate limiting,
+/// while a spread across many registries can go wider.
+pub fn auto_concurrency(domains: &[String]) -> usize {
+    let cpus = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    auto_concurrency_for(distinct_endpoints(domains), cpus, domains.len())
+}
+
+/// Count the distinct RDAP hosts that will serve `domains`.
+///
+/// Uses the built-in registry and cached bootstrap entries only, so it never
+/// touches the network; domains whose endpoint isn't known yet count once
+/// per TLD.
+pub fn distinct_endpoints(domains: &[String]) -> usize {
+    domains
+        .iter()
+        .map(|d| crate::protocols::registry::endpoint_key(d))
+        .collect::<std::collections::HashSet<String>>()
+        .len()
+}
+
+/// Keep at most `per_registry` TLDs for each RDAP endpoint host.
+///
+/// Registries that run many TLDs (e.g. one operator's hundreds of new gTLDs)
+/// would otherwise dominate a broad scan. Input order is preserved, so the
+/// first TLDs listed for a host are the ones kept. Endpoints are resolved
+/// like `distinct_endpoints`; TLDs with no known endpoint form their own group.
+pub fn sample_tlds_per_registry(tlds: &[String], per_registry: usize) -> Vec<String> {
+    sample_per_key(tlds, per_registry, |tld| {
+        crate::protocols::registry::endpoint_key(&format!("x.{}", tld))
+    })
+}
+
+fn sample_per_key<F>(tlds: &[String], per_key: usize, key_of: F) -> Vec<String>
+where
+    F: Fn(&str) -> String,
+{
+    let mut kept_per_key: std::collections::HashMap<String, usize> =
+        std::collections::HashMap::new();
+    tlds.iter()
+        .filter(|tld| {
+            let kept = kept_per_key.entry(key_of(tld)).or_insert(0);
+            *kept += 1;
+            *kept <= per_key
+        })
+        .cloned()
+        .collect()
+}
+
+fn auto_concurrency_for(endpoints: usize, cpus: usize, domains: usize) -> usize {
+    (endpoints * AUTO_PER_ENDPOINT)
+        .min(cpus * AUTO_PER_CPU)
+        .min(AUTO_MAX)
+        .min(domains)
+        .max(1)
+}
+
 /// Validate that a base domain name (without TLD) is acceptable.
 pub(crate) fn is_valid_base_name(domain: &str) -> bool {
     // Minimum length check
@@ -111,6 +396,22 @@ pub(crate) fn is_valid_base_name(domain: &str) -> bool {
     domain.chars().all(|c| c.is_alphanumeric() || c == '-')
 }
 
+/// Validate a generated base name.
+///
+/// Names must pass `is_valid_base_name`. Names with non-ASCII characters are
+/// rejected with `ascii_only`, and otherwise accepted only if the IDNA form
+/// (UTS #46 mapping, `xn--` + punycode) is valid and fits in a 63-byte DNS
+/// label.
+pub(crate) fn is_valid_generated_name(name: &str, ascii_only: bool) -> bool {
+    if !is_valid_base_name(name) {
+        return false;
+    }
+    if name.is_ascii() {
+        return true;
+    }
+    !ascii_only && idna::domain_to_ascii(name).is_ok_and(|ascii| ascii.len() <= 63)
+}
+
 /// Validate that an FQDN has basic valid structure.
 fn is_valid_fqdn(domain: &str) -> bool {
     // Basic checks
@@ -164,6 +465,20 @@ mod tests {
 
     // ── validate_domain ─────────────────────────────────────────────────
 
+    #[test]
+    fn test_validate_tlds_rejects_empty_entries() {
+        assert!(validate_tlds(&["com".to_string(), "io".to_string()]).is_ok());
+
+        let err = validate_tlds(&["".to_string()]).unwrap_err();
+        assert!(matches!(err, DomainCheckError::ConfigError { .. }));
+        assert!(format!("{:?}", err).contains("empty TLD in TLD list"));
+
+        let err = validate_tlds(&["com".to_string(), "   ".to_string()]).unwrap_err();
+        assert!(format!("{:?}", err).contains("entry 2 of 2"), "{:?}", err);
+
+        assert!(validate_tlds(&[".".to_string()]).is_err());
+    }
+
     #[test]
     fn test_validate_domain_valid() {
         assert!(validate_domain("example.com").is_ok());
@@ -184,6 +499,12 @@ mod tests {
         assert!(err.to_string().contains("too short"));
     }
 
+    #[test]
+    fn test_validate_domain_trailing_dot() {
+        assert!(validate_domain("example.com.").is_ok());
+        assert!(validate_domain(".").is_err());
+    }
+
     #[test]
     fn test_validate_domain_whitespace_trimmed() {
         assert!(validate_domain("  example.com  ").is_ok());
@@ -194,6 +515,65 @@ mod tests {
         assert!(validate_domain("   ").is_err());
     }
 
+    // ── validate_domain_strict ──────────────────────────────────────────
+
+    fn strict_reason(domain: &str) -> String {
+        match validate_domain_strict(domain).unwrap_err() {
+            DomainCheckError::InvalidDomain { reason, .. } => reason,
+            other => panic!("expected InvalidDomain, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_domain_strict_accepts_valid_names() {
+        assert!(validate_domain_strict("example.com").is_ok());
+        assert!(validate_domain_strict("my-brand.co.uk.").is_ok());
+        assert!(validate_domain_strict("xn--mnchen-3ya.de").is_ok());
+        assert!(validate_domain_strict("münchen.de").is_ok());
+        assert!(validate_domain_strict(&format!("{}.com", "a".repeat(63))).is_ok());
+    }
+
+    #[test]
+    fn test_validate_domain_strict_label_too_long() {
+        let label = "a".repeat(64);
+        assert_eq!(
+            strict_reason(&format!("{}.com", label)),
+            format!("Label '{}' is 64 characters; the limit is 63", label)
+        );
+        // The lenient check lets it through
+        assert!(validate_domain(&format!("{}.com", label)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_domain_strict_name_too_long() {
+        let domain = format!("{}.com", vec!["a".repeat(26); 11].join("."));
+        assert_eq!(domain.len(), 300);
+        assert_eq!(
+            strict_reason(&domain),
+            "Domain name is 300 characters; the limit is 253"
+        );
+    }
+
+    #[test]
+    fn test_validate_domain_strict_invalid_characters() {
+        assert_eq!(
+            strict_reason("my_brand.com"),
+            "Label 'my_brand' contains invalid character '_'"
+        );
+        assert_eq!(
+            strict_reason("brand!.io"),
+            "Label 'brand!' contains invalid character '!'"
+        );
+        assert_eq!(
+            strict_reason("-brand.com"),
+            "Label '-brand' starts or ends with a hyphen"
+        );
+        assert_eq!(
+            strict_reason("brand..com"),
+            "Domain name contains an empty label (leading or repeated dot)"
+        );
+    }
+
     // ── expand_domain_inputs ────────────────────────────────────────────
 
     #[test]
@@ -267,6 +647,251 @@ mod tests {
         assert_eq!(result, vec!["base1.com", "already.io", "base2.com"]);
     }
 
+    #[test]
+    fn test_expand_trailing_dot_fqdn() {
+        let domains = vec!["example.com.".to_string()];
+        let result = expand_domain_inputs(&domains, &Some(vec!["io".to_string()]));
+        assert_eq!(result, vec!["example.com"]);
+    }
+
+    #[test]
+    fn test_expand_base_name_with_trailing_dot() {
+        let domains = vec!["brand.".to_string()];
+        let tlds = Some(vec!["com".to_string(), "io".to_string()]);
+        let result = expand_domain_inputs(&domains, &tlds);
+        assert_eq!(result, vec!["brand.com", "brand.io"]);
+    }
+
+    #[test]
+    fn test_expand_strips_only_one_trailing_dot() {
+        let domains = vec!["example.com..".to_string()];
+        assert!(expand_domain_inputs(&domains, &None).is_empty());
+    }
+
+    // ── normalize_domain_input ──────────────────────────────────────────
+
+    #[test]
+    fn test_normalize_domain_input() {
+        assert_eq!(normalize_domain_input(" example.com. "), "example.com");
+        assert_eq!(normalize_domain_input("example.com"), "example.com");
+        assert_eq!(normalize_domain_input("."), "");
+    }
+
+    // ── deduplicate_domains ─────────────────────────────────────────────
+
+    #[test]
+    fn test_dedup_no_duplicates() {
+        let domains = vec!["a1.com".to_string(), "b2.com".to_string()];
+        let (unique, dups) = deduplicate_domains(domains);
+        assert_eq!(unique, vec!["a1.com", "b2.com"]);
+        assert_eq!(dups, 0);
+    }
+
+    #[test]
+    fn test_dedup_preserves_first_seen_order() {
+        let domains = vec![
+            "b2.com".to_string(),
+            "a1.com".to_string(),
+            "b2.com".to_string(),
+            "c3.com".to_string(),
+            "a1.com".to_string(),
+        ];
+        let (unique, dups) = deduplicate_domains(domains);
+        assert_eq!(unique, vec!["b2.com", "a1.com", "c3.com"]);
+        assert_eq!(dups, 2);
+    }
+
+    #[test]
+    fn test_dedup_case_insensitive() {
+        let domains = vec!["Example.com".to_string(), "example.COM".to_string()];
+        let (unique, dups) = deduplicate_domains(domains);
+        assert_eq!(unique, vec!["Example.com"]);
+        assert_eq!(dups, 1);
+    }
+
+    #[test]
+    fn test_dedup_empty() {
+        let (unique, dups) = deduplicate_domains(Vec::new());
+        assert!(unique.is_empty());
+        assert_eq!(dups, 0);
+    }
+
+    // ── extract_domain_parts ────────────────────────────────────────────
+
+    #[test]
+    fn test_extract_domain_parts() {
+        assert_eq!(
+            extract_domain_parts("Brand.COM"),
+            Some(("brand".to_string(), "com".to_string()))
+        );
+        assert_eq!(
+            extract_domain_parts("brand.co.uk"),
+            Some(("brand".to_string(), "co.uk".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_extract_domain_parts_invalid() {
+        assert_eq!(extract_domain_parts("brand"), None);
+        assert_eq!(extract_domain_parts(".com"), None);
+        assert_eq!(extract_domain_parts("brand."), None);
+    }
+
+    // ── reserved_label ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_reserved_label_detected() {
+        assert_eq!(reserved_label("nic.com"), Some("nic".to_string()));
+        assert_eq!(reserved_label("WWW.io"), Some("www".to_string()));
+        assert_eq!(reserved_label("whois"), Some("whois".to_string()));
+    }
+
+    #[test]
+    fn test_reserved_label_not_detected() {
+        assert_eq!(reserved_label("brand.com"), None);
+        assert_eq!(reserved_label("nicety.com"), None);
+        assert_eq!(reserved_label("shop.nic.com"), None);
+    }
+
+    // ── alias_domains ───────────────────────────────────────────────────
+
+    fn com_aliases() -> HashMap<String, Vec<String>> {
+        HashMap::from([("com".to_string(), vec!["co".to_string(), "io".to_string()])])
+    }
+
+    #[test]
+    fn test_alias_domains_swaps_tld() {
+        let result = alias_domains("startup.com", &com_aliases());
+        assert_eq!(result, vec!["startup.co", "startup.io"]);
+    }
+
+    #[test]
+    fn test_alias_domains_keeps_subdomain_labels() {
+        let result = alias_domains("app.startup.com", &com_aliases());
+        assert_eq!(result, vec!["app.startup.co", "app.startup.io"]);
+    }
+
+    #[test]
+    fn test_alias_domains_no_alias_for_tld() {
+        assert!(alias_domains("startup.org", &com_aliases()).is_empty());
+    }
+
+    #[test]
+    fn test_alias_domains_case_insensitive_tld() {
+        let result = alias_domains("startup.COM", &com_aliases());
+        assert_eq!(result, vec!["startup.co", "startup.io"]);
+    }
+
+    #[test]
+    fn test_alias_domains_skips_self_alias() {
+        let aliases =
+            HashMap::from([("com".to_string(), vec!["com".to_string(), "co".to_string()])]);
+        assert_eq!(alias_domains("x1.com", &aliases), vec!["x1.co"]);
+    }
+
+    // ── expand_tld_variants ─────────────────────────────────────────────
+
+    #[test]
+    fn test_expand_tld_variants_adds_bundle() {
+        let domains = vec!["brand.xn--fiqs8s".to_string(), "brand.com".to_string()];
+        assert_eq!(
+            expand_tld_variants(&domains),
+            vec!["brand.xn--fiqs8s", "brand.xn--fiqz9s", "brand.com"]
+        );
+    }
+
+    #[test]
+    fn test_expand_tld_variants_skips_duplicates() {
+        let domains = vec![
+            "brand.xn--fiqs8s".to_string(),
+            "brand.xn--fiqz9s".to_string(),
+        ];
+        assert_eq!(
+            expand_tld_variants(&domains),
+            vec!["brand.xn--fiqs8s", "brand.xn--fiqz9s"]
+        );
+    }
+
+    // ── auto_concurrency ────────────────────────────────────────────────
+
+    fn names_under(tlds: &[&str], per_tld: usize) -> Vec<String> {
+        tlds.iter()
+            .flat_map(|tld| (0..per_tld).map(move |i| format!("name{}.{}", i, tld)))
+            .collect()
+    }
+
+    #[test]
+    fn test_auto_concurrency_single_tld_stays_low() {
+        let domains = names_under(&["com"], 200);
+        let concurrency = auto_concurrency(&domains);
+        assert!((1..=AUTO_PER_ENDPOINT).contains(&concurrency));
+    }
+
+    #[test]
+    fn test_auto_concurrency_multi_tld_goes_wider() {
+        let domains = names_under(&["com", "org", "app", "de", "uk", "io", "ch"], 50);
+        let single = auto_concurrency(&names_under(&["com"], 350));
+        let multi = auto_concurrency(&domains);
+        assert!(multi > single);
+        assert!(multi <= AUTO_MAX);
+    }
+
+    #[test]
+    fn test_auto_concurrency_bounds() {
+        assert_eq!(auto_concurrency_for(1, 16, 1000), 4);
+        assert_eq!(auto_concurrency_for(30, 16, 1000), AUTO_MAX);
+        assert_eq!(auto_concurrency_for(30, 2, 1000), 16);
+        assert_eq!(auto_concurrency_for(5, 16, 3), 3);
+        assert_eq!(auto_concurrency_for(0, 16, 0), 1);
+    }
+
+    #[test]
+    fn test_distinct_endpoints_groups_by_host() {
+        assert_eq!(distinct_endpoints(&names_under(&["com"], 20)), 1);
+        // .com and .net share Verisign's server
+        assert_eq!(distinct_endpoints(&names_under(&["com", "net"], 5)), 1);
+        assert_eq!(distinct_endpoints(&names_under(&["com", "org"], 5)), 2);
+        assert_eq!(distinct_endpoints(&[]), 0);
+    }
+
+    #[test]
+    fn test_sample_per_key_caps_each_host() {
+        let hosts = std::collections::HashMap::from([
+            ("com", "rdap.verisign.com"),
+            ("net", "rdap.verisign.com"),
+            ("cc", "rdap.verisign.com"),
+            ("org", "rdap.publicinterestregistry.org"),
+            ("app", "pubapi.registry.google"),
+            ("dev", "pubapi.registry.google"),
+        ]);
+        let tlds: Vec<String> = ["com", "net", "org", "cc", "app", "dev", "zz"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let key_of = |tld: &str| hosts.get(tld).copied().unwrap_or(tld).to_string();
+
+        let sampled = sample_per_key(&tlds, 2, key_of);
+        assert_eq!(sampled, vec!["com", "net", "org", "app", "dev", "zz"]);
+        let sampled = sample_per_key(&tlds, 1, key_of);
+        assert_eq!(sampled, vec!["com", "org", "app", "zz"]);
+
+        let mut per_host = std::collections::HashMap::new();
+        for tld in &sampled {
+            *per_host.entry(key_of(tld)).or_insert(0) += 1;
+        }
+        assert!(per_host.values().all(|&n| n <= 1));
+    }
+
+    #[test]
+    fn test_sample_tlds_per_registry_uses_builtin_endpoints() {
+        let tlds: Vec<String> = ["com", "net", "org"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        // .com and .net share Verisign's server
+        assert_eq!(sample_tlds_per_registry(&tlds, 1), vec!["com", "org"]);
+    }
+
     // ── is_valid_base_name ──────────────────────────────────────────────
 
     #[test]
@@ -289,6 +914,25 @@ mod tests {
         assert!(!is_valid_base_name("test_domain")); // contains underscore
     }
 
+    // ── is_valid_generated_name ─────────────────────────────────────────
+
+    #[test]
+    fn test_generated_unicode_name_dropped_when_ascii_only() {
+        assert!(is_valid_generated_name("café", false));
+        assert!(!is_valid_generated_name("café", true));
+        assert!(is_valid_generated_name("cafe", true));
+    }
+
+    #[test]
+    fn test_generated_unicode_name_punycode_length_limit() {
+        // 30 scattered CJK characters: well under 63 chars, far over in punycode
+        let long: String = (0..30)
+            .filter_map(|i| char::from_u32(0x4E00 + i * 97))
+            .collect();
+        assert!(long.chars().count() < 63);
+        assert!(!is_valid_generated_name(&long, false));
+    }
+
     // ── is_valid_fqdn ───────────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,48 @@
+// domain-check-lib/tests/bootstrap_url.rs
+
+//! Tests for CheckConfig::bootstrap_url. The bootstrap cache is
+//! process-wide, so these live in their own test binary where nothing else
+//! can fill it first.
+
+use domain_check_lib::{CheckConfig, DomainChecker};
+
+#[allow(dead_code)]
+#[path = "../src/test_http.rs"]
+mod test_http;
+
+/// Serve a bootstrap registry at `/dns.json` that maps `.zzmirror` to an RDAP
+/// server on the same port, which answers every domain query with 404.
+async fn mirror_server() -> std::net::SocketAddr {
+    test_http::serve_with(|request| async move {
+        let path = test_http::request_path(&request);
+        Some(if path == "/dns.json" {
+            let host = request
+                .lines()
+                .find_map(|line| line.strip_prefix("host: ").or(line.strip_prefix("Host: ")))
+                .unwrap_or_default();
+            let bootstrap = format!(
+                r#"{{"services": [[["zzmirror"], ["http://{}/rdap/"]]], "version": "1.0"}}"#,
+                host
+            );
+            test_http::response("200 OK", Some("application/json"), &bootstrap)
+        } else if path.starts_with("/rdap/domain/") {
+            test_http::empty("404 Not Found")
+        } else {
+            test_http::empty("500 Internal Server Error")
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_discovery_uses_configured_bootstrap_url() {
+    let addr = mirror_server().await;
+    let checker = DomainChecker::with_config(
+        CheckConfig::default()
+            .with_whois_fallback(false)
+            .with_bootstrap_url(format!("http://{}/dns.json", addr)),
+    );
+
+    let result = checker.check_domain("example.zzmirror").await.unwrap();
+    assert_eq!(result.available, Some(true));
+}
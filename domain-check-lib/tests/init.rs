@@ -0,0 +1,96 @@
+// domain-check-lib/tests/init.rs
+
+//! Tests for init_with. These change process-wide state, so they live in
+//! their own test binary rather than alongside tests that rely on defaults.
+
+use domain_check_lib::{
+    get_rdap_endpoint, init_with, CheckConfig, DomainCheckError, DomainChecker, InitOptions,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[allow(dead_code)]
+#[path = "../src/test_http.rs"]
+mod test_http;
+
+/// Trimmed IANA bootstrap document with a TLD that isn't built in.
+const SNAPSHOT: &str = r#"{
+  "services": [
+    [["zzinit", "zzwarm"], ["https://rdap.example.net/rdap/"]]
+  ],
+  "version": "1.0"
+}"#;
+
+#[tokio::test]
+async fn test_snapshot_resolves_bootstrap_only_tld_offline() {
+    init_with(InitOptions::default().with_bootstrap_snapshot(SNAPSHOT)).unwrap();
+
+    // Bootstrap disabled: a cache miss would be an error, never a fetch
+    assert_eq!(
+        get_rdap_endpoint("zzinit", false).await.unwrap(),
+        "https://rdap.example.net/rdap/domain/"
+    );
+    assert_eq!(
+        get_rdap_endpoint("ZZWARM", false).await.unwrap(),
+        "https://rdap.example.net/rdap/domain/"
+    );
+}
+
+#[test]
+fn test_missing_snapshot_file_is_file_error() {
+    let err =
+        init_with(InitOptions::default().with_bootstrap_snapshot_file("/nonexistent/dir/dns.json"))
+            .unwrap_err();
+    assert!(
+        matches!(err, DomainCheckError::FileError { .. }),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn test_invalid_snapshot_is_rejected() {
+    assert!(init_with(InitOptions::default().with_bootstrap_snapshot("{}")).is_err());
+}
+
+#[tokio::test]
+async fn test_default_config_applies_to_new_checkers() {
+    // Counts queries and answers each with 404 (available)
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&hits);
+    let addr = test_http::serve_with(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        async { Some(test_http::empty("404 Not Found")) }
+    })
+    .await;
+
+    init_with(
+        InitOptions::default().with_default_config(
+            CheckConfig::default()
+                .with_concurrency(7)
+                .with_whois_fallback(false)
+                .with_bootstrap(false)
+                .with_rdap_server_override(format!("http://{}/domain/", addr)),
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(DomainChecker::new().config().concurrency, 7);
+    assert_eq!(DomainChecker::default().config().concurrency, 7);
+    // Explicit configurations are untouched
+    assert_eq!(
+        DomainChecker::with_config(CheckConfig::default())
+            .config()
+            .concurrency,
+        CheckConfig::default().concurrency
+    );
+
+    // The whole installed configuration is applied, not just the fields
+    // read straight from `config()`
+    let result = DomainChecker::new()
+        .check_domain("stubbed.zzinit")
+        .await
+        .unwrap();
+    assert_eq!(result.available, Some(true));
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
@@ -107,6 +107,27 @@ async fn test_known_taken_domain_google_com() {
     );
 }
 
+/// check_domain_detailed returns info even though detailed_info is off.
+/// This hits the network so it's marked #[ignore] for CI unless explicitly run.
+#[tokio::test]
+#[ignore]
+async fn test_check_domain_detailed_ignores_detailed_info_setting() {
+    use domain_check_lib::DomainChecker;
+
+    let checker = DomainChecker::new();
+    assert!(!checker.config().detailed_info);
+
+    let plain = checker.check_domain("google.com").await.unwrap();
+    assert!(plain.info.is_none());
+
+    let detailed = checker.check_domain_detailed("google.com").await.unwrap();
+    assert_eq!(detailed.available, Some(false));
+    assert!(
+        detailed.info.is_some(),
+        "google.com should have RDAP details"
+    );
+}
+
 // ============================================================
 // Bootstrap bulk fetch tests
 // ============================================================
@@ -69,6 +69,11 @@ pub struct GenerateNamesParams {
 
     #[schemars(description = "Include bare name without affixes (default true)")]
     pub include_bare: Option<bool>,
+
+    #[schemars(
+        description = "Drop names with Unicode letters such as \"café\" (default false: Unicode names are kept)"
+    )]
+    pub ascii_only: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -193,11 +198,12 @@ impl DomainCheckServer {
         }
 
         let checker = if params.concurrency.is_some() || params.timeout_secs.is_some() {
-            DomainChecker::with_config(
+            DomainChecker::try_with_config(
                 CheckConfig::default()
                     .with_concurrency(params.concurrency.unwrap_or(20))
                     .with_timeout(Duration::from_secs(params.timeout_secs.unwrap_or(5))),
             )
+            .map_err(|e| e.to_string())?
         } else {
             self.checker.clone()
         };
@@ -232,7 +238,8 @@ impl DomainCheckServer {
             .collect();
 
         let checker = if let Some(c) = params.concurrency {
-            DomainChecker::with_config(CheckConfig::default().with_concurrency(c))
+            DomainChecker::try_with_config(CheckConfig::default().with_concurrency(c))
+                .map_err(|e| e.to_string())?
         } else {
             self.checker.clone()
         };
@@ -255,6 +262,7 @@ impl DomainCheckServer {
             prefixes: params.prefixes.unwrap_or_default(),
             suffixes: params.suffixes.unwrap_or_default(),
             include_bare: params.include_bare.unwrap_or(true),
+            ascii_only: params.ascii_only.unwrap_or(false),
         };
 
         let literals = params.literal_names.unwrap_or_default();
@@ -299,7 +307,7 @@ impl DomainCheckServer {
         Parameters(params): Parameters<DomainInfoParams>,
     ) -> Result<String, String> {
         let config = CheckConfig::default().with_detailed_info(true);
-        let checker = DomainChecker::with_config(config);
+        let checker = DomainChecker::try_with_config(config).map_err(|e| e.to_string())?;
 
         match checker.check_domain(&params.domain).await {
             Ok(r) => {
@@ -380,7 +388,7 @@ fn to_batch_response(results: Vec<domain_check_lib::DomainResult>) -> BatchCheck
 #[cfg(test)]
 mod tests {
     use super::*;
-    use domain_check_lib::{CheckMethod, DomainResult};
+    use domain_check_lib::{CheckMethod, DomainResult, ErrorCategory};
 
     // ── to_json helper ───────────────────────────────────────────────────
 
@@ -582,31 +590,13 @@ mod tests {
 
     #[test]
     fn test_to_batch_response_mixed_results() {
+        let mut timed_out = DomainResult::new("err.xyz", None, CheckMethod::Unknown);
+        timed_out.error_message = Some("timeout".into());
+        timed_out.error_category = Some(ErrorCategory::Timeout);
         let results = vec![
-            DomainResult {
-                domain: "free.com".into(),
-                available: Some(true),
-                info: None,
-                check_duration: None,
-                method_used: CheckMethod::Rdap,
-                error_message: None,
-            },
-            DomainResult {
-                domain: "taken.com".into(),
-                available: Some(false),
-                info: None,
-                check_duration: None,
-                method_used: CheckMethod::Whois,
-                error_message: None,
-            },
-            DomainResult {
-                domain: "err.xyz".into(),
-                available: None,
-                info: None,
-                check_duration: None,
-                method_used: CheckMethod::Unknown,
-                error_message: Some("timeout".into()),
-            },
+            DomainResult::new("free.com", Some(true), CheckMethod::Rdap),
+            DomainResult::new("taken.com", Some(false), CheckMethod::Whois),
+            timed_out,
         ];
         let batch = to_batch_response(results);
         assert_eq!(batch.total, 3);
@@ -621,22 +611,8 @@ mod tests {
     #[test]
     fn test_to_batch_response_all_available() {
         let results = vec![
-            DomainResult {
-                domain: "a.com".into(),
-                available: Some(true),
-                info: None,
-                check_duration: None,
-                method_used: CheckMethod::Rdap,
-                error_message: None,
-            },
-            DomainResult {
-                domain: "b.com".into(),
-                available: Some(true),
-                info: None,
-                check_duration: None,
-                method_used: CheckMethod::Rdap,
-                error_message: None,
-            },
+            DomainResult::new("a.com", Some(true), CheckMethod::Rdap),
+            DomainResult::new("b.com", Some(true), CheckMethod::Rdap),
         ];
         let batch = to_batch_response(results);
         assert_eq!(batch.available, 2);
@@ -779,6 +755,7 @@ mod tests {
                 prefixes: None,
                 suffixes: None,
                 include_bare: None,
+                ascii_only: None,
             }))
             .await;
         assert!(result.is_ok());
@@ -799,6 +776,7 @@ mod tests {
                 prefixes: Some(vec!["get".into()]),
                 suffixes: Some(vec!["ly".into()]),
                 include_bare: Some(true),
+                ascii_only: None,
             }))
             .await;
         assert!(result.is_ok());
@@ -825,6 +803,7 @@ mod tests {
                 prefixes: None,
                 suffixes: None,
                 include_bare: None,
+                ascii_only: None,
             }))
             .await;
         assert!(result.is_err());
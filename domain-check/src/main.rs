@@ -5,15 +5,17 @@
 
 mod ui;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use console::Term;
 use domain_check_lib::{
     get_all_known_tlds, get_available_presets, get_preset_tlds, get_preset_tlds_with_custom,
-    initialize_bootstrap,
+    initialize_bootstrap_with,
 };
-use domain_check_lib::{load_env_config, ConfigManager, FileConfig};
-use domain_check_lib::{CheckConfig, DomainChecker};
-use std::io::BufRead;
+use domain_check_lib::{load_env_config, parse_check_method, ConfigManager, FileConfig};
+use domain_check_lib::{
+    CheckConfig, CheckEvent, Confidence, DateFormat, DomainCheckError, DomainChecker, ErrorReport,
+};
+use std::io::{BufRead, Write};
 use std::process;
 
 /// CLI arguments for domain-check
@@ -22,11 +24,21 @@ use std::process;
 #[command(version = env!("CARGO_PKG_VERSION"))]
 #[command(about = "Check domain availability using RDAP with WHOIS fallback")]
 #[command(disable_help_flag = true)]
+#[command(disable_version_flag = true)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Args {
     /// Show this help message
     #[arg(short = 'h', long = "help", action = clap::ArgAction::SetTrue, global = true)]
     pub help: bool,
 
+    /// Show version (with --json: version, features and built-in TLD count)
+    #[arg(short = 'V', long = "version")]
+    pub version: bool,
+
+    /// Standalone tools that don't check domains
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Domain names to check (base names or FQDNs)
     #[arg(value_name = "DOMAINS", help_heading = "Domain Selection")]
     pub domains: Vec<String>,
@@ -35,10 +47,46 @@ pub struct Args {
     #[arg(short = 't', long = "tld", value_name = "TLD", value_delimiter = ',', action = clap::ArgAction::Append, help_heading = "Domain Selection")]
     pub tlds: Option<Vec<String>>,
 
+    /// Read TLDs to check from a file (one per line)
+    #[arg(
+        long = "tld-file",
+        value_name = "FILE",
+        help_heading = "Domain Selection"
+    )]
+    pub tld_file: Option<String>,
+
     /// Check against all known TLDs
     #[arg(long = "all", help_heading = "Domain Selection")]
     pub all_tlds: bool,
 
+    /// With --all, check only the N most popular TLDs
+    #[arg(
+        long = "tld-limit",
+        value_name = "N",
+        help_heading = "Domain Selection"
+    )]
+    pub tld_limit: Option<usize>,
+
+    /// Keep at most K TLDs per registry (RDAP endpoint host) for broad scans
+    #[arg(
+        long = "sample-tlds",
+        value_name = "K",
+        help_heading = "Domain Selection"
+    )]
+    pub sample_tlds: Option<usize>,
+
+    /// Extra TLDs merged onto -t/--tld-file/--preset/--all (comma-separated)
+    #[arg(long = "add-tld", value_name = "TLD", value_delimiter = ',', action = clap::ArgAction::Append, help_heading = "Domain Selection")]
+    pub add_tlds: Option<Vec<String>>,
+
+    /// Drop these TLDs from the expanded domains (comma-separated)
+    #[arg(long = "exclude-tld", value_name = "TLD", value_delimiter = ',', action = clap::ArgAction::Append, help_heading = "Domain Selection")]
+    pub exclude_tlds: Option<Vec<String>>,
+
+    /// Keep only domains under these TLDs (comma-separated)
+    #[arg(long = "include-only-tld", value_name = "TLD", value_delimiter = ',', action = clap::ArgAction::Append, help_heading = "Domain Selection")]
+    pub include_only_tlds: Option<Vec<String>>,
+
     /// Use a predefined TLD preset (use --list-presets to see all)
     #[arg(
         long = "preset",
@@ -51,7 +99,59 @@ pub struct Args {
     #[arg(long = "list-presets", help_heading = "Domain Selection")]
     pub list_presets: bool,
 
-    /// Input file with domains (one per line)
+    /// Print the resolved TLD list (after presets, --all and exclusions) and exit
+    #[arg(long = "show-tlds", help_heading = "Domain Selection")]
+    pub show_tlds: bool,
+
+    /// Check a random unregistered name per TLD and report how each registry answers
+    #[arg(long = "baseline", help_heading = "Domain Selection")]
+    pub baseline: bool,
+
+    /// Also check configured [tld_aliases] for any taken domain
+    #[arg(long = "with-aliases", help_heading = "Domain Selection")]
+    pub with_aliases: bool,
+
+    /// Also check variant-script forms of bundled TLDs (e.g. .中国 / .中國)
+    #[arg(long = "with-variants", help_heading = "Domain Selection")]
+    pub with_variants: bool,
+
+    /// Warn about IDN inputs that mix scripts or use homoglyphs (see result notes)
+    #[arg(long = "check-punycode-confusables", help_heading = "Domain Selection")]
+    pub check_confusables: bool,
+
+    /// Skip IDN inputs flagged as confusable instead of checking them
+    #[arg(long = "reject-confusables", help_heading = "Domain Selection")]
+    pub reject_confusables: bool,
+
+    /// Skip names that registries commonly reserve (nic, www, whois, ...)
+    #[arg(long = "skip-reserved", help_heading = "Domain Selection")]
+    pub skip_reserved: bool,
+
+    /// Keep domains in the case they were given instead of lowercasing them
+    #[arg(long = "preserve-case", help_heading = "Domain Selection")]
+    pub preserve_case: bool,
+
+    /// Reject domains breaking RFC 1035 length/charset rules before lookup
+    #[arg(long = "strict-validation", help_heading = "Domain Selection")]
+    pub strict_validation: bool,
+
+    /// Resume from a prior --json/--csv run, re-checking only unknown results
+    #[arg(
+        long = "resume",
+        value_name = "FILE",
+        help_heading = "Domain Selection"
+    )]
+    pub resume: Option<String>,
+
+    /// Re-check only the domains a prior --json/--csv run reported available
+    #[arg(
+        long = "recheck-available",
+        value_name = "FILE",
+        help_heading = "Domain Selection"
+    )]
+    pub recheck_available: Option<String>,
+
+    /// Input file with domains (one per line), `-` to read stdin, or an http(s) URL
     #[arg(
         short = 'f',
         long = "file",
@@ -60,6 +160,19 @@ pub struct Args {
     )]
     pub file: Option<String>,
 
+    /// Read --file lazily and check domains as they are read, keeping memory bounded
+    #[arg(long = "stream-file", help_heading = "Domain Selection")]
+    pub stream_file: bool,
+
+    /// Read --file as CSV with a `domain` column and copy these columns into --csv output
+    #[arg(
+        long = "passthrough-columns",
+        value_name = "COLUMNS",
+        value_delimiter = ',',
+        help_heading = "Domain Selection"
+    )]
+    pub passthrough_columns: Option<Vec<String>>,
+
     /// Pattern for name generation (\w=letter, \d=digit, ?=either)
     #[arg(
         long = "pattern",
@@ -91,38 +204,225 @@ pub struct Args {
     #[arg(long = "dry-run", help_heading = "Domain Generation")]
     pub dry_run: bool,
 
+    /// Print the projected request count and run time without checking
+    #[arg(long = "estimate", help_heading = "Domain Generation")]
+    pub estimate: bool,
+
+    /// Assumed RDAP round trip for --estimate, in milliseconds (default: 500)
+    #[arg(
+        long = "estimate-latency",
+        value_name = "MS",
+        help_heading = "Domain Generation"
+    )]
+    pub estimate_latency: Option<u64>,
+
     /// Output results in JSON format
     #[arg(short = 'j', long = "json", help_heading = "Output Format")]
     pub json: bool,
 
+    /// Wrap --json output as {"config": ..., "results": [...]} recording the run settings
+    #[arg(long = "json-envelope", help_heading = "Output Format")]
+    pub json_envelope: bool,
+
     /// Output results in CSV format
     #[arg(long = "csv", help_heading = "Output Format")]
     pub csv: bool,
 
+    /// Output results as newline-delimited JSON (one object per line)
+    #[arg(long = "ndjson", help_heading = "Output Format")]
+    pub ndjson: bool,
+
+    /// Hide results below this confidence: high, medium or low (counted as suppressed)
+    #[arg(
+        long = "min-confidence",
+        value_name = "LEVEL",
+        help_heading = "Output Format"
+    )]
+    pub min_confidence: Option<String>,
+
+    /// Print only true, false or unknown for a single domain
+    #[arg(long = "bool", help_heading = "Output Format")]
+    pub bool_output: bool,
+
+    /// Write NDJSON (as results complete) or CSV results to FILE (requires --ndjson or --csv)
+    #[arg(long = "output", value_name = "FILE", help_heading = "Output Format")]
+    pub output: Option<String>,
+
+    /// Add to the --output file instead of replacing it (CSV header only when the file is empty)
+    #[arg(long = "append", requires = "output", help_heading = "Output Format")]
+    pub append: bool,
+
     /// Enable grouped, structured output with section headers
     #[arg(short = 'p', long = "pretty", help_heading = "Output Format")]
     pub pretty: bool,
 
+    /// Print results as "example.com is AVAILABLE", like the pre-1.0 CLI
+    #[arg(long = "legacy-format", help_heading = "Output Format")]
+    pub legacy_format: bool,
+
+    /// Show only domains whose check failed, with the reason and an error breakdown
+    #[arg(long = "only-errors", help_heading = "Output Format")]
+    pub only_errors: bool,
+
+    /// Group batch text output by status, tld or none (default: status with --pretty, else none)
+    #[arg(long = "group-by", value_name = "MODE", help_heading = "Output Format")]
+    pub group_by: Option<String>,
+
     /// Show detailed domain information when available
     #[arg(short = 'i', long = "info", help_heading = "Output Format")]
     pub info: bool,
 
+    /// Info fields to show and CSV columns: registrar, created, expires, updated, nameservers, status, dnssec (implies --info)
+    #[arg(
+        long = "fields",
+        value_name = "LIST",
+        value_delimiter = ',',
+        help_heading = "Output Format"
+    )]
+    pub fields: Option<Vec<String>>,
+
+    /// Reformat registry dates: iso, date-only, or a strftime pattern such as %d/%m/%Y
+    #[arg(
+        long = "date-format",
+        value_name = "FMT",
+        help_heading = "Output Format"
+    )]
+    pub date_format: Option<String>,
+
+    /// Print a frequency table of registrars among taken domains (implies --info)
+    #[arg(long = "registrar-stats", help_heading = "Output Format")]
+    pub registrar_stats: bool,
+
+    /// Flag base names whose taken TLDs are held by different registrars (implies --info)
+    #[arg(long = "registrar-consistency", help_heading = "Output Format")]
+    pub registrar_consistency: bool,
+
+    /// With --info, label taken domains held by these registrars OWNED and others external
+    #[arg(
+        long = "registrar-allowlist",
+        value_name = "REGISTRAR",
+        value_delimiter = ',',
+        help_heading = "Output Format"
+    )]
+    pub registrar_allowlist: Option<Vec<String>>,
+
+    /// Write a machine-readable run summary (counts, duration, methods, errors) to FILE
+    #[arg(
+        long = "summary-json",
+        value_name = "FILE",
+        help_heading = "Output Format"
+    )]
+    pub summary_json: Option<String>,
+
+    /// Write a JSON manifest (version, time, inputs, config, domain list hash) to FILE
+    #[arg(long = "manifest", value_name = "FILE", help_heading = "Output Format")]
+    pub manifest: Option<String>,
+
+    /// Write domains that ended unknown to FILE, one per line, for a later --file retry
+    #[arg(
+        long = "failures-file",
+        value_name = "FILE",
+        help_heading = "Output Format"
+    )]
+    pub failures_file: Option<String>,
+
+    /// Write launch/start/completion times, endpoint and method per check to FILE (CSV, or JSON for .json)
+    #[arg(
+        long = "timing-log",
+        value_name = "FILE",
+        help_heading = "Output Format"
+    )]
+    pub timing_log: Option<String>,
+
+    /// Print one `key=value` summary line last (total, available, taken, unknown, duration_ms)
+    #[arg(long = "compact-summary", help_heading = "Output Format")]
+    pub compact_summary: bool,
+
+    /// Print result lines without the summary footer
+    #[arg(long = "no-summary", help_heading = "Output Format")]
+    pub no_summary: bool,
+
+    /// Write newline-delimited JSON progress events to stderr for wrapper programs
+    #[arg(long = "progress-json", help_heading = "Output Format")]
+    pub progress_json: bool,
+
+    /// With --progress-json, also emit a `result` event for every domain
+    #[arg(long = "progress-json-results", help_heading = "Output Format")]
+    pub progress_json_results: bool,
+
+    /// Show how many results were answered via RDAP, WHOIS, and bootstrap
+    #[arg(long = "method-stats", help_heading = "Output Format")]
+    pub method_stats: bool,
+
+    /// After the run, print RDAP success/404/error/timeout counts per endpoint to stderr
+    #[arg(long = "endpoint-report", help_heading = "Output Format")]
+    pub endpoint_report: bool,
+
     /// Collect all results before displaying
-    #[arg(long = "batch", help_heading = "Output Format")]
+    #[arg(long = "batch", help_heading = "Processing Modes")]
     pub batch: bool,
 
     /// Show results as they complete
-    #[arg(long = "streaming", help_heading = "Output Format")]
+    #[arg(long = "streaming", help_heading = "Processing Modes")]
     pub streaming: bool,
 
-    /// Max concurrent domain checks (default: 20, max: 100)
+    /// Re-check the domains periodically and report ones that become available
+    #[arg(long = "watch", help_heading = "Processing Modes")]
+    pub watch: bool,
+
+    /// Time between --watch rounds, like "30s" or "5m" (default: [monitoring] interval or 5m)
+    #[arg(
+        long = "watch-interval",
+        value_name = "DURATION",
+        help_heading = "Processing Modes"
+    )]
+    pub watch_interval: Option<String>,
+
+    /// With --watch, POST a JSON event to URL when a domain becomes available
+    #[arg(
+        long = "webhook",
+        value_name = "URL",
+        help_heading = "Processing Modes"
+    )]
+    pub webhook: Option<String>,
+
+    /// Max concurrent domain checks, or "auto" to size it per workload (default: 20, max: 100)
     #[arg(
         short = 'c',
         long = "concurrency",
+        value_name = "N|auto",
         default_value = "20",
         help_heading = "Performance"
     )]
-    pub concurrency: usize,
+    pub concurrency: String,
+
+    /// Ramp up to the full concurrency over this many seconds to avoid an initial burst
+    #[arg(
+        long = "concurrency-ramp",
+        value_name = "SECS",
+        help_heading = "Performance"
+    )]
+    pub concurrency_ramp: Option<u64>,
+
+    /// Max RDAP requests in flight to any one registry host
+    #[arg(
+        long = "concurrency-per-host",
+        value_name = "N",
+        help_heading = "Performance"
+    )]
+    pub concurrency_per_host: Option<usize>,
+
+    /// Check the list in batches of N, printing a running tally after each batch
+    #[arg(long = "batch-size", value_name = "N", help_heading = "Performance")]
+    pub batch_size: Option<usize>,
+
+    /// Retry an RDAP request this many times when it times out (default: 0)
+    #[arg(
+        long = "timeout-retries",
+        value_name = "N",
+        help_heading = "Performance"
+    )]
+    pub timeout_retries: Option<u8>,
 
     /// Override the 5000 domain limit for bulk operations
     #[arg(long = "force", help_heading = "Performance")]
@@ -140,6 +440,38 @@ pub struct Args {
     #[arg(long = "no-whois", help_heading = "Protocol")]
     pub no_whois: bool,
 
+    /// Query RDAP and WHOIS at the same time and keep the first answer (doubles requests)
+    #[arg(long = "race", help_heading = "Protocol")]
+    pub race: bool,
+
+    /// Fetch taken domains' home pages and flag parking pages
+    #[arg(long = "detect-parking", help_heading = "Protocol")]
+    pub detect_parking: bool,
+
+    /// Follow taken domains' home page redirects and report off-site targets
+    #[arg(long = "check-redirects", help_heading = "Protocol")]
+    pub check_redirects: bool,
+
+    /// Nameserver for DNS-dependent features like --detect-parking (IP or IP:PORT, repeatable)
+    #[arg(long = "dns-server", value_name = "ADDR", value_delimiter = ',', action = clap::ArgAction::Append, help_heading = "Protocol")]
+    pub dns_servers: Option<Vec<String>>,
+
+    /// Report taken domains in pendingDelete/redemptionPeriod as available (low confidence)
+    #[arg(long = "treat-dropping-as-available", help_heading = "Protocol")]
+    pub treat_dropping_as_available: bool,
+
+    /// Probe a random name per TLD and mark results unknown if it is reported taken too
+    #[arg(long = "detect-wildcards", help_heading = "Protocol")]
+    pub detect_wildcards: bool,
+
+    /// Send every RDAP query to URL/<domain> regardless of TLD (e.g. a staging server)
+    #[arg(long = "rdap-server", value_name = "URL", help_heading = "Protocol")]
+    pub rdap_server: Option<String>,
+
+    /// Fetch the RDAP bootstrap registry from URL instead of IANA
+    #[arg(long = "bootstrap-url", value_name = "URL", help_heading = "Protocol")]
+    pub bootstrap_url: Option<String>,
+
     /// Use specific config file instead of automatic discovery
     #[arg(long = "config", value_name = "FILE", help_heading = "Configuration")]
     pub config: Option<String>,
@@ -153,9 +485,52 @@ pub struct Args {
     pub verbose: bool,
 }
 
+/// Subcommands of domain-check
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print generated base names without TLD expansion or checking
+    Generate(GenerateArgs),
+}
+
+/// Arguments for `domain-check generate`
+#[derive(clap::Args, Debug)]
+pub struct GenerateArgs {
+    /// Literal base names to include alongside generated ones
+    #[arg(value_name = "NAMES")]
+    pub names: Vec<String>,
+
+    /// Pattern for name generation (\w=letter, \d=digit, ?=either)
+    #[arg(long = "pattern", value_name = "PATTERN", value_delimiter = ',')]
+    pub patterns: Vec<String>,
+
+    /// Prefixes to prepend to names (comma-separated)
+    #[arg(long = "prefix", value_name = "PREFIX", value_delimiter = ',')]
+    pub prefixes: Vec<String>,
+
+    /// Suffixes to append to names (comma-separated)
+    #[arg(long = "suffix", value_name = "SUFFIX", value_delimiter = ',')]
+    pub suffixes: Vec<String>,
+
+    /// Drop names with non-ASCII letters (e.g. café)
+    #[arg(long = "ascii-only")]
+    pub ascii_only: bool,
+
+    /// Print only the number of names
+    #[arg(long = "count", conflicts_with = "json")]
+    pub count: bool,
+
+    /// Print the names as a JSON array
+    #[arg(long = "json")]
+    pub json: bool,
+}
+
 #[tokio::main]
 async fn main() {
-    let args = Args::parse();
+    let mut args = match Args::try_parse() {
+        Ok(args) => args,
+        Err(e) => exit_on_parse_error(e),
+    };
+    args.registrar_allowlist = args.registrar_allowlist.map(clean_registrar_allowlist);
 
     // Handle --help before anything else
     if args.help {
@@ -163,10 +538,32 @@ async fn main() {
         return;
     }
 
+    if args.version {
+        if args.json {
+            match serde_json::to_string_pretty(&VersionInfo::new()) {
+                Ok(json) => println!("{}", json),
+                Err(e) => exit_with_error(true, &e, ErrorReport::new("other", e.to_string())),
+            }
+        } else {
+            println!("domain-check {}", env!("CARGO_PKG_VERSION"));
+        }
+        return;
+    }
+
+    if let Some(Command::Generate(generate)) = &args.command {
+        if let Err(e) = validate_generate_args(generate) {
+            exit_with_error(generate.json, &e, ErrorReport::new("invalid_args", &e));
+        }
+        match run_generate(generate) {
+            Ok(output) => println!("{}", output),
+            Err(e) => exit_with_error(generate.json, &e, error_report(e.as_ref())),
+        }
+        return;
+    }
+
     // Validate arguments
     if let Err(e) = validate_args(&args) {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+        exit_with_error(args.json, &e, ErrorReport::new("invalid_args", &e));
     }
 
     // Handle --list-presets early
@@ -184,9 +581,9 @@ async fn main() {
     }
 
     // Run the domain checking
+    let json = args.json;
     if let Err(e) = run_domain_check(args).await {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+        exit_with_error(json, &e, error_report(e.as_ref()));
     }
 
     // Force immediate exit to avoid hanging on reqwest's connection pool cleanup.
@@ -195,6 +592,49 @@ async fn main() {
     process::exit(0);
 }
 
+/// Report a command-line parsing error and exit.
+///
+/// With `--json` anywhere on the command line, usage errors are reported as
+/// a JSON error object like any other failure; otherwise (and for help or
+/// version output) clap prints its own message.
+fn exit_on_parse_error(error: clap::Error) -> ! {
+    let json = std::env::args().any(|arg| arg == "--json" || arg == "-j");
+    if json && error.use_stderr() {
+        let rendered = error.to_string();
+        let message = rendered
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches("error: ");
+        exit_with_error(true, &rendered, ErrorReport::new("invalid_args", message));
+    }
+    error.exit()
+}
+
+/// Print a fatal error and exit with status 1.
+///
+/// With --json the error goes to stderr as
+/// `{"error": {"kind": "...", "message": "..."}}` so scripts can parse it;
+/// otherwise it is printed as a plain message.
+fn exit_with_error(json: bool, error: &dyn std::fmt::Display, report: ErrorReport) -> ! {
+    if json {
+        eprintln!("{}", serde_json::json!({ "error": report }));
+    } else {
+        eprintln!("Error: {}", error);
+    }
+    process::exit(1);
+}
+
+/// Machine-readable form of a failed run's error.
+///
+/// Library errors keep their variant as `kind`; anything else is `other`.
+fn error_report(error: &(dyn std::error::Error + 'static)) -> ErrorReport {
+    match error.downcast_ref::<DomainCheckError>() {
+        Some(e) => e.into(),
+        None => ErrorReport::new("other", error.to_string()),
+    }
+}
+
 /// Validate command line arguments
 fn validate_args(args: &Args) -> Result<(), String> {
     // --list-presets is self-contained, skip other validation
@@ -202,8 +642,55 @@ fn validate_args(args: &Args) -> Result<(), String> {
         return Ok(());
     }
 
+    // --baseline generates its own names from the TLDs in scope
+    if args.baseline {
+        if !args.domains.is_empty()
+            || args.file.is_some()
+            || args.patterns.is_some()
+            || args.recheck_available.is_some()
+            || args.estimate
+        {
+            return Err(
+                "--baseline checks a random name per TLD; don't combine it with domain names, --file, --pattern, --recheck-available or --estimate"
+                    .to_string(),
+            );
+        }
+        // The baseline table is printed as text or --json only
+        if args.csv
+            || args.ndjson
+            || args.bool_output
+            || args.output.is_some()
+            || args.summary_json.is_some()
+            || args.compact_summary
+        {
+            return Err(
+                "--baseline prints a text table or --json; don't combine it with --csv, --ndjson, --bool, --output, --summary-json or --compact-summary"
+                    .to_string(),
+            );
+        }
+    }
+
+    // --recheck-available brings its own domain list
+    if args.recheck_available.is_some() {
+        if !args.domains.is_empty() || args.file.is_some() || args.patterns.is_some() {
+            return Err(
+                "--recheck-available takes its domains from the prior results; don't combine it with domain names, --file or --pattern"
+                    .to_string(),
+            );
+        }
+        if args.resume.is_some() {
+            return Err("Cannot use --recheck-available with --resume".to_string());
+        }
+    }
+
     // Must have either domains, file, or patterns
-    if args.domains.is_empty() && args.file.is_none() && args.patterns.is_none() {
+    if args.domains.is_empty()
+        && args.file.is_none()
+        && args.patterns.is_none()
+        && args.recheck_available.is_none()
+        && !args.show_tlds
+        && !args.baseline
+    {
         return Err(
             "You must specify domain names, a file with --file, or patterns with --pattern"
                 .to_string(),
@@ -216,9 +703,29 @@ fn validate_args(args: &Args) -> Result<(), String> {
     }
 
     // Can't have multiple output formats
-    let output_formats = [args.json, args.csv].iter().filter(|&&x| x).count();
+    let output_formats = [args.json, args.csv, args.ndjson]
+        .iter()
+        .filter(|&&x| x)
+        .count();
     if output_formats > 1 {
-        return Err("Cannot specify multiple output formats (--json, --csv)".to_string());
+        return Err("Cannot specify multiple output formats (--json, --csv, --ndjson)".to_string());
+    }
+
+    if args.json_envelope && !args.json {
+        return Err("--json-envelope requires --json".to_string());
+    }
+
+    // The output file holds NDJSON lines (written as results complete) or a CSV table
+    if args.output.is_some() && !args.ndjson && !args.csv {
+        return Err("--output requires --ndjson or --csv".to_string());
+    }
+
+    // Alias results are grouped with their primary, which needs all results first
+    if args.streaming && args.with_aliases {
+        return Err(
+            "Cannot use --streaming with --with-aliases. Alias results are grouped after the batch completes"
+                .to_string(),
+        );
     }
 
     // Streaming mode doesn't support structured output formats
@@ -229,65 +736,350 @@ fn validate_args(args: &Args) -> Result<(), String> {
         );
     }
 
-    // Validate concurrency
-    if args.concurrency == 0 || args.concurrency > 100 {
-        return Err("Concurrency must be between 1 and 100".to_string());
+    if let Some(fields) = &args.fields {
+        ui::parse_info_fields(fields)?;
     }
 
-    // Check for conflicting flags
-    let tld_sources = [args.tlds.is_some(), args.preset.is_some(), args.all_tlds]
-        .iter()
-        .filter(|&&x| x)
-        .count();
+    if let Some(format) = &args.date_format {
+        format.parse::<DateFormat>()?;
+    }
 
-    if tld_sources > 1 {
+    if let Some(level) = &args.min_confidence {
+        level.parse::<Confidence>()?;
+    }
+
+    // Grouped layouts need every result before printing
+    if let Some(mode) = &args.group_by {
+        let mode = mode.parse::<ui::GroupBy>()?;
+        if args.streaming && mode != ui::GroupBy::None {
+            return Err(
+                "Cannot use --streaming with --group-by status or tld. Groups are printed after the batch completes"
+                    .to_string(),
+            );
+        }
+    }
+
+    for server in args.dns_servers.iter().flatten() {
+        parse_dns_server(server)?;
+    }
+
+    // --bool answers one question with one word; nothing else may write to stdout
+    if args.bool_output
+        && (args.json
+            || args.csv
+            || args.ndjson
+            || args.pretty
+            || args.streaming
+            || args.with_aliases
+            || args.compact_summary)
+    {
         return Err(
-            "Cannot specify multiple TLD sources. Use only one of: -t/--tld, --preset, or --all"
+            "--bool prints only true/false/unknown; don't combine it with other output formats or --streaming"
                 .to_string(),
         );
     }
 
-    Ok(())
-}
-
-/// Print all available TLD presets with their TLDs, then exit.
-fn print_presets() {
-    use console::Style;
+    if args.registrar_allowlist.is_some() && !args.info {
+        return Err(
+            "--registrar-allowlist matches on registrar names; it requires --info".to_string(),
+        );
+    }
 
-    let heading = Style::new().yellow().bold();
-    let name_style = Style::new().green().bold();
-    let count_style = Style::new().cyan();
+    if args.legacy_format && (args.json || args.csv || args.ndjson || args.bool_output) {
+        return Err(
+            "--legacy-format changes text output only; don't combine it with --json, --csv, --ndjson or --bool"
+                .to_string(),
+        );
+    }
 
-    println!();
-    println!("{}", heading.apply_to("Available TLD Presets:"));
-    println!();
+    if args.only_errors && (args.json || args.csv || args.ndjson || args.bool_output) {
+        return Err(
+            "--only-errors filters text output; don't combine it with --json, --csv, --ndjson or --bool"
+                .to_string(),
+        );
+    }
 
-    for preset_name in get_available_presets() {
-        if let Some(tlds) = get_preset_tlds(preset_name) {
-            let tld_list = tlds.join(", ");
-            println!(
-                "  {} {}  {}",
-                name_style.apply_to(format!("{:<12}", preset_name)),
-                count_style.apply_to(format!("({})", tlds.len())),
-                tld_list,
+    // --stream-file never holds the whole list, so anything that needs it is out
+    if args.stream_file {
+        if args.file.is_none() {
+            return Err("--stream-file requires --file".to_string());
+        }
+        if !args.domains.is_empty()
+            || args.patterns.is_some()
+            || args.prefixes.is_some()
+            || args.suffixes.is_some()
+        {
+            return Err(
+                "--stream-file reads only --file; don't combine it with domain arguments, --pattern, --prefix or --suffix"
+                    .to_string(),
+            );
+        }
+        if args.json
+            || args.csv
+            || args.bool_output
+            || args.batch
+            || args.group_by.is_some()
+            || args.resume.is_some()
+            || args.recheck_available.is_some()
+            || args.with_aliases
+            || args.with_variants
+            || args.reject_confusables
+            || args.summary_json.is_some()
+            || args.manifest.is_some()
+            || args.registrar_stats
+            || args.method_stats
+            || args.registrar_consistency
+            || args.dry_run
+            || args.estimate
+        {
+            return Err(
+                "--stream-file prints results as they arrive and keeps none of them; it can't be combined with --json, --csv, --bool, --batch, --group-by, --resume, --recheck-available, --with-aliases, --with-variants, --reject-confusables, --summary-json, --manifest, --registrar-stats, --registrar-consistency, --method-stats, --dry-run or --estimate"
+                    .to_string(),
+            );
+        }
+        if fixed_concurrency(args)?.is_none() {
+            return Err(
+                "--concurrency auto needs the full domain list; give --stream-file a number"
+                    .to_string(),
             );
         }
     }
 
-    println!();
-    println!("Use: domain-check <name> --preset <preset>");
-}
+    if args.file.as_deref() == Some("-") && (args.stream_file || args.passthrough_columns.is_some())
+    {
+        return Err(
+            "--file - reads stdin up front; it can't be used with --stream-file or --passthrough-columns"
+                .to_string(),
+        );
+    }
 
-/// Determine if bootstrap should be enabled.
-///
-/// Bootstrap is now enabled by default. It can be disabled with `--no-bootstrap`.
-fn should_enable_bootstrap(args: &Args, _resolved_tlds: &Option<Vec<String>>) -> bool {
-    if args.no_bootstrap {
-        return false;
+    if args.file.as_deref().is_some_and(is_remote_list)
+        && (args.stream_file || args.passthrough_columns.is_some())
+    {
+        return Err(
+            "--file with a URL downloads the list up front; it can't be used with --stream-file or --passthrough-columns"
+                .to_string(),
+        );
     }
-    // Bootstrap is enabled by default; --bootstrap flag is now a no-op
-    true
-}
+
+    if args.passthrough_columns.is_some() && (args.file.is_none() || !args.csv) {
+        return Err(
+            "--passthrough-columns copies columns from a CSV --file into --csv output; it needs both"
+                .to_string(),
+        );
+    }
+
+    match args.tld_limit {
+        Some(_) if !args.all_tlds => {
+            return Err("--tld-limit only applies with --all".to_string());
+        }
+        Some(0) => return Err("--tld-limit must be at least 1".to_string()),
+        _ => {}
+    }
+    if args.sample_tlds == Some(0) {
+        return Err("--sample-tlds must be at least 1".to_string());
+    }
+
+    if args.race && args.no_whois {
+        return Err("Cannot use --race with --no-whois: racing needs WHOIS".to_string());
+    }
+
+    if let Some(url) = &args.rdap_server {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(format!(
+                "--rdap-server must be an http:// or https:// URL, got '{}'",
+                url
+            ));
+        }
+    }
+
+    if let Some(url) = &args.bootstrap_url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(format!(
+                "--bootstrap-url must be an http:// or https:// URL, got '{}'",
+                url
+            ));
+        }
+        if args.no_bootstrap {
+            return Err("--bootstrap-url has no effect with --no-bootstrap".to_string());
+        }
+    }
+
+    // Validate concurrency
+    fixed_concurrency(args)?;
+    if args.concurrency_per_host == Some(0) {
+        return Err("--concurrency-per-host must be at least 1".to_string());
+    }
+
+    if let Some(batch_size) = args.batch_size {
+        if batch_size == 0 {
+            return Err("--batch-size must be at least 1".to_string());
+        }
+        if args.json
+            || args.csv
+            || args.bool_output
+            || args.batch
+            || args.streaming
+            || args.stream_file
+            || args.group_by.is_some()
+            || args.resume.is_some()
+            || args.with_aliases
+            || args.detect_parking
+            || args.check_redirects
+            || args.summary_json.is_some()
+            || args.registrar_stats
+            || args.registrar_consistency
+            || args.method_stats
+        {
+            return Err(
+                "--batch-size prints each batch's results and drops them; it can't be combined with --json, --csv, --bool, --batch, --streaming, --stream-file, --group-by, --resume, --with-aliases, --detect-parking, --check-redirects, --summary-json, --registrar-stats, --registrar-consistency or --method-stats"
+                    .to_string(),
+            );
+        }
+    }
+
+    if args.progress_json_results && !args.progress_json {
+        return Err("--progress-json-results only applies with --progress-json".to_string());
+    }
+    // Progress events need a known total and an end
+    if args.progress_json && (args.stream_file || args.watch) {
+        return Err(
+            "--progress-json needs the full domain list and a finished run; it can't be combined with --stream-file or --watch"
+                .to_string(),
+        );
+    }
+
+    if args.watch {
+        if args.json
+            || args.csv
+            || args.ndjson
+            || args.bool_output
+            || args.batch_size.is_some()
+            || args.stream_file
+            || args.resume.is_some()
+            || args.baseline
+        {
+            return Err(
+                "--watch runs until interrupted and prints only changes; it can't be combined with --json, --csv, --ndjson, --bool, --batch-size, --stream-file, --resume or --baseline"
+                    .to_string(),
+            );
+        }
+        if let Some(interval) = &args.watch_interval {
+            if !matches!(parse_timeout_string(interval), Ok(secs) if secs > 0) {
+                return Err(format!(
+                    "Invalid --watch-interval '{}'. Use format like '30s', '5m'",
+                    interval
+                ));
+            }
+        }
+    } else if args.watch_interval.is_some() || args.webhook.is_some() {
+        return Err("--watch-interval and --webhook only apply with --watch".to_string());
+    }
+    if let Some(url) = &args.webhook {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(format!(
+                "--webhook must be an http:// or https:// URL, got '{}'",
+                url
+            ));
+        }
+    }
+
+    match args.estimate_latency {
+        Some(_) if !args.estimate => {
+            return Err("--estimate-latency only applies with --estimate".to_string());
+        }
+        Some(0) => return Err("--estimate-latency must be at least 1 ms".to_string()),
+        _ => {}
+    }
+
+    // Check for conflicting flags
+    // Only CLI sources can collide here; they silently override env and config
+    let tld_sources = [
+        args.tlds.is_some(),
+        args.tld_file.is_some(),
+        args.preset.is_some(),
+        args.all_tlds,
+    ]
+    .iter()
+    .filter(|&&x| x)
+    .count();
+
+    if tld_sources > 1 {
+        return Err(
+            "Cannot specify multiple TLD sources. Use only one of: -t/--tld, --tld-file, --preset, or --all"
+                .to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate `domain-check generate` arguments.
+fn validate_generate_args(args: &GenerateArgs) -> Result<(), String> {
+    if args.names.is_empty() && args.patterns.is_empty() {
+        return Err("generate needs base names or at least one --pattern".to_string());
+    }
+    Ok(())
+}
+
+/// Run `domain-check generate`, returning what to print.
+fn run_generate(args: &GenerateArgs) -> Result<String, Box<dyn std::error::Error>> {
+    let config = domain_check_lib::GenerateConfig {
+        patterns: args.patterns.clone(),
+        prefixes: args.prefixes.clone(),
+        suffixes: args.suffixes.clone(),
+        include_bare: true,
+        ascii_only: args.ascii_only,
+    };
+    let names = domain_check_lib::generate_names(&config, &args.names)?.names;
+
+    Ok(if args.count {
+        names.len().to_string()
+    } else if args.json {
+        serde_json::to_string_pretty(&names)?
+    } else {
+        names.join("\n")
+    })
+}
+
+/// Print all available TLD presets with their TLDs, then exit.
+fn print_presets() {
+    use console::Style;
+
+    let heading = Style::new().yellow().bold();
+    let name_style = Style::new().green().bold();
+    let count_style = Style::new().cyan();
+
+    println!();
+    println!("{}", heading.apply_to("Available TLD Presets:"));
+    println!();
+
+    for preset_name in get_available_presets() {
+        if let Some(tlds) = get_preset_tlds(preset_name) {
+            let tld_list = tlds.join(", ");
+            println!(
+                "  {} {}  {}",
+                name_style.apply_to(format!("{:<12}", preset_name)),
+                count_style.apply_to(format!("({})", tlds.len())),
+                tld_list,
+            );
+        }
+    }
+
+    println!();
+    println!("Use: domain-check <name> --preset <preset>");
+}
+
+/// Determine if bootstrap should be enabled.
+///
+/// Bootstrap is now enabled by default. It can be disabled with `--no-bootstrap`.
+fn should_enable_bootstrap(args: &Args, _resolved_tlds: &Option<Vec<String>>) -> bool {
+    if args.no_bootstrap {
+        return false;
+    }
+    // Bootstrap is enabled by default; --bootstrap flag is now a no-op
+    true
+}
 
 /// Main domain checking logic
 async fn run_domain_check(mut args: Args) -> Result<(), Box<dyn std::error::Error>> {
@@ -297,7 +1089,7 @@ async fn run_domain_check(mut args: Args) -> Result<(), Box<dyn std::error::Erro
         if args.verbose {
             println!("Fetching IANA bootstrap registry for full TLD coverage...");
         }
-        if let Err(e) = initialize_bootstrap().await {
+        if let Err(e) = initialize_bootstrap_with(args.bootstrap_url.as_deref()).await {
             if args.verbose {
                 eprintln!(
                     "Warning: Bootstrap fetch failed ({}), using hardcoded TLDs",
@@ -311,12 +1103,175 @@ async fn run_domain_check(mut args: Args) -> Result<(), Box<dyn std::error::Erro
     // Build configuration from CLI args
     let config = build_config(&args)?;
 
+    if args.show_tlds {
+        let tlds = resolved_tlds(&args, &config);
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&tlds)?);
+        } else {
+            for tld in &tlds {
+                println!("{}", tld);
+            }
+        }
+        return Ok(());
+    }
+
     // Propagate resolved config values back to args for display logic.
     // This ensures config/env settings for --info are respected in output formatting.
     args.info = config.detailed_info;
 
+    if args.baseline {
+        return run_baseline(&args, &config).await;
+    }
+
+    if args.stream_file {
+        return run_file_stream(&args, &config).await;
+    }
+
+    // Read once: supplies the domain list here and the extra --csv columns
+    let passthrough = match (&args.file, &args.passthrough_columns) {
+        (Some(path), Some(columns)) => Some(PassthroughColumns::load(path, columns)?),
+        _ => None,
+    };
+
+    // Refuse to mix column layouts before spending time on checks
+    if let (true, true, Some(path)) = (args.csv, args.append, &args.output) {
+        let header = csv_header(&info_fields(&args), passthrough.as_ref());
+        check_appended_csv_header(path, &header)?;
+    }
+
     // Determine domains to check (pass the config instead of rebuilding)
-    let domains = get_domains_to_check(&args, &config).await?;
+    let domains = match &args.recheck_available {
+        Some(path) => {
+            let domains = previously_available(load_prior_results(path)?);
+            if domains.is_empty() {
+                return Err(format!("No available domains in '{}' to re-check", path).into());
+            }
+            domains
+        }
+        None => get_domains_to_check(&args, &config, passthrough.as_ref()).await?,
+    };
+
+    // Variant TLD bundles: check every script form of the name
+    let domains = if args.with_variants {
+        let expanded = domain_check_lib::expand_tld_variants(&domains);
+        if args.verbose && expanded.len() > domains.len() {
+            let added = expanded.len() - domains.len();
+            eprintln!(
+                "🔧 Added {} variant-TLD domain{}",
+                added,
+                if added == 1 { "" } else { "s" }
+            );
+        }
+        expanded
+    } else {
+        domains
+    };
+
+    // Confusable IDNs: drop them up front when rejecting
+    let domains = if args.reject_confusables {
+        domains
+            .into_iter()
+            .filter(
+                |domain| match domain_check_lib::confusable_warning(domain) {
+                    Some(warning) => {
+                        eprintln!("⚠️ Skipping {}: {}", domain, warning);
+                        false
+                    }
+                    None => true,
+                },
+            )
+            .collect::<Vec<_>>()
+    } else {
+        domains
+    };
+    if domains.is_empty() {
+        return Err("No domains left to check after rejecting confusables".into());
+    }
+
+    // Registry-reserved names: always warn, drop them with --skip-reserved
+    for warning in reserved_label_warnings(&domains, args.skip_reserved) {
+        eprintln!("{}", warning);
+    }
+    let domains = if args.skip_reserved {
+        let kept: Vec<String> = domains
+            .into_iter()
+            .filter(|domain| domain_check_lib::reserved_label(domain).is_none())
+            .collect();
+        if kept.is_empty() {
+            return Err("No domains left to check after skipping reserved names".into());
+        }
+        kept
+    } else {
+        domains
+    };
+
+    // Resume: carry over definitive results from a prior run, re-check the rest
+    let input_order = domains.clone();
+    let (domains, carried) = match &args.resume {
+        Some(path) => {
+            let prior = load_prior_results(path)?;
+            let (to_check, carried) = partition_resume(domains, prior);
+            if args.verbose {
+                eprintln!(
+                    "🔧 Resuming from {}: {} carried over, {} to check",
+                    path,
+                    carried.len(),
+                    to_check.len()
+                );
+            }
+            (to_check, carried)
+        }
+        None => (domains, Vec::new()),
+    };
+
+    // --concurrency auto: size it now that the workload is known. A fixed
+    // value is kept as given, with a warning if per-host limits cap it.
+    let endpoints = domain_check_lib::distinct_endpoints(&domains);
+    let config = if fixed_concurrency(&args)?.is_none() {
+        let mut concurrency = domain_check_lib::auto_concurrency(&domains);
+        if let Some(per_host) = args.concurrency_per_host {
+            concurrency = concurrency.min(endpoints.max(1) * per_host);
+        }
+        if args.verbose {
+            eprintln!("🔧 --concurrency auto resolved to {}", concurrency);
+        }
+        config.with_concurrency(concurrency)
+    } else {
+        if let Some(warning) =
+            concurrency_warning(config.concurrency, args.concurrency_per_host, endpoints)
+        {
+            eprintln!("{}", warning);
+        }
+        config
+    };
+
+    // --bool answers for exactly one domain
+    if args.bool_output && domains.len() + carried.len() != 1 {
+        return Err(format!(
+            "--bool checks exactly one domain, but {} would be checked",
+            domains.len() + carried.len()
+        )
+        .into());
+    }
+
+    // Estimate: project the run's size and duration, then exit
+    if args.estimate {
+        let estimate = RunEstimate::new(
+            domains.len(),
+            endpoints,
+            config.concurrency,
+            args.concurrency_per_host,
+            std::time::Duration::from_millis(
+                args.estimate_latency.unwrap_or(DEFAULT_ESTIMATE_LATENCY_MS),
+            ),
+        );
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&estimate)?);
+        } else {
+            println!("{}", estimate);
+        }
+        return Ok(());
+    }
 
     // Dry-run: print domains and exit without checking
     if args.dry_run {
@@ -352,119 +1307,700 @@ async fn run_domain_check(mut args: Args) -> Result<(), Box<dyn std::error::Erro
         }
     }
 
+    // Provenance is recorded before checking so interrupted runs still have it
+    if let Some(path) = &args.manifest {
+        write_manifest(path, &RunManifest::new(&args, &config, &input_order)?)?;
+    }
+
     // Create domain checker
-    let checker = DomainChecker::with_config(config.clone());
+    let checker = DomainChecker::try_with_config(config.clone())?;
+
+    if args.watch {
+        let notify_command = load_file_config(&args)
+            .and_then(|fc| fc.monitoring)
+            .and_then(|m| m.notify_command);
+        return run_watch(
+            &checker,
+            &domains,
+            &args,
+            watch_interval(&args)?,
+            notify_command.as_deref(),
+        )
+        .await;
+    }
 
     // Decide on processing mode based on domain count and user preferences
-    let use_streaming = should_use_streaming(&args, domains.len());
+    let use_streaming = should_use_streaming(&args, domains.len() + carried.len());
 
-    if use_streaming {
+    if let Some(batch_size) = args.batch_size {
+        run_chunked_check(&checker, &domains, batch_size, &args).await?;
+    } else if use_streaming {
         // Streaming mode for multiple domains - show progress and real-time results
-        run_streaming_check(&checker, &domains, &args, &config.tlds).await?;
+        run_streaming_check(&checker, &domains, carried, &args, &config.tlds).await?;
     } else {
         // Batch mode for single domains or when explicitly requested
-        run_batch_check(&checker, &domains, &args).await?;
+        run_batch_check(
+            &checker,
+            &domains,
+            carried,
+            &input_order,
+            &args,
+            passthrough.as_ref(),
+        )
+        .await?;
     }
 
+    write_timing_log(&args, &config)?;
+
     Ok(())
 }
 
-/// Determine whether to use streaming or batch mode
-fn should_use_streaming(args: &Args, domain_count: usize) -> bool {
-    // Force batch mode if explicitly requested
-    if args.batch {
-        return false;
-    }
+/// Time between `--watch` rounds when neither `--watch-interval` nor the
+/// config file's `[monitoring] interval` is set.
+const DEFAULT_WATCH_INTERVAL_SECS: u64 = 300;
+
+/// How long a `--webhook` POST may take before it counts as failed.
+const WEBHOOK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Resolve the `--watch` interval: CLI > config file > default.
+fn watch_interval(args: &Args) -> Result<std::time::Duration, Box<dyn std::error::Error>> {
+    let configured = load_file_config(args)
+        .and_then(|fc| fc.monitoring)
+        .and_then(|m| m.interval);
+    let secs = match args.watch_interval.as_ref().or(configured.as_ref()) {
+        Some(interval) => parse_timeout_string(interval)?,
+        None => DEFAULT_WATCH_INTERVAL_SECS,
+    };
+    Ok(std::time::Duration::from_secs(secs.max(1)))
+}
 
-    // Force streaming mode if explicitly requested
-    if args.streaming {
-        return true;
+/// Last known status of each watched domain.
+#[derive(Debug, Default)]
+struct WatchState {
+    last: std::collections::HashMap<String, Option<bool>>,
+}
+
+impl WatchState {
+    /// Record one round of results and return the domains that just became
+    /// available. The first round only establishes each domain's status.
+    fn observe(&mut self, results: &[domain_check_lib::DomainResult]) -> Vec<String> {
+        results
+            .iter()
+            .filter_map(|result| {
+                let previous = self.last.insert(result.domain.clone(), result.available);
+                match previous {
+                    Some(was) if was != Some(true) && result.available == Some(true) => {
+                        Some(result.domain.clone())
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
     }
+}
 
-    // Use streaming for multiple domains unless in JSON/CSV mode
-    if domain_count > 1 && !args.json && !args.csv {
-        return true;
+/// JSON body POSTed to `--webhook` when a watched domain becomes available.
+#[derive(serde::Serialize)]
+struct WebhookEvent<'a> {
+    domain: &'a str,
+    event: &'static str,
+    at: String,
+}
+
+/// POST an `available` event for `domain` to `url`.
+async fn post_webhook(url: &str, domain: &str) -> Result<(), String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let event = WebhookEvent {
+        domain,
+        event: "available",
+        at: domain_check_lib::RegistryDate::from_unix_timestamp(now)
+            .format(&domain_check_lib::DateFormat::Iso),
+    };
+    let body = serde_json::to_string(&event).map_err(|e| e.to_string())?;
+
+    let response = domain_check_lib::shared_http_client()
+        .map_err(|e| e.to_string())?
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("server answered {}", response.status()));
     }
+    Ok(())
+}
 
-    // Default to batch mode for single domains or structured output
-    false
+/// Run the config file's `[monitoring] notify_command` through the shell
+/// for a domain that became available, with the domain in
+/// `DOMAIN_CHECK_DOMAIN`.
+async fn run_notify_command(command: &str, domain: &str) -> Result<(), String> {
+    let mut shell = if cfg!(windows) {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    let status = shell
+        .arg(command)
+        .env("DOMAIN_CHECK_DOMAIN", domain)
+        .stdin(std::process::Stdio::null())
+        .status()
+        .await
+        .map_err(|e| format!("could not start: {}", e))?;
+    if !status.success() {
+        return Err(format!("exited with {}", status));
+    }
+    Ok(())
 }
 
-/// Run domain check in streaming mode with real-time progress
-async fn run_streaming_check(
+/// Re-check `domains` every `interval` until interrupted, reporting each
+/// domain that becomes available. A failed round, webhook or notify command
+/// is logged and watching continues.
+async fn run_watch(
     checker: &DomainChecker,
     domains: &[String],
     args: &Args,
-    tlds: &Option<Vec<String>>,
+    interval: std::time::Duration,
+    notify_command: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    use futures_util::StreamExt;
+    let mut state = WatchState::default();
+    let mut first_round = true;
 
-    // Show initial progress message
-    if args.pretty {
-        ui::print_header(domains.len(), checker.config().concurrency, args);
-    } else if args.verbose {
-        println!(
-            "🔍 Checking {} domains with concurrency: {}",
-            domains.len(),
-            checker.config().concurrency
-        );
+    loop {
+        let results = match checker.check_domains(domains).await {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!(
+                    "⚠️ Watch round failed: {} (retrying in {}s)",
+                    e,
+                    interval.as_secs()
+                );
+                tokio::time::sleep(interval).await;
+                continue;
+            }
+        };
+        let became_available = state.observe(&results);
 
-        if args.debug {
-            println!("🔧 Domains: {}", domains.join(", "));
+        if first_round {
+            let available = results.iter().filter(|r| r.available == Some(true)).count();
+            eprintln!(
+                "👀 Watching {} domain{} every {}s ({} available now)",
+                domains.len(),
+                if domains.len() == 1 { "" } else { "s" },
+                interval.as_secs(),
+                available
+            );
+            first_round = false;
         }
 
-        // Show TLD information
-        if !args.json && !args.csv {
-            if args.all_tlds {
-                let tld_count = get_all_known_tlds().len();
-                println!("🌐 Checking against all {} known TLDs", tld_count);
-            } else if let Some(preset) = &args.preset {
-                if let Some(tld_list) = tlds {
-                    println!("🎯 Using '{}' preset ({} TLDs)", preset, tld_list.len());
-                } else {
-                    println!("🎯 Using '{}' preset", preset);
+        for domain in &became_available {
+            println!("🟢 {} is now available", domain);
+            if let Some(url) = &args.webhook {
+                if let Err(e) = post_webhook(url, domain).await {
+                    eprintln!("⚠️ Webhook for {} failed: {}", domain, e);
+                }
+            }
+            if let Some(command) = notify_command {
+                if let Err(e) = run_notify_command(command, domain).await {
+                    eprintln!("⚠️ notify_command for {} failed: {}", domain, e);
                 }
             }
         }
 
-        println!(); // Empty line for readability
+        tokio::time::sleep(interval).await;
     }
+}
 
-    // Track statistics for summary
-    let mut available_count = 0;
-    let mut taken_count = 0;
-    let mut unknown_count = 0;
-    let mut results = Vec::new();
-    let mut completed = 0usize;
-    let total = domains.len();
+/// Write one streamed result in the selected text layout.
+fn write_stream_result(
+    out: &mut impl Write,
+    result: &domain_check_lib::DomainResult,
+    args: &Args,
+    fields: &[ui::InfoField],
+    counter: Option<(usize, usize)>,
+) -> std::io::Result<()> {
+    if args.only_errors && result.available.is_some() {
+        return Ok(());
+    }
+    if args.legacy_format {
+        ui::write_result_legacy(out, result, args.pretty, args.info, fields, args.debug)
+    } else if args.pretty {
+        ui::write_result(
+            out,
+            result,
+            args.info,
+            fields,
+            args.debug,
+            counter,
+            registrar_allowlist(args),
+        )
+    } else {
+        ui::write_result_default(
+            out,
+            result,
+            args.info,
+            fields,
+            args.debug,
+            counter,
+            registrar_allowlist(args),
+        )
+    }
+}
 
-    let start_time = std::time::Instant::now();
+/// Trim --registrar-allowlist entries and drop empty ones, so a trailing
+/// comma doesn't add an entry that matches every registrar.
+fn clean_registrar_allowlist(entries: Vec<String>) -> Vec<String> {
+    entries
+        .into_iter()
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
 
-    // Process each domain individually to preserve context
-    let domain_futures = domains.iter().map(|domain| {
-        let domain = domain.clone();
-        let checker = checker.clone();
-        async move {
-            match checker.check_domain(&domain).await {
-                Ok(result) => result,
-                Err(e) => domain_check_lib::DomainResult {
-                    domain: domain.clone(),
-                    available: None,
-                    info: None,
-                    check_duration: None,
-                    method_used: domain_check_lib::CheckMethod::Unknown,
-                    error_message: Some(e.to_string()),
-                },
-            }
-        }
-    });
+/// Registrars from --registrar-allowlist (empty when not given).
+fn registrar_allowlist(args: &Args) -> &[String] {
+    args.registrar_allowlist.as_deref().unwrap_or_default()
+}
 
-    // Use buffer_unordered to maintain concurrency while preserving domain context
-    let mut stream =
-        futures_util::stream::iter(domain_futures).buffer_unordered(checker.config().concurrency);
+/// Exit quietly with status 0 when stdout has been closed by the reader
+/// (e.g. `domain-check --all | head`); other write errors are returned.
+fn exit_on_broken_pipe(result: std::io::Result<()>) -> std::io::Result<()> {
+    match result {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => process::exit(0),
+        other => other,
+    }
+}
+
+/// HTTP clients for --detect-parking and --check-redirects, built once per
+/// run and shared by every streamed check.
+#[derive(Clone)]
+struct EnrichmentClients {
+    parking: Option<reqwest::Client>,
+    redirects: Option<reqwest::Client>,
+}
+
+impl EnrichmentClients {
+    fn for_run(
+        args: &Args,
+        config: &CheckConfig,
+    ) -> Result<Self, domain_check_lib::DomainCheckError> {
+        Ok(Self {
+            parking: args
+                .detect_parking
+                .then(|| domain_check_lib::parking_client(config))
+                .transpose()?,
+            redirects: args
+                .check_redirects
+                .then(|| domain_check_lib::redirect_client(config))
+                .transpose()?,
+        })
+    }
+}
+
+/// Check one domain for a streaming run, turning a failed check into an
+/// unknown result so every domain yields exactly one line.
+///
+/// The check waits for a permit from `limiter`, which carries any
+/// --concurrency-ramp warm-up.
+async fn check_or_unknown(
+    limiter: std::sync::Arc<tokio::sync::Semaphore>,
+    checker: DomainChecker,
+    domain: String,
+    enrichment: EnrichmentClients,
+) -> domain_check_lib::DomainResult {
+    let launched = std::time::Instant::now();
+    let _permit = limiter.acquire().await.ok();
+    let started = std::time::Instant::now();
+    let result = match checker.check_domain(&domain).await {
+        Ok(mut result) => {
+            if result.available == Some(false) {
+                if let Some(client) = &enrichment.parking {
+                    result.parked = domain_check_lib::detect_parking(client, &domain).await;
+                }
+                if let Some(client) = &enrichment.redirects {
+                    result.redirects_to = domain_check_lib::detect_redirect(client, &domain).await;
+                }
+            }
+            result
+        }
+        Err(e) => domain_check_lib::DomainResult::from_error(domain, &e),
+    };
+    if let Some(log) = &checker.config().timing_log {
+        log.record(
+            &result.domain,
+            launched,
+            started,
+            result.method_used.clone(),
+            checker.config().rdap_server_override.as_deref(),
+        );
+    }
+    result
+}
+
+/// Check `domains` as they are pulled from the iterator, at most
+/// `concurrency` at a time, so only in-flight domains are held in memory.
+fn lazy_check_stream<I, F, Fut>(
+    domains: I,
+    concurrency: usize,
+    check: F,
+) -> impl futures_util::Stream<Item = domain_check_lib::DomainResult>
+where
+    I: Iterator<Item = String>,
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = domain_check_lib::DomainResult>,
+{
+    use futures_util::StreamExt;
+
+    futures_util::stream::iter(domains)
+        .map(check)
+        .buffer_unordered(concurrency.max(1))
+}
+
+/// Check a --file lazily (--stream-file).
+///
+/// Lines are read, expanded with the TLDs in scope and checked as the stream
+/// pulls them; each result is printed (and written to --ndjson or
+/// --failures-file) and then dropped. Domains are not deduplicated and the
+/// large-run confirmation is skipped, since the total isn't known up front.
+async fn run_file_stream(
+    args: &Args,
+    config: &CheckConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use futures_util::StreamExt;
+
+    let path = args
+        .file
+        .as_deref()
+        .ok_or("--stream-file requires --file")?;
+    let mut reader = DomainFileReader::open(path)?;
+
+    let tlds = expansion_tlds(args, config);
+    let exclude = config.exclude_tlds.clone();
+    let include_only: Option<Vec<String>> = args
+        .include_only_tlds
+        .as_ref()
+        .map(|tlds| tlds.iter().map(|t| normalize_cli_tld(t)).collect());
+    let skip_reserved = args.skip_reserved;
+    let preserve_case = args.preserve_case;
+    let domains = reader
+        .by_ref()
+        .flat_map(move |entry| domain_check_lib::expand_domain_inputs(&[entry], &tlds))
+        .filter(move |d| !domain_has_tld(d, &exclude))
+        .filter(move |d| {
+            include_only
+                .as_deref()
+                .is_none_or(|only| domain_has_tld(d, only))
+        })
+        .filter(move |d| !skip_reserved || domain_check_lib::reserved_label(d).is_none())
+        .map(move |d| if preserve_case { d } else { d.to_lowercase() });
+
+    let ndjson_stdout = args.ndjson && args.output.is_none();
+    let mut ndjson = if args.ndjson {
+        Some(NdjsonWriter::create(args.output.as_deref(), args.append)?)
+    } else {
+        None
+    };
+    let mut failures = match &args.failures_file {
+        Some(path) => Some(std::io::BufWriter::new(
+            std::fs::File::create(path)
+                .map_err(|e| format!("Failed to write failures file '{}': {}", path, e))?,
+        )),
+        None => None,
+    };
+
+    if args.verbose && !ndjson_stdout {
+        println!(
+            "🔍 Streaming domains from {} with concurrency: {}",
+            path, config.concurrency
+        );
+        println!();
+    }
+
+    let fields = info_fields(args);
+    let date_format = parse_date_format(args);
+    let min_confidence = parse_min_confidence(args);
+    let (mut available, mut taken, mut unknown, mut suppressed) = (0usize, 0usize, 0usize, 0usize);
+    // Kept only for the --only-errors breakdown
+    let mut errored = Vec::new();
+    let start_time = std::time::Instant::now();
+    let mut stdout = std::io::stdout();
+    let checker = DomainChecker::try_with_config(config.clone())?;
+    let enrichment = EnrichmentClients::for_run(args, config)?;
+
+    // Scoped so the reader's invalid-line count can be read afterwards
+    {
+        let limiter =
+            domain_check_lib::concurrency_limiter(config.concurrency, config.concurrency_ramp);
+        let stream = lazy_check_stream(domains, config.concurrency, |domain| {
+            check_or_unknown(limiter.clone(), checker.clone(), domain, enrichment.clone())
+        });
+        let mut stream = std::pin::pin!(stream);
+
+        while let Some(mut result) = stream.next().await {
+            // --min-confidence only filters what is shown
+            if result.available.is_none() {
+                if let Some(out) = failures.as_mut() {
+                    writeln!(out, "{}", result.domain)?;
+                }
+            }
+            if below_min_confidence(&result, min_confidence) {
+                suppressed += 1;
+                continue;
+            }
+            match result.available {
+                Some(true) => available += 1,
+                Some(false) => taken += 1,
+                None => {
+                    unknown += 1;
+                    if args.only_errors {
+                        errored.push(result.clone());
+                    }
+                }
+            }
+
+            if args.check_confusables {
+                annotate_confusable(&mut result);
+            }
+            if let Some(format) = &date_format {
+                normalize_result_dates(&mut result, format, args.debug);
+            }
+            if let Some(writer) = ndjson.as_mut() {
+                writer.write_result(&result)?;
+            }
+
+            if !ndjson_stdout {
+                exit_on_broken_pipe(write_stream_result(
+                    &mut stdout,
+                    &result,
+                    args,
+                    &fields,
+                    None,
+                ))?;
+            }
+        }
+    }
+
+    if let Some(mut out) = failures {
+        out.flush()?;
+    }
+
+    let total = available + taken + unknown;
+    if total + suppressed == 0 {
+        return Err("No valid domains found in the file.".into());
+    }
+    if total > 1 && !ndjson_stdout && !args.no_summary {
+        exit_on_broken_pipe(writeln!(stdout))?;
+        exit_on_broken_pipe(ui::write_summary(
+            &mut stdout,
+            total,
+            available,
+            taken,
+            unknown,
+            start_time.elapsed(),
+        ))?;
+        if args.only_errors {
+            ui::print_error_breakdown(&errored);
+        }
+    }
+    if reader.invalid > 0 {
+        eprintln!("⚠️ Skipped {} invalid entries in {}", reader.invalid, path);
+    }
+    report_suppressed(suppressed, min_confidence);
+    if args.endpoint_report {
+        ui::print_endpoint_report(&checker.endpoint_stats());
+    }
+    write_timing_log(args, config)?;
+
+    if args.compact_summary {
+        exit_on_broken_pipe(writeln!(
+            stdout,
+            "{}",
+            RunSummary::from_counts(available, taken, unknown, start_time.elapsed()).compact_line()
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Check one random, unregistered name per TLD in scope and print how each
+/// registry answered (--baseline).
+async fn run_baseline(args: &Args, config: &CheckConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let tlds = expansion_tlds(args, config).unwrap_or_else(|| vec!["com".to_string()]);
+    let label = baseline_label();
+    let domains: Vec<String> = tlds
+        .iter()
+        .map(|tld| format!("{}.{}", label, tld.trim().trim_start_matches('.')))
+        .collect();
+
+    if !args.json {
+        eprintln!(
+            "Probing {} TLD{} with {}",
+            tlds.len(),
+            if tlds.len() == 1 { "" } else { "s" },
+            label
+        );
+    }
+
+    let checker = DomainChecker::try_with_config(config.clone())?;
+    let results = checker.check_domains(&domains).await?;
+    let rows = ui::baseline_rows(&results);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else {
+        ui::print_baseline_table(&rows);
+    }
+    Ok(())
+}
+
+/// A label no registry should have registered, unique per run.
+fn baseline_label() -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    format!("dc-baseline-{:08x}{:x}", nanos, std::process::id())
+}
+
+/// Determine whether to use streaming or batch mode
+fn should_use_streaming(args: &Args, domain_count: usize) -> bool {
+    // Force batch mode if explicitly requested (or needed for alias or --group-by grouping)
+    let explicit_grouping = args.group_by.is_some() && group_by(args) != ui::GroupBy::None;
+    if args.batch || args.with_aliases || args.bool_output || explicit_grouping {
+        return false;
+    }
+
+    // Force streaming mode if explicitly requested
+    if args.streaming {
+        return true;
+    }
+
+    // Use streaming for multiple domains unless in JSON/CSV mode
+    if domain_count > 1 && !args.json && !args.csv {
+        return true;
+    }
+
+    // Default to batch mode for single domains or structured output
+    false
+}
+
+/// Run domain check in streaming mode with real-time progress
+///
+/// `carried` holds results resumed from a prior run; they are shown first
+/// and included in the summary, but not re-checked.
+async fn run_streaming_check(
+    checker: &DomainChecker,
+    domains: &[String],
+    carried: Vec<domain_check_lib::DomainResult>,
+    args: &Args,
+    tlds: &Option<Vec<String>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use futures_util::StreamExt;
+
+    let total = domains.len() + carried.len();
+    let fields = info_fields(args);
+
+    // NDJSON goes to the --output file (alongside normal text) or replaces stdout text
+    let ndjson_stdout = args.ndjson && args.output.is_none();
+    let mut ndjson = if args.ndjson {
+        Some(NdjsonWriter::create(args.output.as_deref(), args.append)?)
+    } else {
+        None
+    };
+
+    // Show initial progress message
+    if ndjson_stdout {
+        // Machine-readable output only
+    } else if args.pretty {
+        ui::print_header(total, checker.config().concurrency, args);
+    } else if args.verbose {
+        println!(
+            "🔍 Checking {} domains with concurrency: {}",
+            domains.len(),
+            checker.config().concurrency
+        );
+
+        if args.debug {
+            println!("🔧 Domains: {}", domains.join(", "));
+        }
+
+        // Show TLD information
+        if !args.json && !args.csv {
+            if args.all_tlds {
+                let tld_count = get_all_known_tlds().len();
+                println!("🌐 Checking against all {} known TLDs", tld_count);
+            } else if let Some(preset) = &args.preset {
+                if let Some(tld_list) = tlds {
+                    println!("🎯 Using '{}' preset ({} TLDs)", preset, tld_list.len());
+                } else {
+                    println!("🎯 Using '{}' preset", preset);
+                }
+            }
+        }
+
+        println!(); // Empty line for readability
+    }
+
+    // Track statistics for summary
+    let mut available_count = 0;
+    let mut taken_count = 0;
+    let mut unknown_count = 0;
+    let mut results = Vec::new();
+    let mut completed = 0usize;
+
+    let start_time = std::time::Instant::now();
+    let mut stdout = std::io::stdout();
+    let mut progress = ProgressReporter::new(total, args);
+
+    // Show carried-over results first so the counter reflects the whole run
+    let carried = futures_util::stream::iter(carried);
+
+    // Process each domain individually to preserve context
+    let date_format = parse_date_format(args);
+    let min_confidence = parse_min_confidence(args);
+    let mut suppressed = 0usize;
+    // Results hidden by --min-confidence, still written to --summary-json / --failures-file
+    let mut hidden = Vec::new();
+    let enrichment = EnrichmentClients::for_run(args, checker.config())?;
+    let limiter = domain_check_lib::concurrency_limiter(
+        checker.config().concurrency,
+        checker.config().concurrency_ramp,
+    );
+    let domain_futures = domains.iter().map(|domain| {
+        check_or_unknown(
+            limiter.clone(),
+            checker.clone(),
+            domain.clone(),
+            enrichment.clone(),
+        )
+    });
+
+    // Use buffer_unordered to maintain concurrency while preserving domain context
+    let mut stream = carried.chain(
+        futures_util::stream::iter(domain_futures).buffer_unordered(checker.config().concurrency),
+    );
+
+    // Process results as they complete
+    while let Some(domain_result) = stream.next().await {
+        if let Some(progress) = progress.as_mut() {
+            progress.record(&domain_result);
+        }
+        if below_min_confidence(&domain_result, min_confidence) {
+            completed += 1;
+            suppressed += 1;
+            hidden.push(domain_result);
+            continue;
+        }
 
-    // Process results as they complete
-    while let Some(domain_result) = stream.next().await {
         // Update statistics
         match domain_result.available {
             Some(true) => available_count += 1,
@@ -476,44 +2012,264 @@ async fn run_streaming_check(
 
         completed += 1;
 
+        let mut domain_result = domain_result;
+        if args.check_confusables {
+            annotate_confusable(&mut domain_result);
+        }
+        if let Some(format) = &date_format {
+            normalize_result_dates(&mut domain_result, format, args.debug);
+        }
+
+        // Persist before displaying so an interrupted run keeps every shown result
+        if let Some(writer) = ndjson.as_mut() {
+            writer.write_result(&domain_result)?;
+        }
+
         // Show result immediately
         let counter = if total > 1 {
             Some((completed, total))
         } else {
             None
         };
-        if args.pretty {
-            ui::print_result(&domain_result, args.info, args.debug, counter);
-        } else {
-            ui::print_result_default(&domain_result, args.info, args.debug, counter);
+        if !ndjson_stdout {
+            exit_on_broken_pipe(write_stream_result(
+                &mut stdout,
+                &domain_result,
+                args,
+                &fields,
+                counter,
+            ))?;
         }
         results.push(domain_result);
     }
 
     let duration = start_time.elapsed();
+    if let Some(progress) = &progress {
+        progress.finish(duration);
+    }
 
     // Show final summary for multiple domains
-    if domains.len() > 1 && !args.json && !args.csv {
-        println!();
-        ui::print_summary(
+    if total > 1 && !args.json && !args.csv && !ndjson_stdout && !args.no_summary {
+        exit_on_broken_pipe(writeln!(stdout))?;
+        exit_on_broken_pipe(ui::write_summary(
+            &mut stdout,
             results.len(),
             available_count,
             taken_count,
             unknown_count,
             duration,
-        );
+        ))?;
+        if args.method_stats || args.verbose {
+            ui::print_method_stats(&results);
+        }
+        if args.only_errors {
+            ui::print_error_breakdown(&results);
+        }
+    }
+
+    if args.registrar_stats && !ndjson_stdout {
+        ui::print_registrar_stats(&results);
+    }
+
+    if args.registrar_consistency && !ndjson_stdout {
+        ui::print_registrar_consistency(&results);
+    }
+
+    report_suppressed(suppressed, min_confidence);
+
+    ui::warn_fd_exhaustion(&results, checker.config().concurrency);
+    ui::warn_wildcard_tlds(&checker.wildcard_tlds());
+    if args.endpoint_report {
+        ui::print_endpoint_report(&checker.endpoint_stats());
+    }
+
+    if args.recheck_available.is_some() {
+        print_recheck_report(&results, ndjson_stdout);
+    }
+
+    if args.summary_json.is_some() || args.failures_file.is_some() {
+        let all: Vec<_> = results.iter().chain(&hidden).cloned().collect();
+        if let Some(path) = &args.summary_json {
+            write_summary_json(path, &all, duration)?;
+        }
+        if let Some(path) = &args.failures_file {
+            write_failures_file(path, &all)?;
+        }
+    }
+
+    if args.compact_summary {
+        exit_on_broken_pipe(writeln!(
+            std::io::stdout(),
+            "{}",
+            RunSummary::from_results(&results, duration).compact_line()
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Run domain check in batches of `--batch-size` (see
+/// `DomainChecker::check_domains_chunked`).
+///
+/// Each batch's results are printed (and written with `--ndjson` /
+/// `--failures-file`) when the batch completes, followed by a running tally
+/// on stderr; only the counts are kept between batches.
+async fn run_chunked_check(
+    checker: &DomainChecker,
+    domains: &[String],
+    batch_size: usize,
+    args: &Args,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ndjson_stdout = args.ndjson && args.output.is_none();
+    let mut ndjson = if args.ndjson {
+        Some(NdjsonWriter::create(args.output.as_deref(), args.append)?)
+    } else {
+        None
+    };
+    let mut failures = match &args.failures_file {
+        Some(path) => Some(std::io::BufWriter::new(
+            std::fs::File::create(path)
+                .map_err(|e| format!("Failed to write failures file '{}': {}", path, e))?,
+        )),
+        None => None,
+    };
+
+    let fields = info_fields(args);
+    let date_format = parse_date_format(args);
+    let min_confidence = parse_min_confidence(args);
+    let batches = domains.len().div_ceil(batch_size);
+    let (mut available, mut taken, mut unknown, mut suppressed) = (0usize, 0usize, 0usize, 0usize);
+    let mut batch = 0usize;
+    // Kept only for the --only-errors breakdown
+    let mut errored = Vec::new();
+    // The callback can't return errors; the first one stops output and is returned after the run
+    let mut write_error: Option<Box<dyn std::error::Error>> = None;
+    let mut stdout = std::io::stdout();
+    let start_time = std::time::Instant::now();
+    let mut progress = ProgressReporter::new(domains.len(), args);
+
+    checker
+        .check_domains_chunked(domains, batch_size, |results| {
+            batch += 1;
+            for result in results {
+                if let Some(progress) = progress.as_mut() {
+                    progress.record(result);
+                }
+                // --min-confidence only filters what is shown
+                if let (None, Some(out), None) = (result.available, failures.as_mut(), &write_error)
+                {
+                    if let Err(e) = writeln!(out, "{}", result.domain) {
+                        write_error = Some(e.into());
+                    }
+                }
+                if below_min_confidence(result, min_confidence) {
+                    suppressed += 1;
+                    continue;
+                }
+                match result.available {
+                    Some(true) => available += 1,
+                    Some(false) => taken += 1,
+                    None => unknown += 1,
+                }
+                if write_error.is_some() {
+                    continue;
+                }
+
+                let mut result = result.clone();
+                if args.check_confusables {
+                    annotate_confusable(&mut result);
+                }
+                if let Some(format) = &date_format {
+                    normalize_result_dates(&mut result, format, args.debug);
+                }
+                let written = (|| -> Result<(), Box<dyn std::error::Error>> {
+                    if let Some(writer) = ndjson.as_mut() {
+                        writer.write_result(&result)?;
+                    }
+                    if !ndjson_stdout {
+                        exit_on_broken_pipe(write_stream_result(
+                            &mut stdout,
+                            &result,
+                            args,
+                            &fields,
+                            None,
+                        ))?;
+                    }
+                    Ok(())
+                })();
+                if let Err(e) = written {
+                    write_error = Some(e);
+                }
+                if args.only_errors && result.available.is_none() {
+                    errored.push(result);
+                }
+            }
+            // --progress-json owns stderr
+            if progress.is_none() {
+                eprintln!(
+                    "{}",
+                    ui::format_batch_progress(batch, batches, available, taken, unknown)
+                );
+            }
+        })
+        .await?;
+
+    if let Some(e) = write_error {
+        return Err(e);
+    }
+    if let Some(mut out) = failures {
+        out.flush()?;
+    }
+    if let Some(progress) = &progress {
+        progress.finish(start_time.elapsed());
+    }
+
+    let total = available + taken + unknown;
+    if total > 1 && !ndjson_stdout && !args.no_summary {
+        exit_on_broken_pipe(writeln!(stdout))?;
+        exit_on_broken_pipe(ui::write_summary(
+            &mut stdout,
+            total,
+            available,
+            taken,
+            unknown,
+            start_time.elapsed(),
+        ))?;
+        if args.only_errors {
+            ui::print_error_breakdown(&errored);
+        }
+    }
+    report_suppressed(suppressed, min_confidence);
+    ui::warn_wildcard_tlds(&checker.wildcard_tlds());
+    if args.endpoint_report {
+        ui::print_endpoint_report(&checker.endpoint_stats());
+    }
+
+    if args.compact_summary {
+        exit_on_broken_pipe(writeln!(
+            stdout,
+            "{}",
+            RunSummary::from_counts(available, taken, unknown, start_time.elapsed()).compact_line()
+        ))?;
     }
 
     Ok(())
 }
 
 /// Run domain check in batch mode (collect all results first)
+///
+/// Results resumed from a prior run (`carried`) are merged back in
+/// `input_order` alongside the freshly checked ones.
 async fn run_batch_check(
     checker: &DomainChecker,
     domains: &[String],
+    carried: Vec<domain_check_lib::DomainResult>,
+    input_order: &[String],
     args: &Args,
+    passthrough: Option<&PassthroughColumns>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let is_structured = args.json || args.csv;
+    let ndjson_stdout = args.ndjson && args.output.is_none();
+    let is_structured = args.json || args.csv || ndjson_stdout || args.bool_output;
 
     // Show header (pretty only — default mode lets the spinner + summary speak)
     if args.pretty && !is_structured && domains.len() > 1 {
@@ -541,20 +2297,1075 @@ async fn run_batch_check(
     let start_time = std::time::Instant::now();
 
     // Check all domains (concurrent under the hood)
-    let results = checker.check_domains(domains).await?;
+    let (mut results, progress) = match ProgressReporter::new(domains.len(), args) {
+        Some(mut progress) => {
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            let reporter = tokio::spawn(async move {
+                while let Some(event) = rx.recv().await {
+                    if let CheckEvent::Completed { result } = event {
+                        progress.record(&result);
+                    }
+                }
+                progress
+            });
+            let results = checker.check_domains_with_events(domains, tx).await?;
+            (results, reporter.await.ok())
+        }
+        None => (checker.check_domains(domains).await?, None),
+    };
+
+    if !carried.is_empty() {
+        results = merge_resumed_results(input_order, carried, results);
+    }
+
+    if args.check_confusables {
+        results.iter_mut().for_each(annotate_confusable);
+    }
+
+    if args.detect_parking {
+        domain_check_lib::enrich_parking(&mut results, checker.config()).await;
+    }
+
+    if args.check_redirects {
+        domain_check_lib::enrich_redirects(&mut results, checker.config()).await;
+    }
+
+    // Follow up taken domains with their configured TLD aliases
+    if args.with_aliases {
+        let aliases = &checker.config().tld_aliases;
+        if aliases.is_empty() {
+            eprintln!("⚠️ --with-aliases set but no [tld_aliases] configured");
+        } else {
+            let alias_targets = collect_alias_domains(&results, aliases);
+            if !alias_targets.is_empty() {
+                if args.verbose {
+                    eprintln!(
+                        "🔧 Checking {} alias domain{} for taken results",
+                        alias_targets.len(),
+                        if alias_targets.len() == 1 { "" } else { "s" }
+                    );
+                }
+                let alias_results = checker.check_domains(&alias_targets).await?;
+                results = group_with_aliases(results, alias_results, aliases);
+            }
+        }
+    }
+
+    if let Some(format) = parse_date_format(args) {
+        for result in &mut results {
+            normalize_result_dates(result, &format, args.debug);
+        }
+    }
 
     let duration = start_time.elapsed();
+    if let Some(progress) = &progress {
+        progress.finish(duration);
+    }
 
     // Stop spinner before printing results
     if let Some(s) = spinner {
         s.stop().await;
     }
 
-    // Display results based on format
-    display_results(&results, args, duration)?;
+    ui::warn_fd_exhaustion(&results, checker.config().concurrency);
+    ui::warn_wildcard_tlds(&checker.wildcard_tlds());
+    if args.endpoint_report {
+        ui::print_endpoint_report(&checker.endpoint_stats());
+    }
 
-    Ok(())
-}
+    if args.recheck_available.is_some() {
+        print_recheck_report(&results, is_structured);
+    }
+
+    // Files cover every result; --min-confidence only filters what is shown
+    if let Some(path) = &args.summary_json {
+        write_summary_json(path, &results, duration)?;
+    }
+
+    if let Some(path) = &args.failures_file {
+        write_failures_file(path, &results)?;
+    }
+
+    let min_confidence = parse_min_confidence(args);
+    let (results, suppressed) = filter_min_confidence(results, min_confidence);
+    report_suppressed(suppressed, min_confidence);
+
+    // NDJSON goes to the --output file (alongside normal text) or replaces stdout text
+    if args.ndjson {
+        let mut writer = NdjsonWriter::create(args.output.as_deref(), args.append)?;
+        for result in &results {
+            writer.write_result(result)?;
+        }
+    }
+
+    // Display results based on format
+    if !ndjson_stdout {
+        display_results(&results, args, checker.config(), duration, passthrough)?;
+    }
+
+    if args.compact_summary {
+        exit_on_broken_pipe(writeln!(
+            std::io::stdout(),
+            "{}",
+            RunSummary::from_results(&results, duration).compact_line()
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Add a confusable-script warning to a result's note, if the domain has one.
+fn annotate_confusable(result: &mut domain_check_lib::DomainResult) {
+    if let Some(warning) = domain_check_lib::confusable_warning(&result.domain) {
+        let warning = format!("confusable: {}", warning);
+        result.note = Some(match result.note.take() {
+            Some(note) => format!("{}; {}", note, warning),
+            None => warning,
+        });
+    }
+}
+
+/// Date format selected with --date-format (already validated).
+fn parse_date_format(args: &Args) -> Option<DateFormat> {
+    args.date_format
+        .as_deref()
+        .and_then(|format| format.parse().ok())
+}
+
+/// Parse a --dns-server address, defaulting to port 53 when none is given.
+fn parse_dns_server(server: &str) -> Result<std::net::SocketAddr, String> {
+    let server = server.trim();
+    server
+        .parse::<std::net::SocketAddr>()
+        .or_else(|_| {
+            server
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .parse::<std::net::IpAddr>()
+                .map(|ip| std::net::SocketAddr::new(ip, 53))
+        })
+        .map_err(|_| {
+            format!(
+                "Invalid --dns-server '{}': use an IP address, optionally with a port (e.g. 1.1.1.1 or 10.0.0.53:5353)",
+                server
+            )
+        })
+}
+
+/// The --concurrency value, or None for `auto`.
+fn fixed_concurrency(args: &Args) -> Result<Option<usize>, String> {
+    if args.concurrency.eq_ignore_ascii_case("auto") {
+        return Ok(None);
+    }
+    match args.concurrency.parse::<usize>() {
+        Ok(n) if (1..=100).contains(&n) => Ok(Some(n)),
+        _ => Err("Concurrency must be between 1 and 100, or \"auto\"".to_string()),
+    }
+}
+
+/// Round trip assumed by --estimate when --estimate-latency isn't given.
+const DEFAULT_ESTIMATE_LATENCY_MS: u64 = 500;
+
+/// `--estimate` projection of a run's size and wall-clock time.
+///
+/// Checks run in waves of `concurrency` (further capped by per-host limits),
+/// each taking roughly one round trip. The upper bound doubles that to allow
+/// for slow registries and WHOIS fallbacks.
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct RunEstimate {
+    requests: usize,
+    endpoints: usize,
+    concurrency: usize,
+    min_secs: u64,
+    max_secs: u64,
+}
+
+impl RunEstimate {
+    fn new(
+        requests: usize,
+        endpoints: usize,
+        concurrency: usize,
+        per_host: Option<usize>,
+        latency: std::time::Duration,
+    ) -> Self {
+        let mut effective = concurrency.max(1);
+        if let Some(per_host) = per_host {
+            effective = effective.min(endpoints.max(1) * per_host);
+        }
+        let waves = requests.div_ceil(effective) as u64;
+        let min_ms = waves * latency.as_millis() as u64;
+        Self {
+            requests,
+            endpoints,
+            concurrency,
+            min_secs: min_ms.div_ceil(1000),
+            max_secs: (2 * min_ms).div_ceil(1000),
+        }
+    }
+}
+
+impl std::fmt::Display for RunEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let duration = if self.min_secs == self.max_secs {
+            format!("{}s", self.max_secs)
+        } else {
+            format!("{}–{}s", self.min_secs, self.max_secs)
+        };
+        write!(
+            f,
+            "≈{} request{} across {} endpoint{}, est. {} at concurrency {}",
+            group_thousands(self.requests),
+            if self.requests == 1 { "" } else { "s" },
+            group_thousands(self.endpoints),
+            if self.endpoints == 1 { "" } else { "s" },
+            duration,
+            self.concurrency
+        )
+    }
+}
+
+/// Format a count with comma thousands separators, e.g. 1,200.
+fn group_thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Warning for a --concurrency that --concurrency-per-host makes unreachable.
+///
+/// With `endpoints` distinct RDAP hosts in the workload, at most
+/// `endpoints * per_host` checks can be in flight at once; a higher global
+/// concurrency only adds tasks waiting on a host slot.
+fn concurrency_warning(
+    concurrency: usize,
+    per_host: Option<usize>,
+    endpoints: usize,
+) -> Option<String> {
+    let per_host = per_host?;
+    let reachable = endpoints.max(1) * per_host;
+    if reachable >= concurrency {
+        return None;
+    }
+    Some(format!(
+        "⚠️  --concurrency {} can't be reached: {} endpoint(s) × --concurrency-per-host {} allows at most {} checks in flight; use --concurrency {} or raise --concurrency-per-host",
+        concurrency,
+        endpoints.max(1),
+        per_host,
+        reachable,
+        reachable
+    ))
+}
+
+/// Confidence threshold selected with --min-confidence (already validated).
+fn parse_min_confidence(args: &Args) -> Option<Confidence> {
+    args.min_confidence
+        .as_deref()
+        .and_then(|level| level.parse().ok())
+}
+
+/// Whether a result falls below the --min-confidence threshold.
+///
+/// Results without a confidence level are authoritative and count as high.
+fn below_min_confidence(result: &domain_check_lib::DomainResult, min: Option<Confidence>) -> bool {
+    min.is_some_and(|min| !result.confidence.unwrap_or(Confidence::High).at_least(min))
+}
+
+/// Drop results below the --min-confidence threshold, returning how many were dropped.
+fn filter_min_confidence(
+    results: Vec<domain_check_lib::DomainResult>,
+    min: Option<Confidence>,
+) -> (Vec<domain_check_lib::DomainResult>, usize) {
+    let total = results.len();
+    let kept: Vec<_> = results
+        .into_iter()
+        .filter(|result| !below_min_confidence(result, min))
+        .collect();
+    let suppressed = total - kept.len();
+    (kept, suppressed)
+}
+
+/// Tell the user how many results --min-confidence hid.
+fn report_suppressed(suppressed: usize, min: Option<Confidence>) {
+    if let (true, Some(min)) = (suppressed > 0, min) {
+        eprintln!(
+            "ℹ️ {} low-confidence result{} suppressed (below --min-confidence {})",
+            suppressed,
+            if suppressed == 1 { "" } else { "s" },
+            min
+        );
+    }
+}
+
+/// Rewrite a result's registry dates in the requested format.
+///
+/// Dates that can't be parsed are left verbatim (noted with --debug).
+fn normalize_result_dates(
+    result: &mut domain_check_lib::DomainResult,
+    format: &DateFormat,
+    debug: bool,
+) {
+    let Some(info) = result.info.as_mut() else {
+        return;
+    };
+    for date in [
+        &mut info.creation_date,
+        &mut info.expiration_date,
+        &mut info.updated_date,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        match domain_check_lib::parse_registry_date(date) {
+            Some(parsed) => *date = parsed.format(format),
+            None if debug => eprintln!(
+                "🔧 {}: leaving unrecognized date '{}' as-is",
+                result.domain, date
+            ),
+            None => {}
+        }
+    }
+}
+
+/// Machine-readable run summary written by `--summary-json`.
+#[derive(Debug, serde::Serialize)]
+struct RunSummary {
+    total: usize,
+    available: usize,
+    taken: usize,
+    unknown: usize,
+    duration_ms: u128,
+    /// Result count per check method ("rdap", "whois", ...)
+    by_method: std::collections::BTreeMap<String, usize>,
+    /// Failure count per error category ("timeout", "network", ...)
+    by_error_kind: std::collections::BTreeMap<String, usize>,
+}
+
+impl RunSummary {
+    fn from_results(
+        results: &[domain_check_lib::DomainResult],
+        duration: std::time::Duration,
+    ) -> Self {
+        let mut by_method = std::collections::BTreeMap::new();
+        let mut by_error_kind = std::collections::BTreeMap::new();
+
+        for r in results {
+            *by_method
+                .entry(r.method_used.to_string().to_lowercase())
+                .or_insert(0) += 1;
+
+            if let Some(category) = r.error_category {
+                let kind = serde_json::to_value(category)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_else(|| "other".to_string());
+                *by_error_kind.entry(kind).or_insert(0) += 1;
+            }
+        }
+
+        Self {
+            total: results.len(),
+            available: results.iter().filter(|r| r.available == Some(true)).count(),
+            taken: results
+                .iter()
+                .filter(|r| r.available == Some(false))
+                .count(),
+            unknown: results.iter().filter(|r| r.available.is_none()).count(),
+            duration_ms: duration.as_millis(),
+            by_method,
+            by_error_kind,
+        }
+    }
+
+    /// A summary with only the totals, for runs that don't keep their results.
+    fn from_counts(
+        available: usize,
+        taken: usize,
+        unknown: usize,
+        duration: std::time::Duration,
+    ) -> Self {
+        Self {
+            total: available + taken + unknown,
+            available,
+            taken,
+            unknown,
+            duration_ms: duration.as_millis(),
+            by_method: Default::default(),
+            by_error_kind: Default::default(),
+        }
+    }
+
+    /// Single `key=value` line printed by `--compact-summary`.
+    fn compact_line(&self) -> String {
+        format!(
+            "total={} available={} taken={} unknown={} duration_ms={}",
+            self.total, self.available, self.taken, self.unknown, self.duration_ms
+        )
+    }
+}
+
+/// `--json --json-envelope` output: results plus the settings that produced them.
+#[derive(Debug, serde::Serialize)]
+struct JsonEnvelope<'a> {
+    config: RunConfig,
+    results: &'a [domain_check_lib::DomainResult],
+}
+
+/// Effective run settings recorded in the JSON envelope.
+#[derive(Debug, serde::Serialize)]
+struct RunConfig {
+    concurrency: usize,
+    timeout_ms: u128,
+    rdap_timeout_ms: u128,
+    connect_timeout_ms: u128,
+    whois_timeout_ms: u128,
+    timeout_retries: u8,
+    bootstrap: bool,
+    whois_fallback: bool,
+    /// Where the TLD list came from: "tld", "tld-file", "preset:<name>", "all", "config" or "default"
+    tld_source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tlds: Option<Vec<String>>,
+}
+
+impl RunConfig {
+    fn new(config: &CheckConfig, args: &Args) -> Self {
+        let tld_source = if args.tlds.is_some() {
+            "tld".to_string()
+        } else if args.tld_file.is_some() {
+            "tld-file".to_string()
+        } else if let Some(preset) = &args.preset {
+            format!("preset:{}", preset)
+        } else if args.all_tlds {
+            "all".to_string()
+        } else if config.tlds.is_some() {
+            "config".to_string()
+        } else {
+            "default".to_string()
+        };
+
+        Self {
+            concurrency: config.concurrency,
+            timeout_ms: config.timeout.as_millis(),
+            rdap_timeout_ms: config.rdap_timeout.as_millis(),
+            connect_timeout_ms: config.connect_timeout.as_millis(),
+            whois_timeout_ms: config.whois_timeout.as_millis(),
+            timeout_retries: config.timeout_retries,
+            bootstrap: config.enable_bootstrap,
+            whois_fallback: config.enable_whois_fallback,
+            tld_source,
+            tlds: config.tlds.clone(),
+        }
+    }
+}
+
+/// `--version --json` output: enough to tell builds apart.
+#[derive(Debug, serde::Serialize)]
+struct VersionInfo {
+    version: &'static str,
+    /// Library features compiled in, e.g. `whois` and `bootstrap`
+    features: Vec<&'static str>,
+    /// TLDs with a built-in RDAP endpoint (no bootstrap needed)
+    builtin_tld_count: usize,
+}
+
+impl VersionInfo {
+    fn new() -> Self {
+        let info = domain_check_lib::info();
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            features: info.features,
+            builtin_tld_count: get_all_known_tlds().len(),
+        }
+    }
+}
+
+/// `--manifest` file: what a run checked, when, and with which settings.
+#[derive(Debug, serde::Serialize)]
+struct RunManifest {
+    tool: &'static str,
+    version: &'static str,
+    features: Vec<&'static str>,
+    /// UTC start time, RFC 3339
+    started_at: String,
+    input: ManifestInput,
+    config: RunConfig,
+    /// SHA-256 of `config` serialized as compact JSON
+    config_sha256: String,
+}
+
+/// Where the domains came from and a fingerprint of the final list.
+#[derive(Debug, serde::Serialize)]
+struct ManifestInput {
+    /// Domain names given on the command line
+    arguments: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    patterns: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resume: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recheck_available: Option<String>,
+    domain_count: usize,
+    /// SHA-256 of the checked domains, one per line; matches `sha256sum`
+    /// of a file listing them in the same order
+    domains_sha256: String,
+}
+
+impl RunManifest {
+    fn new(
+        args: &Args,
+        config: &CheckConfig,
+        domains: &[String],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let info = domain_check_lib::info();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let run_config = RunConfig::new(config, args);
+        let config_sha256 = sha256_hex(serde_json::to_string(&run_config)?.as_bytes());
+
+        Ok(Self {
+            tool: "domain-check",
+            version: info.version,
+            features: info.features,
+            started_at: domain_check_lib::RegistryDate::from_unix_timestamp(now)
+                .format(&domain_check_lib::DateFormat::Iso),
+            input: ManifestInput {
+                arguments: args.domains.len(),
+                file: args.file.clone(),
+                patterns: args.patterns.clone(),
+                resume: args.resume.clone(),
+                recheck_available: args.recheck_available.clone(),
+                domain_count: domains.len(),
+                domains_sha256: domain_list_sha256(domains),
+            },
+            config: run_config,
+            config_sha256,
+        })
+    }
+}
+
+/// SHA-256 of the domain list as lowercase lines, each ending in `\n`.
+fn domain_list_sha256(domains: &[String]) -> String {
+    let listing: String = domains
+        .iter()
+        .map(|d| format!("{}\n", d.to_lowercase()))
+        .collect();
+    sha256_hex(listing.as_bytes())
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, data)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Write the `--manifest` file.
+fn write_manifest(path: &str, manifest: &RunManifest) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(manifest)?;
+    std::fs::write(path, json + "\n")
+        .map_err(|e| format!("Failed to write manifest file '{}': {}", path, e))?;
+    Ok(())
+}
+
+/// Write the `--summary-json` file.
+fn write_summary_json(
+    path: &str,
+    results: &[domain_check_lib::DomainResult],
+    duration: std::time::Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let summary = RunSummary::from_results(results, duration);
+    let json = serde_json::to_string_pretty(&summary)?;
+    std::fs::write(path, json + "\n")
+        .map_err(|e| format!("Failed to write summary file '{}': {}", path, e))?;
+    Ok(())
+}
+
+/// Write the domains whose availability is unknown to `path`, one per line.
+///
+/// The file is always written (empty when nothing failed) so a retry script
+/// never picks up a stale list from an earlier run.
+fn write_failures_file(
+    path: &str,
+    results: &[domain_check_lib::DomainResult],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let failures: String = results
+        .iter()
+        .filter(|r| r.available.is_none())
+        .map(|r| format!("{}\n", r.domain))
+        .collect();
+    std::fs::write(path, failures)
+        .map_err(|e| format!("Failed to write failures file '{}': {}", path, e))?;
+    Ok(())
+}
+
+/// Write the `--timing-log` file, if requested.
+fn write_timing_log(args: &Args, config: &CheckConfig) -> Result<(), Box<dyn std::error::Error>> {
+    if let (Some(path), Some(log)) = (&args.timing_log, &config.timing_log) {
+        std::fs::write(path, format_timing_log(path, &log.rows())?)
+            .map_err(|e| format!("Failed to write timing log '{}': {}", path, e))?;
+    }
+    Ok(())
+}
+
+/// Render timing rows as a JSON array for `.json` paths, CSV otherwise.
+fn format_timing_log(
+    path: &str,
+    rows: &[domain_check_lib::CheckTiming],
+) -> Result<String, Box<dyn std::error::Error>> {
+    if path.to_lowercase().ends_with(".json") {
+        return Ok(serde_json::to_string_pretty(rows)? + "\n");
+    }
+
+    let mut csv =
+        String::from("domain,launched_ms,started_ms,completed_ms,duration_ms,endpoint,method\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            csv_field(&row.domain),
+            row.launched_ms,
+            row.started_ms,
+            row.completed_ms,
+            row.duration_ms,
+            csv_field(row.endpoint.as_deref().unwrap_or("")),
+            row.method.to_string().to_lowercase()
+        ));
+    }
+    Ok(csv)
+}
+
+/// Open an `--output` file, replacing it or, with `--append`, adding to it.
+///
+/// Also reports whether the file starts out empty, so a CSV header is only
+/// written once at the top of a dataset built up over several runs.
+fn open_output_file(
+    path: &str,
+    append: bool,
+) -> Result<(std::fs::File, bool), Box<dyn std::error::Error>> {
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .map_err(|e| format!("Failed to open output file '{}': {}", path, e))?;
+    let empty = file.metadata()?.len() == 0;
+    Ok((file, empty))
+}
+
+/// With `--append`, require an existing non-empty CSV `--output` file to
+/// start with the `header` this run would write, so rows with different
+/// columns never end up under one header.
+fn check_appended_csv_header(path: &str, header: &str) -> Result<(), String> {
+    let existing = match std::fs::File::open(path) {
+        Ok(file) => {
+            let mut line = String::new();
+            std::io::BufRead::read_line(&mut std::io::BufReader::new(file), &mut line)
+                .map_err(|e| format!("Failed to read output file '{}': {}", path, e))?;
+            line
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(format!("Failed to open output file '{}': {}", path, e)),
+    };
+    let existing = existing.trim_end_matches(['\r', '\n']);
+    if existing.is_empty() || existing == header {
+        return Ok(());
+    }
+    Err(format!(
+        "Can't --append to '{}': its CSV header is '{}' but this run writes '{}'. Use the same --fields/--passthrough-columns or a new --output file",
+        path, existing, header
+    ))
+}
+
+/// Sink for `--ndjson` output: stdout, or an `--output` file.
+///
+/// The file is opened once per run and flushed after every line, so an
+/// interrupted run leaves a valid (if partial) NDJSON file for `--resume`.
+struct NdjsonWriter {
+    out: Box<dyn Write>,
+}
+
+impl NdjsonWriter {
+    fn create(path: Option<&str>, append: bool) -> Result<Self, Box<dyn std::error::Error>> {
+        let out: Box<dyn Write> = match path {
+            Some(path) => Box::new(open_output_file(path, append)?.0),
+            None => Box::new(std::io::stdout()),
+        };
+        Ok(Self { out })
+    }
+
+    fn write_result(
+        &mut self,
+        result: &domain_check_lib::DomainResult,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let line = serde_json::to_string(result)?;
+        writeln!(self.out, "{}", line)?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Minimum time between two `progress` events from `--progress-json`.
+const PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// `--progress-json` event writer.
+///
+/// Writes one JSON object per line to stderr: a `progress` event with
+/// `done`/`total` at the start, at most every `PROGRESS_INTERVAL` and when
+/// the last domain completes, an optional `result` event per domain, and a
+/// closing `done` event with the final counts.
+struct ProgressReporter {
+    total: usize,
+    done: usize,
+    available: usize,
+    taken: usize,
+    unknown: usize,
+    results: bool,
+    last_progress: std::time::Instant,
+}
+
+impl ProgressReporter {
+    /// A reporter for `total` domains, or None without `--progress-json`.
+    fn new(total: usize, args: &Args) -> Option<Self> {
+        if !args.progress_json {
+            return None;
+        }
+        let reporter = Self {
+            total,
+            done: 0,
+            available: 0,
+            taken: 0,
+            unknown: 0,
+            results: args.progress_json_results,
+            last_progress: std::time::Instant::now(),
+        };
+        reporter.emit(serde_json::json!({ "event": "progress", "done": 0, "total": total }));
+        Some(reporter)
+    }
+
+    fn record(&mut self, result: &domain_check_lib::DomainResult) {
+        self.done += 1;
+        match result.available {
+            Some(true) => self.available += 1,
+            Some(false) => self.taken += 1,
+            None => self.unknown += 1,
+        }
+
+        if self.results {
+            if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(result) {
+                let mut event = serde_json::Map::new();
+                event.insert("event".to_string(), "result".into());
+                event.extend(fields);
+                self.emit(serde_json::Value::Object(event));
+            }
+        }
+
+        if self.done == self.total || self.last_progress.elapsed() >= PROGRESS_INTERVAL {
+            self.last_progress = std::time::Instant::now();
+            self.emit(serde_json::json!({
+                "event": "progress",
+                "done": self.done,
+                "total": self.total,
+            }));
+        }
+    }
+
+    fn finish(&self, duration: std::time::Duration) {
+        self.emit(serde_json::json!({
+            "event": "done",
+            "done": self.done,
+            "total": self.total,
+            "available": self.available,
+            "taken": self.taken,
+            "unknown": self.unknown,
+            "duration_ms": duration.as_millis() as u64,
+        }));
+    }
+
+    fn emit(&self, event: serde_json::Value) {
+        let mut stderr = std::io::stderr().lock();
+        let _ = writeln!(stderr, "{}", event);
+        let _ = stderr.flush();
+    }
+}
+
+/// Load results from a prior `--json` or `--csv` run for `--resume`.
+fn load_prior_results(
+    path: &str,
+) -> Result<Vec<domain_check_lib::DomainResult>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read resume file '{}': {}", path, e))?;
+
+    let lower = path.to_lowercase();
+    if lower.ends_with(".csv") {
+        return Ok(parse_prior_csv(&content));
+    }
+    // A --json-envelope file: take its "results" array
+    if content.trim_start().starts_with('{') {
+        if let Ok(serde_json::Value::Object(mut envelope)) = serde_json::from_str(&content) {
+            if let Some(results) = envelope.remove("results") {
+                return serde_json::from_value(results)
+                    .map_err(|e| format!("Failed to parse resume file '{}': {}", path, e).into());
+            }
+        }
+    }
+    if lower.ends_with(".ndjson")
+        || lower.ends_with(".jsonl")
+        || !content.trim_start().starts_with('[')
+    {
+        return Ok(parse_prior_ndjson(&content));
+    }
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse resume file '{}': {}", path, e).into())
+}
+
+/// Parse `--ndjson` output back into results.
+///
+/// Lines that don't parse are skipped, so a file cut off mid-line by an
+/// interrupted run still yields every complete result.
+fn parse_prior_ndjson(content: &str) -> Vec<domain_check_lib::DomainResult> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Parse the `--csv` output format back into results.
+///
+/// Only the domain and availability columns matter for resuming; the
+/// remaining columns are carried over as detail where present.
+fn parse_prior_csv(content: &str) -> Vec<domain_check_lib::DomainResult> {
+    let mut results = Vec::new();
+    let mut lines = content.lines();
+
+    // Columns after `available` depend on --fields, so locate them by header
+    let header: Vec<String> = lines
+        .next()
+        .unwrap_or("")
+        .split(',')
+        .map(|c| c.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let (registrar_col, created_col, expires_col, method_col) = (
+        column("registrar"),
+        column("created"),
+        column("expires"),
+        column("method"),
+    );
+
+    for line in lines {
+        let cols: Vec<&str> = line.split(',').map(|c| c.trim()).collect();
+        if cols.len() < 2 || cols[0].is_empty() {
+            continue;
+        }
+
+        let available = match cols[1] {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        };
+
+        let field = |idx: Option<usize>| {
+            cols.get(idx?)
+                .filter(|v| !v.is_empty() && **v != "-")
+                .map(|v| v.to_string())
+        };
+        let info = domain_check_lib::DomainInfo {
+            registrar: field(registrar_col),
+            creation_date: field(created_col),
+            expiration_date: field(expires_col),
+            ..Default::default()
+        };
+        let has_info = info.registrar.is_some()
+            || info.creation_date.is_some()
+            || info.expiration_date.is_some();
+
+        let method_used = match field(method_col).map(|m| m.to_lowercase()).as_deref() {
+            Some("rdap") => domain_check_lib::CheckMethod::Rdap,
+            Some("whois") => domain_check_lib::CheckMethod::Whois,
+            Some("bootstrap") => domain_check_lib::CheckMethod::Bootstrap,
+            _ => domain_check_lib::CheckMethod::Unknown,
+        };
+
+        let mut result = domain_check_lib::DomainResult::new(cols[0], available, method_used);
+        result.info = if has_info { Some(info) } else { None };
+        results.push(result);
+    }
+
+    results
+}
+
+/// Split domains into those still needing a check and prior results to carry over.
+///
+/// A prior result is carried over only when its status is definitive
+/// (`Some(true)` / `Some(false)`); unknown results are re-checked. Prior
+/// results for domains not in the current input are ignored.
+fn partition_resume(
+    domains: Vec<String>,
+    prior: Vec<domain_check_lib::DomainResult>,
+) -> (Vec<String>, Vec<domain_check_lib::DomainResult>) {
+    let mut definitive: std::collections::HashMap<String, domain_check_lib::DomainResult> = prior
+        .into_iter()
+        .filter(|r| r.available.is_some())
+        .map(|r| (r.domain.to_lowercase(), r))
+        .collect();
+
+    let mut to_check = Vec::new();
+    let mut carried = Vec::new();
+
+    for domain in domains {
+        match definitive.remove(&domain.to_lowercase()) {
+            Some(result) => carried.push(result),
+            None => to_check.push(domain),
+        }
+    }
+
+    (to_check, carried)
+}
+
+/// Domains a prior run reported available, in their original order.
+fn previously_available(prior: Vec<domain_check_lib::DomainResult>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    prior
+        .into_iter()
+        .filter(|r| r.available == Some(true))
+        .map(|r| r.domain)
+        .filter(|domain| seen.insert(domain.to_lowercase()))
+        .collect()
+}
+
+/// Print the `--recheck-available` report, to stderr when stdout carries
+/// structured output.
+fn print_recheck_report(results: &[domain_check_lib::DomainResult], to_stderr: bool) {
+    let report = recheck_report(results);
+    if to_stderr {
+        eprintln!("{}", report);
+    } else {
+        println!("{}", report);
+    }
+}
+
+/// Summarize a `--recheck-available` run: which domains have since been taken.
+fn recheck_report(results: &[domain_check_lib::DomainResult]) -> String {
+    let taken: Vec<&str> = results
+        .iter()
+        .filter(|r| r.available == Some(false))
+        .map(|r| r.domain.as_str())
+        .collect();
+    let unknown = results.iter().filter(|r| r.available.is_none()).count();
+
+    let mut report = if taken.is_empty() {
+        format!(
+            "✅ All {} previously available domain{} still available",
+            results.len() - unknown,
+            if results.len() - unknown == 1 {
+                " is"
+            } else {
+                "s are"
+            }
+        )
+    } else {
+        format!(
+            "⚠️ {} of {} previously available domain{} now taken: {}",
+            taken.len(),
+            results.len(),
+            if results.len() == 1 { " is" } else { "s are" },
+            taken.join(", ")
+        )
+    };
+    if unknown > 0 {
+        report.push_str(&format!(" ({} could not be verified)", unknown));
+    }
+    report
+}
+
+/// Combine carried-over and fresh results, ordered as in the original input.
+fn merge_resumed_results(
+    input_order: &[String],
+    carried: Vec<domain_check_lib::DomainResult>,
+    fresh: Vec<domain_check_lib::DomainResult>,
+) -> Vec<domain_check_lib::DomainResult> {
+    let position: std::collections::HashMap<String, usize> = input_order
+        .iter()
+        .enumerate()
+        .map(|(i, d)| (d.to_lowercase(), i))
+        .collect();
+
+    let mut merged: Vec<domain_check_lib::DomainResult> =
+        carried.into_iter().chain(fresh).collect();
+    merged.sort_by_key(|r| {
+        position
+            .get(&r.domain.to_lowercase())
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
+    merged
+}
+
+/// Collect alias domains to check for every taken result.
+///
+/// Domains already present in `results` are skipped, and each alias is
+/// returned once even if several primaries map to it.
+fn collect_alias_domains(
+    results: &[domain_check_lib::DomainResult],
+    aliases: &std::collections::HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    let mut seen: std::collections::HashSet<String> =
+        results.iter().map(|r| r.domain.to_lowercase()).collect();
+    let mut targets = Vec::new();
+
+    for result in results.iter().filter(|r| r.available == Some(false)) {
+        for alias in domain_check_lib::alias_domains(&result.domain, aliases) {
+            if seen.insert(alias.to_lowercase()) {
+                targets.push(alias);
+            }
+        }
+    }
+
+    targets
+}
+
+/// Place each alias result directly after the primary domain it came from.
+fn group_with_aliases(
+    primary: Vec<domain_check_lib::DomainResult>,
+    alias_results: Vec<domain_check_lib::DomainResult>,
+    aliases: &std::collections::HashMap<String, Vec<String>>,
+) -> Vec<domain_check_lib::DomainResult> {
+    let mut pending: std::collections::HashMap<String, domain_check_lib::DomainResult> =
+        alias_results
+            .into_iter()
+            .map(|r| (r.domain.to_lowercase(), r))
+            .collect();
+    let mut grouped = Vec::with_capacity(primary.len() + pending.len());
+
+    for result in primary {
+        let followers = if result.available == Some(false) {
+            domain_check_lib::alias_domains(&result.domain, aliases)
+        } else {
+            Vec::new()
+        };
+        grouped.push(result);
+        for alias in followers {
+            if let Some(alias_result) = pending.remove(&alias.to_lowercase()) {
+                grouped.push(alias_result);
+            }
+        }
+    }
+
+    grouped
+}
 
 /// Build CheckConfig from CLI arguments with config file integration.
 ///
@@ -625,7 +3436,7 @@ fn build_config(args: &Args) -> Result<CheckConfig, Box<dyn std::error::Error>>
     }
 
     // Step 2: Apply environment variables (DC_*)
-    config = apply_environment_config(config, args.verbose);
+    config = apply_environment_config(config, args)?;
 
     // Step 3: Apply CLI arguments (highest precedence)
     config = apply_cli_args_to_config(config, args)?;
@@ -638,6 +3449,7 @@ fn merge_file_config_into_check_config(
     mut config: CheckConfig,
     file_config: FileConfig,
 ) -> CheckConfig {
+    let mut preset = None;
     if let Some(defaults) = file_config.defaults {
         // Apply defaults from config file (only if not already set)
         if let Some(concurrency) = defaults.concurrency {
@@ -652,18 +3464,16 @@ fn merge_file_config_into_check_config(
         if let Some(detailed_info) = defaults.detailed_info {
             config.detailed_info = detailed_info;
         }
+        if let Some(exclude_tlds) = defaults.exclude_tlds {
+            config.exclude_tlds = exclude_tlds;
+        }
 
-        // Handle TLDs and presets with proper precedence
+        // `tlds` and `preset` can't both be set (rejected by the config
+        // loader); a preset is resolved below, once custom presets are known
         if let Some(tlds) = defaults.tlds {
-            // Explicit TLD list wins over preset
             config.tlds = Some(tlds);
-        } else if let Some(preset_name) = defaults.preset {
-            // Convert preset name to TLD list
-            // Note: Custom presets will be applied later in the config merge process
-            if let Some(preset_tlds) = get_preset_tlds(&preset_name) {
-                config.tlds = Some(preset_tlds);
-            }
         }
+        preset = defaults.preset;
 
         // Apply timeout settings
         if let Some(timeout_str) = defaults.timeout {
@@ -680,17 +3490,57 @@ fn merge_file_config_into_check_config(
         config.custom_presets = custom_presets;
     }
 
-    config
-}
+    // Custom presets take priority over built-in presets with the same name
+    if let Some(preset_name) = preset {
+        if let Some(preset_tlds) =
+            get_preset_tlds_with_custom(&preset_name, Some(&config.custom_presets))
+        {
+            config.tlds = Some(preset_tlds);
+        }
+    }
 
-/// Apply environment variables to config with comprehensive DC_* support.
-///
-/// Uses the library's load_env_config() for validation and proper handling.
-fn apply_environment_config(mut config: CheckConfig, verbose: bool) -> CheckConfig {
-    let env_config = load_env_config(verbose);
+    // Apply TLD alias groups (used by --with-aliases)
+    if let Some(tld_aliases) = file_config.tld_aliases {
+        config.tld_aliases = tld_aliases;
+    }
+
+    // Apply per-TLD RDAP timeouts (already validated by the config loader)
+    for (tld, timeout_str) in file_config.tld_timeouts.iter().flatten() {
+        if let Ok(timeout_secs) = parse_timeout_string(timeout_str) {
+            config = config.with_tld_timeout(tld, std::time::Duration::from_secs(timeout_secs));
+        }
+    }
+
+    // Apply per-TLD protocol preferences (already validated by the config loader)
+    for (tld, method) in file_config.tld_methods.iter().flatten() {
+        if let Some(method) = parse_check_method(method) {
+            config = config.with_tld_method(tld, method);
+        }
+    }
+
+    config
+}
+
+/// Apply environment variables to config with comprehensive DC_* support.
+///
+/// Uses the library's load_env_config() for validation and proper handling.
+/// DC_TLD and DC_PRESET override config file TLDs; setting both is an error
+/// unless a CLI TLD source makes them irrelevant.
+fn apply_environment_config(
+    mut config: CheckConfig,
+    args: &Args,
+) -> Result<CheckConfig, Box<dyn std::error::Error>> {
+    let env_config = load_env_config(args.verbose);
+
+    if env_config.has_tld_source_conflict() && !has_cli_tld_source(args) {
+        return Err(
+            "Both DC_TLD and DC_PRESET are set; unset one, or pass a TLD source on the command line"
+                .into(),
+        );
+    }
 
     // Check for output format conflicts
-    if env_config.has_output_format_conflict() && verbose {
+    if env_config.has_output_format_conflict() && args.verbose {
         eprintln!("⚠️ Both DC_JSON and DC_CSV are set to true, CLI args will resolve conflict");
     }
 
@@ -731,7 +3581,13 @@ fn apply_environment_config(mut config: CheckConfig, verbose: bool) -> CheckConf
         }
     }
 
-    config
+    Ok(config)
+}
+
+/// Whether the command line picks the TLDs (-t, --tld-file, --preset or --all),
+/// overriding any environment or config file TLDs.
+fn has_cli_tld_source(args: &Args) -> bool {
+    args.tlds.is_some() || args.tld_file.is_some() || args.preset.is_some() || args.all_tlds
 }
 
 /// Apply CLI arguments to config (highest precedence).
@@ -746,9 +3602,11 @@ fn apply_cli_args_to_config(
     // Note: We can't easily detect if clap default was used, so we check against default value
     // This is a limitation - if user explicitly sets --concurrency 20, it won't override env vars
     // But this is acceptable behavior (explicit same-as-default still counts as explicit)
-    if args.concurrency != 20 {
-        // 20 is the clap default
-        config.concurrency = args.concurrency;
+    if let Some(concurrency) = fixed_concurrency(args)? {
+        if concurrency != 20 {
+            // 20 is the clap default
+            config.concurrency = concurrency;
+        }
     }
 
     // Only override boolean settings when the user explicitly passes the flag.
@@ -756,27 +3614,215 @@ fn apply_cli_args_to_config(
     if args.no_whois {
         config.enable_whois_fallback = false;
     }
-    if args.info {
+    if args.race {
+        config.race_protocols = true;
+    }
+    if args.strict_validation {
+        config.strict_validation = true;
+    }
+    if args.endpoint_report {
+        config.track_endpoints = true;
+    }
+    if let Some(secs) = args.concurrency_ramp {
+        config.concurrency_ramp = Some(std::time::Duration::from_secs(secs));
+    }
+    if let Some(per_host) = args.concurrency_per_host {
+        config.concurrency_per_host = Some(per_host);
+    }
+    if let Some(retries) = args.timeout_retries {
+        config.timeout_retries = retries;
+    }
+    if let Some(url) = &args.rdap_server {
+        config.rdap_server_override = Some(url.clone());
+    }
+    if let Some(url) = &args.bootstrap_url {
+        config.bootstrap_url = Some(url.clone());
+    }
+    if args.timing_log.is_some() {
+        config.timing_log = Some(domain_check_lib::TimingLog::new());
+    }
+    if args.treat_dropping_as_available {
+        config.treat_dropping_as_available = true;
+    }
+    if args.detect_wildcards {
+        config.detect_wildcard_tlds = true;
+    }
+    if let Some(servers) = &args.dns_servers {
+        let servers = servers
+            .iter()
+            .map(|server| parse_dns_server(server))
+            .collect::<Result<Vec<_>, _>>()?;
+        config.dns_servers = Some(servers);
+    }
+    if args.info || args.registrar_stats || args.registrar_consistency || args.fields.is_some() {
         config.detailed_info = true;
     }
 
     // Handle TLD precedence: CLI explicit > CLI preset > CLI all > env vars > config file
     if args.tlds.is_some() {
         config.tlds = args.tlds.clone();
+    } else if let Some(tld_file) = &args.tld_file {
+        config.tlds = Some(read_tlds_from_file(tld_file)?);
     } else if let Some(preset) = &args.preset {
         // Use custom presets if available, fall back to built-in
         config.tlds = get_preset_tlds_with_custom(preset, Some(&config.custom_presets));
     } else if args.all_tlds {
-        config.tlds = Some(get_all_known_tlds());
+        config.tlds = Some(match args.tld_limit {
+            Some(limit) => domain_check_lib::get_top_tlds(limit),
+            None => get_all_known_tlds(),
+        });
     }
     // Otherwise keep TLDs from environment or config file (already applied)
 
+    // --exclude-tld adds to any [defaults] exclude_tlds from config
+    if let Some(exclude) = &args.exclude_tlds {
+        for tld in exclude {
+            let tld = normalize_cli_tld(tld);
+            if !tld.is_empty() && !config.exclude_tlds.contains(&tld) {
+                config.exclude_tlds.push(tld);
+            }
+        }
+    }
+
+    // --add-tld extends whatever the base source resolved to (default: com)
+    if let Some(extra) = &args.add_tlds {
+        config.tlds = Some(merge_extra_tlds(config.tlds.take(), extra));
+    }
+
+    // --sample-tlds samples the resolved list, see `expansion_tlds`
+    if args.sample_tlds.is_some() && config.tlds.is_none() {
+        return Err(
+            "--sample-tlds needs a TLD list to sample: use -t, --preset, --all or a configured default"
+                .into(),
+        );
+    }
+
+    // Reject empty TLDs before they expand into malformed names like `brand.`
+    if let Some(tlds) = &config.tlds {
+        domain_check_lib::validate_tlds(tlds)?;
+    }
+
     // Bootstrap logic with environment consideration
     config.enable_bootstrap = should_enable_bootstrap(args, &config.tlds);
 
     Ok(config)
 }
 
+/// One warning line per reserved base label found among `domains`
+fn reserved_label_warnings(domains: &[String], skipping: bool) -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for domain in domains {
+        if let Some(label) = domain_check_lib::reserved_label(domain) {
+            match counts.iter_mut().find(|(l, _)| *l == label) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((label, 1)),
+            }
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|(label, count)| {
+            format!(
+                "⚠️ '{}' is commonly reserved by registries ({} domain{}){}",
+                label,
+                count,
+                if count == 1 { "" } else { "s" },
+                if skipping { ", skipping" } else { "" }
+            )
+        })
+        .collect()
+}
+
+/// Normalize a TLD given on the command line: trim, drop a leading dot, lowercase
+fn normalize_cli_tld(tld: &str) -> String {
+    let tld = tld.trim();
+    tld.strip_prefix('.').unwrap_or(tld).to_lowercase()
+}
+
+/// Whether a domain falls under one of `tlds` (matches the full suffix or its last label)
+fn domain_has_tld(domain: &str, tlds: &[String]) -> bool {
+    let Some((_, suffix)) = domain_check_lib::extract_domain_parts(domain) else {
+        return false;
+    };
+    suffix_has_tld(&suffix, tlds)
+}
+
+fn suffix_has_tld(suffix: &str, tlds: &[String]) -> bool {
+    tlds.iter()
+        .any(|tld| suffix == tld || suffix.ends_with(&format!(".{}", tld)))
+}
+
+/// The TLDs a run would expand base names against, for --show-tlds.
+///
+/// Same sources and filters as `get_domains_to_check`: the configured list
+/// (or `com`), minus excluded TLDs, limited to --include-only-tld, then
+/// sampled per registry with --sample-tlds.
+fn resolved_tlds(args: &Args, config: &CheckConfig) -> Vec<String> {
+    let include_only: Option<Vec<String>> = args
+        .include_only_tlds
+        .as_ref()
+        .map(|tlds| tlds.iter().map(|t| normalize_cli_tld(t)).collect());
+    let mut tlds: Vec<String> = Vec::new();
+    for tld in config
+        .tlds
+        .clone()
+        .unwrap_or_else(|| vec!["com".to_string()])
+    {
+        let tld = normalize_cli_tld(&tld);
+        if tld.is_empty()
+            || tlds.contains(&tld)
+            || suffix_has_tld(&tld, &config.exclude_tlds)
+            || include_only
+                .as_deref()
+                .is_some_and(|only| !suffix_has_tld(&tld, only))
+        {
+            continue;
+        }
+        tlds.push(tld);
+    }
+    match args.sample_tlds {
+        Some(per_registry) => domain_check_lib::sample_tlds_per_registry(&tlds, per_registry),
+        None => tlds,
+    }
+}
+
+/// The TLD list base names are expanded against.
+///
+/// `config.tlds` as is, except with --sample-tlds, where the sample is taken
+/// from the list left after --exclude-tld and --include-only-tld.
+fn expansion_tlds(args: &Args, config: &CheckConfig) -> Option<Vec<String>> {
+    match args.sample_tlds {
+        Some(_) => Some(resolved_tlds(args, config)),
+        None => config.tlds.clone(),
+    }
+}
+
+/// Apply --exclude-tld / [defaults] exclude_tlds and --include-only-tld
+fn filter_domains_by_tld(
+    domains: Vec<String>,
+    exclude: &[String],
+    include_only: Option<&[String]>,
+) -> Vec<String> {
+    domains
+        .into_iter()
+        .filter(|d| !domain_has_tld(d, exclude))
+        .filter(|d| include_only.is_none_or(|only| domain_has_tld(d, only)))
+        .collect()
+}
+
+/// Append extra TLDs to a base list, normalizing and skipping duplicates
+fn merge_extra_tlds(base: Option<Vec<String>>, extra: &[String]) -> Vec<String> {
+    let mut merged = base.unwrap_or_else(|| vec!["com".to_string()]);
+    for tld in extra {
+        let tld = normalize_cli_tld(tld);
+        if !tld.is_empty() && !merged.contains(&tld) {
+            merged.push(tld);
+        }
+    }
+    merged
+}
+
 /// Parse timeout string like "5s", "30s", "2m" into seconds
 fn parse_timeout_string(timeout_str: &str) -> Result<u64, Box<dyn std::error::Error>> {
     let timeout_str = timeout_str.trim().to_lowercase();
@@ -798,10 +3844,39 @@ fn parse_timeout_string(timeout_str: &str) -> Result<u64, Box<dyn std::error::Er
     }
 }
 
-/// Get the list of domains to check from CLI args, environment, or file
+/// Convert `name@tld` or `name/tld` shorthand into `name.tld`.
+///
+/// The right-hand side must be a bare TLD label (letters, digits, hyphens;
+/// no dots), so real email addresses like `user@example.com` are left
+/// untouched and later rejected as invalid domains.
+fn expand_tld_shorthand(input: &str) -> String {
+    let trimmed = input.trim();
+
+    let Some((name, tld)) = trimmed.split_once('@').or_else(|| trimmed.split_once('/')) else {
+        return input.to_string();
+    };
+
+    let is_tld_label = tld.len() >= 2
+        && tld.len() <= 63
+        && tld.starts_with(|c: char| c.is_ascii_alphabetic())
+        && tld.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    let is_name = !name.is_empty() && !name.contains(['@', '/']);
+
+    if is_tld_label && is_name {
+        format!("{}.{}", name, tld)
+    } else {
+        input.to_string()
+    }
+}
+
+/// Get the list of domains to check from CLI args, environment, or file.
+///
+/// With --passthrough-columns the --file entries come from the already
+/// loaded `passthrough` CSV.
 async fn get_domains_to_check(
     args: &Args,
     config: &CheckConfig,
+    passthrough: Option<&PassthroughColumns>,
 ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
     let mut base_names = Vec::new();
 
@@ -812,7 +3887,12 @@ async fn get_domains_to_check(
         if args.verbose {
             println!("🔧 Reading domains from file (CLI --file): {}", cli_file);
         }
-        let file_domains = read_domains_from_file(cli_file).await?;
+        let file_domains = match passthrough {
+            Some(passthrough) => passthrough.domains.clone(),
+            None if cli_file == "-" => read_domains_from_stdin()?,
+            None if is_remote_list(cli_file) => read_domains_from_url(cli_file).await?,
+            None => read_domains_from_file(cli_file).await?,
+        };
         base_names.extend(file_domains);
     } else if let Ok(env_file_path) = std::env::var("DC_FILE") {
         if args.verbose {
@@ -821,10 +3901,38 @@ async fn get_domains_to_check(
                 env_file_path
             );
         }
-        let file_domains = read_domains_from_file(&env_file_path).await?;
+        let file_domains = if is_remote_list(&env_file_path) {
+            read_domains_from_url(&env_file_path).await?
+        } else {
+            read_domains_from_file(&env_file_path).await?
+        };
         base_names.extend(file_domains);
     }
 
+    // Inline TLD shorthand: `brand@io` / `brand/io` → `brand.io`
+    base_names = base_names
+        .iter()
+        .map(|input| expand_tld_shorthand(input))
+        .collect();
+
+    // Warn about the size of a generation run before building it
+    if args.dry_run || args.verbose {
+        let generation = domain_check_lib::GenerateConfig {
+            patterns: args.patterns.clone().unwrap_or_default(),
+            prefixes: get_generation_prefixes(args).unwrap_or_default(),
+            suffixes: get_generation_suffixes(args).unwrap_or_default(),
+            include_bare: true,
+            ..Default::default()
+        };
+        if generation.has_generation() || generation.has_affixes() {
+            let estimate = domain_check_lib::estimate_generation(&generation, &base_names)?;
+            eprintln!(
+                "This will generate ~{} names before TLD expansion",
+                estimate
+            );
+        }
+    }
+
     // Step 2: Expand patterns into base names
     if let Some(patterns) = &args.patterns {
         for pattern in patterns {
@@ -861,7 +3969,41 @@ async fn get_domains_to_check(
     }
 
     // Step 4: TLD expansion (existing, untouched)
-    let expanded_domains = domain_check_lib::expand_domain_inputs(&base_names, &config.tlds);
+    let expanded_domains =
+        domain_check_lib::expand_domain_inputs(&base_names, &expansion_tlds(args, config));
+
+    // Step 5: Drop excluded TLDs / keep only included ones
+    let include_only: Option<Vec<String>> = args
+        .include_only_tlds
+        .as_ref()
+        .map(|tlds| tlds.iter().map(|t| normalize_cli_tld(t)).collect());
+    let expanded_domains = filter_domains_by_tld(
+        expanded_domains,
+        &config.exclude_tlds,
+        include_only.as_deref(),
+    );
+
+    // Lookups are case-insensitive; show domains the same way unless asked not to
+    let expanded_domains = if args.preserve_case {
+        expanded_domains
+    } else {
+        expanded_domains
+            .into_iter()
+            .map(|d| d.to_lowercase())
+            .collect()
+    };
+
+    // Step 6: Collapse duplicates from overlapping inputs (file + args + generation)
+    let (expanded_domains, duplicate_count) =
+        domain_check_lib::deduplicate_domains(expanded_domains);
+
+    if args.verbose && duplicate_count > 0 {
+        eprintln!(
+            "🔧 Removed {} duplicate domain{}",
+            duplicate_count,
+            if duplicate_count == 1 { "" } else { "s" }
+        );
+    }
 
     if expanded_domains.is_empty() {
         return Err("No valid domains found to check".into());
@@ -872,17 +4014,20 @@ async fn get_domains_to_check(
 
 /// Load the generation config from config file, respecting --config flag
 fn load_generation_config(args: &Args) -> Option<domain_check_lib::GenerationConfig> {
+    load_file_config(args).and_then(|fc| fc.generation)
+}
+
+/// Load the config file for sections not merged into `CheckConfig`.
+fn load_file_config(args: &Args) -> Option<FileConfig> {
     let config_manager = ConfigManager::new(false);
 
-    let file_config = if let Some(explicit_path) = &args.config {
+    if let Some(explicit_path) = &args.config {
         config_manager.load_file(explicit_path).ok()
     } else if let Ok(env_path) = std::env::var("DC_CONFIG") {
         config_manager.load_file(&env_path).ok()
     } else {
         config_manager.discover_and_load().ok()
-    };
-
-    file_config.and_then(|fc| fc.generation)
+    }
 }
 
 /// Get effective prefixes: CLI > env var (DC_PREFIX) > config file
@@ -931,6 +4076,35 @@ fn get_generation_suffixes(args: &Args) -> Option<Vec<String>> {
     None
 }
 
+/// Read TLDs from a file: one per line, `#` comments, leading dots optional
+fn read_tlds_from_file(file_path: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(file_path)
+        .map_err(|e| format!("Cannot read TLD file {}: {}", file_path, e))?;
+
+    let mut tlds: Vec<String> = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let entry = line.split('#').next().unwrap_or("").trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let tld = entry.strip_prefix('.').unwrap_or(entry).to_lowercase();
+        if tld.is_empty() || tld.contains('.') || tld.contains(char::is_whitespace) {
+            return Err(
+                format!("Invalid TLD '{}' on line {} of {}", entry, i + 1, file_path).into(),
+            );
+        }
+        if !tlds.contains(&tld) {
+            tlds.push(tld);
+        }
+    }
+
+    if tlds.is_empty() {
+        return Err(format!("No TLDs found in {}", file_path).into());
+    }
+
+    Ok(tlds)
+}
+
 /// Read domains from a file
 async fn read_domains_from_file(
     file_path: &str,
@@ -942,7 +4116,7 @@ async fn read_domains_from_file(
     // Check if file exists
     let path = Path::new(file_path);
     if !path.exists() {
-        return Err(format!("File not found: {}", file_path).into());
+        return Err(DomainCheckError::file_error(file_path, "file not found").into());
     }
 
     let file = File::open(path)?;
@@ -955,366 +4129,2468 @@ async fn read_domains_from_file(
         line_num += 1;
         match line {
             Ok(line) => {
-                let trimmed = line.trim();
-
-                // Skip empty lines and comments
-                if trimmed.is_empty() || trimmed.starts_with('#') {
-                    continue;
+                match parse_domain_line(&line) {
+                    // Add domain (will be expanded later with TLDs if needed)
+                    Ok(Some(domain)) => domains.push(domain.to_string()),
+                    Ok(None) => {}
+                    Err(reason) => invalid_lines.push(format!("Line {}: {}", line_num, reason)),
                 }
+            }
+            Err(e) => {
+                invalid_lines.push(format!("Line {}: Error reading line - {}", line_num, e));
+            }
+        }
+    }
 
-                // Handle inline comments
-                let domain_part = trimmed.split('#').next().unwrap_or("").trim();
-                if domain_part.is_empty() {
-                    continue;
-                }
+    report_invalid_lines(&invalid_lines);
 
-                // Basic domain validation
-                if domain_part.len() < 2 {
-                    invalid_lines.push(format!(
-                        "Line {}: '{}' - domain too short",
-                        line_num, domain_part
-                    ));
-                    continue;
-                }
+    // Check if we have any valid domains
+    if domains.is_empty() {
+        return Err("No valid domains found in the file.".into());
+    }
+
+    Ok(domains)
+}
+
+/// Largest remote domain list `--file <URL>` will download.
+const MAX_REMOTE_LIST_BYTES: usize = 10 * 1024 * 1024;
+
+/// How long fetching a remote domain list may take.
+const REMOTE_LIST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Whether a `--file` value names an http(s) URL rather than a local path.
+fn is_remote_list(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+/// Download a domain list from an http(s) URL and parse it like a local file.
+///
+/// Lists larger than `MAX_REMOTE_LIST_BYTES` are rejected without reading
+/// the rest of the body.
+async fn read_domains_from_url(url: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let fetch_error = |reason: String| DomainCheckError::file_error(url, reason);
+
+    let mut response = domain_check_lib::shared_http_client()?
+        .get(url)
+        .timeout(REMOTE_LIST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| fetch_error(format!("request failed: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(fetch_error(format!("server answered {}", response.status())).into());
+    }
+
+    let too_large = || {
+        fetch_error(format!(
+            "list is larger than {} bytes",
+            MAX_REMOTE_LIST_BYTES
+        ))
+    };
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_REMOTE_LIST_BYTES as u64)
+    {
+        return Err(too_large().into());
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| fetch_error(format!("reading body failed: {}", e)))?
+    {
+        if body.len() + chunk.len() > MAX_REMOTE_LIST_BYTES {
+            return Err(too_large().into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    let mut domains = Vec::new();
+    let mut invalid_lines = Vec::new();
+    for (i, line) in String::from_utf8_lossy(&body).lines().enumerate() {
+        match parse_domain_line(line) {
+            Ok(Some(domain)) => domains.push(domain.to_string()),
+            Ok(None) => {}
+            Err(reason) => invalid_lines.push(format!("Line {}: {}", i + 1, reason)),
+        }
+    }
+    report_invalid_lines(&invalid_lines);
+
+    if domains.is_empty() {
+        return Err(format!("No valid domains found at {}.", url).into());
+    }
+    Ok(domains)
+}
+
+/// Print the first few invalid entries of a domain list to stderr.
+fn report_invalid_lines(invalid_lines: &[String]) {
+    if invalid_lines.is_empty() {
+        return;
+    }
+    eprintln!(
+        "⚠️ Found {} invalid entries in the file:",
+        invalid_lines.len()
+    );
+    for invalid in &invalid_lines[..invalid_lines.len().min(5)] {
+        eprintln!("  {}", invalid);
+    }
+    if invalid_lines.len() > 5 {
+        eprintln!("  ... and {} more invalid entries", invalid_lines.len() - 5);
+    }
+    eprintln!();
+}
+
+/// Read domains piped on stdin (`--file -`).
+///
+/// Input starting with `[` is parsed as a JSON array, so `jq` output can be
+/// piped straight in; anything else is read line by line like a domain file.
+fn read_domains_from_stdin() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    use std::io::Read;
+
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+
+    let domains = if input.trim_start().starts_with('[') {
+        parse_domain_json(&input)?
+    } else {
+        input
+            .lines()
+            .filter_map(|line| parse_domain_line(line).ok().flatten())
+            .map(str::to_string)
+            .collect()
+    };
+
+    if domains.is_empty() {
+        return Err("No valid domains found on stdin.".into());
+    }
+    Ok(domains)
+}
+
+/// Parse a JSON array of domain names, or of objects with a `domain` key
+/// (such as domain-check's own `--json` results).
+fn parse_domain_json(input: &str) -> Result<Vec<String>, String> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(input)
+        .map_err(|e| format!("Invalid JSON domain list on stdin: {}", e))?;
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let domain = match entry {
+                serde_json::Value::String(s) => Some(s.as_str()),
+                serde_json::Value::Object(obj) => obj.get("domain").and_then(|d| d.as_str()),
+                _ => None,
+            };
+            domain.map(|d| d.trim().to_string()).ok_or_else(|| {
+                format!(
+                    "Entry {} of the JSON domain list is not a string or an object with a \"domain\" string",
+                    i + 1
+                )
+            })
+        })
+        .filter(|entry| !matches!(entry, Ok(d) if d.is_empty()))
+        .collect()
+}
+
+/// Parse one line of a domain file.
+///
+/// Returns the entry, `None` for blank and comment lines, or why the line is
+/// invalid.
+fn parse_domain_line(line: &str) -> Result<Option<&str>, String> {
+    // Skip empty lines and comments, including inline ones
+    let domain_part = line.split('#').next().unwrap_or("").trim();
+    if domain_part.is_empty() {
+        return Ok(None);
+    }
+
+    // Basic domain validation
+    if domain_part.len() < 2 {
+        return Err(format!("'{}' - domain too short", domain_part));
+    }
+
+    Ok(Some(domain_part))
+}
 
-                // Add domain (will be expanded later with TLDs if needed)
-                domains.push(domain_part.to_string());
+/// Split one CSV line into fields, honouring double-quoted fields.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
             }
-            Err(e) => {
-                invalid_lines.push(format!("Line {}: Error reading line - {}", line_num, e));
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Quote a CSV output value when it contains a comma, quote or newline.
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+/// Input columns carried into --csv output by --passthrough-columns.
+///
+/// The --file is read as CSV: the first non-comment line is a header with a
+/// `domain` column, and each row's values for `columns` are kept by domain.
+struct PassthroughColumns {
+    columns: Vec<String>,
+    /// Domain entries in file order, as for `read_domains_from_file`
+    domains: Vec<String>,
+    /// Lowercased domain entry → values, in `columns` order
+    rows: std::collections::HashMap<String, Vec<String>>,
+}
+
+impl PassthroughColumns {
+    fn load(file_path: &str, columns: &[String]) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(file_path)
+            .map_err(|e| DomainCheckError::file_error(file_path, e.to_string()))?;
+        Self::parse(file_path, &content, columns)
+    }
+
+    fn parse(
+        file_path: &str,
+        content: &str,
+        columns: &[String],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut lines = content
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+        let header = split_csv_line(lines.next().unwrap_or_default());
+        let position = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    DomainCheckError::file_error(
+                        file_path,
+                        format!("CSV header has no '{}' column", name),
+                    )
+                })
+        };
+        let domain_index = position("domain")?;
+        let indexes = columns
+            .iter()
+            .map(|c| position(c))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut domains = Vec::new();
+        let mut rows = std::collections::HashMap::new();
+        for line in lines {
+            let fields = split_csv_line(line);
+            let Some(domain) = fields.get(domain_index).filter(|d| !d.is_empty()) else {
+                continue;
+            };
+            let values = indexes
+                .iter()
+                .map(|&i| fields.get(i).cloned().unwrap_or_default())
+                .collect();
+            rows.insert(domain.to_lowercase(), values);
+            domains.push(domain.clone());
+        }
+
+        if domains.is_empty() {
+            return Err(DomainCheckError::file_error(file_path, "no valid domains found").into());
+        }
+
+        Ok(Self {
+            columns: columns.to_vec(),
+            domains,
+            rows,
+        })
+    }
+
+    /// Values for a checked domain: its own row, or the row of the base name
+    /// it was expanded from.
+    fn values_for(&self, domain: &str) -> Option<&[String]> {
+        let domain = domain.to_lowercase();
+        let base = domain.split('.').next().unwrap_or_default();
+        self.rows
+            .get(&domain)
+            .or_else(|| self.rows.get(base))
+            .map(Vec::as_slice)
+    }
+}
+
+/// Reads a domain file one line at a time for --stream-file.
+///
+/// Follows the same rules as `read_domains_from_file` and applies the
+/// `name@tld` shorthand; the first few invalid lines are reported as they
+/// are reached and the rest are only counted.
+struct DomainFileReader {
+    lines: std::io::Lines<std::io::BufReader<std::fs::File>>,
+    line_num: usize,
+    invalid: usize,
+}
+
+impl DomainFileReader {
+    fn open(file_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let file = std::fs::File::open(file_path)
+            .map_err(|e| DomainCheckError::file_error(file_path, e.to_string()))?;
+        Ok(Self {
+            lines: std::io::BufReader::new(file).lines(),
+            line_num: 0,
+            invalid: 0,
+        })
+    }
+
+    fn report_invalid(&mut self, reason: String) {
+        self.invalid += 1;
+        if self.invalid <= 5 {
+            eprintln!("⚠️ Line {}: {}", self.line_num, reason);
+        }
+    }
+}
+
+impl Iterator for DomainFileReader {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            let line = self.lines.next()?;
+            self.line_num += 1;
+            match line {
+                Ok(line) => match parse_domain_line(&line) {
+                    Ok(Some(domain)) => return Some(expand_tld_shorthand(domain)),
+                    Ok(None) => {}
+                    Err(reason) => self.report_invalid(reason),
+                },
+                Err(e) => self.report_invalid(format!("Error reading line - {}", e)),
+            }
+        }
+    }
+}
+
+fn display_results(
+    results: &[domain_check_lib::DomainResult],
+    args: &Args,
+    config: &CheckConfig,
+    duration: std::time::Duration,
+    passthrough: Option<&PassthroughColumns>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if args.bool_output {
+        let mut stdout = std::io::stdout();
+        for result in results {
+            exit_on_broken_pipe(writeln!(stdout, "{}", bool_answer(result)))?;
+        }
+    } else if args.json && args.json_envelope {
+        let envelope = JsonEnvelope {
+            config: RunConfig::new(config, args),
+            results,
+        };
+        let json = serde_json::to_string_pretty(&envelope)?;
+        exit_on_broken_pipe(writeln!(std::io::stdout(), "{}", json))?;
+    } else if args.json {
+        display_json_results(results)?;
+    } else if args.csv {
+        let fields = info_fields(args);
+        match &args.output {
+            Some(path) => {
+                let (mut file, empty) = open_output_file(path, args.append)?;
+                display_csv_results(&mut file, results, &fields, passthrough, empty)?;
+            }
+            None => {
+                display_csv_results(&mut std::io::stdout(), results, &fields, passthrough, true)?
             }
         }
+    } else {
+        display_text_results(results, args, duration)?;
+    }
+
+    Ok(())
+}
+
+/// The --bool answer for a result: `true`, `false` or `unknown`.
+fn bool_answer(result: &domain_check_lib::DomainResult) -> &'static str {
+    match result.available {
+        Some(true) => "true",
+        Some(false) => "false",
+        None => "unknown",
+    }
+}
+
+/// Batch text layout: --group-by when given (already validated), otherwise
+/// status sections with --pretty and a flat list without.
+fn group_by(args: &Args) -> ui::GroupBy {
+    match args.group_by.as_deref().and_then(|mode| mode.parse().ok()) {
+        Some(mode) => mode,
+        None if args.pretty => ui::GroupBy::Status,
+        None => ui::GroupBy::None,
+    }
+}
+
+/// Info fields selected with --fields (already validated), or the defaults
+fn info_fields(args: &Args) -> Vec<ui::InfoField> {
+    args.fields
+        .as_ref()
+        .and_then(|names| ui::parse_info_fields(names).ok())
+        .unwrap_or_else(|| ui::InfoField::DEFAULT.to_vec())
+}
+
+/// Display results in JSON format
+fn display_json_results(
+    results: &[domain_check_lib::DomainResult],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(results)?;
+    exit_on_broken_pipe(writeln!(std::io::stdout(), "{}", json))?;
+    Ok(())
+}
+
+/// The --csv header row for the selected fields and passthrough columns.
+fn csv_header(fields: &[ui::InfoField], passthrough: Option<&PassthroughColumns>) -> String {
+    let columns: Vec<&str> = fields.iter().map(|f| f.name()).collect();
+    let mut header = format!("domain,available,{},method", columns.join(","));
+    if let Some(passthrough) = passthrough {
+        for column in &passthrough.columns {
+            header.push(',');
+            header.push_str(&csv_field(column));
+        }
+    }
+    header
+}
+
+/// Display results in CSV format, with the header row unless `header` is false
+fn display_csv_results(
+    out: &mut impl Write,
+    results: &[domain_check_lib::DomainResult],
+    fields: &[ui::InfoField],
+    passthrough: Option<&PassthroughColumns>,
+    header: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if header {
+        exit_on_broken_pipe(writeln!(out, "{}", csv_header(fields, passthrough)))?;
+    }
+
+    for result in results {
+        let available = match result.available {
+            Some(true) => "true",
+            Some(false) => "false",
+            None => "unknown",
+        };
+
+        // List fields use ';' so they stay in one column
+        let values: Vec<String> = fields
+            .iter()
+            .map(|f| {
+                result
+                    .info
+                    .as_ref()
+                    .and_then(|i| f.value(i, ";"))
+                    .unwrap_or_else(|| "-".to_string())
+            })
+            .collect();
+
+        let mut line = format!(
+            "{},{},{},{}",
+            result.domain,
+            available,
+            values.join(","),
+            result.method_used
+        );
+        if let Some(passthrough) = passthrough {
+            match passthrough.values_for(&result.domain) {
+                Some(extra) => {
+                    for value in extra {
+                        line.push(',');
+                        line.push_str(&csv_field(value));
+                    }
+                }
+                None => line.push_str(&",-".repeat(passthrough.columns.len())),
+            }
+        }
+        exit_on_broken_pipe(writeln!(out, "{}", line))?;
+    }
+
+    Ok(())
+}
+
+/// Display results in human-readable text format
+fn display_text_results(
+    results: &[domain_check_lib::DomainResult],
+    args: &Args,
+    duration: std::time::Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let fields = info_fields(args);
+    let errored: Vec<domain_check_lib::DomainResult>;
+    let shown = if args.only_errors {
+        errored = results
+            .iter()
+            .filter(|r| r.available.is_none())
+            .cloned()
+            .collect();
+        &errored[..]
+    } else {
+        results
+    };
+
+    if args.legacy_format {
+        // The old CLI printed a flat list in input order
+        let mut stdout = std::io::stdout();
+        for result in shown {
+            exit_on_broken_pipe(ui::write_result_legacy(
+                &mut stdout,
+                result,
+                args.pretty,
+                args.info,
+                &fields,
+                args.debug,
+            ))?;
+        }
+    } else {
+        match group_by(args) {
+            // Pretty mode: grouped layout with section headers
+            ui::GroupBy::Status => exit_on_broken_pipe(ui::write_grouped_results(
+                &mut std::io::stdout(),
+                shown,
+                args.info,
+                &fields,
+                args.debug,
+                registrar_allowlist(args),
+            ))?,
+            ui::GroupBy::Tld => exit_on_broken_pipe(ui::write_tld_grouped_results(
+                &mut std::io::stdout(),
+                shown,
+                args.info,
+                &fields,
+                args.debug,
+                registrar_allowlist(args),
+            ))?,
+            // Default mode: colored flat list
+            ui::GroupBy::None => {
+                let mut stdout = std::io::stdout();
+                for result in shown {
+                    exit_on_broken_pipe(ui::write_result_default(
+                        &mut stdout,
+                        result,
+                        args.info,
+                        &fields,
+                        args.debug,
+                        None,
+                        registrar_allowlist(args),
+                    ))?;
+                }
+            }
+        }
+    }
+
+    // Shared summary for both modes
+    if results.len() > 1 && !args.no_summary {
+        let available = results.iter().filter(|r| r.available == Some(true)).count();
+        let taken = results
+            .iter()
+            .filter(|r| r.available == Some(false))
+            .count();
+        let unknown = results.iter().filter(|r| r.available.is_none()).count();
+        let mut stdout = std::io::stdout();
+        exit_on_broken_pipe(writeln!(stdout))?;
+        exit_on_broken_pipe(ui::write_summary(
+            &mut stdout,
+            results.len(),
+            available,
+            taken,
+            unknown,
+            duration,
+        ))?;
+        if args.method_stats || args.verbose {
+            ui::print_method_stats(results);
+        }
+        if args.only_errors {
+            ui::print_error_breakdown(results);
+        }
+    }
+
+    if args.registrar_stats {
+        ui::print_registrar_stats(results);
+    }
+
+    if args.registrar_consistency {
+        ui::print_registrar_consistency(results);
+    }
+
+    Ok(())
+}
+
+// domain-check/src/main.rs tests module
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Helper function with all required fields
+    fn create_test_args() -> Args {
+        Args {
+            domains: vec![], // Empty domains for testing
+            tlds: None,
+            tld_file: None,
+            add_tlds: None,
+            exclude_tlds: None,
+            include_only_tlds: None,
+            file: None,
+            config: None,
+            concurrency: "20".to_string(),
+            concurrency_ramp: None,
+            concurrency_per_host: None,
+            timeout_retries: None,
+            rdap_server: None,
+            bootstrap_url: None,
+            timing_log: None,
+            force: false,
+            info: false,
+            registrar_stats: false,
+            registrar_consistency: false,
+            registrar_allowlist: None,
+            method_stats: false,
+            endpoint_report: false,
+            summary_json: None,
+            manifest: None,
+            failures_file: None,
+            compact_summary: false,
+            no_summary: false,
+            progress_json: false,
+            progress_json_results: false,
+            watch: false,
+            watch_interval: None,
+            webhook: None,
+            batch_size: None,
+            preserve_case: false,
+            fields: None,
+            date_format: None,
+            json_envelope: false,
+            ndjson: false,
+            bool_output: false,
+            legacy_format: false,
+            only_errors: false,
+            min_confidence: None,
+            output: None,
+            append: false,
+            no_whois: false,
+            race: false,
+            treat_dropping_as_available: false,
+            detect_wildcards: false,
+            detect_parking: false,
+            check_redirects: false,
+            dns_servers: None,
+            no_bootstrap: false,
+            json: false,
+            csv: false,
+            pretty: false,
+            group_by: None,
+            batch: false,
+            streaming: false,
+            debug: false,
+            verbose: false,
+            all_tlds: false,
+            tld_limit: None,
+            sample_tlds: None,
+            preset: None,
+            list_presets: false,
+            show_tlds: false,
+            stream_file: false,
+            passthrough_columns: None,
+            baseline: false,
+            with_aliases: false,
+            with_variants: false,
+            resume: None,
+            recheck_available: None,
+            check_confusables: false,
+            reject_confusables: false,
+            skip_reserved: false,
+            strict_validation: false,
+            patterns: None,
+            prefixes: None,
+            suffixes: None,
+            dry_run: false,
+            estimate: false,
+            estimate_latency: None,
+            yes: false,
+            help: false,
+            version: false,
+            command: None,
+        }
+    }
+
+    #[test]
+    fn test_should_enable_bootstrap_default() {
+        // Bootstrap is now enabled by default
+        let args = create_test_args();
+        let tlds = Some(vec!["com".to_string(), "org".to_string()]);
+        assert!(should_enable_bootstrap(&args, &tlds));
+    }
+
+    #[test]
+    fn test_should_disable_bootstrap_with_flag() {
+        // --no-bootstrap disables it
+        let mut args = create_test_args();
+        args.no_bootstrap = true;
+        let tlds = Some(vec!["com".to_string(), "org".to_string()]);
+        assert!(!should_enable_bootstrap(&args, &tlds));
+    }
+
+    // validation tests to include required domains
+    #[test]
+    fn test_validate_args_invalid_preset_now_allowed() {
+        // After Phase 4: Invalid presets are allowed in validate_args()
+        // and checked later during config resolution
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.preset = Some("invalid_preset".to_string());
+
+        let result = validate_args(&args);
+        assert!(result.is_ok()); // Now passes validation, fails later in config resolution
+    }
+
+    #[test]
+    fn test_validate_args_conflicting_flags() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()]; // Add required domain
+        args.tlds = Some(vec!["com".to_string()]);
+        args.preset = Some("startup".to_string());
+
+        let result = validate_args(&args);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .contains("Cannot specify multiple TLD sources"));
+    }
+
+    #[test]
+    fn test_validate_args_all_and_preset_conflict() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()]; // Add required domain
+        args.all_tlds = true;
+        args.preset = Some("startup".to_string());
+
+        let result = validate_args(&args);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .contains("Cannot specify multiple TLD sources"));
+    }
+
+    #[test]
+    fn test_validate_args_tld_file_and_tld_conflict() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.tlds = Some(vec!["com".to_string()]);
+        args.tld_file = Some("tlds.txt".to_string());
+
+        let result = validate_args(&args);
+        assert!(result
+            .unwrap_err()
+            .contains("Cannot specify multiple TLD sources"));
+    }
+
+    #[test]
+    fn test_read_tlds_from_file_normalizes() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            "# targets
+.COM
+io  # startup
+
+org
+com
+",
+        )
+        .unwrap();
+
+        let tlds = read_tlds_from_file(file.path().to_str().unwrap()).unwrap();
+        assert_eq!(tlds, vec!["com", "io", "org"]);
+    }
+
+    #[test]
+    fn test_read_tlds_from_file_rejects_multi_label() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            "com
+co.uk
+",
+        )
+        .unwrap();
+
+        let err = read_tlds_from_file(file.path().to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_validate_args_valid_preset() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()]; // Add required domain
+        args.preset = Some("startup".to_string());
+
+        let result = validate_args(&args);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_validate_args_valid_all_flag() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()]; // Add required domain
+        args.all_tlds = true;
+
+        let result = validate_args(&args);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_validate_args_streaming_with_json_rejected() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.streaming = true;
+        args.json = true;
+
+        let result = validate_args(&args);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("--streaming"));
+    }
+
+    #[test]
+    fn test_validate_args_streaming_with_csv_rejected() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.streaming = true;
+        args.csv = true;
+
+        let result = validate_args(&args);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("--streaming"));
+    }
+
+    #[test]
+    fn test_validate_args_batch_with_json_allowed() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.batch = true;
+        args.json = true;
+
+        let result = validate_args(&args);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_no_whois_flag_only_disables() {
+        // When --no-whois is NOT passed, config/env values should be preserved
+        let args = create_test_args(); // no_whois defaults to false
+        let config = CheckConfig {
+            enable_whois_fallback: false, // Simulates config setting
+            ..CheckConfig::default()
+        };
+
+        let result = apply_cli_args_to_config(config, &args).unwrap();
+        assert!(
+            !result.enable_whois_fallback,
+            "Config whois_fallback=false should be preserved when --no-whois is not passed"
+        );
+    }
+
+    #[test]
+    fn test_no_whois_flag_overrides_config() {
+        // When --no-whois IS passed, it should disable whois regardless of config
+        let mut args = create_test_args();
+        args.no_whois = true;
+        let config = CheckConfig {
+            enable_whois_fallback: true,
+            ..CheckConfig::default()
+        };
+
+        let result = apply_cli_args_to_config(config, &args).unwrap();
+        assert!(
+            !result.enable_whois_fallback,
+            "--no-whois should disable whois fallback"
+        );
+    }
+
+    #[test]
+    fn test_info_flag_only_enables() {
+        // When --info is NOT passed, config/env values should be preserved
+        let args = create_test_args(); // info defaults to false
+        let config = CheckConfig {
+            detailed_info: true, // Simulates config setting
+            ..CheckConfig::default()
+        };
+
+        let result = apply_cli_args_to_config(config, &args).unwrap();
+        assert!(
+            result.detailed_info,
+            "Config detailed_info=true should be preserved when --info is not passed"
+        );
+    }
+
+    #[test]
+    fn test_info_flag_overrides_config() {
+        // When --info IS passed, it should enable info regardless of config
+        let mut args = create_test_args();
+        args.info = true;
+        let config = CheckConfig {
+            detailed_info: false,
+            ..CheckConfig::default()
+        };
+
+        let result = apply_cli_args_to_config(config, &args).unwrap();
+        assert!(result.detailed_info, "--info should enable detailed info");
+    }
+
+    #[test]
+    fn test_reserved_label_warnings() {
+        let domains = vec![
+            "nic.com".to_string(),
+            "brand.com".to_string(),
+            "nic.io".to_string(),
+            "www.dev".to_string(),
+        ];
+
+        let warnings = reserved_label_warnings(&domains, false);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("'nic'"));
+        assert!(warnings[0].contains("(2 domains)"));
+        assert!(warnings[1].contains("'www'"));
+        assert!(!warnings[1].contains("skipping"));
+
+        assert!(reserved_label_warnings(&domains, true)[0].ends_with(", skipping"));
+        assert!(reserved_label_warnings(&["brand.com".to_string()], false).is_empty());
+    }
+
+    #[test]
+    fn test_filter_domains_by_tld() {
+        let domains: Vec<String> = ["a.com", "a.loan", "a.top", "a.co.uk", "a.io"]
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+
+        let excluded = filter_domains_by_tld(
+            domains.clone(),
+            &["loan".to_string(), "uk".to_string()],
+            None,
+        );
+        assert_eq!(excluded, vec!["a.com", "a.top", "a.io"]);
+
+        let only = vec!["com".to_string(), "io".to_string()];
+        let included = filter_domains_by_tld(domains, &["io".to_string()], Some(&only));
+        assert_eq!(included, vec!["a.com"]);
+    }
+
+    #[test]
+    fn test_exclude_tld_merges_with_config() {
+        let mut args = create_test_args();
+        args.exclude_tlds = Some(vec![".TOP".to_string(), "loan".to_string()]);
+        let config = CheckConfig {
+            exclude_tlds: vec!["loan".to_string()],
+            ..CheckConfig::default()
+        };
+
+        let result = apply_cli_args_to_config(config, &args).unwrap();
+        assert_eq!(result.exclude_tlds, vec!["loan", "top"]);
+    }
+
+    #[test]
+    fn test_resolved_tlds_applies_exclusions() {
+        let mut args = create_test_args();
+        args.preset = Some("startup".to_string());
+        args.exclude_tlds = Some(vec![".IO".to_string()]);
+        args.show_tlds = true;
+        let config = build_config(&args).unwrap();
+        let tlds = resolved_tlds(&args, &config);
+        assert!(!tlds.contains(&"io".to_string()));
+        assert!(tlds.contains(&"com".to_string()));
+
+        args.include_only_tlds = Some(vec!["com".to_string(), "ai".to_string()]);
+        assert_eq!(resolved_tlds(&args, &config), vec!["com", "ai"]);
+    }
+
+    #[test]
+    fn test_is_remote_list() {
+        assert!(is_remote_list("https://example.com/domains.txt"));
+        assert!(is_remote_list("http://10.0.0.1:8080/list"));
+        assert!(!is_remote_list("domains.txt"));
+        assert!(!is_remote_list("-"));
+        assert!(!is_remote_list("ftp://example.com/list"));
+    }
+
+    #[test]
+    fn test_validate_args_remote_file_with_stream_file_rejected() {
+        let mut args = create_test_args();
+        args.file = Some("https://example.com/list.txt".to_string());
+        args.stream_file = true;
+        args.concurrency = "10".to_string();
+        assert!(validate_args(&args).unwrap_err().contains("URL"));
+    }
+
+    #[test]
+    fn test_add_tld_extends_preset() {
+        let mut args = create_test_args();
+        args.preset = Some("startup".to_string());
+        args.add_tlds = Some(vec!["gg".to_string(), ".SH".to_string(), "io".to_string()]);
+
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        let tlds = result.tlds.unwrap();
+        let preset = domain_check_lib::get_preset_tlds("startup").unwrap();
+
+        assert_eq!(tlds.len(), preset.len() + 2);
+        assert_eq!(&tlds[..preset.len()], &preset[..]);
+        assert_eq!(&tlds[preset.len()..], &["gg", "sh"]);
+    }
+
+    #[test]
+    fn test_add_tld_without_base_source_extends_com() {
+        let mut args = create_test_args();
+        args.add_tlds = Some(vec!["io".to_string()]);
+
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert_eq!(result.tlds, Some(vec!["com".to_string(), "io".to_string()]));
+    }
+
+    fn make_result(domain: &str, available: Option<bool>) -> domain_check_lib::DomainResult {
+        domain_check_lib::DomainResult::new(domain, available, domain_check_lib::CheckMethod::Rdap)
+    }
+
+    #[test]
+    fn test_collect_alias_domains_for_taken_com() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "[tld_aliases]\ncom = [\"co\", \"io\"]\n").unwrap();
+        let file_config = ConfigManager::new(false).load_file(file.path()).unwrap();
+        let config = merge_file_config_into_check_config(CheckConfig::default(), file_config);
+
+        let results = vec![
+            make_result("startup.com", Some(false)),
+            make_result("free.com", Some(true)),
+        ];
+        let targets = collect_alias_domains(&results, &config.tld_aliases);
+        assert_eq!(targets, vec!["startup.co", "startup.io"]);
+    }
+
+    /// Load a config file with `contents` the way build_config does.
+    fn config_from_file(contents: &str) -> CheckConfig {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), contents).unwrap();
+        let file_config = ConfigManager::new(false).load_file(file.path()).unwrap();
+        merge_file_config_into_check_config(CheckConfig::default(), file_config)
+    }
+
+    #[test]
+    fn test_config_tlds_used_without_cli_source() {
+        let config = config_from_file("[defaults]\ntlds = [\"io\", \"dev\"]\n");
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        let result = apply_cli_args_to_config(config, &args).unwrap();
+        assert_eq!(result.tlds, Some(vec!["io".to_string(), "dev".to_string()]));
+    }
+
+    #[test]
+    fn test_cli_tld_overrides_config_tlds() {
+        let config = config_from_file("[defaults]\ntlds = [\"io\", \"dev\"]\n");
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.tlds = Some(vec!["org".to_string()]);
+        assert!(validate_args(&args).is_ok());
+        let result = apply_cli_args_to_config(config, &args).unwrap();
+        assert_eq!(result.tlds, Some(vec!["org".to_string()]));
+    }
+
+    #[test]
+    fn test_config_preset_resolves_custom_presets() {
+        let config = config_from_file(
+            "[defaults]\npreset = \"mine\"\n\n[custom_presets]\nmine = [\"app\", \"dev\"]\n",
+        );
+        assert_eq!(
+            config.tlds,
+            Some(vec!["app".to_string(), "dev".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_config_preset_and_tlds_conflict() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            "[defaults]\npreset = \"startup\"\ntlds = [\"com\"]\n",
+        )
+        .unwrap();
+        let err = ConfigManager::new(false)
+            .load_file(file.path())
+            .unwrap_err();
+        assert!(err.to_string().contains("'preset' and 'tlds'"), "{}", err);
+    }
+
+    #[test]
+    fn test_has_cli_tld_source() {
+        let mut args = create_test_args();
+        assert!(!has_cli_tld_source(&args));
+        args.all_tlds = true;
+        assert!(has_cli_tld_source(&args));
+    }
+
+    #[test]
+    fn test_tld_timeouts_from_config_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "[tld_timeouts]\nch = \"15s\"\n").unwrap();
+        let file_config = ConfigManager::new(false).load_file(file.path()).unwrap();
+        let config = merge_file_config_into_check_config(CheckConfig::default(), file_config);
+
+        assert_eq!(
+            config.tld_timeouts.get("ch"),
+            Some(&std::time::Duration::from_secs(15))
+        );
+    }
+
+    #[test]
+    fn test_tld_methods_from_config_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "[tld_methods]\nde = \"whois\"\n").unwrap();
+        let file_config = ConfigManager::new(false).load_file(file.path()).unwrap();
+        let config = merge_file_config_into_check_config(CheckConfig::default(), file_config);
+
+        assert_eq!(
+            config.tld_method_preference.get("de"),
+            Some(&domain_check_lib::CheckMethod::Whois)
+        );
+    }
+
+    #[test]
+    fn test_collect_alias_domains_skips_already_checked() {
+        let aliases =
+            std::collections::HashMap::from([("com".to_string(), vec!["co".into(), "io".into()])]);
+        let results = vec![
+            make_result("startup.com", Some(false)),
+            make_result("startup.io", Some(true)),
+        ];
+        let targets = collect_alias_domains(&results, &aliases);
+        assert_eq!(targets, vec!["startup.co"]);
+    }
+
+    #[test]
+    fn test_group_with_aliases_places_aliases_after_primary() {
+        let aliases =
+            std::collections::HashMap::from([("com".to_string(), vec!["co".into(), "io".into()])]);
+        let primary = vec![
+            make_result("alpha.com", Some(false)),
+            make_result("beta.com", Some(true)),
+        ];
+        let alias_results = vec![
+            make_result("alpha.io", Some(false)),
+            make_result("alpha.co", Some(true)),
+        ];
+
+        let grouped = group_with_aliases(primary, alias_results, &aliases);
+        let order: Vec<&str> = grouped.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(order, vec!["alpha.com", "alpha.co", "alpha.io", "beta.com"]);
+    }
+
+    #[test]
+    fn test_validate_args_streaming_with_aliases_rejected() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.streaming = true;
+        args.with_aliases = true;
+
+        let result = validate_args(&args);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("--with-aliases"));
+    }
+
+    #[test]
+    fn test_partition_resume_rechecks_only_unknowns() {
+        let prior = vec![
+            make_result("alpha.com", Some(true)),
+            make_result("beta.com", Some(false)),
+            make_result("gamma.com", None),
+            make_result("stale.com", Some(true)), // not in current input
+        ];
+        let domains = vec![
+            "alpha.com".to_string(),
+            "beta.com".to_string(),
+            "gamma.com".to_string(),
+            "delta.com".to_string(),
+        ];
+
+        let (to_check, carried) = partition_resume(domains, prior);
+        assert_eq!(to_check, vec!["gamma.com", "delta.com"]);
+        let carried_domains: Vec<&str> = carried.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(carried_domains, vec!["alpha.com", "beta.com"]);
+    }
+
+    #[test]
+    fn test_previously_available_from_prior_file() {
+        let file = tempfile::NamedTempFile::with_suffix(".json").unwrap();
+        std::fs::write(
+            file.path(),
+            r#"[
+  {"domain": "alpha.com", "available": true, "method_used": "rdap"},
+  {"domain": "beta.com", "available": false, "method_used": "rdap"},
+  {"domain": "gamma.com", "available": null, "method_used": "unknown"},
+  {"domain": "delta.io", "available": true, "method_used": "whois"}
+]"#,
+        )
+        .unwrap();
+
+        let prior = load_prior_results(file.path().to_str().unwrap()).unwrap();
+        assert_eq!(previously_available(prior), vec!["alpha.com", "delta.io"]);
+    }
+
+    #[test]
+    fn test_recheck_report_lists_newly_taken() {
+        let results = vec![
+            make_result("alpha.com", Some(true)),
+            make_result("delta.io", Some(false)),
+        ];
+        assert_eq!(
+            recheck_report(&results),
+            "⚠️ 1 of 2 previously available domains are now taken: delta.io"
+        );
+
+        let results = vec![
+            make_result("alpha.com", Some(true)),
+            make_result("delta.io", None),
+        ];
+        assert_eq!(
+            recheck_report(&results),
+            "✅ All 1 previously available domain is still available (1 could not be verified)"
+        );
+    }
+
+    #[test]
+    fn test_validate_args_recheck_available_is_a_domain_source() {
+        let mut args = create_test_args();
+        args.recheck_available = Some("prior.json".to_string());
+        assert!(validate_args(&args).is_ok());
+
+        args.domains = vec!["extra".to_string()];
+        let err = validate_args(&args).unwrap_err();
+        assert!(err.contains("--recheck-available"), "{}", err);
+    }
+
+    #[test]
+    fn test_merge_resumed_results_keeps_input_order() {
+        let order = vec![
+            "alpha.com".to_string(),
+            "beta.com".to_string(),
+            "gamma.com".to_string(),
+        ];
+        let carried = vec![
+            make_result("alpha.com", Some(true)),
+            make_result("gamma.com", Some(false)),
+        ];
+        let fresh = vec![make_result("beta.com", Some(true))];
+
+        let merged = merge_resumed_results(&order, carried, fresh);
+        let domains: Vec<&str> = merged.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(domains, vec!["alpha.com", "beta.com", "gamma.com"]);
+    }
+
+    #[test]
+    fn test_split_csv_line_handles_quotes() {
+        assert_eq!(
+            split_csv_line(r#"acme.com, alice ,"renew, ""soon""""#),
+            vec!["acme.com", "alice", r#"renew, "soon""#]
+        );
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field(r#"a, "b""#), r#""a, ""b""""#);
+    }
+
+    #[test]
+    fn test_passthrough_columns_join_by_domain() {
+        let csv = "# portfolio\nDomain,owner,notes\nacme.com,alice,legacy\nbeta,bob,\n";
+        let columns = vec!["owner".to_string()];
+        let passthrough = PassthroughColumns::parse("p.csv", csv, &columns).unwrap();
+
+        assert_eq!(passthrough.domains, vec!["acme.com", "beta"]);
+        assert_eq!(
+            passthrough.values_for("ACME.com"),
+            Some(&["alice".to_string()][..])
+        );
+        // Base names match every TLD they were expanded to
+        assert_eq!(
+            passthrough.values_for("beta.io"),
+            Some(&["bob".to_string()][..])
+        );
+        assert_eq!(passthrough.values_for("other.com"), None);
+
+        let missing = vec!["team".to_string()];
+        let err = PassthroughColumns::parse("p.csv", csv, &missing)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("'team'"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_args_passthrough_needs_file_and_csv() {
+        let mut args = create_test_args();
+        args.passthrough_columns = Some(vec!["owner".to_string()]);
+        args.file = Some("portfolio.csv".to_string());
+        assert!(validate_args(&args).is_err());
+
+        args.csv = true;
+        assert!(validate_args(&args).is_ok());
+
+        args.file = None;
+        args.domains = vec!["test".to_string()];
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_parse_prior_csv() {
+        let csv = "domain,available,registrar,created,expires,method\n\
+                   alpha.com,true,-,-,-,RDAP\n\
+                   beta.com,false,GoDaddy,2020-01-01,-,WHOIS\n\
+                   gamma.com,unknown,-,-,-,Unknown\n";
+        let results = parse_prior_csv(csv);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].available, Some(true));
+        assert!(results[0].info.is_none());
+        assert_eq!(results[1].available, Some(false));
+        assert_eq!(
+            results[1].info.as_ref().unwrap().registrar.as_deref(),
+            Some("GoDaddy")
+        );
+        assert_eq!(results[1].method_used, domain_check_lib::CheckMethod::Whois);
+        assert_eq!(results[2].available, None);
+    }
+
+    #[test]
+    fn test_appended_csv_header_must_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        let path = path.to_str().unwrap();
+        let header = "domain,available,registrar,method";
+
+        // Missing and empty files take any header
+        assert!(check_appended_csv_header(path, header).is_ok());
+        std::fs::write(path, "").unwrap();
+        assert!(check_appended_csv_header(path, header).is_ok());
+
+        std::fs::write(path, format!("{}\r\nold.com,true,-,RDAP\n", header)).unwrap();
+        assert!(check_appended_csv_header(path, header).is_ok());
+        let err = check_appended_csv_header(path, "domain,available,created,method").unwrap_err();
+        assert!(err.contains("registrar"), "{}", err);
+    }
+
+    #[test]
+    fn test_ndjson_writer_appends() {
+        let file = tempfile::NamedTempFile::with_suffix(".ndjson").unwrap();
+        let path = file.path().to_str().unwrap().to_string();
+
+        for (domain, append) in [("alpha.com", false), ("beta.com", true)] {
+            let mut writer = NdjsonWriter::create(Some(&path), append).unwrap();
+            writer
+                .write_result(&make_result(domain, Some(true)))
+                .unwrap();
+        }
+        let results = parse_prior_ndjson(&std::fs::read_to_string(&path).unwrap());
+        let domains: Vec<&str> = results.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(domains, vec!["alpha.com", "beta.com"]);
+
+        // Without --append the file is replaced
+        NdjsonWriter::create(Some(&path), false).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ndjson_writer_flushes_each_line() {
+        let file = tempfile::NamedTempFile::with_suffix(".ndjson").unwrap();
+        let path = file.path().to_str().unwrap().to_string();
+
+        let mut writer = NdjsonWriter::create(Some(&path), false).unwrap();
+        writer
+            .write_result(&make_result("alpha.com", Some(true)))
+            .unwrap();
+        writer
+            .write_result(&make_result("beta.com", Some(false)))
+            .unwrap();
+
+        // Simulate the process dying mid-run: no drop, no final flush
+        std::mem::forget(writer);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            let parsed: domain_check_lib::DomainResult = serde_json::from_str(line).unwrap();
+            assert!(parsed.available.is_some());
+        }
+    }
+
+    #[test]
+    fn test_parse_prior_ndjson_skips_truncated_line() {
+        let ndjson = "{\"domain\":\"alpha.com\",\"available\":true,\"method_used\":\"rdap\"}\n\
+                      {\"domain\":\"beta.com\",\"available\":false,\"method_used\":\"whois\"}\n\
+                      {\"domain\":\"gamma.com\",\"avail";
+        let results = parse_prior_ndjson(ndjson);
+        let domains: Vec<&str> = results.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(domains, vec!["alpha.com", "beta.com"]);
+    }
+
+    #[test]
+    fn test_output_requires_ndjson() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.output = Some("results.ndjson".to_string());
+        assert!(validate_args(&args).is_err());
+
+        args.ndjson = true;
+        args.streaming = true;
+        assert!(validate_args(&args).is_ok());
+    }
+
+    #[test]
+    fn test_ndjson_conflicts_with_json() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.ndjson = true;
+        args.json = true;
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_parse_dns_server_defaults_port() {
+        assert_eq!(
+            parse_dns_server("1.1.1.1").unwrap(),
+            "1.1.1.1:53".parse().unwrap()
+        );
+        assert_eq!(
+            parse_dns_server("10.0.0.53:5353").unwrap(),
+            "10.0.0.53:5353".parse().unwrap()
+        );
+        assert_eq!(
+            parse_dns_server("[::1]").unwrap(),
+            "[::1]:53".parse().unwrap()
+        );
+        assert!(parse_dns_server("dns.example").is_err());
+    }
+
+    #[test]
+    fn test_dns_servers_applied_to_config() {
+        let mut args = create_test_args();
+        args.dns_servers = Some(vec!["9.9.9.9".to_string()]);
+        let config = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert_eq!(
+            config.dns_servers,
+            Some(vec!["9.9.9.9:53".parse().unwrap()])
+        );
+    }
+
+    #[test]
+    fn test_min_confidence_high_hides_low_confidence_available() {
+        let mut guess = make_result("guess.com", Some(true));
+        guess.confidence = Some(Confidence::Low);
+        let authoritative = make_result("free.com", Some(true));
+
+        let (kept, suppressed) =
+            filter_min_confidence(vec![guess, authoritative], Some(Confidence::High));
+        assert_eq!(suppressed, 1);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].domain, "free.com");
+    }
+
+    #[test]
+    fn test_min_confidence_unset_keeps_everything() {
+        let mut guess = make_result("guess.com", Some(true));
+        guess.confidence = Some(Confidence::Low);
+        assert!(!below_min_confidence(&guess, None));
+        assert!(below_min_confidence(&guess, Some(Confidence::Medium)));
+        assert!(!below_min_confidence(&guess, Some(Confidence::Low)));
+    }
+
+    #[test]
+    fn test_invalid_min_confidence_rejected() {
+        let mut args = create_test_args();
+        args.domains = vec!["example.com".to_string()];
+        args.min_confidence = Some("certain".to_string());
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_group_by_defaults_follow_pretty() {
+        let mut args = create_test_args();
+        assert_eq!(group_by(&args), ui::GroupBy::None);
+        args.pretty = true;
+        assert_eq!(group_by(&args), ui::GroupBy::Status);
+        assert!(should_use_streaming(&args, 10));
+        args.group_by = Some("tld".to_string());
+        assert_eq!(group_by(&args), ui::GroupBy::Tld);
+        assert!(!should_use_streaming(&args, 10));
+    }
+
+    #[test]
+    fn test_group_by_tld_conflicts_with_streaming() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.streaming = true;
+        args.group_by = Some("tld".to_string());
+        assert!(validate_args(&args).is_err());
+        args.group_by = Some("none".to_string());
+        assert!(validate_args(&args).is_ok());
+    }
+
+    #[test]
+    fn test_parse_domain_line() {
+        assert_eq!(
+            parse_domain_line("  brand.com  # main"),
+            Ok(Some("brand.com"))
+        );
+        assert_eq!(parse_domain_line("# comment"), Ok(None));
+        assert_eq!(parse_domain_line("   "), Ok(None));
+        assert!(parse_domain_line("x").is_err());
+    }
+
+    #[test]
+    fn test_parse_domain_json() {
+        assert_eq!(
+            parse_domain_json(r#"["a.com", " b.io ", ""]"#),
+            Ok(vec!["a.com".to_string(), "b.io".to_string()])
+        );
+        assert_eq!(
+            parse_domain_json(r#"[{"domain": "a.com", "available": true}, "brand"]"#),
+            Ok(vec!["a.com".to_string(), "brand".to_string()])
+        );
+        assert!(parse_domain_json(r#"["a.com", 42]"#).is_err());
+        assert!(parse_domain_json(r#"[{"name": "a.com"}]"#).is_err());
+        assert!(parse_domain_json("[a.com]").is_err());
+    }
+
+    #[test]
+    fn test_stdin_file_rejects_streaming() {
+        let mut args = create_test_args();
+        args.file = Some("-".to_string());
+        assert!(validate_args(&args).is_ok());
+        args.stream_file = true;
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stream_file_reads_lazily_with_bounded_memory() {
+        use futures_util::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        const LINES: usize = 200_000;
+        const CONCURRENCY: usize = 8;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        {
+            let mut out = std::io::BufWriter::new(file.as_file_mut());
+            writeln!(out, "# generated list").unwrap();
+            for i in 0..LINES {
+                writeln!(out, "name{}.com", i).unwrap();
+            }
+            writeln!(out, "x").unwrap();
+        }
+
+        let mut reader = DomainFileReader::open(file.path().to_str().unwrap()).unwrap();
+        let pulled = AtomicUsize::new(0);
+        let mut consumed = 0;
+        let mut max_ahead = 0;
+        {
+            let domains = reader.by_ref().inspect(|_| {
+                pulled.fetch_add(1, Ordering::SeqCst);
+            });
+            let stream = lazy_check_stream(domains, CONCURRENCY, |domain| async move {
+                make_result(&domain, Some(true))
+            });
+            let mut stream = std::pin::pin!(stream);
+            while stream.next().await.is_some() {
+                consumed += 1;
+                max_ahead = max_ahead.max(pulled.load(Ordering::SeqCst) - consumed);
+            }
+        }
+
+        assert_eq!(consumed, LINES);
+        // Only the in-flight window is ever read ahead of the consumer
+        assert!(max_ahead <= CONCURRENCY, "read {} ahead", max_ahead);
+        assert_eq!(reader.invalid, 1);
+    }
+
+    #[test]
+    fn test_stream_file_rejects_whole_list_features() {
+        let mut args = create_test_args();
+        args.stream_file = true;
+        assert!(validate_args(&args).is_err()); // needs --file
+
+        args.file = Some("domains.txt".to_string());
+        assert!(validate_args(&args).is_ok());
+
+        args.json = true;
+        assert!(validate_args(&args).is_err());
+        args.json = false;
+        args.registrar_consistency = true;
+        assert!(validate_args(&args).is_err());
+        args.registrar_consistency = false;
+        args.concurrency = "auto".to_string();
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_concurrency_accepts_number_or_auto() {
+        let mut args = create_test_args();
+        args.concurrency = "50".to_string();
+        assert_eq!(fixed_concurrency(&args), Ok(Some(50)));
+        args.concurrency = "auto".to_string();
+        assert_eq!(fixed_concurrency(&args), Ok(None));
+        for bad in ["0", "101", "fast"] {
+            args.concurrency = bad.to_string();
+            assert!(fixed_concurrency(&args).is_err());
+        }
+    }
+
+    #[test]
+    fn test_legacy_format_rejects_structured_output() {
+        let mut args = create_test_args();
+        args.legacy_format = true;
+        args.domains = vec!["example.com".to_string()];
+        assert!(validate_args(&args).is_ok());
+        args.json = true;
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_only_errors_shows_just_failed_checks_with_reasons() {
+        console::set_colors_enabled(false);
+        let mut args = create_test_args();
+        args.only_errors = true;
+
+        let mut timed_out = make_result("slow.dev", None);
+        timed_out.error_category = Some(domain_check_lib::ErrorCategory::Timeout);
+        let mut limited = make_result("busy.io", None);
+        limited.error_category = Some(domain_check_lib::ErrorCategory::RateLimited);
+        let results = [
+            make_result("free.com", Some(true)),
+            timed_out,
+            make_result("taken.com", Some(false)),
+            limited,
+        ];
+
+        let mut out = Vec::new();
+        for result in &results {
+            write_stream_result(&mut out, result, &args, &[], None).unwrap();
+        }
+        let out = String::from_utf8(out).unwrap();
+        assert!(
+            out.contains("slow.dev") && out.contains("(timeout)"),
+            "{}",
+            out
+        );
+        assert!(
+            out.contains("busy.io") && out.contains("(rate limited)"),
+            "{}",
+            out
+        );
+        assert!(
+            !out.contains("free.com") && !out.contains("taken.com"),
+            "{}",
+            out
+        );
+        assert_eq!(out.lines().count(), 2, "{}", out);
+
+        assert_eq!(
+            ui::format_error_breakdown(&results).as_deref(),
+            Some("errors: 1 rate limited, 1 timeout")
+        );
+    }
+
+    #[test]
+    fn test_estimate_scales_with_domains_and_concurrency() {
+        let latency = std::time::Duration::from_millis(500);
+        let base = RunEstimate::new(1200, 340, 50, None, latency);
+        assert_eq!((base.min_secs, base.max_secs), (12, 24));
+        assert_eq!(
+            base.to_string(),
+            "≈1,200 requests across 340 endpoints, est. 12–24s at concurrency 50"
+        );
+
+        // Twice the domains, twice the time; twice the concurrency, half
+        let more = RunEstimate::new(2400, 340, 50, None, latency);
+        assert_eq!(more.min_secs, 2 * base.min_secs);
+        let wider = RunEstimate::new(1200, 340, 100, None, latency);
+        assert_eq!(wider.min_secs * 2, base.min_secs);
+
+        // Per-host limits cap the useful concurrency
+        let capped = RunEstimate::new(1200, 1, 50, Some(5), latency);
+        assert_eq!(capped.min_secs, 120);
+
+        let tiny = RunEstimate::new(1, 1, 20, None, latency);
+        assert_eq!(
+            tiny.to_string(),
+            "≈1 request across 1 endpoint, est. 1s at concurrency 20"
+        );
+    }
+
+    #[test]
+    fn test_validate_args_batch_size() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.batch_size = Some(0);
+        assert!(validate_args(&args).is_err());
+        args.batch_size = Some(50);
+        assert!(validate_args(&args).is_ok());
+        args.json = true;
+        assert!(validate_args(&args).unwrap_err().contains("--batch-size"));
+    }
+
+    #[test]
+    fn test_progress_json_results_requires_progress_json() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.progress_json_results = true;
+        assert!(validate_args(&args).is_err());
+        args.progress_json = true;
+        assert!(validate_args(&args).is_ok());
+    }
+
+    #[test]
+    fn test_progress_json_rejected_with_stream_file_and_watch() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.progress_json = true;
+        args.watch = true;
+        assert!(validate_args(&args).is_err());
+
+        args.watch = false;
+        args.domains.clear();
+        args.file = Some("domains.txt".to_string());
+        args.stream_file = true;
+        assert!(validate_args(&args).is_err());
+        args.stream_file = false;
+        assert!(validate_args(&args).is_ok());
+    }
+
+    #[test]
+    fn test_registrar_allowlist_trailing_comma() {
+        let args = Args::try_parse_from([
+            "domain-check",
+            "brand",
+            "--registrar-allowlist",
+            "godaddy, ,",
+        ])
+        .unwrap();
+        assert_eq!(
+            clean_registrar_allowlist(args.registrar_allowlist.unwrap()),
+            vec!["godaddy"]
+        );
+    }
+
+    #[test]
+    fn test_sample_tlds_keeps_one_per_registry() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.tlds = Some(vec!["com".into(), "net".into(), "org".into()]);
+        args.sample_tlds = Some(1);
+        assert!(validate_args(&args).is_ok());
+        let config = build_config(&args).unwrap();
+        assert_eq!(resolved_tlds(&args, &config), vec!["com", "org"]);
+
+        args.sample_tlds = Some(0);
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_sample_tlds_runs_after_exclusions() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.tlds = Some(vec!["com".into(), "net".into(), "org".into()]);
+        args.exclude_tlds = Some(vec!["com".to_string()]);
+        args.sample_tlds = Some(1);
+        let config = build_config(&args).unwrap();
+        // net takes com's place as the Verisign representative
+        assert_eq!(resolved_tlds(&args, &config), vec!["net", "org"]);
+        assert_eq!(
+            expansion_tlds(&args, &config),
+            Some(vec!["net".to_string(), "org".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_sample_tlds_needs_a_tld_list() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.sample_tlds = Some(1);
+        assert!(build_config(&args).is_err());
+    }
+
+    #[test]
+    fn test_webhook_requires_watch_and_http_url() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.webhook = Some("https://hooks.example.com/dc".to_string());
+        assert!(validate_args(&args).is_err());
+        args.watch = true;
+        assert!(validate_args(&args).is_ok());
+        args.webhook = Some("hooks.example.com".to_string());
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_watch_rejects_bad_interval_and_json() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.watch = true;
+        args.watch_interval = Some("soon".to_string());
+        assert!(validate_args(&args).is_err());
+        args.watch_interval = Some("30s".to_string());
+        assert!(validate_args(&args).is_ok());
+        args.json = true;
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_watch_state_reports_transitions_to_available() {
+        let mut state = WatchState::default();
+        // First round sets the baseline, even for domains already available
+        let first = vec![
+            make_result("a.com", Some(false)),
+            make_result("b.com", None),
+            make_result("c.com", Some(true)),
+        ];
+        assert!(state.observe(&first).is_empty());
+
+        let second = vec![
+            make_result("a.com", Some(true)),
+            make_result("b.com", Some(true)),
+            make_result("c.com", Some(true)),
+        ];
+        assert_eq!(state.observe(&second), vec!["a.com", "b.com"]);
+        assert!(state.observe(&second).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_posts_available_event() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Read until the JSON body has arrived
+            while !request.ends_with(b"}") {
+                let n = stream.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let _ = stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                .await;
+            String::from_utf8(request).unwrap()
+        });
+
+        let mut state = WatchState::default();
+        state.observe(&[make_result("brand.com", Some(false))]);
+        let became_available = state.observe(&[make_result("brand.com", Some(true))]);
+        let url = format!("http://{}/hook", addr);
+        for domain in &became_available {
+            post_webhook(&url, domain).await.unwrap();
+        }
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /hook "), "{}", request);
+        let body = &request[request.find("\r\n\r\n").unwrap() + 4..];
+        let event: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(event["domain"], "brand.com");
+        assert_eq!(event["event"], "available");
+        assert!(event["at"].as_str().unwrap().contains('T'), "{}", event);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_failure_is_an_error_not_a_panic() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        assert!(post_webhook(&format!("http://{}/hook", addr), "brand.com")
+            .await
+            .is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_notify_command_gets_domain_and_reports_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("notified");
+        let command = format!("printf %s \"$DOMAIN_CHECK_DOMAIN\" > '{}'", out.display());
+        run_notify_command(&command, "brand.com").await.unwrap();
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "brand.com");
+
+        assert!(run_notify_command("exit 3", "brand.com").await.is_err());
+    }
+
+    #[test]
+    fn test_estimate_latency_requires_estimate() {
+        let mut args = create_test_args();
+        args.domains = vec!["brand".to_string()];
+        args.estimate_latency = Some(200);
+        assert!(validate_args(&args).is_err());
+        args.estimate = true;
+        assert!(validate_args(&args).is_ok());
+        args.estimate_latency = Some(0);
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_group_thousands() {
+        assert_eq!(group_thousands(0), "0");
+        assert_eq!(group_thousands(999), "999");
+        assert_eq!(group_thousands(1200), "1,200");
+        assert_eq!(group_thousands(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn test_only_errors_rejects_structured_output() {
+        let mut args = create_test_args();
+        args.only_errors = true;
+        args.domains = vec!["example.com".to_string()];
+        assert!(validate_args(&args).is_ok());
+        args.csv = true;
+        assert!(validate_args(&args).is_err());
+        args.csv = false;
+        args.ndjson = true;
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_baseline_needs_no_domains_but_rejects_them() {
+        let mut args = create_test_args();
+        args.baseline = true;
+        args.tlds = Some(vec!["com".to_string(), "io".to_string()]);
+        assert!(validate_args(&args).is_ok());
+
+        args.domains = vec!["brand".to_string()];
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_baseline_runs_shared_checks() {
+        let mut args = create_test_args();
+        args.baseline = true;
+        args.rdap_server = Some("ftp://bad".to_string());
+        assert!(validate_args(&args).is_err());
+
+        args.rdap_server = None;
+        args.race = true;
+        args.no_whois = true;
+        assert!(validate_args(&args).is_err());
+
+        args.race = false;
+        args.no_whois = false;
+        args.concurrency_per_host = Some(0);
+        assert!(validate_args(&args).is_err());
+
+        args.concurrency_per_host = None;
+        args.json_envelope = true;
+        assert!(validate_args(&args).is_err());
+        args.json = true;
+        assert!(validate_args(&args).is_ok());
+    }
+
+    #[test]
+    fn test_baseline_label_is_valid_base_name() {
+        let label = baseline_label();
+        assert!(label.starts_with("dc-baseline-"));
+        assert_eq!(
+            domain_check_lib::expand_domain_inputs(std::slice::from_ref(&label), &None),
+            vec![format!("{}.com", label)]
+        );
+    }
+
+    #[test]
+    fn test_bool_answer_for_available_result() {
+        assert_eq!(bool_answer(&make_result("free.com", Some(true))), "true");
+        assert_eq!(bool_answer(&make_result("taken.com", Some(false))), "false");
+        assert_eq!(bool_answer(&make_result("odd.com", None)), "unknown");
+    }
+
+    #[test]
+    fn test_bool_conflicts_with_other_output_formats() {
+        let mut args = create_test_args();
+        args.domains = vec!["example.com".to_string()];
+        args.bool_output = true;
+        assert!(validate_args(&args).is_ok());
+        assert!(!should_use_streaming(&args, 1));
+
+        args.json = true;
+        assert!(validate_args(&args).is_err());
+    }
+
+    #[test]
+    fn test_failures_file_lists_only_unknowns() {
+        let results = vec![
+            make_result("a.com", Some(true)),
+            make_result("b.com", None),
+            make_result("c.com", Some(false)),
+            make_result("d.io", None),
+        ];
+
+        let file = tempfile::NamedTempFile::with_suffix(".txt").unwrap();
+        let path = file.path().to_str().unwrap();
+        write_failures_file(path, &results).unwrap();
+
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "b.com\nd.io\n");
+    }
+
+    #[test]
+    fn test_manifest_records_version_and_stable_hash() {
+        let mut args = create_test_args();
+        args.domains = vec!["a".to_string(), "B.com".to_string()];
+        let domains = vec!["a.com".to_string(), "B.com".to_string()];
+        let config = CheckConfig::default();
+
+        let file = tempfile::NamedTempFile::with_suffix(".json").unwrap();
+        let path = file.path().to_str().unwrap();
+        write_manifest(path, &RunManifest::new(&args, &config, &domains).unwrap()).unwrap();
+
+        let manifest: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(manifest["version"], domain_check_lib::VERSION);
+        assert_eq!(manifest["input"]["arguments"], 2);
+        assert_eq!(manifest["input"]["domain_count"], 2);
+        // Same as `printf 'a.com\nb.com\n' | sha256sum`
+        assert_eq!(
+            manifest["input"]["domains_sha256"],
+            "18352c8245cbba65ec25cb456df9b53edec15d7d25560e34a80944be44abea75"
+        );
+        assert_eq!(
+            manifest["config_sha256"],
+            RunManifest::new(&args, &config, &domains)
+                .unwrap()
+                .config_sha256
+        );
     }
 
-    // Report invalid lines if any
-    if !invalid_lines.is_empty() {
-        eprintln!(
-            "⚠️ Found {} invalid entries in the file:",
-            invalid_lines.len()
+    #[test]
+    fn test_timing_log_formats() {
+        let rows = vec![
+            domain_check_lib::CheckTiming {
+                domain: "a.com".to_string(),
+                launched_ms: 0,
+                started_ms: 2,
+                completed_ms: 40,
+                duration_ms: 38,
+                endpoint: Some("rdap.verisign.com".to_string()),
+                method: domain_check_lib::CheckMethod::Rdap,
+            },
+            domain_check_lib::CheckTiming {
+                domain: "b.ch".to_string(),
+                launched_ms: 1,
+                started_ms: 40,
+                completed_ms: 90,
+                duration_ms: 50,
+                endpoint: None,
+                method: domain_check_lib::CheckMethod::Whois,
+            },
+        ];
+
+        assert_eq!(
+            format_timing_log("timings.csv", &rows).unwrap(),
+            "domain,launched_ms,started_ms,completed_ms,duration_ms,endpoint,method\n\
+             a.com,0,2,40,38,rdap.verisign.com,rdap\n\
+             b.ch,1,40,90,50,,whois\n"
         );
-        for invalid in &invalid_lines[..invalid_lines.len().min(5)] {
-            eprintln!("  {}", invalid);
-        }
-        if invalid_lines.len() > 5 {
-            eprintln!("  ... and {} more invalid entries", invalid_lines.len() - 5);
-        }
-        eprintln!();
-    }
 
-    // Check if we have any valid domains
-    if domains.is_empty() {
-        return Err("No valid domains found in the file.".into());
+        let json: serde_json::Value =
+            serde_json::from_str(&format_timing_log("timings.JSON", &rows).unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 2);
+        assert_eq!(json[1]["started_ms"], 40);
+        assert!(json[1].get("endpoint").is_none());
     }
 
-    Ok(domains)
-}
+    #[test]
+    fn test_timing_log_sets_config() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(result.timing_log.is_none());
 
-fn display_results(
-    results: &[domain_check_lib::DomainResult],
-    args: &Args,
-    duration: std::time::Duration,
-) -> Result<(), Box<dyn std::error::Error>> {
-    if args.json {
-        display_json_results(results)?;
-    } else if args.csv {
-        display_csv_results(results)?;
-    } else {
-        display_text_results(results, args, duration)?;
+        args.timing_log = Some("timings.csv".to_string());
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(result.timing_log.is_some());
     }
 
-    Ok(())
-}
-
-/// Display results in JSON format
-fn display_json_results(
-    results: &[domain_check_lib::DomainResult],
-) -> Result<(), Box<dyn std::error::Error>> {
-    let json = serde_json::to_string_pretty(results)?;
-    println!("{}", json);
-    Ok(())
-}
+    #[test]
+    fn test_summary_json_counts() {
+        let mut whois = make_result("b.ch", Some(false));
+        whois.method_used = domain_check_lib::CheckMethod::Whois;
+        let mut timeout = make_result("c.com", None);
+        timeout.method_used = domain_check_lib::CheckMethod::Unknown;
+        timeout.error_category = Some(domain_check_lib::ErrorCategory::Timeout);
+        let mut unknown_tld = make_result("d.zz", None);
+        unknown_tld.method_used = domain_check_lib::CheckMethod::Unknown;
+        unknown_tld.error_category = Some(domain_check_lib::ErrorCategory::UnknownTld);
+        let results = vec![
+            make_result("a.com", Some(true)),
+            make_result("e.com", Some(false)),
+            whois,
+            timeout,
+            unknown_tld,
+        ];
+
+        let file = tempfile::NamedTempFile::with_suffix(".json").unwrap();
+        let path = file.path().to_str().unwrap();
+        write_summary_json(path, &results, std::time::Duration::from_millis(1500)).unwrap();
+
+        let summary: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(summary["total"], 5);
+        assert_eq!(summary["available"], 1);
+        assert_eq!(summary["taken"], 2);
+        assert_eq!(summary["unknown"], 2);
+        assert_eq!(summary["duration_ms"], 1500);
+        assert_eq!(summary["by_method"]["rdap"], 2);
+        assert_eq!(summary["by_method"]["whois"], 1);
+        assert_eq!(summary["by_method"]["unknown"], 2);
+        assert_eq!(summary["by_error_kind"]["timeout"], 1);
+        assert_eq!(summary["by_error_kind"]["unknown_tld"], 1);
+    }
 
-/// Display results in CSV format
-fn display_csv_results(
-    results: &[domain_check_lib::DomainResult],
-) -> Result<(), Box<dyn std::error::Error>> {
-    println!("domain,available,registrar,created,expires,method");
+    #[test]
+    fn test_validate_args_unknown_field() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.fields = Some(vec!["registrar".to_string(), "owner".to_string()]);
 
-    for result in results {
-        let available = match result.available {
-            Some(true) => "true",
-            Some(false) => "false",
-            None => "unknown",
-        };
+        let err = validate_args(&args).unwrap_err();
+        assert!(err.contains("Unknown field 'owner'"), "{}", err);
+    }
 
-        let registrar = result
-            .info
-            .as_ref()
-            .and_then(|i| i.registrar.as_deref())
-            .unwrap_or("-");
+    #[test]
+    fn test_endpoint_report_enables_tracking() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(!result.track_endpoints);
 
-        let created = result
-            .info
-            .as_ref()
-            .and_then(|i| i.creation_date.as_deref())
-            .unwrap_or("-");
+        args.endpoint_report = true;
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(result.track_endpoints);
+    }
 
-        let expires = result
-            .info
-            .as_ref()
-            .and_then(|i| i.expiration_date.as_deref())
-            .unwrap_or("-");
+    #[test]
+    fn test_strict_validation_sets_config() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(!result.strict_validation);
 
-        println!(
-            "{},{},{},{},{},{}",
-            result.domain, available, registrar, created, expires, result.method_used
-        );
+        args.strict_validation = true;
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(result.strict_validation);
     }
 
-    Ok(())
-}
+    #[test]
+    fn test_timeout_retries_sets_config() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert_eq!(result.timeout_retries, 0);
 
-/// Display results in human-readable text format
-fn display_text_results(
-    results: &[domain_check_lib::DomainResult],
-    args: &Args,
-    duration: std::time::Duration,
-) -> Result<(), Box<dyn std::error::Error>> {
-    if args.pretty {
-        // Pretty mode: grouped layout with section headers
-        ui::print_grouped_results(results, args.info, args.debug);
-    } else {
-        // Default mode: colored flat list
-        for result in results {
-            ui::print_result_default(result, args.info, args.debug, None);
-        }
+        args.timeout_retries = Some(2);
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert_eq!(result.timeout_retries, 2);
     }
 
-    // Shared summary for both modes
-    if results.len() > 1 {
-        let available = results.iter().filter(|r| r.available == Some(true)).count();
-        let taken = results
-            .iter()
-            .filter(|r| r.available == Some(false))
-            .count();
-        let unknown = results.iter().filter(|r| r.available.is_none()).count();
-        println!();
-        ui::print_summary(results.len(), available, taken, unknown, duration);
+    #[test]
+    fn test_concurrency_ramp_sets_config() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert_eq!(result.concurrency_ramp, None);
+
+        args.concurrency_ramp = Some(5);
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert_eq!(
+            result.concurrency_ramp,
+            Some(std::time::Duration::from_secs(5))
+        );
     }
 
-    Ok(())
-}
-
-// domain-check/src/main.rs tests module
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_concurrency_per_host_sets_config() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.concurrency_per_host = Some(0);
+        assert!(validate_args(&args).is_err());
 
-    // Helper function with all required fields
-    fn create_test_args() -> Args {
-        Args {
-            domains: vec![], // Empty domains for testing
-            tlds: None,
-            file: None,
-            config: None,
-            concurrency: 20,
-            force: false,
-            info: false,
-            no_whois: false,
-            no_bootstrap: false,
-            json: false,
-            csv: false,
-            pretty: false,
-            batch: false,
-            streaming: false,
-            debug: false,
-            verbose: false,
-            all_tlds: false,
-            preset: None,
-            list_presets: false,
-            patterns: None,
-            prefixes: None,
-            suffixes: None,
-            dry_run: false,
-            yes: false,
-            help: false,
-        }
+        args.concurrency_per_host = Some(3);
+        assert!(validate_args(&args).is_ok());
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert_eq!(result.concurrency_per_host, Some(3));
     }
 
     #[test]
-    fn test_should_enable_bootstrap_default() {
-        // Bootstrap is now enabled by default
-        let args = create_test_args();
-        let tlds = Some(vec!["com".to_string(), "org".to_string()]);
-        assert!(should_enable_bootstrap(&args, &tlds));
+    fn test_concurrency_warning_for_single_endpoint_workload() {
+        let domains: Vec<String> = (0..50).map(|i| format!("name{}.com", i)).collect();
+        let endpoints = domain_check_lib::distinct_endpoints(&domains);
+        assert_eq!(endpoints, 1);
+
+        let warning = concurrency_warning(100, Some(2), endpoints).unwrap();
+        assert!(warning.contains("at most 2 checks"), "{}", warning);
+        assert!(warning.contains("--concurrency 2 "), "{}", warning);
+
+        // Reachable, or no per-host limit: nothing to warn about
+        assert_eq!(concurrency_warning(2, Some(2), endpoints), None);
+        assert_eq!(concurrency_warning(20, Some(5), 4), None);
+        assert_eq!(concurrency_warning(100, None, endpoints), None);
     }
 
     #[test]
-    fn test_should_disable_bootstrap_with_flag() {
-        // --no-bootstrap disables it
+    fn test_bootstrap_url_sets_config() {
         let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.bootstrap_url = Some("https://mirror.example/rdap/dns.json".to_string());
+        assert!(validate_args(&args).is_ok());
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert_eq!(
+            result.bootstrap_url.as_deref(),
+            Some("https://mirror.example/rdap/dns.json")
+        );
+
         args.no_bootstrap = true;
-        let tlds = Some(vec!["com".to_string(), "org".to_string()]);
-        assert!(!should_enable_bootstrap(&args, &tlds));
+        assert!(validate_args(&args).is_err());
+        args.no_bootstrap = false;
+        args.bootstrap_url = Some("mirror.example/dns.json".to_string());
+        assert!(validate_args(&args).is_err());
     }
 
-    // validation tests to include required domains
     #[test]
-    fn test_validate_args_invalid_preset_now_allowed() {
-        // After Phase 4: Invalid presets are allowed in validate_args()
-        // and checked later during config resolution
+    fn test_rdap_server_sets_config_and_requires_http_url() {
         let mut args = create_test_args();
         args.domains = vec!["test".to_string()];
-        args.preset = Some("invalid_preset".to_string());
+        args.rdap_server = Some("http://localhost:8080/rdap/domain".to_string());
+        assert!(validate_args(&args).is_ok());
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert_eq!(
+            result.rdap_server_override.as_deref(),
+            Some("http://localhost:8080/rdap/domain")
+        );
 
-        let result = validate_args(&args);
-        assert!(result.is_ok()); // Now passes validation, fails later in config resolution
+        args.rdap_server = Some("localhost:8080".to_string());
+        let err = validate_args(&args).unwrap_err();
+        assert!(err.contains("--rdap-server"), "{}", err);
     }
 
     #[test]
-    fn test_validate_args_conflicting_flags() {
+    fn test_race_sets_config_and_conflicts_with_no_whois() {
         let mut args = create_test_args();
-        args.domains = vec!["test".to_string()]; // Add required domain
-        args.tlds = Some(vec!["com".to_string()]);
-        args.preset = Some("startup".to_string());
+        args.domains = vec!["test".to_string()];
+        args.race = true;
+        assert!(validate_args(&args).is_ok());
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(result.race_protocols);
 
-        let result = validate_args(&args);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .contains("Cannot specify multiple TLD sources"));
+        args.no_whois = true;
+        let err = validate_args(&args).unwrap_err();
+        assert!(err.contains("--race"), "{}", err);
     }
 
     #[test]
-    fn test_validate_args_all_and_preset_conflict() {
+    fn test_empty_tld_is_rejected() {
         let mut args = create_test_args();
-        args.domains = vec!["test".to_string()]; // Add required domain
-        args.all_tlds = true;
-        args.preset = Some("startup".to_string());
+        args.tlds = Some(vec!["".to_string()]);
+        let err = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap_err();
+        assert!(err.to_string().contains("empty TLD in TLD list"), "{}", err);
 
-        let result = validate_args(&args);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .contains("Cannot specify multiple TLD sources"));
+        args.tlds = Some(vec!["com".to_string(), "  ".to_string()]);
+        let err = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap_err();
+        assert!(err.to_string().contains("empty TLD in TLD list"), "{}", err);
     }
 
     #[test]
-    fn test_validate_args_valid_preset() {
+    fn test_detect_wildcards_enables_probe() {
         let mut args = create_test_args();
-        args.domains = vec!["test".to_string()]; // Add required domain
-        args.preset = Some("startup".to_string());
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(!result.detect_wildcard_tlds);
 
-        let result = validate_args(&args);
-        assert!(result.is_ok());
+        args.detect_wildcards = true;
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(result.detect_wildcard_tlds);
     }
 
     #[test]
-    fn test_validate_args_valid_all_flag() {
+    fn test_fields_implies_info() {
         let mut args = create_test_args();
-        args.domains = vec!["test".to_string()]; // Add required domain
-        args.all_tlds = true;
+        args.fields = Some(vec!["status".to_string()]);
 
-        let result = validate_args(&args);
-        assert!(result.is_ok());
+        let result = apply_cli_args_to_config(CheckConfig::default(), &args).unwrap();
+        assert!(result.detailed_info);
+        assert_eq!(info_fields(&args), vec![ui::InfoField::Status]);
     }
 
     #[test]
-    fn test_validate_args_streaming_with_json_rejected() {
+    fn test_validate_args_date_format() {
         let mut args = create_test_args();
         args.domains = vec!["test".to_string()];
-        args.streaming = true;
-        args.json = true;
+        args.date_format = Some("date-only".to_string());
+        assert!(validate_args(&args).is_ok());
 
-        let result = validate_args(&args);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("--streaming"));
+        args.date_format = Some("yesterday".to_string());
+        let err = validate_args(&args).unwrap_err();
+        assert!(err.contains("Invalid date format"), "{}", err);
     }
 
     #[test]
-    fn test_validate_args_streaming_with_csv_rejected() {
-        let mut args = create_test_args();
-        args.domains = vec!["test".to_string()];
-        args.streaming = true;
-        args.csv = true;
+    fn test_normalize_result_dates() {
+        let mut result = domain_check_lib::DomainResult::new(
+            "example.de",
+            Some(false),
+            domain_check_lib::CheckMethod::Whois,
+        );
+        result.info = Some(domain_check_lib::DomainInfo {
+            creation_date: Some("1995-08-14T04:00:00Z".to_string()),
+            updated_date: Some("18.03.2020".to_string()),
+            expiration_date: Some("sometime next year".to_string()),
+            ..Default::default()
+        });
+
+        normalize_result_dates(&mut result, &DateFormat::DateOnly, false);
+        let info = result.info.unwrap();
+        assert_eq!(info.creation_date.as_deref(), Some("1995-08-14"));
+        assert_eq!(info.updated_date.as_deref(), Some("2020-03-18"));
+        assert_eq!(info.expiration_date.as_deref(), Some("sometime next year"));
+    }
 
-        let result = validate_args(&args);
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("--streaming"));
+    #[test]
+    fn test_parse_prior_csv_with_custom_fields() {
+        let csv = "domain,available,status,expires,method\n\
+                   a.com,false,active,2030-01-01,RDAP\n";
+        let results = parse_prior_csv(csv);
+        assert_eq!(results.len(), 1);
+        let info = results[0].info.as_ref().unwrap();
+        assert_eq!(info.expiration_date.as_deref(), Some("2030-01-01"));
+        assert!(info.registrar.is_none());
+        assert_eq!(results[0].method_used, domain_check_lib::CheckMethod::Rdap);
     }
 
     #[test]
-    fn test_validate_args_batch_with_json_allowed() {
+    fn test_json_envelope_config_matches_run() {
         let mut args = create_test_args();
-        args.domains = vec!["test".to_string()];
-        args.batch = true;
         args.json = true;
+        args.json_envelope = true;
+        args.preset = Some("startup".to_string());
+        let config = CheckConfig::default()
+            .with_concurrency(7)
+            .with_whois_fallback(false);
+
+        let results = vec![make_result("a.com", Some(true))];
+        let envelope = JsonEnvelope {
+            config: RunConfig::new(&config, &args),
+            results: &results,
+        };
+        let json = serde_json::to_value(&envelope).unwrap();
 
-        let result = validate_args(&args);
-        assert!(result.is_ok());
+        assert_eq!(json["config"]["concurrency"], 7);
+        assert_eq!(json["config"]["timeout_ms"], 5000);
+        assert_eq!(json["config"]["whois_fallback"], false);
+        assert_eq!(json["config"]["tld_source"], "preset:startup");
+        assert_eq!(json["results"][0]["domain"], "a.com");
     }
 
     #[test]
-    fn test_no_whois_flag_only_disables() {
-        // When --no-whois is NOT passed, config/env values should be preserved
-        let args = create_test_args(); // no_whois defaults to false
-        let config = CheckConfig {
-            enable_whois_fallback: false, // Simulates config setting
-            ..CheckConfig::default()
-        };
+    fn test_json_envelope_requires_json() {
+        let mut args = create_test_args();
+        args.domains = vec!["test".to_string()];
+        args.json_envelope = true;
+        assert!(validate_args(&args).is_err());
+    }
 
-        let result = apply_cli_args_to_config(config, &args).unwrap();
-        assert!(
-            !result.enable_whois_fallback,
-            "Config whois_fallback=false should be preserved when --no-whois is not passed"
+    #[test]
+    fn test_load_prior_results_from_envelope() {
+        let file = tempfile::NamedTempFile::with_suffix(".json").unwrap();
+        std::fs::write(
+            file.path(),
+            r#"{"config": {"concurrency": 20}, "results": [{"domain": "a.com", "available": true, "method_used": "rdap"}]}"#,
+        )
+        .unwrap();
+
+        let results = load_prior_results(file.path().to_str().unwrap()).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].domain, "a.com");
+    }
+
+    #[test]
+    fn test_compact_summary_line() {
+        let results = vec![
+            make_result("a.com", Some(true)),
+            make_result("b.com", Some(true)),
+            make_result("c.com", Some(false)),
+            make_result("d.com", None),
+        ];
+        let summary = RunSummary::from_results(&results, std::time::Duration::from_millis(4900));
+        assert_eq!(
+            summary.compact_line(),
+            "total=4 available=2 taken=1 unknown=1 duration_ms=4900"
+        );
+        assert_eq!(
+            RunSummary::from_counts(2, 1, 1, std::time::Duration::from_millis(4900)).compact_line(),
+            summary.compact_line()
         );
     }
 
     #[test]
-    fn test_no_whois_flag_overrides_config() {
-        // When --no-whois IS passed, it should disable whois regardless of config
-        let mut args = create_test_args();
-        args.no_whois = true;
-        let config = CheckConfig {
-            enable_whois_fallback: true,
-            ..CheckConfig::default()
-        };
+    fn test_annotate_confusable() {
+        let mut safe = make_result("apple.com", Some(false));
+        annotate_confusable(&mut safe);
+        assert!(safe.note.is_none());
+
+        let mut mixed = make_result("xn--pple-43d.com", Some(true));
+        mixed.note = Some("existing".to_string());
+        annotate_confusable(&mut mixed);
+        let note = mixed.note.unwrap();
+        assert!(note.starts_with("existing; confusable:"), "{}", note);
+    }
 
-        let result = apply_cli_args_to_config(config, &args).unwrap();
-        assert!(
-            !result.enable_whois_fallback,
-            "--no-whois should disable whois fallback"
-        );
+    #[test]
+    fn test_expand_tld_shorthand_at() {
+        assert_eq!(expand_tld_shorthand("brand@io"), "brand.io");
+        assert_eq!(expand_tld_shorthand("brand@ai"), "brand.ai");
     }
 
     #[test]
-    fn test_info_flag_only_enables() {
-        // When --info is NOT passed, config/env values should be preserved
-        let args = create_test_args(); // info defaults to false
-        let config = CheckConfig {
-            detailed_info: true, // Simulates config setting
-            ..CheckConfig::default()
-        };
+    fn test_expand_tld_shorthand_slash() {
+        assert_eq!(expand_tld_shorthand("brand/io"), "brand.io");
+        assert_eq!(expand_tld_shorthand(" brand/dev "), "brand.dev");
+    }
 
-        let result = apply_cli_args_to_config(config, &args).unwrap();
-        assert!(
-            result.detailed_info,
-            "Config detailed_info=true should be preserved when --info is not passed"
+    #[test]
+    fn test_expand_tld_shorthand_rejects_email() {
+        assert_eq!(expand_tld_shorthand("user@example.com"), "user@example.com");
+        let expanded = domain_check_lib::expand_domain_inputs(
+            &[expand_tld_shorthand("user@example.com")],
+            &None,
         );
+        assert!(expanded.is_empty());
     }
 
     #[test]
-    fn test_info_flag_overrides_config() {
-        // When --info IS passed, it should enable info regardless of config
+    fn test_expand_tld_shorthand_leaves_other_inputs() {
+        assert_eq!(expand_tld_shorthand("brand"), "brand");
+        assert_eq!(expand_tld_shorthand("brand.io"), "brand.io");
+        assert_eq!(expand_tld_shorthand("@io"), "@io");
+        assert_eq!(expand_tld_shorthand("brand@1x"), "brand@1x");
+        assert_eq!(expand_tld_shorthand("a/b/c"), "a/b/c");
+    }
+
+    #[test]
+    fn test_registrar_stats_enables_detailed_info() {
         let mut args = create_test_args();
-        args.info = true;
+        args.registrar_stats = true;
         let config = CheckConfig {
             detailed_info: false,
             ..CheckConfig::default()
         };
 
         let result = apply_cli_args_to_config(config, &args).unwrap();
-        assert!(result.detailed_info, "--info should enable detailed info");
+        assert!(
+            result.detailed_info,
+            "--registrar-stats should auto-enable detailed info"
+        );
     }
 }
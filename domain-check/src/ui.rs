@@ -8,7 +8,9 @@
 //! Pretty mode: everything above plus grouped layout, column alignment, styled header.
 
 use console::{pad_str, style, Alignment, Term};
-use domain_check_lib::{DomainInfo, DomainResult};
+use domain_check_lib::{CheckMethod, DomainInfo, DomainResult, EndpointStats, ErrorCategory};
+use std::borrow::Cow;
+use std::io::{self, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -58,6 +60,20 @@ pub fn print_custom_help() {
         style("--pattern <PATTERN>").white(),
         style("[--flags]").dim()
     );
+    println!(
+        "   {} {} {}",
+        style("domain-check generate").cyan().bold(),
+        style("[NAMES] --pattern <PATTERN>").white(),
+        style("[--ascii-only] [--count | --json]").dim()
+    );
+
+    // COMMANDS
+    print_section("COMMANDS");
+    print_flag(
+        "",
+        "generate",
+        "Print generated base names without checking them",
+    );
 
     // DOMAIN SELECTION
     print_section("DOMAIN SELECTION");
@@ -71,17 +87,108 @@ pub fn print_custom_help() {
         "--tld <TLD>",
         "TLDs to check (comma-separated or multiple -t)",
     );
+    print_flag(
+        "",
+        "--tld-file <FILE>",
+        "Read TLDs from a file (one per line)",
+    );
     print_flag("", "--all", "Check against all known TLDs");
+    print_flag(
+        "",
+        "--tld-limit <N>",
+        "With --all, only the N most popular TLDs",
+    );
+    print_flag(
+        "",
+        "--sample-tlds <K>",
+        "Keep at most K TLDs per registry (RDAP host)",
+    );
     print_flag("", "--preset <NAME>", "Use a predefined TLD preset");
+    print_flag(
+        "",
+        "--add-tld <TLD>",
+        "Extra TLDs merged onto the preset/--all/-t list",
+    );
+    print_flag(
+        "",
+        "--exclude-tld <TLD>",
+        "Drop these TLDs from the domain set",
+    );
+    print_flag(
+        "",
+        "--include-only-tld <TLD>",
+        "Keep only domains under these TLDs",
+    );
     print_flag(
         "",
         "--list-presets",
         "List all available TLD presets and exit",
     );
+    print_flag("", "--show-tlds", "Print the resolved TLD list and exit");
+    print_flag(
+        "",
+        "--baseline",
+        "Probe a random name per TLD to test registries",
+    );
+    print_flag(
+        "",
+        "--check-punycode-confusables",
+        "Warn about mixed-script / homoglyph IDNs",
+    );
+    print_flag(
+        "",
+        "--reject-confusables",
+        "Skip IDN inputs flagged as confusable",
+    );
+    print_flag(
+        "",
+        "--skip-reserved",
+        "Skip registry-reserved names (nic, www, whois)",
+    );
+    print_flag(
+        "",
+        "--preserve-case",
+        "Keep input casing instead of lowercasing domains",
+    );
+    print_flag(
+        "",
+        "--strict-validation",
+        "Reject names breaking RFC 1035 label rules",
+    );
+    print_flag(
+        "",
+        "--resume <FILE>",
+        "Resume a prior --json/--csv/--ndjson run (re-check unknowns)",
+    );
+    print_flag(
+        "",
+        "--recheck-available <FILE>",
+        "Re-check a prior run's available domains only",
+    );
+    print_flag(
+        "",
+        "--with-aliases",
+        "Also check [tld_aliases] for taken domains",
+    );
+    print_flag(
+        "",
+        "--with-variants",
+        "Also check variant-script forms of bundled TLDs",
+    );
     print_flag(
         "-f",
         "--file <FILE>",
-        "Input file with domains (one per line)",
+        "Domains file (one per line), - for stdin, or a URL",
+    );
+    print_flag(
+        "",
+        "--stream-file",
+        "Read --file lazily; bounded memory, no dedup",
+    );
+    print_flag(
+        "",
+        "--passthrough-columns <LIST>",
+        "Copy CSV --file columns into --csv output",
     );
 
     // DOMAIN GENERATION
@@ -106,23 +213,175 @@ pub fn print_custom_help() {
         "--dry-run",
         "Preview generated domains without checking",
     );
+    print_flag(
+        "",
+        "--estimate",
+        "Project request count and run time without checking",
+    );
+    print_flag(
+        "",
+        "--estimate-latency <MS>",
+        "Assumed round trip for --estimate (default: 500)",
+    );
 
     // OUTPUT FORMAT
     print_section("OUTPUT FORMAT");
     print_flag("-j", "--json", "Output results in JSON format");
+    print_flag(
+        "",
+        "--json-envelope",
+        "Wrap --json as {config, results} with run settings",
+    );
     print_flag("", "--csv", "Output results in CSV format");
+    print_flag("", "--ndjson", "Output one JSON object per line");
+    print_flag(
+        "",
+        "--output <FILE>",
+        "Write --ndjson/--csv results to FILE",
+    );
+    print_flag(
+        "",
+        "--append",
+        "Add to the --output file instead of replacing it",
+    );
+    print_flag(
+        "",
+        "--min-confidence <LEVEL>",
+        "Hide results below high, medium or low confidence",
+    );
+    print_flag("", "--bool", "Print only true/false/unknown for one domain");
     print_flag("-p", "--pretty", "Grouped output with section headers");
+    print_flag(
+        "",
+        "--legacy-format",
+        "Old \"example.com is AVAILABLE\" phrasing",
+    );
+    print_flag("", "--only-errors", "Show only failed checks, with reasons");
+    print_flag(
+        "",
+        "--group-by <MODE>",
+        "Group batch output by status, tld or none",
+    );
     print_flag("-i", "--info", "Show detailed domain information");
+    print_flag(
+        "",
+        "--fields <LIST>",
+        "Info fields / CSV columns to show (implies --info)",
+    );
+    print_flag(
+        "",
+        "--date-format <FMT>",
+        "Registry dates as iso, date-only or strftime",
+    );
+    print_flag(
+        "",
+        "--registrar-stats",
+        "Registrar frequency table for taken domains",
+    );
+    print_flag(
+        "",
+        "--summary-json <FILE>",
+        "Write run summary counts as JSON to FILE",
+    );
+    print_flag(
+        "",
+        "--manifest <FILE>",
+        "Write version, inputs, config and list hash",
+    );
+    print_flag(
+        "",
+        "--failures-file <FILE>",
+        "Write unknown domains to FILE for a retry",
+    );
+    print_flag(
+        "",
+        "--timing-log <FILE>",
+        "Write per-check launch/finish times (CSV/JSON)",
+    );
+    print_flag(
+        "",
+        "--compact-summary",
+        "Print a final key=value summary line for scripts",
+    );
+    print_flag("", "--no-summary", "Omit the summary footer");
+    print_flag(
+        "",
+        "--progress-json",
+        "NDJSON progress events on stderr for wrappers",
+    );
+    print_flag(
+        "",
+        "--progress-json-results",
+        "Also emit a result event per domain",
+    );
+    print_flag(
+        "",
+        "--method-stats",
+        "Show how many results came via RDAP/WHOIS",
+    );
+    print_flag(
+        "",
+        "--endpoint-report",
+        "Per-registry RDAP ok/404/error/timeout counts",
+    );
+    print_flag(
+        "",
+        "--registrar-consistency",
+        "Flag base names held by different registrars",
+    );
+    print_flag(
+        "",
+        "--registrar-allowlist <LIST>",
+        "With --info, label taken domains at these registrars OWNED",
+    );
+
+    // PROCESSING MODES
+    print_section("PROCESSING MODES");
     print_flag("", "--batch", "Collect all results before displaying");
     print_flag("", "--streaming", "Show results as they complete");
+    print_flag(
+        "",
+        "--watch",
+        "Re-check periodically, report newly available",
+    );
+    print_flag(
+        "",
+        "--watch-interval <DURATION>",
+        "Time between --watch rounds (default: 5m)",
+    );
+    print_flag(
+        "",
+        "--webhook <URL>",
+        "With --watch, POST JSON when a domain frees up",
+    );
 
     // PERFORMANCE
     print_section("PERFORMANCE");
     print_flag(
         "-c",
-        "--concurrency <N>",
+        "--concurrency <N|auto>",
         "Max concurrent checks (default: 20, max: 100)",
     );
+    print_flag(
+        "",
+        "--concurrency-ramp <SECS>",
+        "Warm up to full concurrency over SECS seconds",
+    );
+    print_flag(
+        "",
+        "--concurrency-per-host <N>",
+        "Max RDAP requests in flight per registry host",
+    );
+    print_flag(
+        "",
+        "--batch-size <N>",
+        "Check in batches of N with a tally after each",
+    );
+    print_flag(
+        "",
+        "--timeout-retries <N>",
+        "Retry timed-out RDAP requests N times (default: 0)",
+    );
     print_flag("", "--force", "Override the 5000 domain limit");
     print_flag("-y", "--yes", "Skip confirmation prompts");
 
@@ -134,6 +393,46 @@ pub fn print_custom_help() {
         "Disable IANA bootstrap (hardcoded TLDs only)",
     );
     print_flag("", "--no-whois", "Disable automatic WHOIS fallback");
+    print_flag(
+        "",
+        "--race",
+        "Query RDAP and WHOIS at once, first answer wins",
+    );
+    print_flag(
+        "",
+        "--detect-parking",
+        "Flag taken domains that serve parking pages",
+    );
+    print_flag(
+        "",
+        "--check-redirects",
+        "Show where taken domains' home pages redirect",
+    );
+    print_flag(
+        "",
+        "--dns-server <ADDR>",
+        "Nameserver for DNS-based features (repeatable)",
+    );
+    print_flag(
+        "",
+        "--treat-dropping-as-available",
+        "Report pendingDelete/redemption as available",
+    );
+    print_flag(
+        "",
+        "--detect-wildcards",
+        "Probe each TLD for wildcard (all-taken) registries",
+    );
+    print_flag(
+        "",
+        "--rdap-server <URL>",
+        "Send every RDAP query to URL/<domain>, any TLD",
+    );
+    print_flag(
+        "",
+        "--bootstrap-url <URL>",
+        "Fetch the RDAP bootstrap registry from a mirror",
+    );
 
     // CONFIGURATION
     print_section("CONFIGURATION");
@@ -144,7 +443,7 @@ pub fn print_custom_help() {
     // GENERAL
     print_section("GENERAL");
     print_flag("-h", "--help", "Show this help message");
-    print_flag("-V", "--version", "Show version");
+    print_flag("-V", "--version", "Show version (--json for build details)");
 
     // EXAMPLES
     print_section("EXAMPLES");
@@ -158,6 +457,10 @@ pub fn print_custom_help() {
         "domain-check --pattern \"app\\d\" --dry-run",
         "Preview pattern-generated names",
     );
+    print_example(
+        "domain-check generate --pattern \"app\\d\" --count",
+        "Count generated names without checking",
+    );
 
     println!();
 }
@@ -282,15 +585,18 @@ pub fn print_header(domain_count: usize, concurrency: usize, args: &Args) {
 
 // ── Single result line ───────────────────────────────────────────────────────
 
-/// Format and print a single domain result with colors and alignment.
+/// Format and write a single domain result with colors and alignment.
 ///
 /// If `counter` is Some((current, total)), a progress prefix like `[3/8]` is shown.
-pub fn print_result(
+pub fn write_result(
+    out: &mut impl Write,
     result: &DomainResult,
     show_info: bool,
+    fields: &[InfoField],
     debug: bool,
     counter: Option<(usize, usize)>,
-) {
+    owned: &[String],
+) -> io::Result<()> {
     let domain_width = 30;
     let padded_domain = pad_str(&result.domain, domain_width, Alignment::Left, Some(".."));
 
@@ -303,67 +609,73 @@ pub fn print_result(
 
     match result.available {
         Some(true) => {
-            println!(
-                "  {}{}  {}",
+            writeln!(
+                out,
+                "  {}{}  {}{}",
                 prefix,
                 style(&padded_domain).white(),
                 style("AVAILABLE").green().bold(),
-            );
+                confidence_suffix(result),
+            )?;
         }
         Some(false) => {
             let info_str = if show_info {
                 result
                     .info
                     .as_ref()
-                    .map(|i| format!("  {}", style(format_domain_info(i)).dim()))
+                    .map(|i| format!("  {}", style(format_domain_info(i, fields)).dim()))
                     .unwrap_or_default()
             } else {
                 String::new()
             };
-            println!(
-                "  {}{}  {}{}",
+            writeln!(
+                out,
+                "  {}{}  {}{}{}{}",
                 prefix,
                 style(&padded_domain).white(),
-                style("TAKEN").red().bold(),
+                taken_status(result, owned),
+                parked_suffix(result),
+                redirect_suffix(result),
                 info_str,
-            );
+            )?;
         }
         None => {
             let reason = brief_error(result);
-            println!(
+            writeln!(
+                out,
                 "  {}{}  {}  {}",
                 prefix,
                 style(&padded_domain).white(),
                 style("UNKNOWN").yellow(),
                 style(reason).dim(),
-            );
+            )?;
         }
     }
 
+    write_note(out, result, "    ")?;
+
     if debug {
-        if let Some(duration) = result.check_duration {
-            println!(
-                "    {} Checked in {}ms via {}",
-                style("└─").dim(),
-                duration.as_millis(),
-                result.method_used,
-            );
-        }
+        write_debug_line(out, result, "    ")?;
     }
+
+    Ok(())
 }
 
 // ── Default result line (colored, flat) ───────────────────────────────────────
 
-/// Print a single domain result with colored status words but flat layout.
+/// Write a single domain result with colored status words but flat layout.
 /// No padding or column alignment — just `domain STATUS` with color.
 ///
 /// If `counter` is Some((current, total)), a progress prefix like `[3/8]` is shown.
-pub fn print_result_default(
+pub fn write_result_default(
+    out: &mut impl Write,
     result: &DomainResult,
     show_info: bool,
+    fields: &[InfoField],
     debug: bool,
     counter: Option<(usize, usize)>,
-) {
+    owned: &[String],
+) -> io::Result<()> {
     let prefix = match counter {
         Some((cur, total)) => format!("{} ", style(format!("[{}/{}]", cur, total)).dim()),
         None => String::new(),
@@ -371,60 +683,114 @@ pub fn print_result_default(
 
     match result.available {
         Some(true) => {
-            println!(
-                "{}{} {}",
+            writeln!(
+                out,
+                "{}{} {}{}",
                 prefix,
                 result.domain,
                 style("AVAILABLE").green().bold(),
-            );
+                confidence_suffix(result),
+            )?;
         }
         Some(false) => {
             let info_str = if show_info {
                 result
                     .info
                     .as_ref()
-                    .map(|i| format!(" ({})", style(format_domain_info(i)).dim()))
+                    .map(|i| format!(" ({})", style(format_domain_info(i, fields)).dim()))
                     .unwrap_or_default()
             } else {
                 String::new()
             };
-            println!(
-                "{}{} {}{}",
+            writeln!(
+                out,
+                "{}{} {}{}{}{}",
                 prefix,
                 result.domain,
-                style("TAKEN").red().bold(),
+                taken_status(result, owned),
+                parked_suffix(result),
+                redirect_suffix(result),
                 info_str,
-            );
+            )?;
         }
         None => {
             let reason = brief_error(result);
-            println!(
+            writeln!(
+                out,
                 "{}{} {} {}",
                 prefix,
                 result.domain,
                 style("UNKNOWN").yellow(),
                 style(reason).dim(),
-            );
+            )?;
         }
     }
 
+    write_note(out, result, "    ")?;
+
     if debug {
-        if let Some(duration) = result.check_duration {
-            println!(
-                "    {} Checked in {}ms via {}",
-                style("└─").dim(),
-                duration.as_millis(),
-                result.method_used,
-            );
+        write_debug_line(out, result, "    ")?;
+    }
+
+    Ok(())
+}
+
+// ── Legacy result line ───────────────────────────────────────────────────────
+
+/// Build the pre-1.0 `example.com is AVAILABLE` line, with the old status
+/// emoji in front when `emoji` is set.
+pub fn legacy_line(result: &DomainResult, emoji: bool) -> String {
+    let (icon, status) = match result.available {
+        Some(true) => ("🟢", "AVAILABLE"),
+        Some(false) => ("🔴", "TAKEN"),
+        None => ("⚠️", "UNKNOWN"),
+    };
+    if emoji {
+        format!("{} {} is {}", icon, result.domain, status)
+    } else {
+        format!("{} is {}", result.domain, status)
+    }
+}
+
+/// Write a result in the legacy phrasing (--legacy-format).
+///
+/// Pretty mode keeps the emoji the old CLI used; plain mode drops them so
+/// scripts matching on "is AVAILABLE" keep working.
+pub fn write_result_legacy(
+    out: &mut impl Write,
+    result: &DomainResult,
+    pretty: bool,
+    show_info: bool,
+    fields: &[InfoField],
+    debug: bool,
+) -> io::Result<()> {
+    let info_str = match (&result.info, result.available) {
+        (Some(info), Some(false)) if show_info => {
+            format!(" ({})", format_domain_info(info, fields))
         }
+        _ => String::new(),
+    };
+    writeln!(out, "{}{}", legacy_line(result, pretty), info_str)?;
+
+    if debug {
+        write_debug_line(out, result, "    ")?;
     }
+
+    Ok(())
 }
 
 // ── Grouped batch output (Issue #17 core) ────────────────────────────────────
 
-/// Print results grouped by status: Available, Taken, Unknown.
+/// Write results grouped by status: Available, Taken, Unknown.
 /// Empty sections are omitted entirely.
-pub fn print_grouped_results(results: &[DomainResult], show_info: bool, debug: bool) {
+pub fn write_grouped_results(
+    out: &mut impl Write,
+    results: &[DomainResult],
+    show_info: bool,
+    fields: &[InfoField],
+    debug: bool,
+    owned: &[String],
+) -> io::Result<()> {
     let mut available: Vec<&DomainResult> = Vec::new();
     let mut taken: Vec<&DomainResult> = Vec::new();
     let mut unknown: Vec<&DomainResult> = Vec::new();
@@ -438,127 +804,796 @@ pub fn print_grouped_results(results: &[DomainResult], show_info: bool, debug: b
     }
 
     if !available.is_empty() {
-        println!(
+        writeln!(
+            out,
             "  {} {}",
             style(format!("── Available ({}) ", available.len()))
                 .green()
                 .bold(),
             style("─".repeat(40)).green().dim(),
-        );
+        )?;
         for r in &available {
-            print_grouped_line(r, show_info, debug);
+            write_grouped_line(out, r, show_info, fields, debug, owned)?;
         }
-        println!();
+        writeln!(out)?;
     }
 
     if !taken.is_empty() {
-        println!(
+        writeln!(
+            out,
             "  {} {}",
             style(format!("── Taken ({}) ", taken.len())).red().bold(),
             style("─".repeat(44)).red().dim(),
-        );
+        )?;
         for r in &taken {
-            print_grouped_line(r, show_info, debug);
+            write_grouped_line(out, r, show_info, fields, debug, owned)?;
         }
-        println!();
+        writeln!(out)?;
     }
 
     if !unknown.is_empty() {
-        println!(
+        writeln!(
+            out,
             "  {} {}",
             style(format!("── Unknown ({}) ", unknown.len()))
                 .yellow()
                 .bold(),
             style("─".repeat(40)).yellow().dim(),
-        );
+        )?;
         for r in &unknown {
-            print_grouped_line(r, show_info, debug);
+            write_grouped_line(out, r, show_info, fields, debug, owned)?;
         }
-        println!();
+        writeln!(out)?;
     }
+    Ok(())
 }
 
-/// Print a single line inside a grouped section.
-fn print_grouped_line(result: &DomainResult, show_info: bool, debug: bool) {
+/// Write a single line inside a grouped section.
+fn write_grouped_line(
+    out: &mut impl Write,
+    result: &DomainResult,
+    show_info: bool,
+    fields: &[InfoField],
+    debug: bool,
+    owned: &[String],
+) -> io::Result<()> {
     let domain_width = 30;
     let padded = pad_str(&result.domain, domain_width, Alignment::Left, Some(".."));
 
     match result.available {
         Some(true) => {
-            println!("    {}", style(&padded).white());
+            writeln!(
+                out,
+                "    {}{}",
+                style(&padded).white(),
+                confidence_suffix(result)
+            )?;
         }
         Some(false) => {
             let info_str = if show_info {
                 result
                     .info
                     .as_ref()
-                    .map(|i| format!("  {}", style(format_domain_info(i)).dim()))
+                    .map(|i| format!("  {}", style(format_domain_info(i, fields)).dim()))
                     .unwrap_or_default()
             } else {
                 String::new()
             };
-            println!("    {}{}", style(&padded).white(), info_str);
+            writeln!(
+                out,
+                "    {}{}{}{}{}",
+                style(&padded).white(),
+                ownership_suffix(result, owned),
+                parked_suffix(result),
+                redirect_suffix(result),
+                info_str
+            )?;
         }
         None => {
             let reason = brief_error(result);
-            println!("    {}  {}", style(&padded).white(), style(reason).dim());
+            writeln!(
+                out,
+                "    {}  {}",
+                style(&padded).white(),
+                style(reason).dim()
+            )?;
         }
     }
 
+    write_note(out, result, "      ")?;
+
     if debug {
-        if let Some(duration) = result.check_duration {
-            println!(
-                "      {} Checked in {}ms via {}",
-                style("└─").dim(),
-                duration.as_millis(),
-                result.method_used,
-            );
-        }
+        write_debug_line(out, result, "      ")?;
     }
+    Ok(())
 }
 
-// ── Summary ──────────────────────────────────────────────────────────────────
-
-/// Print the final summary bar with colored counts.
-pub fn print_summary(
-    total: usize,
-    available: usize,
-    taken: usize,
-    unknown: usize,
-    duration: Duration,
-) {
-    println!(
-        "  {}",
-        style("────────────────────────────────────────────────────").dim()
-    );
-    println!(
-        "  {} domain{} in {:.1}s  {}  {}  {}  {}  {}  {}",
-        style(total).bold(),
-        if total == 1 { "" } else { "s" },
-        duration.as_secs_f64(),
-        style("|").dim(),
-        style(format!("{} available", available)).green(),
-        style("|").dim(),
-        style(format!("{} taken", taken)).red(),
-        style("|").dim(),
-        style(format!("{} unknown", unknown)).yellow(),
-    );
+// ── Grouped by TLD (--group-by tld) ──────────────────────────────────────────
+
+/// Layout for batch text output, selected with --group-by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Available / Taken / Unknown sections (the --pretty layout)
+    Status,
+    /// One section per TLD
+    Tld,
+    /// Flat list in input order
+    None,
 }
 
-// ── Helpers ──────────────────────────────────────────────────────────────────
+impl std::str::FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "status" => Ok(GroupBy::Status),
+            "tld" => Ok(GroupBy::Tld),
+            "none" => Ok(GroupBy::None),
+            other => Err(format!(
+                "Invalid --group-by '{}': use 'status', 'tld' or 'none'",
+                other
+            )),
+        }
+    }
+}
 
-/// Format domain info (registrar, dates) into a concise string.
-pub fn format_domain_info(info: &DomainInfo) -> String {
-    let mut parts = Vec::new();
-    if let Some(registrar) = &info.registrar {
-        parts.push(format!("Registrar: {}", registrar));
+/// Group results by TLD, keeping TLDs and results in first-seen order.
+///
+/// Multi-level suffixes stay together (`brand.co.uk` goes under `co.uk`).
+pub fn group_by_tld(results: &[DomainResult]) -> Vec<(String, Vec<&DomainResult>)> {
+    let mut groups: Vec<(String, Vec<&DomainResult>)> = Vec::new();
+    for result in results {
+        let tld = domain_check_lib::extract_domain_parts(&result.domain)
+            .map(|(_, tld)| tld)
+            .unwrap_or_default();
+        match groups.iter_mut().find(|(t, _)| *t == tld) {
+            Some((_, members)) => members.push(result),
+            None => groups.push((tld, vec![result])),
+        }
     }
-    if let Some(created) = &info.creation_date {
-        parts.push(format!("Created: {}", created));
+    groups
+}
+
+/// Section title for one TLD, e.g. ".com (2 available, 1 taken)".
+fn tld_section_title(tld: &str, members: &[&DomainResult]) -> String {
+    let available = members.iter().filter(|r| r.available == Some(true)).count();
+    let taken = members
+        .iter()
+        .filter(|r| r.available == Some(false))
+        .count();
+    let unknown = members.iter().filter(|r| r.available.is_none()).count();
+
+    let counts: Vec<String> = [
+        (available, "available"),
+        (taken, "taken"),
+        (unknown, "unknown"),
+    ]
+    .iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, label)| format!("{} {}", count, label))
+    .collect();
+    format!(".{} ({})", tld, counts.join(", "))
+}
+
+/// Write results in one section per TLD, each line with its status.
+pub fn write_tld_grouped_results(
+    out: &mut impl Write,
+    results: &[DomainResult],
+    show_info: bool,
+    fields: &[InfoField],
+    debug: bool,
+    owned: &[String],
+) -> io::Result<()> {
+    for (tld, members) in group_by_tld(results) {
+        let title = tld_section_title(&tld, &members);
+        writeln!(
+            out,
+            "  {} {}",
+            style(format!("── {} ", title)).cyan().bold(),
+            style("─".repeat(48usize.saturating_sub(title.len())))
+                .cyan()
+                .dim(),
+        )?;
+        for r in &members {
+            write_tld_line(out, r, show_info, fields, debug, owned)?;
+        }
+        writeln!(out)?;
     }
-    if let Some(expires) = &info.expiration_date {
-        parts.push(format!("Expires: {}", expires));
+    Ok(())
+}
+
+/// Write a single line inside a TLD section.
+fn write_tld_line(
+    out: &mut impl Write,
+    result: &DomainResult,
+    show_info: bool,
+    fields: &[InfoField],
+    debug: bool,
+    owned: &[String],
+) -> io::Result<()> {
+    let domain_width = 30;
+    let padded = pad_str(&result.domain, domain_width, Alignment::Left, Some(".."));
+
+    match result.available {
+        Some(true) => writeln!(
+            out,
+            "    {}{}{}",
+            style(&padded).white(),
+            style("AVAILABLE").green().bold(),
+            confidence_suffix(result)
+        )?,
+        Some(false) => {
+            let info_str = if show_info {
+                result
+                    .info
+                    .as_ref()
+                    .map(|i| format!("  {}", style(format_domain_info(i, fields)).dim()))
+                    .unwrap_or_default()
+            } else {
+                String::new()
+            };
+            writeln!(
+                out,
+                "    {}{}{}{}{}",
+                style(&padded).white(),
+                taken_status(result, owned),
+                parked_suffix(result),
+                redirect_suffix(result),
+                info_str
+            )?;
+        }
+        None => writeln!(
+            out,
+            "    {}{}  {}",
+            style(&padded).white(),
+            style("UNKNOWN").yellow(),
+            style(brief_error(result)).dim()
+        )?,
+    }
+
+    write_note(out, result, "      ")?;
+
+    if debug {
+        write_debug_line(out, result, "      ")?;
     }
+    Ok(())
+}
+
+// ── Summary ──────────────────────────────────────────────────────────────────
+
+/// Write the final summary bar with colored counts.
+pub fn write_summary(
+    out: &mut impl Write,
+    total: usize,
+    available: usize,
+    taken: usize,
+    unknown: usize,
+    duration: Duration,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "  {}",
+        style("────────────────────────────────────────────────────").dim()
+    )?;
+    writeln!(
+        out,
+        "  {} domain{} in {:.1}s  {}  {}  {}  {}  {}  {}",
+        style(total).bold(),
+        if total == 1 { "" } else { "s" },
+        duration.as_secs_f64(),
+        style("|").dim(),
+        style(format!("{} available", available)).green(),
+        style("|").dim(),
+        style(format!("{} taken", taken)).red(),
+        style("|").dim(),
+        style(format!("{} unknown", unknown)).yellow(),
+    )?;
+    Ok(())
+}
+
+/// Running tally printed after each `--batch-size` batch, e.g.
+/// "batch 3/10 done: 120 available, 95 taken, 5 unknown so far".
+pub fn format_batch_progress(
+    batch: usize,
+    batches: usize,
+    available: usize,
+    taken: usize,
+    unknown: usize,
+) -> String {
+    format!(
+        "batch {}/{} done: {} available, {} taken, {} unknown so far",
+        batch, batches, available, taken, unknown
+    )
+}
+
+/// Summarize which protocol answered each result, e.g.
+/// "methods: 1180 RDAP, 20 WHOIS, 5 Bootstrap".
+///
+/// Methods with no results are omitted; returns None for an empty slice.
+pub fn format_method_breakdown(results: &[DomainResult]) -> Option<String> {
+    let methods = [
+        CheckMethod::Rdap,
+        CheckMethod::Whois,
+        CheckMethod::Bootstrap,
+        CheckMethod::Unknown,
+    ];
+
+    let parts: Vec<String> = methods
+        .iter()
+        .filter_map(|method| {
+            let count = results.iter().filter(|r| &r.method_used == method).count();
+            (count > 0).then(|| format!("{} {}", count, method))
+        })
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(format!("methods: {}", parts.join(", ")))
+    }
+}
+
+/// Print the protocol breakdown line under the summary.
+pub fn print_method_stats(results: &[DomainResult]) {
+    if let Some(line) = format_method_breakdown(results) {
+        println!("  {}", style(line).dim());
+    }
+}
+
+/// Summarize why checks failed, e.g. "errors: 3 timeout, 1 rate limited".
+///
+/// Reasons use the same labels as the per-domain lines, most frequent first;
+/// returns None when nothing failed.
+pub fn format_error_breakdown(results: &[DomainResult]) -> Option<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for result in results.iter().filter(|r| r.available.is_none()) {
+        let reason = brief_error(result);
+        let reason = reason.trim_start_matches('(').trim_end_matches(')');
+        match counts.iter_mut().find(|(r, _)| r == reason) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((reason.to_string(), 1)),
+        }
+    }
+    if counts.is_empty() {
+        return None;
+    }
+
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let parts: Vec<String> = counts
+        .iter()
+        .map(|(reason, count)| format!("{} {}", count, reason))
+        .collect();
+    Some(format!("errors: {}", parts.join(", ")))
+}
+
+/// Print the error breakdown line under the summary (--only-errors).
+pub fn print_error_breakdown(results: &[DomainResult]) {
+    if let Some(line) = format_error_breakdown(results) {
+        println!("  {}", style(line).dim());
+    }
+}
+
+/// Warn on stderr when checks failed because the process ran out of file
+/// descriptors, and suggest how to fix it.
+pub fn warn_fd_exhaustion(results: &[DomainResult], concurrency: usize) {
+    let count = results
+        .iter()
+        .filter(|r| r.error_category == Some(ErrorCategory::TooManyOpenFiles))
+        .count();
+
+    if count > 0 {
+        eprintln!(
+            "⚠️ {} check{} failed with 'too many open files' at concurrency {}. \
+             Lower --concurrency or raise the limit with `ulimit -n`.",
+            count,
+            if count == 1 { "" } else { "s" },
+            concurrency
+        );
+    }
+}
+
+/// One --endpoint-report line: lookups sent to a host and how they ended.
+pub fn format_endpoint_line(stats: &EndpointStats, host_width: usize) -> String {
+    format!(
+        "{:<width$}  {} lookups: {} ok, {} not found, {} errors, {} timeouts",
+        stats.host,
+        stats.total(),
+        stats.success,
+        stats.not_found,
+        stats.errors,
+        stats.timeouts,
+        width = host_width
+    )
+}
+
+/// Print per-endpoint RDAP outcomes to stderr, most failures first.
+pub fn print_endpoint_report(stats: &[EndpointStats]) {
+    if stats.is_empty() {
+        return;
+    }
+    let width = stats.iter().map(|s| s.host.len()).max().unwrap_or(0);
+    eprintln!();
+    eprintln!("{}", style("RDAP endpoints:").bold());
+    for endpoint in stats {
+        let line = format_endpoint_line(endpoint, width);
+        if endpoint.failures() > 0 {
+            eprintln!("  {}", style(line).yellow());
+        } else {
+            eprintln!("  {}", line);
+        }
+    }
+}
+
+/// Warn on stderr about TLDs whose random-name probe was reported taken.
+pub fn warn_wildcard_tlds(tlds: &[String]) {
+    for tld in tlds {
+        eprintln!(
+            "⚠️ .{} may be wildcarded: a random name was reported taken, so its taken results are marked unknown",
+            tld
+        );
+    }
+}
+
+// ── Registrar stats ──────────────────────────────────────────────────────────
+
+/// Count registrars among taken domains, most frequent first.
+///
+/// Domains without a registrar are counted as "unknown". Ties are broken
+/// alphabetically so the output is stable across runs.
+pub fn registrar_frequencies(results: &[DomainResult]) -> Vec<(String, usize)> {
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+
+    for r in results.iter().filter(|r| r.available == Some(false)) {
+        let registrar = r
+            .info
+            .as_ref()
+            .and_then(|i| i.registrar.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        *counts.entry(registrar).or_insert(0) += 1;
+    }
+
+    let mut freq: Vec<(String, usize)> = counts.into_iter().collect();
+    freq.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    freq
+}
+
+/// Print the registrar frequency table for `--registrar-stats`.
+pub fn print_registrar_stats(results: &[DomainResult]) {
+    let freq = registrar_frequencies(results);
+
+    println!();
+    println!("  {}", style("Registrars (taken domains)").bold());
+
+    if freq.is_empty() {
+        println!("    {}", style("No taken domains").dim());
+        return;
+    }
+
+    for (registrar, count) in &freq {
+        let padded = pad_str(registrar, 40, Alignment::Left, Some(".."));
+        println!("    {}  {}", padded, style(count).bold());
+    }
+}
+
+// ── Registrar consistency ────────────────────────────────────────────────────
+
+/// Taken domains sharing a base name, with the registrar holding each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistrarGroup {
+    /// Base name shared by the domains (e.g. "brand")
+    pub base: String,
+    /// `(domain, registrar)` pairs in result order; missing registrars are "unknown"
+    pub holdings: Vec<(String, String)>,
+    /// True when every known registrar in the group is the same
+    pub consistent: bool,
+}
+
+/// Group taken domains by base name and check whether one registrar holds them all.
+///
+/// Only base names taken in two or more TLDs are reported. Registrars are
+/// compared case-insensitively, and unknown registrars don't count as a
+/// mismatch. Groups keep first-seen order.
+pub fn registrar_consistency(results: &[DomainResult]) -> Vec<RegistrarGroup> {
+    let mut groups: Vec<RegistrarGroup> = Vec::new();
+
+    for r in results.iter().filter(|r| r.available == Some(false)) {
+        let Some((base, _)) = domain_check_lib::extract_domain_parts(&r.domain) else {
+            continue;
+        };
+        let registrar = r
+            .info
+            .as_ref()
+            .and_then(|i| i.registrar.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        match groups.iter_mut().find(|g| g.base == base) {
+            Some(group) => group.holdings.push((r.domain.clone(), registrar)),
+            None => groups.push(RegistrarGroup {
+                base,
+                holdings: vec![(r.domain.clone(), registrar)],
+                consistent: true,
+            }),
+        }
+    }
+
+    groups.retain(|g| g.holdings.len() > 1);
+    for group in &mut groups {
+        let mut known: Vec<String> = group
+            .holdings
+            .iter()
+            .map(|(_, registrar)| registrar.to_lowercase())
+            .filter(|registrar| registrar != "unknown")
+            .collect();
+        known.sort();
+        known.dedup();
+        group.consistent = known.len() <= 1;
+    }
+    groups
+}
+
+/// Print the `--registrar-consistency` report.
+pub fn print_registrar_consistency(results: &[DomainResult]) {
+    let groups = registrar_consistency(results);
+
+    println!();
+    println!(
+        "  {}",
+        style("Registrar consistency (taken across TLDs)").bold()
+    );
+
+    if groups.is_empty() {
+        println!(
+            "    {}",
+            style("No base names taken in more than one TLD").dim()
+        );
+        return;
+    }
+
+    for group in &groups {
+        if group.consistent {
+            println!(
+                "    {} {}  {}",
+                style("✓").green(),
+                style(&group.base).bold(),
+                style(format!(
+                    "{} domains, {}",
+                    group.holdings.len(),
+                    group.holdings[0].1
+                ))
+                .dim()
+            );
+        } else {
+            println!(
+                "    {} {}  {}",
+                style("⚠").yellow(),
+                style(&group.base).bold(),
+                style("registrars differ").yellow()
+            );
+            for (domain, registrar) in &group.holdings {
+                println!(
+                    "        {}  {}",
+                    pad_str(domain, 30, Alignment::Left, None),
+                    registrar
+                );
+            }
+        }
+    }
+}
+
+// ── Registry baseline (--baseline) ───────────────────────────────────────────
+
+/// How a registry answered for a name that can't be registered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Baseline {
+    /// Reported available, as it should be
+    Ok,
+    /// Reported taken: the registry (or its RDAP server) answers for every name
+    AlwaysTaken,
+    /// No answer could be obtained
+    Error,
+}
+
+impl std::fmt::Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Baseline::Ok => write!(f, "OK"),
+            Baseline::AlwaysTaken => write!(f, "always-taken"),
+            Baseline::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// One TLD's baseline probe result.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct BaselineRow {
+    pub tld: String,
+    pub baseline: Baseline,
+    /// Protocol that answered, or the failure reason
+    pub detail: String,
+}
+
+/// Classify baseline probe results (one random name per TLD).
+pub fn baseline_rows(results: &[DomainResult]) -> Vec<BaselineRow> {
+    results
+        .iter()
+        .map(|r| {
+            let tld = domain_check_lib::extract_domain_parts(&r.domain)
+                .map(|(_, tld)| tld)
+                .unwrap_or_default();
+            let (baseline, detail) = match r.available {
+                Some(true) => (Baseline::Ok, format!("available via {}", r.method_used)),
+                Some(false) => (
+                    Baseline::AlwaysTaken,
+                    format!("taken via {}", r.method_used),
+                ),
+                None => (
+                    Baseline::Error,
+                    brief_error(r)
+                        .trim_matches(|c| c == '(' || c == ')')
+                        .to_string(),
+                ),
+            };
+            BaselineRow {
+                tld,
+                baseline,
+                detail,
+            }
+        })
+        .collect()
+}
+
+/// Print the TLD → baseline behavior table.
+pub fn print_baseline_table(rows: &[BaselineRow]) {
+    println!();
+    println!(
+        "  {}",
+        style("Registry baseline (random unregistered name)").bold()
+    );
+    for row in rows {
+        let tld = pad_str(&format!(".{}", row.tld), 16, Alignment::Left, Some("..")).to_string();
+        let label = row.baseline.to_string();
+        let baseline = pad_str(&label, 14, Alignment::Left, None).to_string();
+        let baseline = match row.baseline {
+            Baseline::Ok => style(baseline).green(),
+            Baseline::AlwaysTaken => style(baseline).red(),
+            Baseline::Error => style(baseline).yellow(),
+        };
+        println!("    {}  {}  {}", tld, baseline, style(&row.detail).dim());
+    }
+}
+
+// ── Helpers ──────────────────────────────────────────────────────────────────
+
+/// Build the `--debug` timing line, noting the RDAP HTTP status when known.
+fn debug_line(result: &DomainResult) -> Option<String> {
+    let duration = result.check_duration?;
+    let mut line = format!(
+        "Checked in {}ms via {}",
+        duration.as_millis(),
+        result.method_used
+    );
+    if let Some(status) = result.rdap_status {
+        line.push_str(&format!(" (HTTP {})", status));
+    }
+    Some(line)
+}
+
+/// Write the `--debug` timing line under a result.
+fn write_debug_line(out: &mut impl Write, result: &DomainResult, indent: &str) -> io::Result<()> {
+    match debug_line(result) {
+        Some(line) => writeln!(out, "{}{} {}", indent, style("└─").dim(), line),
+        None => Ok(()),
+    }
+}
+
+/// A `DomainInfo` field selectable with `--fields`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InfoField {
+    Registrar,
+    Created,
+    Expires,
+    Updated,
+    Nameservers,
+    Status,
+    Dnssec,
+}
+
+impl InfoField {
+    /// Fields shown when `--fields` isn't given.
+    pub const DEFAULT: &'static [InfoField] =
+        &[InfoField::Registrar, InfoField::Created, InfoField::Expires];
+
+    const ALL: &'static [InfoField] = &[
+        InfoField::Registrar,
+        InfoField::Created,
+        InfoField::Expires,
+        InfoField::Updated,
+        InfoField::Nameservers,
+        InfoField::Status,
+        InfoField::Dnssec,
+    ];
+
+    /// Name used by `--fields` and as the CSV column header.
+    pub fn name(self) -> &'static str {
+        match self {
+            InfoField::Registrar => "registrar",
+            InfoField::Created => "created",
+            InfoField::Expires => "expires",
+            InfoField::Updated => "updated",
+            InfoField::Nameservers => "nameservers",
+            InfoField::Status => "status",
+            InfoField::Dnssec => "dnssec",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            InfoField::Registrar => "Registrar",
+            InfoField::Created => "Created",
+            InfoField::Expires => "Expires",
+            InfoField::Updated => "Updated",
+            InfoField::Nameservers => "Nameservers",
+            InfoField::Status => "Status",
+            InfoField::Dnssec => "DNSSEC",
+        }
+    }
+
+    /// The field's value, or None when the registry didn't provide it.
+    ///
+    /// List fields are joined with `sep`.
+    pub fn value(self, info: &DomainInfo, sep: &str) -> Option<String> {
+        let join = |items: &[String]| (!items.is_empty()).then(|| items.join(sep));
+        match self {
+            InfoField::Registrar => info.registrar.clone(),
+            InfoField::Created => info.creation_date.clone(),
+            InfoField::Expires => info.expiration_date.clone(),
+            InfoField::Updated => info.updated_date.clone(),
+            InfoField::Nameservers => join(&info.nameservers),
+            InfoField::Status => join(&info.status),
+            InfoField::Dnssec => info
+                .dnssec
+                .map(|signed| if signed { "signed" } else { "unsigned" }.to_string()),
+        }
+    }
+}
+
+/// Parse a `--fields` list, rejecting unknown names.
+pub fn parse_info_fields(names: &[String]) -> Result<Vec<InfoField>, String> {
+    let mut fields = Vec::new();
+    for name in names {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            continue;
+        }
+        let field = InfoField::ALL
+            .iter()
+            .copied()
+            .find(|f| f.name() == name)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = InfoField::ALL.iter().map(|f| f.name()).collect();
+                format!(
+                    "Unknown field '{}' for --fields (valid: {})",
+                    name,
+                    valid.join(", ")
+                )
+            })?;
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    if fields.is_empty() {
+        return Err("--fields needs at least one field name".to_string());
+    }
+    Ok(fields)
+}
+
+/// Format the selected info fields on one line, skipping missing values.
+pub fn format_domain_info(info: &DomainInfo, fields: &[InfoField]) -> String {
+    let parts: Vec<String> = fields
+        .iter()
+        .filter_map(|f| f.value(info, " ").map(|v| format!("{}: {}", f.label(), v)))
+        .collect();
     if parts.is_empty() {
         "No info available".to_string()
     } else {
@@ -566,25 +1601,115 @@ pub fn format_domain_info(info: &DomainInfo) -> String {
     }
 }
 
-/// Extract a brief error reason from a DomainResult with unknown status.
-fn brief_error(result: &DomainResult) -> &str {
-    match &result.error_message {
-        Some(msg) => {
-            let m = msg.to_lowercase();
-            if m.contains("timeout") || m.contains("timed out") {
-                "(timeout)"
-            } else if m.contains("network") || m.contains("dns") || m.contains("connect") {
-                "(network error)"
-            } else if m.contains("parse") || m.contains("json") {
-                "(parsing error)"
-            } else if m.contains("unknown") || m.contains("tld") || m.contains("bootstrap") {
-                "(unknown TLD)"
-            } else {
-                "(error)"
-            }
+/// Write a result's note (e.g. a confusable warning) on its own line.
+///
+/// Notes attached to a confidence level are already shown inline.
+fn write_note(out: &mut impl Write, result: &DomainResult, indent: &str) -> io::Result<()> {
+    match (&result.note, result.confidence) {
+        (Some(note), None) => writeln!(
+            out,
+            "{}{} {}",
+            indent,
+            style("⚠").yellow(),
+            style(note).dim()
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Whether a taken domain's registrar is on the --registrar-allowlist.
+///
+/// Entries match case-insensitively anywhere in the registrar name, so
+/// `godaddy` matches "GoDaddy.com, LLC". None when no allowlist is given,
+/// the domain isn't taken, or its registrar is unknown.
+pub fn is_owned(result: &DomainResult, owned: &[String]) -> Option<bool> {
+    if owned.is_empty() || result.available != Some(false) {
+        return None;
+    }
+    let registrar = result.info.as_ref()?.registrar.as_ref()?.to_lowercase();
+    Some(owned.iter().any(|entry| {
+        let entry = entry.trim().to_lowercase();
+        !entry.is_empty() && registrar.contains(&entry)
+    }))
+}
+
+/// Status word for a taken domain: OWNED for allowlisted registrars,
+/// "TAKEN (external)" for others, plain TAKEN without an allowlist.
+fn taken_status(result: &DomainResult, owned: &[String]) -> String {
+    match is_owned(result, owned) {
+        Some(true) => style("OWNED").cyan().bold().to_string(),
+        Some(false) => style("TAKEN (external)").red().bold().to_string(),
+        None => style("TAKEN").red().bold().to_string(),
+    }
+}
+
+/// " (owned)" / " (external)" marker inside the grouped Taken section.
+fn ownership_suffix(result: &DomainResult, owned: &[String]) -> String {
+    match is_owned(result, owned) {
+        Some(true) => format!(" {}", style("(owned)").cyan()),
+        Some(false) => format!(" {}", style("(external)").red()),
+        None => String::new(),
+    }
+}
+
+/// " → host" marker for taken domains redirecting off-site, or empty.
+fn redirect_suffix(result: &DomainResult) -> String {
+    match &result.redirects_to {
+        Some(host) => format!(" {}", style(format!("→ {}", host)).magenta()),
+        None => String::new(),
+    }
+}
+
+/// " (parked)" marker for taken domains serving a parking page, or empty.
+fn parked_suffix(result: &DomainResult) -> String {
+    if result.parked == Some(true) {
+        format!(" {}", style("(parked)").yellow())
+    } else {
+        String::new()
+    }
+}
+
+/// Dimmed "(low confidence: ...)" marker for inferred verdicts, or empty.
+fn confidence_suffix(result: &DomainResult) -> String {
+    match result.confidence {
+        Some(confidence) => {
+            let label = match &result.note {
+                Some(note) => format!("({} confidence: {})", confidence, note),
+                None => format!("({} confidence)", confidence),
+            };
+            format!("  {}", style(label).dim())
         }
-        None => "(unknown status)",
+        None => String::new(),
+    }
+}
+
+/// Extract a brief error reason from a DomainResult with unknown status.
+///
+/// Uses the library's variant-based `ErrorCategory`, so message text (which
+/// may include registrar names) never influences the label. A timeout that
+/// exhausted its retries says how many attempts were made.
+fn brief_error(result: &DomainResult) -> Cow<'static, str> {
+    if let (Some(ErrorCategory::Timeout), Some(attempts @ 2..)) =
+        (result.error_category, result.attempts)
+    {
+        return Cow::Owned(format!("(timed out after {} attempts)", attempts));
     }
+
+    let label = match (result.error_category, &result.error_message) {
+        (Some(category), _) => match category {
+            ErrorCategory::Timeout => "(timeout)",
+            ErrorCategory::Network => "(network error)",
+            ErrorCategory::Parse => "(parsing error)",
+            ErrorCategory::RateLimited => "(rate limited)",
+            ErrorCategory::Blocked => "(blocked)",
+            ErrorCategory::UnknownTld => "(unknown TLD)",
+            ErrorCategory::TooManyOpenFiles => "(too many open files)",
+            ErrorCategory::Other => "(error)",
+        },
+        (None, Some(_)) => "(error)",
+        (None, None) => "(unknown status)",
+    };
+    Cow::Borrowed(label)
 }
 
 // ── Tests ────────────────────────────────────────────────────────────────────
@@ -592,32 +1717,21 @@ fn brief_error(result: &DomainResult) -> &str {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use domain_check_lib::CheckMethod;
 
     fn make_result(domain: &str, available: Option<bool>) -> DomainResult {
-        DomainResult {
-            domain: domain.to_string(),
-            available,
-            info: None,
-            check_duration: None,
-            method_used: CheckMethod::Rdap,
-            error_message: if available.is_none() {
-                Some("timeout".to_string())
-            } else {
-                None
-            },
+        let mut result = DomainResult::new(domain, available, CheckMethod::Rdap);
+        if available.is_none() {
+            result.error_message = Some("timeout".to_string());
+            result.error_category = Some(ErrorCategory::Timeout);
         }
+        result
     }
 
-    fn make_result_with_error(error: &str) -> DomainResult {
-        DomainResult {
-            domain: "test.com".to_string(),
-            available: None,
-            info: None,
-            check_duration: None,
-            method_used: CheckMethod::Unknown,
-            error_message: Some(error.to_string()),
-        }
+    fn make_result_with_error(error: &str, category: ErrorCategory) -> DomainResult {
+        let mut result = DomainResult::new("test.com", None, CheckMethod::Unknown);
+        result.error_message = Some(error.to_string());
+        result.error_category = Some(category);
+        result
     }
 
     // ── brief_error ─────────────────────────────────────────────────────
@@ -629,66 +1743,475 @@ mod tests {
     }
 
     #[test]
-    fn test_brief_error_timed_out() {
-        let r = make_result_with_error("request timed out after 5s");
-        assert_eq!(brief_error(&r), "(timeout)");
+    fn test_brief_error_network() {
+        let r = make_result_with_error("dns lookup failed", ErrorCategory::Network);
+        assert_eq!(brief_error(&r), "(network error)");
     }
 
     #[test]
-    fn test_brief_error_network() {
-        let r = make_result_with_error("dns lookup failed");
-        assert_eq!(brief_error(&r), "(network error)");
+    fn test_brief_error_parsing() {
+        let r = make_result_with_error("failed to parse json response", ErrorCategory::Parse);
+        assert_eq!(brief_error(&r), "(parsing error)");
     }
 
     #[test]
-    fn test_brief_error_connect() {
-        let r = make_result_with_error("failed to connect to server");
-        assert_eq!(brief_error(&r), "(network error)");
+    fn test_brief_error_rate_limited() {
+        let r = make_result_with_error("too many requests", ErrorCategory::RateLimited);
+        assert_eq!(brief_error(&r), "(rate limited)");
     }
 
     #[test]
-    fn test_brief_error_parsing() {
-        let r = make_result_with_error("failed to parse json response");
-        assert_eq!(brief_error(&r), "(parsing error)");
+    fn test_brief_error_timeout_after_retries() {
+        let mut r = make_result_with_error("request timed out", ErrorCategory::Timeout);
+        r.attempts = Some(3);
+        assert_eq!(brief_error(&r), "(timed out after 3 attempts)");
+
+        r.attempts = Some(1);
+        assert_eq!(brief_error(&r), "(timeout)");
     }
 
     #[test]
-    fn test_brief_error_json() {
-        let r = make_result_with_error("invalid JSON in response");
-        assert_eq!(brief_error(&r), "(parsing error)");
+    fn test_brief_error_blocked() {
+        let r = make_result_with_error("HTTP 451", ErrorCategory::Blocked);
+        assert_eq!(brief_error(&r), "(blocked)");
     }
 
     #[test]
     fn test_brief_error_unknown_tld() {
-        let r = make_result_with_error("unknown TLD .xyz123");
+        let r = make_result_with_error("unknown TLD .xyz123", ErrorCategory::UnknownTld);
         assert_eq!(brief_error(&r), "(unknown TLD)");
     }
 
     #[test]
-    fn test_brief_error_bootstrap() {
-        let r = make_result_with_error("bootstrap registry failed");
-        assert_eq!(brief_error(&r), "(unknown TLD)");
+    fn test_brief_error_generic() {
+        let r = make_result_with_error("something unexpected happened", ErrorCategory::Other);
+        assert_eq!(brief_error(&r), "(error)");
     }
 
     #[test]
-    fn test_brief_error_generic() {
-        let r = make_result_with_error("something unexpected happened");
+    fn test_brief_error_ignores_message_text() {
+        // Registrar names like "Network Solutions" must not trip the network label
+        let r = make_result_with_error(
+            "Registrar: Network Solutions, LLC (timeout)",
+            ErrorCategory::Other,
+        );
+        assert_eq!(brief_error(&r), "(error)");
+    }
+
+    #[test]
+    fn test_brief_error_message_without_category() {
+        let mut r = make_result_with_error("request timed out", ErrorCategory::Timeout);
+        r.error_category = None;
         assert_eq!(brief_error(&r), "(error)");
     }
 
     #[test]
     fn test_brief_error_no_message() {
-        let r = DomainResult {
-            error_message: None,
-            ..make_result("a.com", None)
-        };
+        let mut r = make_result("a.com", None);
+        r.error_message = None;
+        r.error_category = None;
         assert_eq!(brief_error(&r), "(unknown status)");
     }
 
+    // ── legacy_line ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_legacy_line_plain_has_no_emoji() {
+        let r = make_result("example.com", Some(true));
+        assert_eq!(legacy_line(&r, false), "example.com is AVAILABLE");
+        let r = make_result("example.com", Some(false));
+        assert_eq!(legacy_line(&r, false), "example.com is TAKEN");
+    }
+
     #[test]
-    fn test_brief_error_case_insensitive() {
-        let r = make_result_with_error("TIMEOUT occurred");
-        assert_eq!(brief_error(&r), "(timeout)");
+    fn test_legacy_line_pretty_keeps_emoji() {
+        let r = make_result("example.com", Some(true));
+        assert_eq!(legacy_line(&r, true), "🟢 example.com is AVAILABLE");
+        let r = make_result("example.com", None);
+        assert_eq!(legacy_line(&r, true), "⚠️ example.com is UNKNOWN");
+    }
+
+    // ── registrar_frequencies ───────────────────────────────────────────
+
+    fn make_taken_with_registrar(domain: &str, registrar: Option<&str>) -> DomainResult {
+        let mut result = make_result(domain, Some(false));
+        result.info = Some(DomainInfo {
+            registrar: registrar.map(|r| r.to_string()),
+            ..Default::default()
+        });
+        result
+    }
+
+    #[test]
+    fn test_registrar_frequencies_counts_and_order() {
+        let results = vec![
+            make_taken_with_registrar("a.com", Some("Namecheap")),
+            make_taken_with_registrar("b.com", Some("GoDaddy")),
+            make_taken_with_registrar("c.com", Some("GoDaddy")),
+            make_taken_with_registrar("d.com", Some("MarkMonitor")),
+            make_taken_with_registrar("e.com", Some("GoDaddy")),
+            make_taken_with_registrar("f.com", Some("Namecheap")),
+            make_result("free.com", Some(true)),
+        ];
+
+        let freq = registrar_frequencies(&results);
+        assert_eq!(
+            freq,
+            vec![
+                ("GoDaddy".to_string(), 3),
+                ("Namecheap".to_string(), 2),
+                ("MarkMonitor".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_registrar_frequencies_none_is_unknown() {
+        let results = vec![
+            make_taken_with_registrar("a.com", None),
+            make_result("b.com", Some(false)), // no info at all
+            make_taken_with_registrar("c.com", Some("GoDaddy")),
+        ];
+
+        let freq = registrar_frequencies(&results);
+        assert_eq!(
+            freq,
+            vec![("unknown".to_string(), 2), ("GoDaddy".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_registrar_frequencies_ignores_available_and_unknown() {
+        let results = vec![make_result("a.com", Some(true)), make_result("b.com", None)];
+        assert!(registrar_frequencies(&results).is_empty());
+    }
+
+    // ── format_method_breakdown ─────────────────────────────────────────
+
+    #[test]
+    fn test_format_method_breakdown_counts() {
+        let mut results = Vec::new();
+        for i in 0..4 {
+            results.push(make_result(&format!("r{}.com", i), Some(true)));
+        }
+        for i in 0..2 {
+            let mut r = make_result(&format!("w{}.ch", i), Some(false));
+            r.method_used = CheckMethod::Whois;
+            results.push(r);
+        }
+        let mut b = make_result("b.xyz", Some(false));
+        b.method_used = CheckMethod::Bootstrap;
+        results.push(b);
+
+        assert_eq!(
+            format_method_breakdown(&results).as_deref(),
+            Some("methods: 4 RDAP, 2 WHOIS, 1 Bootstrap")
+        );
+    }
+
+    #[test]
+    fn test_format_method_breakdown_includes_unknown() {
+        let mut r = make_result("x.zz", None);
+        r.method_used = CheckMethod::Unknown;
+        let results = vec![make_result("a.com", Some(true)), r];
+        assert_eq!(
+            format_method_breakdown(&results).as_deref(),
+            Some("methods: 1 RDAP, 1 Unknown")
+        );
+    }
+
+    #[test]
+    fn test_format_method_breakdown_empty() {
+        assert!(format_method_breakdown(&[]).is_none());
+    }
+
+    // ── format_error_breakdown ──────────────────────────────────────────
+
+    #[test]
+    fn test_format_error_breakdown_most_frequent_first() {
+        let results = vec![
+            make_result("a.com", None),
+            make_result("b.com", Some(true)),
+            make_result_with_error("x", ErrorCategory::RateLimited),
+            make_result("c.com", None),
+        ];
+        assert_eq!(
+            format_error_breakdown(&results).as_deref(),
+            Some("errors: 2 timeout, 1 rate limited")
+        );
+    }
+
+    #[test]
+    fn test_format_error_breakdown_none_without_errors() {
+        assert!(format_error_breakdown(&[make_result("a.com", Some(false))]).is_none());
+    }
+
+    // ── confidence_suffix ───────────────────────────────────────────────
+
+    #[test]
+    fn test_confidence_suffix() {
+        console::set_colors_enabled(false);
+        let mut r = make_result("drop.com", Some(true));
+        assert_eq!(confidence_suffix(&r), "");
+
+        r.confidence = Some(domain_check_lib::Confidence::Low);
+        r.note = Some("in redemption".to_string());
+        assert_eq!(confidence_suffix(&r), "  (low confidence: in redemption)");
+    }
+
+    // ── taken_status ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_taken_status_splits_by_registrar_allowlist() {
+        console::set_colors_enabled(false);
+        let with_registrar = |domain: &str, registrar: &str| {
+            let mut r = make_result(domain, Some(false));
+            r.info = Some(DomainInfo {
+                registrar: Some(registrar.to_string()),
+                ..Default::default()
+            });
+            r
+        };
+        let ours = with_registrar("mine.com", "GoDaddy.com, LLC");
+        let theirs = with_registrar("other.com", "NameCheap, Inc.");
+        let allowlist = vec!["godaddy".to_string()];
+
+        assert_eq!(taken_status(&ours, &allowlist), "OWNED");
+        assert_eq!(taken_status(&theirs, &allowlist), "TAKEN (external)");
+        assert_eq!(ownership_suffix(&ours, &allowlist), " (owned)");
+        assert_eq!(ownership_suffix(&theirs, &allowlist), " (external)");
+
+        // No allowlist, or no registrar to match, keeps the plain label
+        assert_eq!(taken_status(&ours, &[]), "TAKEN");
+        let unknown_registrar = make_result("bare.com", Some(false));
+        assert_eq!(taken_status(&unknown_registrar, &allowlist), "TAKEN");
+        assert_eq!(
+            is_owned(&make_result("free.com", Some(true)), &allowlist),
+            None
+        );
+    }
+
+    #[test]
+    fn test_empty_allowlist_entry_matches_nothing() {
+        let mut theirs = make_result("other.com", Some(false));
+        theirs.info = Some(DomainInfo {
+            registrar: Some("NameCheap, Inc.".to_string()),
+            ..Default::default()
+        });
+        // `--registrar-allowlist godaddy,` leaves an empty entry behind
+        let allowlist = vec!["godaddy".to_string(), " ".to_string()];
+        assert_eq!(is_owned(&theirs, &allowlist), Some(false));
+    }
+
+    // ── format_endpoint_line ────────────────────────────────────────────
+
+    #[test]
+    fn test_format_endpoint_line() {
+        let stats = EndpointStats {
+            host: "rdap.nic.foo".to_string(),
+            success: 0,
+            not_found: 0,
+            errors: 0,
+            timeouts: 40,
+        };
+        assert_eq!(
+            format_endpoint_line(&stats, 14),
+            "rdap.nic.foo    40 lookups: 0 ok, 0 not found, 0 errors, 40 timeouts"
+        );
+    }
+
+    // ── redirect_suffix ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_redirect_suffix() {
+        console::set_colors_enabled(false);
+        let mut r = make_result("examp1e.com", Some(false));
+        assert_eq!(redirect_suffix(&r), "");
+        r.redirects_to = Some("www.example.com".to_string());
+        assert_eq!(redirect_suffix(&r), " → www.example.com");
+    }
+
+    // ── parked_suffix ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_parked_suffix() {
+        console::set_colors_enabled(false);
+        let mut r = make_result("parked.com", Some(false));
+        assert_eq!(parked_suffix(&r), "");
+        r.parked = Some(false);
+        assert_eq!(parked_suffix(&r), "");
+        r.parked = Some(true);
+        assert_eq!(parked_suffix(&r), " (parked)");
+    }
+
+    // ── debug_line ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_debug_line_includes_rdap_status() {
+        let mut r = make_result("example.com", Some(true));
+        r.check_duration = Some(std::time::Duration::from_millis(42));
+        assert_eq!(debug_line(&r).unwrap(), "Checked in 42ms via RDAP");
+        r.rdap_status = Some(404);
+        assert_eq!(
+            debug_line(&r).unwrap(),
+            "Checked in 42ms via RDAP (HTTP 404)"
+        );
+        r.check_duration = None;
+        assert!(debug_line(&r).is_none());
+    }
+
+    // ── registrar_consistency ───────────────────────────────────────────
+
+    #[test]
+    fn test_registrar_consistency_flags_mixed_registrars() {
+        let results = vec![
+            make_taken_with_registrar("brand.com", Some("MarkMonitor")),
+            make_taken_with_registrar("brand.io", Some("MarkMonitor")),
+            make_taken_with_registrar("brand.ai", Some("Namecheap")),
+            make_result("brand.dev", Some(true)),
+        ];
+
+        let groups = registrar_consistency(&results);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].base, "brand");
+        assert_eq!(groups[0].holdings.len(), 3);
+        assert!(!groups[0].consistent);
+    }
+
+    #[test]
+    fn test_registrar_consistency_same_registrar() {
+        let results = vec![
+            make_taken_with_registrar("brand.com", Some("MarkMonitor")),
+            make_taken_with_registrar("brand.io", Some("markmonitor")),
+            make_taken_with_registrar("brand.ai", None),
+        ];
+
+        let groups = registrar_consistency(&results);
+        assert_eq!(groups.len(), 1);
+        assert!(groups[0].consistent);
+    }
+
+    #[test]
+    fn test_registrar_consistency_skips_single_tld_names() {
+        let results = vec![
+            make_taken_with_registrar("alpha.com", Some("GoDaddy")),
+            make_taken_with_registrar("beta.com", Some("Namecheap")),
+        ];
+        assert!(registrar_consistency(&results).is_empty());
+    }
+
+    // ── group_by_tld ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_group_by_tld_sections_in_first_seen_order() {
+        let results = vec![
+            make_result("brand.com", Some(false)),
+            make_result("brand.co.uk", Some(true)),
+            make_result("other.com", Some(true)),
+        ];
+        let groups = group_by_tld(&results);
+        let tlds: Vec<&str> = groups.iter().map(|(tld, _)| tld.as_str()).collect();
+        assert_eq!(tlds, vec!["com", "co.uk"]);
+        assert_eq!(groups[0].1.len(), 2);
+    }
+
+    #[test]
+    fn test_tld_section_titles() {
+        let results = vec![
+            make_result("brand.com", Some(false)),
+            make_result("other.com", Some(true)),
+            make_result("brand.io", None),
+        ];
+        let titles: Vec<String> = group_by_tld(&results)
+            .iter()
+            .map(|(tld, members)| tld_section_title(tld, members))
+            .collect();
+        assert_eq!(
+            titles,
+            vec![".com (1 available, 1 taken)", ".io (1 unknown)"]
+        );
+    }
+
+    #[test]
+    fn test_group_by_from_str() {
+        assert_eq!("tld".parse::<GroupBy>(), Ok(GroupBy::Tld));
+        assert_eq!("Status".parse::<GroupBy>(), Ok(GroupBy::Status));
+        assert_eq!("none".parse::<GroupBy>(), Ok(GroupBy::None));
+        assert!("registrar".parse::<GroupBy>().is_err());
+    }
+
+    // ── write_grouped_results / write_summary ───────────────────────────
+
+    /// A reader that has gone away, like `domain-check ... | head`.
+    struct ClosedPipe;
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_grouped_output_written_to_writer() {
+        let results = vec![
+            make_result("brand.com", Some(false)),
+            make_result("brand.io", Some(true)),
+        ];
+        let mut out = Vec::new();
+        write_grouped_results(&mut out, &results, false, InfoField::DEFAULT, false, &[]).unwrap();
+        write_tld_grouped_results(&mut out, &results, false, InfoField::DEFAULT, false, &[])
+            .unwrap();
+        write_summary(&mut out, 2, 1, 1, 0, Duration::from_millis(1500)).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Available (1)"), "{}", text);
+        assert!(text.contains(".com (1 taken)"), "{}", text);
+        assert!(text.contains("in 1.5s"), "{}", text);
+    }
+
+    #[test]
+    fn test_closed_pipe_is_an_error_not_a_panic() {
+        let results = vec![make_result("brand.com", Some(true))];
+        let err = write_grouped_results(
+            &mut ClosedPipe,
+            &results,
+            false,
+            InfoField::DEFAULT,
+            false,
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        assert!(write_summary(&mut ClosedPipe, 1, 1, 0, 0, Duration::ZERO).is_err());
+    }
+
+    // ── baseline_rows ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_baseline_rows_one_per_tld() {
+        let results = vec![
+            make_result("dc-baseline-1.com", Some(true)),
+            make_result("dc-baseline-1.xyz", Some(false)),
+            make_result("dc-baseline-1.co.uk", None),
+        ];
+        let rows = baseline_rows(&results);
+        let table: Vec<(&str, String)> = rows
+            .iter()
+            .map(|r| (r.tld.as_str(), r.baseline.to_string()))
+            .collect();
+        assert_eq!(
+            table,
+            vec![
+                ("com", "OK".to_string()),
+                ("xyz", "always-taken".to_string()),
+                ("co.uk", "error".to_string()),
+            ]
+        );
+        assert_eq!(rows[0].detail, "available via RDAP");
+        assert_eq!(rows[2].detail, "timeout");
     }
 
     // ── format_domain_info ──────────────────────────────────────────────
@@ -701,7 +2224,7 @@ mod tests {
             expiration_date: Some("2025-01-01".to_string()),
             ..Default::default()
         };
-        let formatted = format_domain_info(&info);
+        let formatted = format_domain_info(&info, InfoField::DEFAULT);
         assert!(formatted.contains("Registrar: GoDaddy"));
         assert!(formatted.contains("Created: 2020-01-01"));
         assert!(formatted.contains("Expires: 2025-01-01"));
@@ -710,7 +2233,10 @@ mod tests {
     #[test]
     fn test_format_domain_info_empty() {
         let info = DomainInfo::default();
-        assert_eq!(format_domain_info(&info), "No info available");
+        assert_eq!(
+            format_domain_info(&info, InfoField::DEFAULT),
+            "No info available"
+        );
     }
 
     #[test]
@@ -719,7 +2245,10 @@ mod tests {
             registrar: Some("Namecheap".to_string()),
             ..Default::default()
         };
-        assert_eq!(format_domain_info(&info), "Registrar: Namecheap");
+        assert_eq!(
+            format_domain_info(&info, InfoField::DEFAULT),
+            "Registrar: Namecheap"
+        );
     }
 
     #[test]
@@ -729,12 +2258,48 @@ mod tests {
             expiration_date: Some("2025-01-01".to_string()),
             ..Default::default()
         };
-        let formatted = format_domain_info(&info);
+        let formatted = format_domain_info(&info, InfoField::DEFAULT);
         assert!(formatted.contains("Created: 2020-01-01"));
         assert!(formatted.contains("Expires: 2025-01-01"));
         assert!(!formatted.contains("Registrar"));
     }
 
+    #[test]
+    fn test_format_domain_info_selected_fields_only() {
+        let info = DomainInfo {
+            registrar: Some("GoDaddy".to_string()),
+            creation_date: Some("2020-01-01".to_string()),
+            status: vec!["active".to_string(), "client hold".to_string()],
+            nameservers: vec!["ns1.example.net".to_string(), "ns2.example.net".to_string()],
+            dnssec: Some(true),
+            ..Default::default()
+        };
+        let fields = [InfoField::Nameservers, InfoField::Status];
+        assert_eq!(
+            format_domain_info(&info, &fields),
+            "Nameservers: ns1.example.net ns2.example.net, Status: active client hold"
+        );
+        assert_eq!(
+            format_domain_info(&info, &[InfoField::Dnssec]),
+            "DNSSEC: signed"
+        );
+    }
+
+    #[test]
+    fn test_parse_info_fields() {
+        let names: Vec<String> = ["Expires", "status", "expires"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            parse_info_fields(&names).unwrap(),
+            vec![InfoField::Expires, InfoField::Status]
+        );
+
+        let err = parse_info_fields(&["owner".to_string()]).unwrap_err();
+        assert!(err.contains("Unknown field 'owner'"), "{}", err);
+    }
+
     #[test]
     fn test_format_domain_info_comma_separated() {
         let info = DomainInfo {
@@ -742,7 +2307,7 @@ mod tests {
             creation_date: Some("2020".to_string()),
             ..Default::default()
         };
-        let formatted = format_domain_info(&info);
+        let formatted = format_domain_info(&info, InfoField::DEFAULT);
         assert!(formatted.contains(", "));
     }
 }
@@ -5,6 +5,12 @@ use predicates::prelude::*;
 use std::fs;
 use tempfile::NamedTempFile;
 
+mod common;
+use common::{
+    counting_rdap_server, not_found_rdap_server, registered_rdap_server, response, serve_forever,
+    text_server,
+};
+
 /// Helper to create a test domains file
 fn create_test_domains_file(domains: &[&str]) -> NamedTempFile {
     let file = NamedTempFile::new().expect("Failed to create temp file");
@@ -61,6 +67,55 @@ fn test_list_presets_no_domains_required() {
     cmd.assert().success();
 }
 
+#[test]
+fn test_show_tlds_prints_preset_and_exits() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["--preset", "startup", "--show-tlds"]);
+    cmd.assert()
+        .success()
+        .stdout("com\norg\nio\nai\ntech\napp\ndev\nxyz\n");
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    let output = cmd
+        .args([
+            "--preset",
+            "startup",
+            "--exclude-tld",
+            "ai",
+            "--show-tlds",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let tlds: Vec<String> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(tlds, ["com", "org", "io", "tech", "app", "dev", "xyz"]);
+}
+
+#[test]
+fn test_version_plain_and_json() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.arg("--version");
+    cmd.assert()
+        .success()
+        .stdout(format!("domain-check {}\n", env!("CARGO_PKG_VERSION")));
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    let output = cmd.args(["--version", "--json"]).output().unwrap();
+    assert!(output.status.success());
+    let parsed: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("should be valid JSON");
+    assert_eq!(parsed["version"], env!("CARGO_PKG_VERSION"));
+    let features: Vec<&str> = parsed["features"]
+        .as_array()
+        .expect("features should be an array")
+        .iter()
+        .filter_map(|f| f.as_str())
+        .collect();
+    assert_eq!(features, domain_check_lib::info().features);
+    assert!(parsed["builtin_tld_count"].as_u64().unwrap() > 0);
+}
+
 #[test]
 fn test_all_flag_functionality() {
     let mut cmd = Command::cargo_bin("domain-check").unwrap();
@@ -464,6 +519,70 @@ fn test_streaming_with_json_rejected() {
     ));
 }
 
+#[test]
+fn test_streaming_exits_cleanly_when_reader_closes() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    // 2000 unknown-TLD results overflow the pipe buffer, so the CLI is still
+    // writing when the reader goes away (like `| head -1`)
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("domain-check"))
+        .args([
+            "--pattern",
+            "zz\\d\\d\\d",
+            "-t",
+            "zzzz,yyyy",
+            "--no-bootstrap",
+            "--no-whois",
+            "--streaming",
+            "--yes",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut first = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut first)
+        .unwrap();
+    assert!(first.contains("zz000"), "{}", first);
+
+    let output = child.wait_with_output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+    assert!(output.status.success(), "{:?}", output.status);
+}
+
+#[test]
+fn test_batch_exits_cleanly_when_reader_closed() {
+    use std::process::Stdio;
+
+    // Like `| head -0`: the reader is gone before anything is written
+    for format in ["--json", "--csv"] {
+        let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("domain-check"))
+            .args([
+                "alpha",
+                "beta",
+                "-t",
+                "zzzz",
+                "--no-bootstrap",
+                "--no-whois",
+            ])
+            .args(["--batch", "--compact-summary", format])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        drop(child.stdout.take());
+
+        let output = child.wait_with_output().unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!stderr.contains("panicked"), "{}: {}", format, stderr);
+        assert!(output.status.success(), "{}: {:?}", format, output.status);
+    }
+}
+
 #[test]
 fn test_streaming_with_csv_rejected() {
     let mut cmd = Command::cargo_bin("domain-check").unwrap();
@@ -553,6 +672,180 @@ fn test_dry_run_prints_domains_exits_zero() {
         .stderr(predicate::str::contains("10 domains would be checked"));
 }
 
+#[test]
+fn test_no_summary_keeps_results_and_drops_footer() {
+    let server = not_found_rdap_server();
+    for mode in ["--streaming", "--batch"] {
+        let mut cmd = Command::cargo_bin("domain-check").unwrap();
+        cmd.args(["alpha", "-t", "com,org", "--no-whois", "--rdap-server"])
+            .arg(&server)
+            .args([mode, "--no-summary"]);
+
+        let output = cmd.output().unwrap();
+        assert!(output.status.success(), "{}", mode);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("alpha.com"), "{}: {}", mode, stdout);
+        assert!(stdout.contains("alpha.org"), "{}: {}", mode, stdout);
+        assert!(!stdout.contains("domains in"), "{}: {}", mode, stdout);
+    }
+
+    // Without the flag the footer is there
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["alpha", "-t", "com,org", "--no-whois", "--rdap-server"])
+        .arg(&server);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("2 domains in"));
+}
+
+#[test]
+fn test_file_url_checks_remote_list() {
+    let list = text_server("# shared list\nalpha.com\nbeta.org\n\ngamma\n");
+    let rdap = not_found_rdap_server();
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    let output = cmd
+        .args([
+            "--file",
+            &list,
+            "--no-whois",
+            "--json",
+            "--rdap-server",
+            &rdap,
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let results: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let mut domains: Vec<&str> = results
+        .iter()
+        .map(|r| r["domain"].as_str().unwrap())
+        .collect();
+    domains.sort_unstable();
+    assert_eq!(domains, ["alpha.com", "beta.org", "gamma.com"]);
+    assert!(results.iter().all(|r| r["available"] == true));
+}
+
+#[test]
+fn test_progress_json_events_on_stderr() {
+    let server = not_found_rdap_server();
+    for mode in [
+        &["--streaming"][..],
+        &["--json"][..],
+        &["--batch-size", "2"][..],
+    ] {
+        let mut cmd = Command::cargo_bin("domain-check").unwrap();
+        cmd.args(["alpha", "-t", "com,org,net", "--no-whois", "--rdap-server"])
+            .arg(&server)
+            .args(mode)
+            .args(["--progress-json", "--progress-json-results"]);
+
+        let output = cmd.output().unwrap();
+        assert!(output.status.success(), "{:?}", mode);
+        let events: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stderr)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert!(
+            events.iter().any(|e| e["event"] == "progress"),
+            "{:?}",
+            events
+        );
+        let results = events.iter().filter(|e| e["event"] == "result").count();
+        assert_eq!(results, 3, "{:?}", events);
+        let last = events.last().unwrap();
+        assert_eq!(last["event"], "done");
+        assert_eq!(last["done"], 3);
+        assert_eq!(last["total"], 3);
+        assert_eq!(last["available"], 3);
+    }
+}
+
+#[test]
+fn test_batch_size_prints_interim_tallies() {
+    let server = not_found_rdap_server();
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["alpha", "beta", "-t", "com,org,net,io", "--no-whois"])
+        .args(["--batch-size", "3", "--rdap-server"])
+        .arg(&server);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let tallies: Vec<&str> = stderr.lines().filter(|l| l.starts_with("batch ")).collect();
+    assert_eq!(
+        tallies,
+        [
+            "batch 1/3 done: 3 available, 0 taken, 0 unknown so far",
+            "batch 2/3 done: 6 available, 0 taken, 0 unknown so far",
+            "batch 3/3 done: 8 available, 0 taken, 0 unknown so far",
+        ]
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("beta.io"), "{}", stdout);
+    assert!(stdout.contains("8 domains in"), "{}", stdout);
+    assert!(stdout.contains("8 available"), "{}", stdout);
+}
+
+#[test]
+fn test_domains_lowercased_unless_preserve_case() {
+    let server = not_found_rdap_server();
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    let output = cmd
+        .args([
+            "MyBrand.COM",
+            "--no-whois",
+            "--json",
+            "--rdap-server",
+            &server,
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let results: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results[0]["domain"], "mybrand.com");
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["MyBrand.COM", "--dry-run", "--preserve-case"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("MyBrand.COM\n"));
+}
+
+#[test]
+fn test_json_array_on_stdin() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["--file", "-", "--dry-run"])
+        .write_stdin(r#"["alpha.com", {"domain": "beta.io"}, "gamma"]"#);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("alpha.com"))
+        .stdout(predicate::str::contains("beta.io"))
+        .stdout(predicate::str::contains("gamma.com"))
+        .stderr(predicate::str::contains("3 domains would be checked"));
+}
+
+#[test]
+fn test_lines_on_stdin() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["--file", "-", "--dry-run"])
+        .write_stdin("alpha.com\n# skipped\nbeta.io\n");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("alpha.com"))
+        .stdout(predicate::str::contains("beta.io"))
+        .stderr(predicate::str::contains("2 domains would be checked"));
+}
+
 #[test]
 fn test_dry_run_json_output() {
     // --dry-run --json should output a valid JSON array
@@ -569,6 +862,223 @@ fn test_dry_run_json_output() {
     assert!(arr.contains(&serde_json::Value::String("ab0.com".to_string())));
 }
 
+#[test]
+fn test_missing_file_with_json_emits_error_object() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["--file", "does-not-exist.txt", "--json"]);
+
+    let output = cmd.output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let parsed: serde_json::Value =
+        serde_json::from_slice(&output.stderr).expect("stderr should be a JSON error object");
+    assert_eq!(parsed["error"]["kind"], "file");
+    assert_eq!(
+        parsed["error"]["message"],
+        "does-not-exist.txt: file not found"
+    );
+}
+
+#[test]
+fn test_usage_error_with_json_emits_error_object() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["brand", "--json", "--no-such-flag"]);
+
+    let output = cmd.output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let parsed: serde_json::Value =
+        serde_json::from_slice(&output.stderr).expect("stderr should be a JSON error object");
+    assert_eq!(parsed["error"]["kind"], "invalid_args");
+    assert!(
+        parsed["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("--no-such-flag"),
+        "{}",
+        parsed
+    );
+
+    // Without --json clap reports it as usual
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["brand", "--no-such-flag"]);
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("--no-such-flag"));
+}
+
+#[test]
+fn test_generate_json_error_kinds() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["generate", "--pattern", "ab\\x", "--json"]);
+    let output = cmd.output().unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(parsed["error"]["kind"], "invalid_pattern");
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["generate", "--json"]);
+    let output = cmd.output().unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(parsed["error"]["kind"], "invalid_args");
+}
+
+#[test]
+fn test_passthrough_columns_in_csv_output() {
+    let file = NamedTempFile::new().unwrap();
+    fs::write(
+        file.path(),
+        "domain,owner,notes\nacme.zzzz,alice,renewal\nbeta.zzzz,bob,\"parked, for now\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "--file",
+        file.path().to_str().unwrap(),
+        "--csv",
+        "--passthrough-columns",
+        "owner",
+        "--no-bootstrap",
+        "--no-whois",
+    ]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    assert!(lines.next().unwrap().ends_with(",method,owner"));
+    let rows: Vec<&str> = lines.collect();
+    assert_eq!(rows.len(), 2);
+    assert!(rows
+        .iter()
+        .any(|r| r.starts_with("acme.zzzz,") && r.ends_with(",alice")));
+    assert!(rows
+        .iter()
+        .any(|r| r.starts_with("beta.zzzz,") && r.ends_with(",bob")));
+}
+
+#[test]
+fn test_generate_count() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["generate", "--pattern", "app\\d", "--count"]);
+
+    cmd.assert().success().stdout("10\n");
+}
+
+#[test]
+fn test_generate_prints_base_names_without_tlds() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["generate", "brand", "--prefix", "get"]);
+
+    cmd.assert().success().stdout("getbrand\nbrand\n");
+}
+
+#[test]
+fn test_generate_json_array() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["generate", "--pattern", "ab\\d", "--json"]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let parsed: Vec<String> =
+        serde_json::from_slice(&output.stdout).expect("should be a JSON array of names");
+    assert_eq!(parsed.len(), 10);
+    assert_eq!(parsed[0], "ab0");
+}
+
+#[test]
+fn test_generate_ascii_only_drops_unicode_names() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["generate", "café", "cafe"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("café"));
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["generate", "café", "cafe", "--ascii-only"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::diff("cafe\n"));
+}
+
+#[test]
+fn test_generate_requires_input() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["generate"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--pattern"));
+}
+
+#[test]
+fn test_file_with_tld_file_cross_product() {
+    // 3 names × 4 TLDs from separate files = 12 domains
+    let names = create_test_domains_file(&["alpha", "bravo", "charlie"]);
+    let tlds = create_test_domains_file(&["com", ".io", "org", "dev"]);
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "--file",
+        names.path().to_str().unwrap(),
+        "--tld-file",
+        tlds.path().to_str().unwrap(),
+        "--dry-run",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("alpha.com"))
+        .stdout(predicate::str::contains("charlie.dev"))
+        .stdout(predicate::str::contains("bravo.io"))
+        .stderr(predicate::str::contains("12 domains would be checked"));
+}
+
+#[test]
+fn test_add_tld_merges_with_preset() {
+    // --add-tld is additive, so it doesn't conflict with --preset
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "brand",
+        "--preset",
+        "startup",
+        "--add-tld",
+        "gg,sh",
+        "--dry-run",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("brand.io"))
+        .stdout(predicate::str::contains("brand.gg"))
+        .stdout(predicate::str::contains("brand.sh"))
+        .stderr(predicate::str::contains("10 domains would be checked"));
+}
+
+#[test]
+fn test_all_with_exclude_tld_drops_one() {
+    // The hardcoded (no-bootstrap) TLD set minus .org
+    let known = domain_check_lib::get_all_known_tlds().len();
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "brand",
+        "--all",
+        "--no-bootstrap",
+        "--exclude-tld",
+        "org",
+        "--dry-run",
+    ]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("brand.com"))
+        .stdout(predicate::str::contains("brand.org").not())
+        .stderr(predicate::str::contains(format!(
+            "{} domains would be checked",
+            known - 1
+        )));
+}
+
 #[test]
 fn test_pattern_with_preset_orthogonal() {
     // --pattern and --preset should work together (patterns generate names, preset expands TLDs)
@@ -698,6 +1208,7 @@ fn test_dry_run_with_prefix_and_suffix() {
     assert!(stdout.contains("myapp.com"));
     assert!(stdout.contains("apphub.com"));
     assert!(stdout.contains("app.com"));
+    assert!(stderr.contains("This will generate ~6 names"));
     assert!(stderr.contains("6 domains would be checked"));
 }
 
@@ -713,6 +1224,126 @@ fn test_dry_run_no_network_requests() {
         .stderr(predicate::str::contains("100 domains would be checked"));
 }
 
+#[test]
+fn test_estimate_rejected_where_it_would_run_checks() {
+    let (rdap, hits) = counting_rdap_server();
+    let file = create_test_domains_file(&["alpha.com", "beta.com"]);
+    let path = file.path().to_str().unwrap();
+
+    for args in [
+        vec!["--file", path, "--stream-file"],
+        vec!["--baseline", "-t", "com"],
+    ] {
+        let mut cmd = Command::cargo_bin("domain-check").unwrap();
+        let output = cmd
+            .args(&args)
+            .args(["--estimate", "--no-whois", "--rdap-server", &rdap])
+            .output()
+            .unwrap();
+        assert!(!output.status.success(), "{:?}", args);
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("--estimate"),
+            "{:?}",
+            args
+        );
+        assert!(output.stdout.is_empty(), "{:?}", args);
+    }
+    assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_baseline_rejects_unsupported_output() {
+    let (rdap, hits) = counting_rdap_server();
+    let dir = tempfile::TempDir::new().unwrap();
+    let out = dir.path().join("out.csv");
+    let out = out.to_str().unwrap();
+
+    for args in [
+        vec!["--csv"],
+        vec!["--ndjson"],
+        vec!["--bool"],
+        vec!["--csv", "--output", out],
+        vec!["--summary-json", out],
+        vec!["--compact-summary"],
+    ] {
+        let mut cmd = Command::cargo_bin("domain-check").unwrap();
+        let output = cmd
+            .args([
+                "--baseline",
+                "-t",
+                "com",
+                "--no-whois",
+                "--rdap-server",
+                &rdap,
+            ])
+            .args(&args)
+            .output()
+            .unwrap();
+        assert!(!output.status.success(), "{:?}", args);
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("--baseline"),
+            "{:?}",
+            args
+        );
+        assert!(output.stdout.is_empty(), "{:?}", args);
+    }
+    assert_eq!(hits.load(std::sync::atomic::Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_baseline_rejects_invalid_rdap_server() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["--baseline", "-t", "zzzz", "--no-bootstrap"])
+        .args(["--rdap-server", "ftp://bad"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--rdap-server must be an http"));
+}
+
+#[test]
+fn test_stream_file_honours_env_info_and_compact_summary() {
+    let rdap = registered_rdap_server("Example Registrar, Inc.");
+    let file = create_test_domains_file(&["alpha.com", "beta.com"]);
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.env("DC_DETAILED_INFO", "true")
+        .args(["--file", file.path().to_str().unwrap(), "--stream-file"])
+        .args(["--concurrency", "2", "--no-whois", "--compact-summary"])
+        .args(["--rdap-server", &rdap]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Example Registrar, Inc."))
+        .stdout(predicate::str::contains("total=2 available=0 taken=2"));
+}
+
+#[test]
+fn test_min_confidence_hides_output_but_not_summary_json() {
+    // pendingDelete + --treat-dropping-as-available → low-confidence available
+    let body = r#"{"objectClassName":"domain","status":["pending delete"]}"#;
+    let addr = serve_forever(response("200 OK", Some("application/rdap+json"), body));
+    let rdap = format!("http://{}/domain/", addr);
+    let dir = tempfile::tempdir().unwrap();
+    let summary = dir.path().join("summary.json");
+
+    for mode in ["--batch", "--streaming"] {
+        let mut cmd = Command::cargo_bin("domain-check").unwrap();
+        cmd.args(["dropping.com", "freeing.com", mode, "--ndjson"])
+            .args(["--no-whois", "--treat-dropping-as-available"])
+            .args(["--min-confidence", "high", "--rdap-server", &rdap])
+            .args(["--summary-json", summary.to_str().unwrap()]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("dropping.com").not());
+
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&summary).unwrap()).unwrap();
+        assert_eq!(written["total"], 2, "{} {}", mode, written);
+        assert_eq!(written["available"], 2, "{} {}", mode, written);
+    }
+}
+
 #[test]
 fn test_pattern_with_file_input() {
     // Patterns + file input should combine
@@ -743,6 +1374,301 @@ fn test_pattern_with_file_input() {
     assert!(stderr.contains("12 domains would be checked"));
 }
 
+#[test]
+fn test_dry_run_verbose_reports_duplicates() {
+    // File + args + pattern overlap: "zz1" and "mysite" appear twice, "zz3.com" via FQDN
+    let file = create_test_domains_file(&["mysite", "zz1", "zz3.com"]);
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "mysite",
+        "--file",
+        file.path().to_str().unwrap(),
+        "--pattern",
+        "zz\\d",
+        "-t",
+        "com",
+        "--dry-run",
+        "--verbose",
+    ]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // 1 arg + 3 file + 10 pattern = 14 inputs, 3 duplicates → 11 unique
+    assert!(stderr.contains("Removed 3 duplicate domains"));
+    assert!(stderr.contains("11 domains would be checked"));
+}
+
+#[test]
+fn test_dry_run_duplicates_silent_without_verbose() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["dupe", "dupe", "-t", "com", "--dry-run"]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("duplicate"));
+    assert!(stderr.contains("1 domains would be checked"));
+}
+
+#[test]
+fn test_dry_run_tld_shorthand() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["brand@io", "brand/ai", "user@example.com", "--dry-run"]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("brand.io"));
+    assert!(stdout.contains("brand.ai"));
+    assert!(!stdout.contains("example.com"));
+}
+
+#[test]
+fn test_reject_confusables_skips_homoglyphs() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "apple.com",
+        "xn--pple-43d.com",
+        "--reject-confusables",
+        "--dry-run",
+    ]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.contains("apple.com"));
+    assert!(!stdout.contains("xn--pple-43d.com"));
+    assert!(stderr.contains("Skipping xn--pple-43d.com"));
+}
+
+#[test]
+fn test_resume_rechecks_only_unknowns() {
+    let prior = NamedTempFile::with_suffix(".json").unwrap();
+    fs::write(
+        prior.path(),
+        r#"[
+  {"domain": "alpha.com", "available": true, "method_used": "rdap"},
+  {"domain": "beta.com", "available": false, "method_used": "whois"},
+  {"domain": "gamma.com", "available": null, "method_used": "unknown", "error_message": "timeout"}
+]"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "alpha",
+        "beta",
+        "gamma",
+        "delta",
+        "-t",
+        "com",
+        "--resume",
+        prior.path().to_str().unwrap(),
+        "--dry-run",
+    ]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // Definitive prior results are skipped; unknown and new domains remain
+    assert!(!stdout.contains("alpha.com"));
+    assert!(!stdout.contains("beta.com"));
+    assert!(stdout.contains("gamma.com"));
+    assert!(stdout.contains("delta.com"));
+    assert!(stderr.contains("2 domains would be checked"));
+}
+
+#[test]
+fn test_empty_tld_is_rejected() {
+    for tld in ["", "   "] {
+        let mut cmd = Command::cargo_bin("domain-check").unwrap();
+        cmd.args(["brand", "-t", tld, "--dry-run"]);
+        cmd.assert()
+            .failure()
+            .stderr(predicate::str::contains("empty TLD in TLD list"));
+    }
+}
+
+#[test]
+fn test_with_variants_checks_variant_pair() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["brand.xn--fiqs8s", "--with-variants", "--dry-run"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("brand.xn--fiqs8s"))
+        .stdout(predicate::str::contains("brand.xn--fiqz9s"));
+}
+
+#[test]
+fn test_bool_rejects_multiple_domains() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["example.com", "example.org", "--bool"]);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--bool checks exactly one domain, but 2 would be checked",
+    ));
+}
+
+#[test]
+fn test_recheck_available_checks_only_prior_available() {
+    let prior = NamedTempFile::with_suffix(".json").unwrap();
+    fs::write(
+        prior.path(),
+        r#"[
+  {"domain": "alpha.com", "available": true, "method_used": "rdap"},
+  {"domain": "beta.com", "available": false, "method_used": "whois"},
+  {"domain": "gamma.com", "available": null, "method_used": "unknown", "error_message": "timeout"},
+  {"domain": "delta.io", "available": true, "method_used": "rdap"}
+]"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "--recheck-available",
+        prior.path().to_str().unwrap(),
+        "--dry-run",
+    ]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec!["alpha.com", "delta.io"]
+    );
+    assert!(stderr.contains("2 domains would be checked"));
+}
+
+#[test]
+fn test_resume_from_truncated_ndjson() {
+    // An interrupted --output run can end mid-line; complete lines still count
+    let prior = NamedTempFile::with_suffix(".ndjson").unwrap();
+    fs::write(
+        prior.path(),
+        "{\"domain\":\"alpha.com\",\"available\":true,\"method_used\":\"rdap\"}\n\
+         {\"domain\":\"beta.com\",\"avail",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "alpha",
+        "beta",
+        "-t",
+        "com",
+        "--resume",
+        prior.path().to_str().unwrap(),
+        "--dry-run",
+    ]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("alpha.com"));
+    assert!(stdout.contains("beta.com"));
+}
+
+#[test]
+fn test_output_without_ndjson_rejected() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["test", "--output", "results.ndjson"]);
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--output requires --ndjson or --csv",
+    ));
+}
+
+#[test]
+fn test_csv_append_writes_header_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("all.csv");
+
+    for domain in ["first.zzzz", "second.zzzz"] {
+        let mut cmd = Command::cargo_bin("domain-check").unwrap();
+        cmd.args([
+            domain,
+            "--csv",
+            "--output",
+            output.to_str().unwrap(),
+            "--append",
+            "--no-bootstrap",
+            "--no-whois",
+        ]);
+        cmd.assert().success();
+    }
+
+    let content = fs::read_to_string(&output).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", content);
+    assert!(lines[0].starts_with("domain,available,"));
+    assert!(lines[1].starts_with("first.zzzz,"));
+    assert!(lines[2].starts_with("second.zzzz,"));
+    assert_eq!(lines.iter().filter(|l| l.starts_with("domain,")).count(), 1);
+}
+
+#[test]
+fn test_csv_append_rejects_different_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("all.csv");
+    fs::write(
+        &output,
+        "domain,available,registrar,method\nold.zzzz,true,-,RDAP\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "second.zzzz",
+        "--csv",
+        "--fields",
+        "registrar,created",
+        "--output",
+        output.to_str().unwrap(),
+        "--append",
+        "--no-bootstrap",
+        "--no-whois",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("CSV header"));
+
+    // The existing file is left as it was
+    assert_eq!(fs::read_to_string(&output).unwrap().lines().count(), 2);
+}
+
+#[test]
+fn test_append_requires_output() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["test", "--csv", "--append"]);
+
+    cmd.assert().failure();
+}
+
+#[test]
+fn test_resume_missing_file_errors() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["alpha", "--resume", "/nonexistent/prior.json", "--dry-run"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to read resume file"));
+}
+
 #[test]
 fn test_multiple_patterns() {
     // Multiple comma-separated patterns
@@ -970,6 +1896,49 @@ fn test_no_bootstrap_with_all_limits_to_hardcoded() {
     );
 }
 
+#[test]
+fn test_all_with_tld_limit_keeps_most_popular() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args([
+        "testname",
+        "--all",
+        "--tld-limit",
+        "10",
+        "--no-bootstrap",
+        "--dry-run",
+    ]);
+
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let domains: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        domains,
+        vec![
+            "testname.com",
+            "testname.net",
+            "testname.org",
+            "testname.io",
+            "testname.ai",
+            "testname.app",
+            "testname.dev",
+            "testname.xyz",
+            "testname.info",
+            "testname.me",
+        ]
+    );
+}
+
+#[test]
+fn test_tld_limit_requires_all() {
+    let mut cmd = Command::cargo_bin("domain-check").unwrap();
+    cmd.args(["testname", "--tld-limit", "10", "--dry-run"]);
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "--tld-limit only applies with --all",
+    ));
+}
+
 #[test]
 fn test_all_with_bootstrap_returns_more_than_32_tlds() {
     // --all (without --no-bootstrap) should return >32 TLDs after bootstrap fetch
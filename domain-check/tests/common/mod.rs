@@ -0,0 +1,74 @@
+// domain-check/tests/common/mod.rs
+
+//! Local HTTP stubs shared by the CLI integration tests.
+
+use std::io::{Read, Write};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A `Connection: close` response with `body`, and a `Content-Type` header
+/// when `content_type` is given.
+pub fn response(status: &str, content_type: Option<&str>, body: &str) -> String {
+    let content_type = content_type
+        .map(|ct| format!("Content-Type: {}\r\n", ct))
+        .unwrap_or_default();
+    format!(
+        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// Answer every request with `response` on a background thread and return
+/// the server's address.
+pub fn serve_forever(response: String) -> SocketAddr {
+    serve_counting(response).0
+}
+
+/// Like `serve_forever`, also returning how many requests have been served.
+pub fn serve_counting(response: String) -> (SocketAddr, Arc<AtomicUsize>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&hits);
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf);
+            counter.fetch_add(1, Ordering::SeqCst);
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (addr, hits)
+}
+
+/// Local RDAP server answering every query with 404 (available).
+pub fn not_found_rdap_server() -> String {
+    let addr = serve_forever(response("404 Not Found", None, ""));
+    format!("http://{}/domain/", addr)
+}
+
+/// Like `not_found_rdap_server`, also returning the query count.
+pub fn counting_rdap_server() -> (String, Arc<AtomicUsize>) {
+    let (addr, hits) = serve_counting(response("404 Not Found", None, ""));
+    (format!("http://{}/domain/", addr), hits)
+}
+
+/// Local HTTP server answering every request with `body` as plain text.
+pub fn text_server(body: &str) -> String {
+    let addr = serve_forever(response("200 OK", Some("text/plain"), body));
+    format!("http://{}/domains.txt", addr)
+}
+
+/// Local RDAP server answering every query with a registered record.
+pub fn registered_rdap_server(registrar: &str) -> String {
+    let body = format!(
+        r#"{{"objectClassName":"domain","status":["active"],"entities":[{{"roles":["registrar"],"vcardArray":["vcard",[["fn",{{}},"text","{}"]]]}}]}}"#,
+        registrar
+    );
+    let addr = serve_forever(response("200 OK", Some("application/rdap+json"), &body));
+    format!("http://{}/domain/", addr)
+}